@@ -81,6 +81,13 @@ pub struct UpstreamConfig {
     #[clap(long, default_value = "50")]
     #[serde(default)]
     pub replication_pool_size: usize,
+
+    /// Have the upstream database report the GTID of each transaction committed on connections to
+    /// it (mysql only), which is required for read-your-writes consistency. Set by the adapter
+    /// when read-your-writes consistency is enabled, rather than from the command line.
+    #[clap(skip)]
+    #[serde(default)]
+    pub track_session_gtids: bool,
 }
 
 impl UpstreamConfig {
@@ -134,6 +141,7 @@ impl Default for UpstreamConfig {
             snapshot_report_interval_secs: 30,
             ssl_root_cert: None,
             replication_pool_size: 50,
+            track_session_gtids: false,
         }
     }
 }
@@ -3,7 +3,7 @@ use std::fmt;
 use nom_sql::BinaryOperator as SqlBinaryOperator;
 use readyset_data::dialect::SqlEngine;
 use readyset_data::{DfType, Dialect};
-use readyset_errors::{invalid_err, unsupported, ReadySetResult};
+use readyset_errors::{internal, invalid_err, unsupported, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
 /// Binary infix operators with [`Expr`](crate::Expr) on both the left- and right-hand sides
@@ -68,6 +68,24 @@ pub enum BinaryOperator {
     /// `/`
     Divide,
 
+    /// `&`
+    BitwiseAnd,
+
+    /// `|`
+    BitwiseOr,
+
+    /// `^` in MySQL
+    BitwiseXor,
+
+    /// `<<`
+    ShiftLeft,
+
+    /// `>>`
+    ShiftRight,
+
+    /// `||` string concatenation
+    Concat,
+
     /// `?`
     JsonExists,
 
@@ -80,15 +98,13 @@ pub enum BinaryOperator {
     /// `||`
     JsonConcat,
 
-    /// (Unimplemented) [MySQL `->`](https://dev.mysql.com/doc/refman/5.7/en/json-search-functions.html#operator_json-column-path)
+    /// [MySQL `->`](https://dev.mysql.com/doc/refman/5.7/en/json-search-functions.html#operator_json-column-path)
     /// operator to extract JSON values via a path: `json -> jsonpath` to `json`.
-    // TODO(ENG-1517)
     JsonPathExtract,
 
-    /// (Unimplemented) [MySQL `->>`](https://dev.mysql.com/doc/refman/5.7/en/json-search-functions.html#operator_json-inline-path)
+    /// [MySQL `->>`](https://dev.mysql.com/doc/refman/5.7/en/json-search-functions.html#operator_json-inline-path)
     /// operator to extract JSON values and apply [`json_unquote`](https://dev.mysql.com/doc/refman/5.7/en/json-modification-functions.html#function_json-unquote):
     /// `json ->> jsonpath` to unquoted `text`.
-    // TODO(ENG-1518)
     JsonPathExtractUnquote,
 
     /// PostgreSQL `->` operator to extract JSON values as JSON via a key:
@@ -122,6 +138,15 @@ pub enum BinaryOperator {
 
     /// PostgreSQL `#-` operator to remove from JSONB values via a key/index.
     JsonSubtractPath,
+
+    /// PostgreSQL `@>` applied to a range: `range @> {range,element}` to `bool`.
+    RangeContains,
+
+    /// PostgreSQL `<@` applied to a range: `{range,element} <@ range` to `bool`.
+    RangeContainedIn,
+
+    /// PostgreSQL `&&` operator to check whether two ranges overlap: `range && range` to `bool`.
+    RangeOverlaps,
 }
 
 impl BinaryOperator {
@@ -130,7 +155,7 @@ impl BinaryOperator {
         op: SqlBinaryOperator,
         dialect: Dialect,
         left_type: &DfType,
-        _right_type: &DfType,
+        right_type: &DfType,
     ) -> ReadySetResult<Self> {
         use SqlBinaryOperator::*;
         let res = match op {
@@ -151,6 +176,20 @@ impl BinaryOperator {
             HashSubtract => Self::JsonSubtractPath,
             Multiply => Self::Multiply,
             Divide => Self::Divide,
+            // `%` is lowered to a call to the `mod` builtin function, which handles division by
+            // zero differently for each dialect.
+            Modulo => internal!("'{op}' should have been lowered to a function call"),
+            BitwiseAnd => Self::BitwiseAnd,
+            BitwiseOr => Self::BitwiseOr,
+            ShiftLeft => Self::ShiftLeft,
+            ShiftRight => Self::ShiftRight,
+            Caret => match dialect.engine() {
+                SqlEngine::MySQL => Self::BitwiseXor,
+                // Exponentiation is lowered to a call to the `power` builtin function.
+                SqlEngine::PostgreSQL => {
+                    internal!("'{op}' should have been lowered to a function call")
+                }
+            },
             Like => Self::Like,
             NotLike => Self::NotLike,
             ILike => Self::ILike,
@@ -162,14 +201,13 @@ impl BinaryOperator {
             QuestionMark => Self::JsonExists,
             QuestionMarkPipe => Self::JsonAnyExists,
             QuestionMarkAnd => Self::JsonAllExists,
-            // TODO When we want to implement the double pipe string concat operator, we'll need to
-            // look at the types of the arguments to this operator to infer which `BinaryOperator`
-            // variant to return. For now we just support the JSON `||` concat though:
             DoublePipe => {
-                if dialect.double_pipe_is_concat() {
+                if !dialect.double_pipe_is_concat() {
+                    Self::Or
+                } else if left_type.is_any_json() || right_type.is_any_json() {
                     Self::JsonConcat
                 } else {
-                    Self::Or
+                    Self::Concat
                 }
             }
             Arrow1 => match dialect.engine() {
@@ -185,8 +223,24 @@ impl BinaryOperator {
             }
             HashArrow1 => Self::JsonKeyPathExtract,
             HashArrow2 => Self::JsonKeyPathExtractText,
+            AtArrowRight | AtArrowLeft if left_type.is_range() || right_type.is_range() => {
+                if op == AtArrowRight {
+                    Self::RangeContains
+                } else {
+                    Self::RangeContainedIn
+                }
+            }
             AtArrowRight => Self::JsonContains,
             AtArrowLeft => Self::JsonContainedIn,
+            // MySQL parses `&&` as a (deprecated) synonym for `AND`, but with a different
+            // precedence than the one we parse it with, so we can't lower it faithfully.
+            DoubleAmpersand if dialect.engine() != SqlEngine::PostgreSQL => {
+                unsupported!("'{op}' not available in {}", dialect.engine())
+            }
+            DoubleAmpersand => Self::RangeOverlaps,
+            AtAt => {
+                unsupported!("Full-text search predicates are only supported as query parameters")
+            }
         };
         Ok(res)
     }
@@ -238,8 +292,22 @@ impl BinaryOperator {
                 error(Left, "JSON")
             }
 
+            // range, unknown
+            Self::RangeContains | Self::RangeOverlaps
+                if left_type.is_known() && !left_type.is_range() =>
+            {
+                error(Left, "RANGE")
+            }
+
             // Right type checks:
 
+            // range, unknown
+            Self::RangeContainedIn | Self::RangeOverlaps
+                if right_type.is_known() && !right_type.is_range() =>
+            {
+                error(Right, "RANGE")
+            }
+
             // text, char, varchar, unknown
             Self::JsonExists if right_type.is_known() && !right_type.is_any_text() => {
                 error(Right, "TEXT")
@@ -270,13 +338,14 @@ impl BinaryOperator {
         }
     }
 
-    /// Returns this operator's output type given its input types, or
+    /// Returns this operator's output type given its input types and the SQL dialect, or
     /// [`ReadySetError::InvalidQuery`](readyset_errors::ReadySetError::InvalidQuery) if it could
     /// not be inferred.
     pub(crate) fn output_type(
         &self,
         left_type: &DfType,
         right_type: &DfType,
+        dialect: Dialect,
     ) -> ReadySetResult<DfType> {
         self.check_arg_types(left_type, right_type)?;
 
@@ -299,11 +368,43 @@ impl BinaryOperator {
             | Self::JsonAnyExists
             | Self::JsonAllExists
             | Self::JsonContains
-            | Self::JsonContainedIn => Ok(DfType::Bool),
+            | Self::JsonContainedIn
+            | Self::RangeContains
+            | Self::RangeContainedIn
+            | Self::RangeOverlaps => Ok(DfType::Bool),
 
             Self::JsonPathExtractUnquote
             | Self::JsonKeyExtractText
-            | Self::JsonKeyPathExtractText => Ok(DfType::DEFAULT_TEXT),
+            | Self::JsonKeyPathExtractText
+            | Self::Concat => Ok(DfType::DEFAULT_TEXT),
+
+            // MySQL performs bit operations on (and returns) unsigned 64-bit integers, whereas
+            // PostgreSQL operates on the integer type of its operands.
+            Self::BitwiseAnd
+            | Self::BitwiseOr
+            | Self::BitwiseXor
+            | Self::ShiftLeft
+            | Self::ShiftRight
+                if dialect.engine() == SqlEngine::MySQL =>
+            {
+                Ok(DfType::UnsignedBigInt)
+            }
+            Self::BitwiseAnd | Self::BitwiseOr | Self::BitwiseXor
+                if left_type.is_any_int() && right_type.is_any_int() =>
+            {
+                // Mixing integer types results in the wider of the two.
+                let types = [left_type, right_type];
+                if types.contains(&&DfType::BigInt) {
+                    Ok(DfType::BigInt)
+                } else if types.contains(&&DfType::Int) {
+                    Ok(DfType::Int)
+                } else {
+                    Ok(left_type.clone())
+                }
+            }
+            Self::BitwiseAnd | Self::BitwiseOr | Self::BitwiseXor if left_type.is_unknown() => {
+                Ok(right_type.clone())
+            }
 
             _ => Ok(left_type.clone()),
         }
@@ -332,6 +433,12 @@ impl fmt::Display for BinaryOperator {
             Self::JsonSubtractPath => "#-",
             Self::Multiply => "*",
             Self::Divide => "/",
+            Self::BitwiseAnd => "&",
+            Self::BitwiseOr => "|",
+            Self::BitwiseXor => "^",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::Concat => "||",
             Self::JsonExists => "?",
             Self::JsonAnyExists => "?|",
             Self::JsonAllExists => "?&",
@@ -342,6 +449,9 @@ impl fmt::Display for BinaryOperator {
             Self::JsonKeyPathExtractText => "#>>",
             Self::JsonContains => "@>",
             Self::JsonContainedIn => "<@",
+            Self::RangeContains => "@>",
+            Self::RangeContainedIn => "<@",
+            Self::RangeOverlaps => "&&",
         };
         f.write_str(op)
     }
@@ -365,13 +475,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn range_operator_lowering() {
+        let int_range = DfType::Range(Box::new(DfType::Int));
+        let lower = |op, left_type: &DfType, right_type: &DfType| {
+            BinaryOperator::from_sql_op(op, Dialect::DEFAULT_POSTGRESQL, left_type, right_type)
+        };
+
+        assert_eq!(
+            lower(SqlBinaryOperator::AtArrowRight, &int_range, &DfType::Int).unwrap(),
+            BinaryOperator::RangeContains
+        );
+        assert_eq!(
+            lower(SqlBinaryOperator::AtArrowLeft, &DfType::Unknown, &int_range).unwrap(),
+            BinaryOperator::RangeContainedIn
+        );
+        assert_eq!(
+            lower(
+                SqlBinaryOperator::AtArrowRight,
+                &DfType::Jsonb,
+                &DfType::Jsonb
+            )
+            .unwrap(),
+            BinaryOperator::JsonContains
+        );
+        assert_eq!(
+            lower(SqlBinaryOperator::DoubleAmpersand, &int_range, &int_range).unwrap(),
+            BinaryOperator::RangeOverlaps
+        );
+        BinaryOperator::from_sql_op(
+            SqlBinaryOperator::DoubleAmpersand,
+            Dialect::DEFAULT_MYSQL,
+            &DfType::Int,
+            &DfType::Int,
+        )
+        .unwrap_err();
+        BinaryOperator::RangeOverlaps
+            .output_type(&int_range, &DfType::Int, Dialect::DEFAULT_POSTGRESQL)
+            .unwrap_err();
+    }
+
     mod output_type {
         use super::*;
 
         #[track_caller]
         fn test_json_extract(op: BinaryOperator, left_type: DfType, output_type: DfType) {
             assert_eq!(
-                op.output_type(&left_type, &DfType::DEFAULT_TEXT).unwrap(),
+                op.output_type(&left_type, &DfType::DEFAULT_TEXT, Dialect::DEFAULT_MYSQL)
+                    .unwrap(),
                 output_type
             );
         }
@@ -379,8 +530,12 @@ mod tests {
         #[track_caller]
         fn test_json_key_path_extract(op: BinaryOperator, left_type: DfType, output_type: DfType) {
             assert_eq!(
-                op.output_type(&left_type, &DfType::Array(Box::new(DfType::DEFAULT_TEXT)))
-                    .unwrap(),
+                op.output_type(
+                    &left_type,
+                    &DfType::Array(Box::new(DfType::DEFAULT_TEXT)),
+                    Dialect::DEFAULT_POSTGRESQL,
+                )
+                .unwrap(),
                 output_type
             );
         }
@@ -1,7 +1,7 @@
 use std::borrow::Borrow;
 
 use readyset_data::{Array, ArrayD, DfType, DfValue, IxDyn};
-use readyset_errors::{invalid_err, unsupported, ReadySetError, ReadySetResult};
+use readyset_errors::{internal, invalid_err, ReadySetError, ReadySetResult};
 use serde_json::Value as JsonValue;
 
 use crate::like::{CaseInsensitive, CaseSensitive, LikePattern};
@@ -18,6 +18,7 @@ macro_rules! non_null {
 
 mod builtins;
 mod json;
+mod range;
 
 impl Expr {
     /// Evaluate this expression, given a source record to pull columns from
@@ -33,7 +34,10 @@ impl Expr {
                 .ok_or(ReadySetError::ProjectExprInvalidColumnIndex(*index)),
             Expr::Literal { val, .. } => Ok(val.clone()),
             Expr::Op {
-                op, left, right, ..
+                op,
+                left,
+                right,
+                ty,
             } => {
                 use BinaryOperator::*;
 
@@ -78,8 +82,35 @@ impl Expr {
                     Subtract => Ok((non_null!(&left) - non_null!(&right))?),
                     Multiply => Ok((non_null!(&left) * non_null!(&right))?),
                     Divide => Ok((non_null!(&left) / non_null!(&right))?),
+                    BitwiseAnd | BitwiseOr | BitwiseXor | ShiftLeft | ShiftRight => {
+                        eval_bitwise(*op, non_null!(&left), non_null!(&right), ty)
+                    }
+                    Concat => {
+                        let left = non_null!(left).coerce_to(&DfType::DEFAULT_TEXT, left_ty)?;
+                        let right = non_null!(right).coerce_to(&DfType::DEFAULT_TEXT, right_ty)?;
+                        Ok(
+                            format!("{}{}", <&str>::try_from(&left)?, <&str>::try_from(&right)?)
+                                .into(),
+                        )
+                    }
                     And => Ok((non_null!(left).is_truthy() && non_null!(right).is_truthy()).into()),
                     Or => Ok((non_null!(left).is_truthy() || non_null!(right).is_truthy()).into()),
+                    Equal | NotEqual | Greater | GreaterOrEqual | Less | LessOrEqual
+                        if left_ty.is_jsonb() && right_ty.is_jsonb() =>
+                    {
+                        // `jsonb` values have their own ordering, which doesn't match the ordering
+                        // of their text representation
+                        let ord = non_null!(&left).jsonb_cmp(non_null!(&right))?;
+                        Ok(match op {
+                            Equal => ord.is_eq(),
+                            NotEqual => ord.is_ne(),
+                            Greater => ord.is_gt(),
+                            GreaterOrEqual => ord.is_ge(),
+                            Less => ord.is_lt(),
+                            _ => ord.is_le(),
+                        }
+                        .into())
+                    }
                     Equal => Ok((non_null!(left)
                         == non_null!(right).coerce_to(left_ty, right_ty)?)
                     .into()),
@@ -99,10 +130,10 @@ impl Expr {
 
                     // JSON operators:
                     JsonExists => {
-                        let json_value = left.to_json()?;
+                        let json_value = left.as_json()?;
                         let key = <&str>::try_from(&right)?;
 
-                        let result = match json_value {
+                        let result = match &*json_value {
                             JsonValue::Object(map) => map.contains_key(key),
                             JsonValue::Array(vec) => vec.iter().any(|v| v.as_str() == Some(key)),
                             _ => false,
@@ -110,10 +141,10 @@ impl Expr {
                         Ok(result.into())
                     }
                     json_op @ (JsonAnyExists | JsonAllExists) => {
-                        let json_value = left.to_json()?;
+                        let json_value = left.as_json()?;
                         let keys = right.as_array().and_then(Array::to_str_vec)?;
 
-                        let result = match (json_op, json_value) {
+                        let result = match (json_op, &*json_value) {
                             (JsonAnyExists, JsonValue::Object(map)) => {
                                 keys.into_iter().any(|k| map.contains_key(k))
                             }
@@ -130,21 +161,22 @@ impl Expr {
                         };
                         Ok(result.into())
                     }
-                    // TODO(ENG-1517)
-                    // TODO(ENG-1518)
                     JsonPathExtract | JsonPathExtractUnquote => {
-                        // TODO: Perform `JSON_EXTRACT` conditionally followed by `JSON_UNQUOTE` for
-                        // `->>`.
-                        unsupported!("'{op}' operator not implemented yet for MySQL")
+                        let json = non_null!(&left).as_json()?;
+                        let path = <&str>::try_from(non_null!(&right))?;
+
+                        let extracted = json::json_extract_paths(&json, &[path])?;
+                        if *op == JsonPathExtract || extracted.is_none() {
+                            return Ok(extracted);
+                        }
+
+                        Ok(json::json_unquote(<&str>::try_from(&extracted)?)?.into())
                     }
 
                     JsonKeyExtract | JsonKeyExtractText => {
-                        // Both extraction operations behave the same in PostgreSQL except for the
-                        // return type, which is handled during expression lowering.
+                        let json = left.as_json()?;
 
-                        let json = left.to_json()?;
-
-                        let json_inner: Option<&JsonValue> = match &json {
+                        let json_inner: Option<&JsonValue> = match &*json {
                             JsonValue::Array(array) => isize::try_from(&right)
                                 .ok()
                                 .and_then(|index| utils::index_bidirectional(array, index)),
@@ -155,32 +187,41 @@ impl Expr {
                             _ => None,
                         };
 
-                        Ok(json_inner
-                            .map(|inner| inner.to_string().into())
-                            .unwrap_or_default())
+                        match json_inner {
+                            Some(inner) if *op == JsonKeyExtractText => {
+                                Ok(json::json_to_text(inner))
+                            }
+                            Some(inner) => Ok(inner.to_string().into()),
+                            None => Ok(DfValue::None),
+                        }
                     }
 
                     JsonKeyPathExtract | JsonKeyPathExtractText => {
-                        // Both extraction operations behave the same in PostgreSQL except for the
-                        // return type, which is handled during expression lowering.
-                        //
-                        // Type errors are also handled during expression lowering.
+                        // Type errors are handled during expression lowering.
                         json::json_extract_key_path(
-                            &left.to_json()?,
+                            &left.as_json()?,
                             // PostgreSQL docs state `text[]` but in practice it allows using
                             // multi-dimensional arrays here.
                             right.as_array()?.values(),
+                            *op == JsonKeyPathExtractText,
                         )
                     }
 
                     JsonContains => {
-                        Ok(json::json_contains(&left.to_json()?, &right.to_json()?).into())
+                        Ok(json::json_contains(&left.as_json()?, &right.as_json()?).into())
                     }
                     JsonContainedIn => {
                         // Evaluate `left` first for consistency.
-                        let child = left.to_json()?;
-                        Ok(json::json_contains(&right.to_json()?, &child).into())
+                        let child = left.as_json()?;
+                        Ok(json::json_contains(&right.as_json()?, &child).into())
                     }
+                    RangeContains | RangeContainedIn | RangeOverlaps => range::eval_range_op(
+                        *op,
+                        non_null!(&left),
+                        left_ty,
+                        non_null!(&right),
+                        right_ty,
+                    ),
                     JsonConcat => {
                         let mut left_json = left.to_json()?;
                         let mut right_json = right.to_json()?;
@@ -309,6 +350,61 @@ impl Expr {
     }
 }
 
+/// Evaluates one of the bitwise operators.
+///
+/// MySQL bitwise operators are lowered with an unsigned 64-bit integer type, and operate on
+/// unsigned 64-bit integers with negative operands reinterpreted as two's complement. PostgreSQL
+/// operates on signed integers of the type the expression was lowered with, and shifts wrap around
+/// the width of that type.
+fn eval_bitwise(
+    op: BinaryOperator,
+    left: &DfValue,
+    right: &DfValue,
+    ty: &DfType,
+) -> ReadySetResult<DfValue> {
+    use BinaryOperator::*;
+
+    if *ty == DfType::UnsignedBigInt {
+        let to_u64 = |val: &DfValue| -> ReadySetResult<u64> {
+            match val {
+                DfValue::UnsignedInt(val) => Ok(*val),
+                _ => Ok(i64::try_from(&val.coerce_to(&DfType::BigInt, &DfType::Unknown)?)? as u64),
+            }
+        };
+        let (left, right) = (to_u64(left)?, to_u64(right)?);
+        // Shifting by 64 or more bits always results in 0.
+        let shift = u32::try_from(right).unwrap_or(u32::MAX);
+
+        let res = match op {
+            BitwiseAnd => left & right,
+            BitwiseOr => left | right,
+            BitwiseXor => left ^ right,
+            ShiftLeft => left.checked_shl(shift).unwrap_or(0),
+            ShiftRight => left.checked_shr(shift).unwrap_or(0),
+            _ => internal!("'{op}' is not a bitwise operator"),
+        };
+        return Ok(DfValue::UnsignedInt(res));
+    }
+
+    let (left, right) = (i64::try_from(left)?, i64::try_from(right)?);
+    let shift = right as u32;
+
+    // `smallint` shifts are performed on 32-bit integers before being truncated.
+    let res = match (op, ty) {
+        (BitwiseAnd, _) => left & right,
+        (BitwiseOr, _) => left | right,
+        (BitwiseXor, _) => left ^ right,
+        (ShiftLeft, DfType::SmallInt) => i64::from((left as i32).wrapping_shl(shift) as i16),
+        (ShiftLeft, DfType::Int) => i64::from((left as i32).wrapping_shl(shift)),
+        (ShiftLeft, _) => left.wrapping_shl(shift),
+        (ShiftRight, DfType::SmallInt) => i64::from((left as i32).wrapping_shr(shift) as i16),
+        (ShiftRight, DfType::Int) => i64::from((left as i32).wrapping_shr(shift)),
+        (ShiftRight, _) => left.wrapping_shr(shift),
+        _ => internal!("'{op}' is not a bitwise operator"),
+    };
+    Ok(DfValue::Int(res))
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryInto;
@@ -391,6 +487,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn eval_modulo() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("7 % 3", dialect), 1.into());
+            assert_eq!(eval_expr("(-7) % 3", dialect), (-1).into());
+            assert_eq!(eval_expr("7 % null", dialect), DfValue::None);
+        }
+        assert_eq!(eval_expr("7 MOD 3", MySQL), 1.into());
+        assert_eq!(eval_expr("7 % 0", MySQL), DfValue::None);
+        try_eval_expr("7 % 0", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn eval_bitwise_mysql() {
+        #[track_caller]
+        fn test(expr: &str, expected: u64) {
+            assert_eq!(
+                eval_expr(expr, MySQL),
+                DfValue::UnsignedInt(expected),
+                "incorrect result for `{expr}`"
+            );
+        }
+
+        test("5 & 3", 1);
+        test("5 | 3", 7);
+        test("5 ^ 3", 6);
+        test("1 << 2", 4);
+        test("16 >> 2", 4);
+        test("1 << 63", 1 << 63);
+        test("1 << 64", 0);
+        test("(-1) >> 64", 0);
+        test("(-1) & 255", 255);
+        test("(-1) | 0", u64::MAX);
+        test("(-2) >> 1", u64::MAX >> 1);
+        test("1 | 2 & 3", 3);
+        assert_eq!(eval_expr("1 & null", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn eval_bitwise_postgres() {
+        #[track_caller]
+        fn test(expr: &str, expected: i64) {
+            assert_eq!(
+                eval_expr(expr, PostgreSQL),
+                DfValue::Int(expected),
+                "incorrect result for `{expr}`"
+            );
+        }
+
+        test("5::int & 3::int", 1);
+        test("5::int | 3::smallint", 7);
+        test("(-1)::bigint & 255::int", 255);
+        test("1::int << 2", 4);
+        test("1::int << 31", i32::MIN.into());
+        test("1::int << 32", 1);
+        test("1::smallint << 15", i16::MIN.into());
+        test("1::smallint << 16", 0);
+        test("1::bigint << 63", i64::MIN);
+        test("(-8)::bigint >> 1", -4);
+        assert_eq!(eval_expr("1::int | null::int", PostgreSQL), DfValue::None);
+        assert_eq!(
+            eval_expr("2 ^ 10", PostgreSQL),
+            DfValue::try_from(1024.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn eval_concat() {
+        assert_eq!(eval_expr("'ab' || 'cd'", PostgreSQL), "abcd".into());
+        assert_eq!(eval_expr("'ab' || 1", PostgreSQL), "ab1".into());
+        assert_eq!(eval_expr("'ab' || null", PostgreSQL), DfValue::None);
+        assert_eq!(
+            eval_expr("'[1]'::jsonb || '[2]'::jsonb", PostgreSQL)
+                .to_json()
+                .unwrap(),
+            json!([1, 2])
+        );
+        // `||` is logical OR in MySQL by default
+        assert_eq!(eval_expr("0 || 1", MySQL), true.into());
+    }
+
     #[test]
     fn eval_json_exists() {
         let expr = Op {
@@ -826,6 +1003,55 @@ mod tests {
         assert_op!(BinaryOperator::Equal, text_dt, 1u8);
     }
 
+    #[test]
+    fn eval_jsonb_comparisons() {
+        let expr = |op| Op {
+            left: Box::new(column_with_type(0, DfType::Jsonb)),
+            right: Box::new(column_with_type(1, DfType::Jsonb)),
+            op,
+            ty: DfType::Bool,
+        };
+        let test_eval = |op, left: &str, right: &str| {
+            let jsonb = |s: &str| {
+                DfValue::from(s)
+                    .coerce_to(&DfType::Jsonb, &DfType::Unknown)
+                    .unwrap()
+            };
+            expr(op).eval(&[jsonb(left), jsonb(right)]).unwrap()
+        };
+
+        assert_eq!(
+            test_eval(
+                BinaryOperator::Equal,
+                r#"{"b": 1, "a": 2}"#,
+                r#"{"a":2,"b":1}"#
+            ),
+            true.into()
+        );
+        assert_eq!(test_eval(BinaryOperator::Equal, "1.0", "1"), true.into());
+        assert_eq!(
+            test_eval(BinaryOperator::NotEqual, "1.0", "1"),
+            false.into()
+        );
+        // Compared numerically, not as text
+        assert_eq!(test_eval(BinaryOperator::Greater, "10", "9"), true.into());
+        // Longer arrays are greater, regardless of their elements
+        assert_eq!(
+            test_eval(BinaryOperator::Greater, "[1, 2]", "[3]"),
+            true.into()
+        );
+        // Objects are greater than any array
+        assert_eq!(test_eval(BinaryOperator::Less, "[1, 2]", "{}"), true.into());
+        assert_eq!(
+            test_eval(BinaryOperator::LessOrEqual, r#""a""#, "null"),
+            false.into()
+        );
+        assert_eq!(
+            test_eval(BinaryOperator::GreaterOrEqual, "true", "false"),
+            true.into()
+        );
+    }
+
     #[test]
     fn eval_cast() {
         let expr = Cast {
@@ -1021,9 +1247,8 @@ mod tests {
     #[test]
     fn eval_json_key_extract() {
         #[track_caller]
-        fn test(json: &str, key: &str, expected: &str) {
-            // Both ops behave the same except for their return type.
-            for op in ["->", "->>"] {
+        fn test(json: &str, key: &str, expected: Option<&str>, expected_text: Option<&str>) {
+            for (op, expected) in [("->", expected), ("->>", expected_text)] {
                 for json_type in ["json", "jsonb"] {
                     let expr = format!("'{json}'::{json_type} {op} {key}");
                     assert_eq!(
@@ -1035,22 +1260,24 @@ mod tests {
             }
         }
 
-        let array = "[\"world\", 123]";
-        test(array, "0", "\"world\"");
-        test(array, "1", "123");
+        let array = "[\"world\", 123, null]";
+        test(array, "0", Some("\"world\""), Some("world"));
+        test(array, "1", Some("123"), Some("123"));
+        test(array, "2", Some("null"), None);
+        test(array, "3", None, None);
 
-        let object = r#"{ "hello": "world", "abc": 123 }"#;
-        test(object, "'hello'::text", "\"world\"");
-        test(object, "'abc'::char(3)", "123");
+        let object = r#"{ "hello": "world", "abc": 123, "obj": {"x": "y"} }"#;
+        test(object, "'hello'::text", Some("\"world\""), Some("world"));
+        test(object, "'abc'::char(3)", Some("123"), Some("123"));
+        test(object, "'obj'", Some(r#"{"x":"y"}"#), Some(r#"{"x":"y"}"#));
     }
 
     /// Tests evaluation of `JsonKeyPathExtract` and `JsonKeyPathExtractText` binary ops.
     #[test]
     fn eval_json_key_path_extract() {
         #[track_caller]
-        fn test(json: &str, key: &str, expected: Option<&str>) {
-            // Both ops behave the same except for their return type.
-            for op in ["#>", "#>>"] {
+        fn test(json: &str, key: &str, expected: Option<&str>, expected_text: Option<&str>) {
+            for (op, expected) in [("#>", expected), ("#>>", expected_text)] {
                 for json_type in ["json", "jsonb"] {
                     let expr = format!("'{json}'::{json_type} {op} {key}");
                     assert_eq!(
@@ -1062,28 +1289,93 @@ mod tests {
             }
         }
 
-        let array = "[[\"world\", 123]]";
+        let array = "[[\"world\", 123, null]]";
 
-        test(array, "array['1']", None);
-        test(array, "array[null::text]", None);
+        test(array, "array['1']", None, None);
+        test(array, "array[null::text]", None, None);
 
-        test(array, "array['0', '0']", Some("\"world\""));
-        test(array, "array['0', '1']", Some("123"));
-        test(array, "array['0', '   1']", Some("123"));
-        test(array, "array['0', '2']", None);
-        test(array, "array['0', null::text]", None);
+        test(array, "array['0', '0']", Some("\"world\""), Some("world"));
+        test(array, "array['0', '1']", Some("123"), Some("123"));
+        test(array, "array['0', '   1']", Some("123"), Some("123"));
+        test(array, "array['0', '2']", Some("null"), None);
+        test(array, "array['0', '3']", None, None);
+        test(array, "array['0', null::text]", None, None);
 
         let object = r#"{ "hello": ["world"], "abc": [123] }"#;
 
-        test(object, "array[null::text]", None);
-        test(object, "array['world']", None);
+        test(object, "array[null::text]", None, None);
+        test(object, "array['world']", None, None);
+
+        test(
+            object,
+            "array['hello', '0']",
+            Some("\"world\""),
+            Some("world"),
+        );
+        test(object, "array['hello', '1']", None, None);
+        test(object, "array['hello', null::text]", None, None);
+
+        test(
+            object,
+            "array['abc'::char(3), '0']",
+            Some("123"),
+            Some("123"),
+        );
+        test(object, "array['abc'::char(3), null::text]", None, None);
+    }
+
+    /// Tests evaluation of `JsonPathExtract` and `JsonPathExtractUnquote` binary ops.
+    #[test]
+    fn eval_json_path_extract() {
+        #[track_caller]
+        fn test(json: &str, path: &str, expected: Option<&str>, expected_unquoted: Option<&str>) {
+            for (op, expected) in [
+                (BinaryOperator::JsonPathExtract, expected),
+                (BinaryOperator::JsonPathExtractUnquote, expected_unquoted),
+            ] {
+                let expr = Op {
+                    left: Box::new(column_with_type(0, DfType::Json)),
+                    right: Box::new(make_literal(path.into())),
+                    op,
+                    ty: op
+                        .output_type(
+                            &DfType::Json,
+                            &DfType::DEFAULT_TEXT,
+                            crate::Dialect::DEFAULT_MYSQL,
+                        )
+                        .unwrap(),
+                };
+                assert_eq!(
+                    expr.eval(&[DfValue::from(json)]).unwrap(),
+                    expected.into(),
+                    "incorrect result for `{json} {op} '{path}'`"
+                );
+            }
+        }
+
+        let json = r#"{"status": "active", "n": 1, "tags": ["a", "b"], "none": null}"#;
 
-        test(object, "array['hello', '0']", Some("\"world\""));
-        test(object, "array['hello', '1']", None);
-        test(object, "array['hello', null::text]", None);
+        test(json, "$.status", Some("\"active\""), Some("active"));
+        test(json, "$.n", Some("1"), Some("1"));
+        test(json, "$.tags", Some(r#"["a", "b"]"#), Some(r#"["a", "b"]"#));
+        test(json, "$.tags[last]", Some("\"b\""), Some("b"));
+        test(
+            json,
+            "$.tags[*]",
+            Some(r#"["a", "b"]"#),
+            Some(r#"["a", "b"]"#),
+        );
+        test(json, "$.none", Some("null"), Some("null"));
+        test(json, "$.missing", None, None);
 
-        test(object, "array['abc'::char(3), '0']", Some("123"));
-        test(object, "array['abc'::char(3), null::text]", None);
+        let expr = Op {
+            left: Box::new(column_with_type(0, DfType::Json)),
+            right: Box::new(make_literal("$.status".into())),
+            op: BinaryOperator::JsonPathExtractUnquote,
+            ty: DfType::DEFAULT_TEXT,
+        };
+        assert_eq!(expr.eval(&[DfValue::None]).unwrap(), DfValue::None);
+        expr.eval(&[DfValue::from("not json")]).unwrap_err();
     }
 
     /// Tests evaluation of `JsonSubtractPath` binary ops.
@@ -1201,4 +1493,93 @@ mod tests {
             // test("0.1", "1.0e-1", true);
         }
     }
+
+    /// Tests evaluation of `RangeContains`, `RangeContainedIn`, and `RangeOverlaps` binary ops.
+    mod range_ops {
+        use super::*;
+
+        #[track_caller]
+        fn test_contains(range: &str, child: &str, expected: bool) {
+            for expr in [
+                format!("'{range}'::int4range @> {child}"),
+                format!("{child} <@ '{range}'::int4range"),
+            ] {
+                assert_eq!(
+                    eval_expr(&expr, PostgreSQL),
+                    expected.into(),
+                    "incorrect result for `{expr}`"
+                );
+            }
+        }
+
+        #[test]
+        fn contains_element() {
+            test_contains("[1,5)", "1", true);
+            test_contains("[1,5)", "4", true);
+            test_contains("[1,5)", "5", false);
+            test_contains("(1,5]", "1", false);
+            test_contains("(1,5]", "5", true);
+            test_contains("(,5)", "-1000", true);
+            test_contains("empty", "1", false);
+        }
+
+        #[test]
+        fn contains_range() {
+            test_contains("[1,5)", "'[2,4)'", true);
+            test_contains("[1,5)", "'[1,4]'", true);
+            test_contains("[1,5)", "'[2,5]'", false);
+            test_contains("[1,5)", "'empty'", true);
+            test_contains("(,)", "'[2,5]'::int4range", true);
+            test_contains("empty", "'[2,5]'", false);
+        }
+
+        #[test]
+        fn overlaps() {
+            for (left, right, expected) in [
+                ("[1,5)", "[4,10)", true),
+                ("[1,5)", "[5,10)", false),
+                ("[1,5]", "[5,10)", true),
+                ("(,)", "[5,10)", true),
+                ("[1,5)", "empty", false),
+            ] {
+                for expr in [
+                    format!("'{left}'::int4range && '{right}'::int4range"),
+                    format!("'{right}'::int4range && '{left}'"),
+                ] {
+                    assert_eq!(
+                        eval_expr(&expr, PostgreSQL),
+                        expected.into(),
+                        "incorrect result for `{expr}`"
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn timestamp_ranges() {
+            assert_eq!(
+                eval_expr(
+                    "'[2020-01-01 00:00:00,2020-02-01 00:00:00)'::tsrange @> \
+                     '2020-01-15 12:00:00'::timestamp",
+                    PostgreSQL
+                ),
+                true.into()
+            );
+            assert_eq!(
+                eval_expr(
+                    "'[2020-01-01,2020-02-01)'::daterange && '[2020-02-01,2020-03-01)'::daterange",
+                    PostgreSQL
+                ),
+                false.into()
+            );
+        }
+
+        #[test]
+        fn null_operands() {
+            assert_eq!(
+                eval_expr("'[1,5)'::int4range @> null", PostgreSQL),
+                DfValue::None
+            );
+        }
+    }
 }
@@ -3,17 +3,23 @@ use std::cmp::Ordering;
 use std::fmt::Write;
 use std::ops::{Add, Div, Mul, Sub};
 
-use chrono::{Datelike, LocalResult, Month, NaiveDate, NaiveDateTime, TimeZone, Timelike, Weekday};
+use chrono::{
+    Datelike, Duration, FixedOffset, LocalResult, Month, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Timelike, Weekday,
+};
 use chrono_tz::Tz;
 use maths::int::integer_rnd;
 use mysql_time::MySqlTime;
-use readyset_data::{DfType, DfValue};
-use readyset_errors::{invalid_err, ReadySetError, ReadySetResult};
+use nom_sql::TimestampField;
+use readyset_data::dialect::SqlEngine;
+use readyset_data::{Collation, DfType, DfValue, Interval};
+use readyset_errors::{internal, invalid_err, ReadySetError, ReadySetResult};
+use regex::RegexBuilder;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use vec1::Vec1;
 
-use crate::{BuiltinFunction, Expr};
+use crate::{BuiltinFunction, Dialect, Expr, TrimSide};
 
 macro_rules! try_cast_or_none {
     ($df_value:expr, $to_ty:expr, $from_ty:expr) => {{
@@ -24,6 +30,30 @@ macro_rules! try_cast_or_none {
     }};
 }
 
+/// Evaluates the given expression against the given record and coerces the result to text,
+/// returning null from the enclosing function if the result is null
+macro_rules! eval_text {
+    ($expr:expr, $record:expr) => {
+        non_null!($expr.eval($record)?).coerce_to(&DfType::DEFAULT_TEXT, $expr.ty())?
+    };
+}
+
+/// Evaluates the given expression against the given record and coerces the result to an integer,
+/// returning null from the enclosing function if the result is null
+macro_rules! eval_int {
+    ($expr:expr, $record:expr) => {
+        <i64>::try_from(non_null!($expr.eval($record)?).coerce_to(&DfType::BigInt, $expr.ty())?)?
+    };
+}
+
+/// Evaluates the given expression against the given record and coerces the result to a double,
+/// returning null from the enclosing function if the result is null
+macro_rules! eval_double {
+    ($expr:expr, $record:expr) => {
+        <f64>::try_from(non_null!($expr.eval($record)?).coerce_to(&DfType::Double, $expr.ty())?)?
+    };
+}
+
 /// Returns the type of data stored in a JSON value as a string.
 fn get_json_value_type(json: &serde_json::Value) -> &'static str {
     match json {
@@ -348,6 +378,323 @@ where
     Ok(try_cast_or_none!(res, ty, res_ty))
 }
 
+/// Pads `s` to `len` characters by repeating `fill` on the left (if `left` is true) or the right of
+/// the string, truncating `s` to `len` characters if it's already longer.
+fn pad(s: &str, len: i64, fill: &str, dialect: Dialect, left: bool) -> DfValue {
+    let is_mysql = dialect.engine() == SqlEngine::MySQL;
+    if len < 0 {
+        // MySQL returns NULL for negative lengths, whereas PostgreSQL treats them as zero
+        return if is_mysql { DfValue::None } else { "".into() };
+    }
+
+    let len = len as usize;
+    let s_len = s.chars().count();
+    if len <= s_len {
+        return s.chars().take(len).collect::<String>().into();
+    }
+
+    if fill.is_empty() {
+        return if is_mysql { DfValue::None } else { s.into() };
+    }
+
+    let padding = fill.chars().cycle().take(len - s_len);
+    if left {
+        padding.chain(s.chars()).collect::<String>().into()
+    } else {
+        s.chars().chain(padding).collect::<String>().into()
+    }
+}
+
+/// Returns the first (if `left` is true) or last `n` characters of `s`.
+///
+/// If `n` is negative, MySQL returns an empty string, whereas PostgreSQL returns all but the last
+/// (or first) `-n` characters.
+fn left_or_right(s: &str, n: i64, dialect: Dialect, left: bool) -> DfValue {
+    let s_len = s.chars().count() as i64;
+    let take = if n >= 0 {
+        n.min(s_len)
+    } else if dialect.engine() == SqlEngine::MySQL {
+        0
+    } else {
+        (s_len + n).max(0)
+    };
+
+    if left {
+        s.chars().take(take as usize).collect::<String>().into()
+    } else {
+        s.chars()
+            .skip((s_len - take) as usize)
+            .collect::<String>()
+            .into()
+    }
+}
+
+/// Translates a [PostgreSQL regular expression replacement string][pg-docs], which refers to
+/// capture groups with `\1` through `\9` and to the whole match with `\&`, to the syntax used by
+/// [`regex::Regex::replace`].
+///
+/// [pg-docs]: https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP
+fn postgres_regex_replacement(replacement: &str) -> String {
+    let mut res = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(group @ '1'..='9') => {
+                    res.push_str("${");
+                    res.push(group);
+                    res.push('}');
+                }
+                Some('&') => res.push_str("${0}"),
+                Some('\\') => res.push('\\'),
+                Some('$') => res.push_str("\\$$"),
+                Some(c) => {
+                    res.push('\\');
+                    res.push(c);
+                }
+                None => res.push('\\'),
+            },
+            '$' => res.push_str("$$"),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Translates a [MySQL regular expression replacement string][mysql-docs], which refers to
+/// capture groups with `$1`, `$2`, etc. and escapes characters with `\`, to the syntax used by
+/// [`regex::Regex::replace`].
+///
+/// [mysql-docs]: https://dev.mysql.com/doc/refman/8.0/en/regexp.html#function_regexp-replace
+fn mysql_regex_replacement(replacement: &str) -> String {
+    let mut res = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('$') => res.push_str("$$"),
+                Some(c) => res.push(c),
+                None => {}
+            },
+            '$' => {
+                let mut group = String::new();
+                while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+                    group.push(digit);
+                }
+                if group.is_empty() {
+                    res.push_str("$$");
+                } else {
+                    write!(res, "${{{group}}}").unwrap();
+                }
+            }
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Coerces the given value to a timestamp, returning its local date and time along with its
+/// timezone offset, if it has one
+fn timestamp_with_offset(
+    value: &DfValue,
+    from_ty: &DfType,
+) -> ReadySetResult<(NaiveDateTime, Option<FixedOffset>)> {
+    let ts = match value {
+        DfValue::TimestampTz(ts) => *ts,
+        _ => {
+            // Coerce with the maximum precision, so that we don't lose any subsecond digits
+            let timestamp_ty = DfType::Timestamp {
+                subsecond_digits: 6,
+            };
+            match value.coerce_to(&timestamp_ty, from_ty)? {
+                DfValue::TimestampTz(ts) => ts,
+                _ => return Err(invalid_err!("Could not convert {value} to a timestamp")),
+            }
+        }
+    };
+    let datetime = ts.to_chrono();
+    Ok((
+        datetime.naive_local(),
+        ts.has_timezone().then_some(*datetime.offset()),
+    ))
+}
+
+/// Constructs a value of the given date or timestamp type from a local date and time, and the
+/// timezone offset it was originally in, if any
+fn timestamp_value(datetime: NaiveDateTime, offset: Option<FixedOffset>, ty: &DfType) -> DfValue {
+    match (ty, offset) {
+        (DfType::Date, _) => datetime.date().into(),
+        (_, Some(offset)) => offset
+            .from_local_datetime(&datetime)
+            .single()
+            .map(DfValue::from)
+            .unwrap_or_else(|| datetime.into()),
+        (_, None) => datetime.into(),
+    }
+}
+
+/// Adds the interval described by `interval` and `unit` to `date` (or subtracts it, if `subtract`
+/// is true), returning a value of type `ty`
+fn date_add(
+    date: &DfValue,
+    date_ty: &DfType,
+    interval: &str,
+    unit: Option<TimestampField>,
+    subtract: bool,
+    dialect: Dialect,
+    ty: &DfType,
+) -> ReadySetResult<DfValue> {
+    let interval = match (dialect.engine(), unit) {
+        (SqlEngine::MySQL, Some(unit)) => Interval::from_mysql_str(interval, unit)?,
+        (SqlEngine::MySQL, None) => internal!("MySQL intervals must have a unit"),
+        (SqlEngine::PostgreSQL, unit) => Interval::from_postgres_str(interval, unit)?,
+    };
+    let interval = if subtract { -interval } else { interval };
+
+    let (datetime, offset) = timestamp_with_offset(date, date_ty)?;
+    let datetime = interval
+        .add_to_datetime(datetime)
+        .ok_or_else(|| invalid_err!("timestamp out of range"))?;
+    Ok(timestamp_value(datetime, offset, ty))
+}
+
+/// Returns the week number of the given date using MySQL's default week mode (mode 0), in which
+/// weeks start on Sunday and days before the first Sunday of the year are in week 0
+fn mysql_week(date: NaiveDate) -> i64 {
+    let ordinal0 = date.ordinal0() as i64;
+    let first_weekday = (date.weekday().num_days_from_sunday() as i64 - ordinal0).rem_euclid(7);
+    let first_sunday = (7 - first_weekday) % 7;
+    if ordinal0 < first_sunday {
+        0
+    } else {
+        (ordinal0 - first_sunday) / 7 + 1
+    }
+}
+
+/// Extracts the given field from a local date and time (with an optional timezone offset)
+/// according to the rules for `EXTRACT` in the given dialect
+fn extract(
+    field: TimestampField,
+    datetime: NaiveDateTime,
+    offset: Option<FixedOffset>,
+    dialect: Dialect,
+) -> DfValue {
+    use TimestampField::*;
+
+    let is_mysql = dialect.engine() == SqlEngine::MySQL;
+    let micros = (datetime.nanosecond() / 1_000) as i64;
+    let second = datetime.second() as i64;
+    let minute = datetime.minute() as i64;
+    let hour = datetime.hour() as i64;
+    let day = datetime.day() as i64;
+    let month = datetime.month() as i64;
+    let year = datetime.year() as i64;
+    let time_of_day = hour * 10_000 + minute * 100 + second;
+
+    // The value of the field, along with the number of (decimal) digits of it which are
+    // fractional
+    let (value, scale) = match field {
+        Microsecond if is_mysql => (micros, 0),
+        Microsecond => (second * 1_000_000 + micros, 0),
+        Millisecond => (second * 1_000_000 + micros, 3),
+        Second if is_mysql => (second, 0),
+        Second => (second * 1_000_000 + micros, 6),
+        Minute => (minute, 0),
+        Hour => (hour, 0),
+        Day => (day, 0),
+        Week if is_mysql => (mysql_week(datetime.date()), 0),
+        Week => (datetime.iso_week().week() as i64, 0),
+        Month => (month, 0),
+        Quarter => ((month - 1) / 3 + 1, 0),
+        Year => (year, 0),
+        Decade => (year.div_euclid(10), 0),
+        Century if year > 0 => ((year + 99) / 100, 0),
+        Century => (-((99 - (year - 1)) / 100), 0),
+        Millennium if year > 0 => ((year + 999) / 1000, 0),
+        Millennium => (-((999 - (year - 1)) / 1000), 0),
+        Dow => (datetime.weekday().num_days_from_sunday() as i64, 0),
+        IsoDow => (datetime.weekday().number_from_monday() as i64, 0),
+        Doy => (datetime.ordinal() as i64, 0),
+        Epoch => {
+            let utc = match offset {
+                Some(offset) => datetime - Duration::seconds(offset.local_minus_utc() as i64),
+                None => datetime,
+            };
+            (utc.timestamp() * 1_000_000 + micros, 6)
+        }
+        SecondMicrosecond => (second * 1_000_000 + micros, 0),
+        MinuteMicrosecond => ((minute * 100 + second) * 1_000_000 + micros, 0),
+        MinuteSecond => (minute * 100 + second, 0),
+        HourMicrosecond => (time_of_day * 1_000_000 + micros, 0),
+        HourSecond => (time_of_day, 0),
+        HourMinute => (hour * 100 + minute, 0),
+        DayMicrosecond => ((day * 1_000_000 + time_of_day) * 1_000_000 + micros, 0),
+        DaySecond => (day * 1_000_000 + time_of_day, 0),
+        DayMinute => (day * 10_000 + hour * 100 + minute, 0),
+        DayHour => (day * 100 + hour, 0),
+        YearMonth => (year * 100 + month, 0),
+    };
+
+    if is_mysql {
+        DfValue::Int(value)
+    } else {
+        Decimal::new(value, scale).into()
+    }
+}
+
+/// Truncates the given date and time to the precision of `field`, as PostgreSQL's `date_trunc`
+/// does
+fn date_trunc(field: TimestampField, datetime: NaiveDateTime) -> ReadySetResult<NaiveDateTime> {
+    use TimestampField::*;
+
+    let (date, time) = (datetime.date(), datetime.time());
+    let (year, month) = (date.year(), date.month());
+    let (hour, minute, second) = (time.hour(), time.minute(), time.second());
+    let micros = time.nanosecond() / 1_000;
+    let midnight = NaiveTime::from_hms(0, 0, 0);
+    let first_day_of_year = |year: i32| {
+        NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| invalid_err!("timestamp out of range"))
+    };
+
+    let (date, time) = match field {
+        Microsecond => (
+            date,
+            NaiveTime::from_hms_micro(hour, minute, second, micros),
+        ),
+        Millisecond => (
+            date,
+            NaiveTime::from_hms_micro(hour, minute, second, micros / 1_000 * 1_000),
+        ),
+        Second => (date, NaiveTime::from_hms(hour, minute, second)),
+        Minute => (date, NaiveTime::from_hms(hour, minute, 0)),
+        Hour => (date, NaiveTime::from_hms(hour, 0, 0)),
+        Day => (date, midnight),
+        Week => (
+            date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            midnight,
+        ),
+        Month => (NaiveDate::from_ymd(year, month, 1), midnight),
+        Quarter => (
+            NaiveDate::from_ymd(year, (month - 1) / 3 * 3 + 1, 1),
+            midnight,
+        ),
+        Year => (first_day_of_year(year)?, midnight),
+        Decade => (first_day_of_year(year.div_euclid(10) * 10)?, midnight),
+        // Centuries and millennia start with the year ending in 1
+        Century => (
+            first_day_of_year((year - 1).div_euclid(100) * 100 + 1)?,
+            midnight,
+        ),
+        Millennium => (
+            first_day_of_year((year - 1).div_euclid(1000) * 1000 + 1)?,
+            midnight,
+        ),
+        _ => return Err(invalid_err!("unit {field} not supported for date_trunc")),
+    };
+
+    Ok(date.and_time(time))
+}
+
 impl BuiltinFunction {
     pub(crate) fn eval<D>(&self, ty: &DfType, record: &[D]) -> ReadySetResult<DfValue>
     where
@@ -566,8 +913,8 @@ impl BuiltinFunction {
                 }
             }
             BuiltinFunction::JsonTypeof(expr) => {
-                let json = non_null!(expr.eval(record)?).to_json()?;
-                Ok(get_json_value_type(&json).into())
+                let json = non_null!(expr.eval(record)?);
+                Ok(get_json_value_type(&json.as_json()?).into())
             }
             BuiltinFunction::JsonStripNulls(expr) => {
                 let mut json = non_null!(expr.eval(record)?).to_json()?;
@@ -575,19 +922,40 @@ impl BuiltinFunction {
                 Ok(json.into())
             }
             BuiltinFunction::JsonArrayLength(expr) => non_null!(expr.eval(record)?)
-                .to_json()?
+                .as_json()?
                 .as_array()
                 .map(|array| DfValue::from(array.len()))
                 .ok_or_else(|| invalid_err!("cannot get array length of a non-array")),
             BuiltinFunction::JsonExtractPath { json, keys } => {
-                let json = json.eval(record)?.to_json()?;
+                let json = json.eval(record)?;
+                let json = json.as_json()?;
 
                 let keys = keys
                     .iter()
                     .map(|key| key.eval(record))
                     .collect::<ReadySetResult<Vec<_>>>()?;
 
-                crate::eval::json::json_extract_key_path(&json, &keys)
+                // `json[b]_extract_path_text` is lowered with a text return type.
+                crate::eval::json::json_extract_key_path(&json, &keys, ty.is_any_text())
+            }
+            BuiltinFunction::JsonExtract { json, paths } => {
+                let json = non_null!(json.eval(record)?);
+                let json = json.as_json()?;
+
+                let paths = paths
+                    .iter()
+                    .map(|path| path.eval(record))
+                    .collect::<ReadySetResult<Vec<_>>>()?;
+                let mut path_strs = Vec::with_capacity(paths.len());
+                for path in &paths {
+                    path_strs.push(<&str>::try_from(non_null!(path))?);
+                }
+
+                crate::eval::json::json_extract_paths(&json, &path_strs)
+            }
+            BuiltinFunction::JsonUnquote(expr) => {
+                let json = non_null!(expr.eval(record)?);
+                Ok(crate::eval::json::json_unquote(<&str>::try_from(&json)?)?.into())
             }
             BuiltinFunction::JsonbInsert(target_json, key_path, inserted_json, insert_after) => {
                 let mut target_json = non_null!(target_json.eval(record)?).to_json()?;
@@ -716,6 +1084,310 @@ impl BuiltinFunction {
             BuiltinFunction::Least { args, compare_as } => {
                 greatest_or_least(args, record, compare_as, ty, |v1, v2| v1 < v2)
             }
+            BuiltinFunction::Lower(arg) => {
+                let string = eval_text!(arg, record);
+                Ok(<&str>::try_from(&string)?.to_lowercase().into())
+            }
+            BuiltinFunction::Upper(arg) => {
+                let string = eval_text!(arg, record);
+                Ok(<&str>::try_from(&string)?.to_uppercase().into())
+            }
+            BuiltinFunction::CharLength(arg) => {
+                let string = eval_text!(arg, record);
+                Ok(DfValue::from(<&str>::try_from(&string)?.chars().count()))
+            }
+            BuiltinFunction::OctetLength(arg) => match non_null!(arg.eval(record)?) {
+                DfValue::ByteArray(bytes) => Ok(DfValue::from(bytes.len())),
+                val => {
+                    let string = val.coerce_to(&DfType::DEFAULT_TEXT, arg.ty())?;
+                    Ok(DfValue::from(<&str>::try_from(&string)?.len()))
+                }
+            },
+            BuiltinFunction::Trim {
+                string,
+                chars,
+                side,
+            } => {
+                let string = eval_text!(string, record);
+                let chars = match chars {
+                    Some(chars) => eval_text!(chars, record),
+                    None => " ".into(),
+                };
+                let s = <&str>::try_from(&string)?;
+                let chars = <&str>::try_from(&chars)?.chars().collect::<Vec<_>>();
+
+                Ok(match side {
+                    TrimSide::Leading => s.trim_start_matches(&chars[..]),
+                    TrimSide::Trailing => s.trim_end_matches(&chars[..]),
+                    TrimSide::Both => s.trim_matches(&chars[..]),
+                }
+                .into())
+            }
+            BuiltinFunction::Replace(string, from, to) => {
+                let string = eval_text!(string, record);
+                let from = eval_text!(from, record);
+                let to = eval_text!(to, record);
+                let s = <&str>::try_from(&string)?;
+                let from = <&str>::try_from(&from)?;
+
+                if from.is_empty() {
+                    return Ok(s.into());
+                }
+
+                Ok(s.replace(from, (&to).try_into()?).into())
+            }
+            BuiltinFunction::Lpad {
+                string,
+                len,
+                fill,
+                dialect,
+            }
+            | BuiltinFunction::Rpad {
+                string,
+                len,
+                fill,
+                dialect,
+            } => {
+                let string = eval_text!(string, record);
+                let len = eval_int!(len, record);
+                let fill = match fill {
+                    Some(fill) => eval_text!(fill, record),
+                    None => " ".into(),
+                };
+
+                Ok(pad(
+                    (&string).try_into()?,
+                    len,
+                    (&fill).try_into()?,
+                    *dialect,
+                    matches!(self, BuiltinFunction::Lpad { .. }),
+                ))
+            }
+            BuiltinFunction::Left {
+                string,
+                len,
+                dialect,
+            }
+            | BuiltinFunction::Right {
+                string,
+                len,
+                dialect,
+            } => {
+                let string = eval_text!(string, record);
+                let len = eval_int!(len, record);
+
+                Ok(left_or_right(
+                    (&string).try_into()?,
+                    len,
+                    *dialect,
+                    matches!(self, BuiltinFunction::Left { .. }),
+                ))
+            }
+            BuiltinFunction::Md5(arg) => {
+                let digest = match non_null!(arg.eval(record)?) {
+                    DfValue::ByteArray(bytes) => md5::compute(bytes.as_slice()),
+                    val => {
+                        let string = val.coerce_to(&DfType::DEFAULT_TEXT, arg.ty())?;
+                        md5::compute(<&str>::try_from(&string)?)
+                    }
+                };
+                Ok(format!("{:x}", digest).into())
+            }
+            BuiltinFunction::RegexpReplace {
+                string,
+                pattern,
+                replacement,
+                flags,
+                dialect,
+            } => {
+                // Matching is case-insensitive if the string being matched against has a
+                // case-insensitive collation
+                let mut case_insensitive = matches!(
+                    string.ty().collation(),
+                    Some(
+                        Collation::Citext
+                            | Collation::Utf8AiCi
+                            | Collation::Utf8UnicodeCi
+                            | Collation::Utf8GeneralCi
+                    )
+                );
+                // MySQL always replaces all occurrences of the pattern, whereas PostgreSQL only
+                // replaces the first occurrence unless passed the `g` flag
+                let mut global = dialect.engine() == SqlEngine::MySQL;
+
+                let string = eval_text!(string, record);
+                let pattern = eval_text!(pattern, record);
+                let replacement = eval_text!(replacement, record);
+                if let Some(flags) = flags {
+                    let flags = eval_text!(flags, record);
+                    for flag in <&str>::try_from(&flags)?.chars() {
+                        match flag {
+                            'g' => global = true,
+                            'i' => case_insensitive = true,
+                            'c' => case_insensitive = false,
+                            _ => {
+                                return Err(invalid_err!(
+                                    "invalid regular expression option: \"{flag}\""
+                                ))
+                            }
+                        }
+                    }
+                }
+
+                let re = RegexBuilder::new((&pattern).try_into()?)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                    .map_err(|e| invalid_err!("invalid regular expression: {e}"))?;
+                let replacement = match dialect.engine() {
+                    SqlEngine::MySQL => mysql_regex_replacement((&replacement).try_into()?),
+                    SqlEngine::PostgreSQL => postgres_regex_replacement((&replacement).try_into()?),
+                };
+
+                let s = <&str>::try_from(&string)?;
+                Ok(if global {
+                    re.replace_all(s, replacement.as_str())
+                } else {
+                    re.replace(s, replacement.as_str())
+                }
+                .into_owned()
+                .into())
+            }
+            BuiltinFunction::Abs(arg) => match non_null!(arg.eval(record)?) {
+                DfValue::Int(i) => i
+                    .checked_abs()
+                    .map(DfValue::Int)
+                    .ok_or_else(|| invalid_err!("integer out of range")),
+                val @ DfValue::UnsignedInt(_) => Ok(val),
+                DfValue::Float(f) => Ok(DfValue::Float(f.abs())),
+                DfValue::Double(f) => Ok(DfValue::Double(f.abs())),
+                DfValue::Numeric(d) => Ok(DfValue::from(d.abs())),
+                val => {
+                    let f = <f64>::try_from(val.coerce_to(&DfType::Double, arg.ty())?)?;
+                    DfValue::try_from(f.abs())
+                }
+            },
+            BuiltinFunction::Floor(arg) | BuiltinFunction::Ceil(arg) => {
+                let floor = matches!(self, BuiltinFunction::Floor(_));
+                let res = match non_null!(arg.eval(record)?) {
+                    val @ (DfValue::Int(_) | DfValue::UnsignedInt(_)) => val,
+                    DfValue::Numeric(d) => DfValue::from(if floor { d.floor() } else { d.ceil() }),
+                    val => {
+                        let f = <f64>::try_from(val.coerce_to(&DfType::Double, arg.ty())?)?;
+                        DfValue::try_from(if floor { f.floor() } else { f.ceil() })?
+                    }
+                };
+
+                if ty.is_unknown() {
+                    Ok(res)
+                } else {
+                    let res_ty = res.infer_dataflow_type();
+                    res.coerce_to(ty, &res_ty)
+                }
+            }
+            BuiltinFunction::Mod {
+                dividend,
+                divisor,
+                dialect,
+            } => {
+                let division_by_zero = || match dialect.engine() {
+                    SqlEngine::MySQL => Ok(DfValue::None),
+                    SqlEngine::PostgreSQL => Err(invalid_err!("division by zero")),
+                };
+
+                if ty.is_any_int() {
+                    let dividend = eval_int!(dividend, record);
+                    let divisor = eval_int!(divisor, record);
+                    if divisor == 0 {
+                        return division_by_zero();
+                    }
+                    // The sign of the result matches the sign of the dividend in both MySQL and
+                    // PostgreSQL, as it does in Rust
+                    Ok(DfValue::Int(dividend.wrapping_rem(divisor)))
+                } else if matches!(ty, DfType::Numeric { .. }) {
+                    let dividend = Decimal::try_from(&non_null!(dividend.eval(record)?))?;
+                    let divisor = Decimal::try_from(&non_null!(divisor.eval(record)?))?;
+                    match dividend.checked_rem(divisor) {
+                        Some(res) => Ok(res.into()),
+                        None => division_by_zero(),
+                    }
+                } else {
+                    let dividend = eval_double!(dividend, record);
+                    let divisor = eval_double!(divisor, record);
+                    if divisor == 0.0 {
+                        return division_by_zero();
+                    }
+                    DfValue::try_from(dividend % divisor)
+                }
+            }
+            BuiltinFunction::Power {
+                base,
+                exponent,
+                dialect,
+            } => {
+                let base = eval_double!(base, record);
+                let exponent = eval_double!(exponent, record);
+                let res = base.powf(exponent);
+
+                if res.is_nan() {
+                    match dialect.engine() {
+                        SqlEngine::MySQL => Ok(DfValue::None),
+                        SqlEngine::PostgreSQL => Err(invalid_err!(
+                            "a negative number raised to a non-integer power yields a complex \
+                             result"
+                        )),
+                    }
+                } else if res.is_infinite() {
+                    Err(invalid_err!("value out of range: overflow"))
+                } else {
+                    DfValue::try_from(res)
+                }
+            }
+            BuiltinFunction::DateAdd {
+                date,
+                interval,
+                unit,
+                subtract,
+                dialect,
+            } => {
+                let date_value = non_null!(date.eval(record)?);
+                let interval_value = eval_text!(interval, record);
+                let res = date_add(
+                    &date_value,
+                    date.ty(),
+                    <&str>::try_from(&interval_value)?,
+                    *unit,
+                    *subtract,
+                    *dialect,
+                    ty,
+                );
+
+                match res {
+                    // MySQL returns NULL for invalid dates and intervals, rather than an error
+                    Err(_) if dialect.engine() == SqlEngine::MySQL => Ok(DfValue::None),
+                    res => res,
+                }
+            }
+            BuiltinFunction::Extract {
+                field,
+                expr,
+                dialect,
+            } => {
+                let value = non_null!(expr.eval(record)?);
+                match timestamp_with_offset(&value, expr.ty()) {
+                    Ok((datetime, offset)) => Ok(extract(*field, datetime, offset, *dialect)),
+                    Err(_) if dialect.engine() == SqlEngine::MySQL => Ok(DfValue::None),
+                    Err(e) => Err(e),
+                }
+            }
+            BuiltinFunction::DateTrunc(field, expr) => {
+                let value = non_null!(expr.eval(record)?);
+                let (datetime, offset) = timestamp_with_offset(&value, expr.ty())?;
+                Ok(timestamp_value(date_trunc(*field, datetime)?, offset, ty))
+            }
+            BuiltinFunction::FullTextMatch { document, query } => {
+                let document = eval_text!(document, record);
+                Ok(query.matches(<&str>::try_from(&document)?).into())
+            }
         }
     }
 }
@@ -731,8 +1403,8 @@ mod tests {
     use test_strategy::proptest;
 
     use super::*;
-    use crate::eval::tests::eval_expr;
-    use crate::lower::tests::resolve_columns;
+    use crate::eval::tests::{eval_expr, try_eval_expr};
+    use crate::lower::tests::{no_op_lower_context, resolve_columns};
     use crate::utils::{make_call, make_column, make_literal, strings_to_array_expr};
     use crate::Dialect;
 
@@ -1626,6 +2298,410 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lower_and_upper() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("lower('AbC')", dialect), "abc".into());
+            assert_eq!(eval_expr("upper('AbC')", dialect), "ABC".into());
+            assert_eq!(eval_expr("lower(null)", dialect), DfValue::None);
+        }
+        assert_eq!(eval_expr("lcase('AbC')", MySQL), "abc".into());
+        assert_eq!(eval_expr("ucase('AbC')", MySQL), "ABC".into());
+    }
+
+    #[test]
+    fn length() {
+        assert_eq!(eval_expr("length('héllo')", MySQL), 6.into());
+        assert_eq!(eval_expr("length('héllo')", PostgreSQL), 5.into());
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("char_length('héllo')", dialect), 5.into());
+            assert_eq!(eval_expr("octet_length('héllo')", dialect), 6.into());
+            assert_eq!(eval_expr("char_length(null)", dialect), DfValue::None);
+        }
+    }
+
+    #[test]
+    fn trim() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("trim('  abc  ')", dialect), "abc".into());
+            assert_eq!(eval_expr("ltrim('  abc  ')", dialect), "abc  ".into());
+            assert_eq!(eval_expr("rtrim('  abc  ')", dialect), "  abc".into());
+        }
+        assert_eq!(
+            eval_expr("btrim('xyabcyx', 'xy')", PostgreSQL),
+            "abc".into()
+        );
+        assert_eq!(
+            eval_expr("ltrim('xyabcyx', 'xy')", PostgreSQL),
+            "abcyx".into()
+        );
+        try_eval_expr("btrim('abc')", MySQL).unwrap_err();
+        try_eval_expr("ltrim('xyabc', 'xy')", MySQL).unwrap_err();
+    }
+
+    #[test]
+    fn replace() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(
+                eval_expr("replace('abcabc', 'b', 'XY')", dialect),
+                "aXYcaXYc".into()
+            );
+            assert_eq!(eval_expr("replace('abc', '', 'X')", dialect), "abc".into());
+            assert_eq!(
+                eval_expr("replace('abc', null, 'X')", dialect),
+                DfValue::None
+            );
+        }
+    }
+
+    #[test]
+    fn lpad_and_rpad() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("lpad('hi', 5, 'xy')", dialect), "xyxhi".into());
+            assert_eq!(eval_expr("rpad('hi', 5, 'xy')", dialect), "hixyx".into());
+            assert_eq!(eval_expr("lpad('hello', 2, 'x')", dialect), "he".into());
+            assert_eq!(eval_expr("rpad('hello', 2, 'x')", dialect), "he".into());
+        }
+
+        assert_eq!(eval_expr("lpad('hi', 4)", PostgreSQL), "  hi".into());
+        try_eval_expr("lpad('hi', 4)", MySQL).unwrap_err();
+
+        assert_eq!(eval_expr("lpad('hi', -1, 'x')", MySQL), DfValue::None);
+        assert_eq!(eval_expr("lpad('hi', -1, 'x')", PostgreSQL), "".into());
+        assert_eq!(eval_expr("rpad('hi', 4, '')", MySQL), DfValue::None);
+        assert_eq!(eval_expr("rpad('hi', 4, '')", PostgreSQL), "hi".into());
+    }
+
+    #[test]
+    fn left_and_right() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("left('abcde', 2)", dialect), "ab".into());
+            assert_eq!(eval_expr("right('abcde', 2)", dialect), "de".into());
+            assert_eq!(eval_expr("left('abcde', 10)", dialect), "abcde".into());
+            assert_eq!(eval_expr("right('abcde', 10)", dialect), "abcde".into());
+        }
+
+        assert_eq!(eval_expr("left('abcde', -2)", MySQL), "".into());
+        assert_eq!(eval_expr("right('abcde', -2)", MySQL), "".into());
+        assert_eq!(eval_expr("left('abcde', -2)", PostgreSQL), "abc".into());
+        assert_eq!(eval_expr("right('abcde', -2)", PostgreSQL), "cde".into());
+    }
+
+    #[test]
+    fn md5() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(
+                eval_expr("md5('abc')", dialect),
+                "900150983cd24fb0d6963f7d28e17f72".into()
+            );
+        }
+    }
+
+    #[test]
+    fn regexp_replace() {
+        assert_eq!(
+            eval_expr("regexp_replace('foobarbaz', 'ba.', 'X')", MySQL),
+            "fooXX".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('abc', '(b)', '[$1]')", MySQL),
+            "a[b]c".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('foobarbaz', 'ba.', 'X')", PostgreSQL),
+            "fooXbaz".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('foobarbaz', 'ba.', 'X', 'g')", PostgreSQL),
+            "fooXX".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('ABC', 'b', 'x', 'i')", PostgreSQL),
+            "AxC".into()
+        );
+        try_eval_expr("regexp_replace('abc', 'b', 'x', 'q')", PostgreSQL).unwrap_err();
+        try_eval_expr("regexp_replace('abc', '(', 'x')", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn regex_replacement_translation() {
+        assert_eq!(postgres_regex_replacement(r"X\1Y"), "X${1}Y");
+        assert_eq!(postgres_regex_replacement(r"<\&>"), "<${0}>");
+        assert_eq!(postgres_regex_replacement(r"a\\b$"), r"a\b$$");
+        assert_eq!(mysql_regex_replacement("X$1Y$12"), "X${1}Y${12}");
+        assert_eq!(mysql_regex_replacement(r"\$1 $"), "$$1 $$");
+    }
+
+    #[test]
+    fn abs() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("abs(-3)", dialect), 3.into());
+            assert_eq!(eval_expr("abs(3)", dialect), 3.into());
+            assert_eq!(
+                eval_expr("abs(-1.5)", dialect),
+                (1.5_f64).try_into().unwrap()
+            );
+            assert_eq!(eval_expr("abs(null)", dialect), DfValue::None);
+        }
+    }
+
+    #[test]
+    fn floor_and_ceil() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(
+                eval_expr("floor(-1.5)", dialect),
+                (-2.0_f64).try_into().unwrap()
+            );
+            assert_eq!(
+                eval_expr("ceil(1.2)", dialect),
+                (2.0_f64).try_into().unwrap()
+            );
+            assert_eq!(
+                eval_expr("ceiling(1.2)", dialect),
+                (2.0_f64).try_into().unwrap()
+            );
+        }
+
+        assert_eq!(eval_expr("floor(3)", MySQL), DfValue::Int(3));
+        assert_eq!(eval_expr("floor(3)", PostgreSQL), DfValue::Double(3.0));
+    }
+
+    #[test]
+    fn mod_function() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(eval_expr("mod(10, 3)", dialect), 1.into());
+            assert_eq!(eval_expr("mod(-10, 3)", dialect), (-1).into());
+            assert_eq!(
+                eval_expr("mod(5.5, 2)", dialect),
+                (1.5_f64).try_into().unwrap()
+            );
+        }
+
+        assert_eq!(eval_expr("mod(10, 0)", MySQL), DfValue::None);
+        try_eval_expr("mod(10, 0)", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn power() {
+        for dialect in [MySQL, PostgreSQL] {
+            assert_eq!(
+                eval_expr("power(2, 10)", dialect),
+                (1024.0_f64).try_into().unwrap()
+            );
+            assert_eq!(
+                eval_expr("pow(4, 0.5)", dialect),
+                (2.0_f64).try_into().unwrap()
+            );
+        }
+
+        assert_eq!(eval_expr("power(-8, 0.5)", MySQL), DfValue::None);
+        try_eval_expr("power(-8, 0.5)", PostgreSQL).unwrap_err();
+    }
+
+    #[track_caller]
+    fn lowering_fails(expr: &str, dialect: nom_sql::Dialect) {
+        let expr_dialect = match dialect {
+            MySQL => Dialect::DEFAULT_MYSQL,
+            PostgreSQL => Dialect::DEFAULT_POSTGRESQL,
+        };
+        Expr::lower(
+            parse_expr(dialect, expr).unwrap(),
+            expr_dialect,
+            no_op_lower_context(),
+        )
+        .unwrap_err();
+    }
+
+    fn datetime(s: &str) -> DfValue {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f")
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn date_add_mysql() {
+        assert_eq!(
+            eval_expr("date_add('2022-01-31 10:00:00', INTERVAL 1 MONTH)", MySQL),
+            datetime("2022-02-28 10:00:00")
+        );
+        assert_eq!(
+            eval_expr(
+                "date_sub('2022-03-01 00:00:00', INTERVAL '1:30' HOUR_MINUTE)",
+                MySQL
+            ),
+            datetime("2022-02-28 22:30:00")
+        );
+        assert_eq!(
+            eval_expr("'2022-12-31 23:59:59' + INTERVAL 1 SECOND", MySQL),
+            datetime("2023-01-01 00:00:00")
+        );
+        assert_eq!(
+            eval_expr("INTERVAL 2 DAY + '2022-01-01 00:00:00'", MySQL),
+            datetime("2022-01-03 00:00:00")
+        );
+        assert_eq!(
+            eval_expr("adddate('2022-01-01 00:00:00', 7)", MySQL),
+            datetime("2022-01-08 00:00:00")
+        );
+        assert_eq!(
+            eval_expr("subdate('2022-01-01 00:00:00', INTERVAL 1 YEAR)", MySQL),
+            datetime("2021-01-01 00:00:00")
+        );
+        assert_eq!(
+            eval_expr("date_add('not a date', INTERVAL 1 DAY)", MySQL),
+            DfValue::None
+        );
+        assert_eq!(
+            eval_expr("date_add('2022-01-01', INTERVAL 'x' DAY)", MySQL),
+            DfValue::None
+        );
+        assert_eq!(
+            eval_expr("date_add(null, INTERVAL 1 DAY)", MySQL),
+            DfValue::None
+        );
+    }
+
+    #[test]
+    fn date_add_mysql_date_column() {
+        let expr = Expr::lower(
+            parse_expr(MySQL, "d + INTERVAL 1 DAY").unwrap(),
+            Dialect::DEFAULT_MYSQL,
+            resolve_columns(|_| Ok((0, DfType::Date))),
+        )
+        .unwrap();
+        assert_eq!(*expr.ty(), DfType::Date);
+        assert_eq!(
+            expr.eval::<DfValue>(&[NaiveDate::from_ymd(2022, 2, 28).into()])
+                .unwrap(),
+            NaiveDate::from_ymd(2022, 3, 1).into()
+        );
+
+        let expr = Expr::lower(
+            parse_expr(MySQL, "d + INTERVAL 1 HOUR").unwrap(),
+            Dialect::DEFAULT_MYSQL,
+            resolve_columns(|_| Ok((0, DfType::Date))),
+        )
+        .unwrap();
+        assert!(matches!(expr.ty(), DfType::DateTime { .. }));
+        assert_eq!(
+            expr.eval::<DfValue>(&[NaiveDate::from_ymd(2022, 2, 28).into()])
+                .unwrap(),
+            datetime("2022-02-28 01:00:00")
+        );
+    }
+
+    #[test]
+    fn date_add_postgres() {
+        assert_eq!(
+            eval_expr(
+                "'2022-01-31 10:00:00'::timestamp + interval '1 month 2 hours'",
+                PostgreSQL
+            ),
+            datetime("2022-02-28 12:00:00")
+        );
+        assert_eq!(
+            eval_expr("'2022-03-01'::date - interval '7 days'", PostgreSQL),
+            datetime("2022-02-22 00:00:00")
+        );
+        assert_eq!(
+            eval_expr(
+                "'2022-01-01 00:00:00'::timestamp - interval '1' hour",
+                PostgreSQL
+            ),
+            datetime("2021-12-31 23:00:00")
+        );
+        try_eval_expr(
+            "'2022-01-01'::timestamp + interval '1 fortnight'",
+            PostgreSQL,
+        )
+        .unwrap_err();
+        lowering_fails("interval '1 day'", PostgreSQL);
+    }
+
+    #[test]
+    fn extract_mysql() {
+        let ts = "'2022-03-04 05:06:07.5'";
+        for (field, expected) in [
+            ("YEAR", 2022),
+            ("QUARTER", 1),
+            ("MONTH", 3),
+            ("WEEK", 9),
+            ("DAY", 4),
+            ("HOUR", 5),
+            ("MINUTE", 6),
+            ("SECOND", 7),
+            ("MICROSECOND", 500000),
+            ("YEAR_MONTH", 202203),
+            ("DAY_HOUR", 405),
+            ("HOUR_SECOND", 50607),
+            ("SECOND_MICROSECOND", 7500000),
+        ] {
+            assert_eq!(
+                eval_expr(&format!("extract({field} FROM {ts})"), MySQL),
+                DfValue::Int(expected),
+                "EXTRACT({field})"
+            );
+        }
+        assert_eq!(
+            eval_expr("extract(year from 'not a date')", MySQL),
+            DfValue::None
+        );
+        lowering_fails("extract(epoch from '2022-03-04')", MySQL);
+    }
+
+    #[test]
+    fn extract_postgres() {
+        let ts = "'2022-03-04 05:06:07.5'::timestamp";
+        for (field, expected) in [
+            ("year", Decimal::new(2022, 0)),
+            ("month", Decimal::new(3, 0)),
+            ("day", Decimal::new(4, 0)),
+            ("hour", Decimal::new(5, 0)),
+            ("second", Decimal::new(75, 1)),
+            ("milliseconds", Decimal::new(7500, 0)),
+            ("microseconds", Decimal::new(7500000, 0)),
+            ("week", Decimal::new(9, 0)),
+            ("dow", Decimal::new(5, 0)),
+            ("isodow", Decimal::new(5, 0)),
+            ("doy", Decimal::new(63, 0)),
+            ("century", Decimal::new(21, 0)),
+            ("epoch", Decimal::new(16463703675, 1)),
+        ] {
+            assert_eq!(
+                eval_expr(&format!("extract({field} FROM {ts})"), PostgreSQL),
+                expected.into(),
+                "EXTRACT({field})"
+            );
+        }
+        lowering_fails("extract(year_month from '2022-03-04'::date)", PostgreSQL);
+    }
+
+    #[test]
+    fn date_trunc() {
+        let ts = "'2022-05-18 13:45:30.123456'::timestamp";
+        for (field, expected) in [
+            ("milliseconds", "2022-05-18 13:45:30.123"),
+            ("second", "2022-05-18 13:45:30"),
+            ("minute", "2022-05-18 13:45:00"),
+            ("hour", "2022-05-18 13:00:00"),
+            ("day", "2022-05-18 00:00:00"),
+            ("week", "2022-05-16 00:00:00"),
+            ("month", "2022-05-01 00:00:00"),
+            ("quarter", "2022-04-01 00:00:00"),
+            ("year", "2022-01-01 00:00:00"),
+            ("decade", "2020-01-01 00:00:00"),
+            ("century", "2001-01-01 00:00:00"),
+        ] {
+            assert_eq!(
+                eval_expr(&format!("date_trunc('{field}', {ts})"), PostgreSQL),
+                datetime(expected),
+                "date_trunc('{field}')"
+            );
+        }
+        lowering_fails(&format!("date_trunc('fortnight', {ts})"), PostgreSQL);
+        lowering_fails("date_trunc('hour', '2022-05-18 13:45:30')", MySQL);
+    }
+
     #[track_caller]
     fn date_format(time: &str, fmt: &str) -> DfValue {
         lazy_static! {
@@ -1833,12 +2909,12 @@ mod tests {
         #[test]
         fn json_extract_path() {
             #[track_caller]
-            fn test(object: &str, keys: &str, expected: Option<&str>) {
-                for f in [
-                    "json_extract_path",
-                    "jsonb_extract_path",
-                    "json_extract_path_text",
-                    "jsonb_extract_path_text",
+            fn test(object: &str, keys: &str, expected: Option<&str>, expected_text: Option<&str>) {
+                for (f, expected) in [
+                    ("json_extract_path", expected),
+                    ("jsonb_extract_path", expected),
+                    ("json_extract_path_text", expected_text),
+                    ("jsonb_extract_path_text", expected_text),
                 ] {
                     let expr = format!("{f}('{object}', {keys})");
                     assert_eq!(
@@ -1849,27 +2925,105 @@ mod tests {
                 }
             }
 
-            let array = "[[\"world\", 123]]";
+            let array = "[[\"world\", 123, null]]";
 
-            test(array, "'1'", None);
-            test(array, "null::text", None);
+            test(array, "'1'", None, None);
+            test(array, "null::text", None, None);
 
-            test(array, "'0', '0'", Some("\"world\""));
-            test(array, "'0', '1'", Some("123"));
-            test(array, "'0', '2'", None);
-            test(array, "'0', null::text", None);
+            test(array, "'0', '0'", Some("\"world\""), Some("world"));
+            test(array, "'0', '1'", Some("123"), Some("123"));
+            test(array, "'0', '2'", Some("null"), None);
+            test(array, "'0', '3'", None, None);
+            test(array, "'0', null::text", None, None);
 
             let object = r#"{ "hello": ["world"], "abc": [123] }"#;
 
-            test(object, "null::text", None);
-            test(object, "'world'", None);
+            test(object, "null::text", None, None);
+            test(object, "'world'", None, None);
+
+            test(object, "'hello', '0'", Some("\"world\""), Some("world"));
+            test(object, "'hello', '1'", None, None);
+            test(object, "'hello', null::text", None, None);
+
+            test(object, "'abc'::char(3), '0'", Some("123"), Some("123"));
+            test(object, "'abc'::char(3), null::text", None, None);
+        }
+
+        #[test]
+        fn json_extract() {
+            #[track_caller]
+            fn test(json: &str, paths: &str, expected: Option<&str>) {
+                let expr = format!("json_extract('{json}', {paths})");
+                assert_eq!(
+                    eval_expr(&expr, MySQL),
+                    expected.into(),
+                    "incorrect result for `{expr}`"
+                );
+            }
 
-            test(object, "'hello', '0'", Some("\"world\""));
-            test(object, "'hello', '1'", None);
-            test(object, "'hello', null::text", None);
+            let json = r#"{"a": {"b": [10, 20, {"c": "x"}]}, "d e": true, "f": null}"#;
+
+            test(json, "'$'", Some(json));
+            test(json, "'$.a.b[0]'", Some("10"));
+            test(json, "'$.a.b[last]'", Some(r#"{"c": "x"}"#));
+            test(json, "'$.a.b[last-1]'", Some("20"));
+            test(json, "'$.a.b[last-3]'", None);
+            test(json, "'$.a.b[2].c'", Some("\"x\""));
+            test(json, "'$.\"d e\"'", Some("true"));
+            test(json, "'$.f'", Some("null"));
+            test(json, "'$.missing'", None);
+            test(json, "null", None);
+
+            // Non-array values are treated as single-element arrays.
+            test(json, "'$.a[0].b[0]'", Some("10"));
+            test(json, "'$.a[1]'", None);
+
+            // Wildcards, ranges, and multiple paths wrap the results in an array.
+            test(json, "'$.a.b[*]'", Some(r#"[10, 20, {"c": "x"}]"#));
+            test(json, "'$.a.b[0 to 1]'", Some("[10, 20]"));
+            test(json, "'$.a.b[1 to last]'", Some(r#"[20, {"c": "x"}]"#));
+            test(json, "'$.a.*[0]'", Some("[10]"));
+            test(json, "'$**.c'", Some(r#"["x"]"#));
+            test(json, "'$.a.b[0]', '$.a.b[1]'", Some("[10, 20]"));
+            test(json, "'$.a.b[0]', '$.missing'", Some("[10]"));
+            test(json, "'$.a.b[5]', '$.missing'", None);
+            test(json, "'$.a.b[*].missing'", None);
+            test(json, "'$.a.b[0]', null", None);
+
+            for path in [
+                "'a'",
+                "'$.'",
+                "'$[x]'",
+                "'$**'",
+                "'$.a.b[last-]'",
+                "'$.\"a'",
+            ] {
+                try_eval_expr(&format!("json_extract('{json}', {path})"), MySQL).unwrap_err();
+            }
+            try_eval_expr("json_extract('not json', '$')", MySQL).unwrap_err();
+        }
+
+        #[test]
+        fn json_unquote() {
+            #[track_caller]
+            fn test(value: &str, expected: Option<&str>) {
+                let expr = format!("json_unquote({value})");
+                assert_eq!(
+                    eval_expr(&expr, MySQL),
+                    expected.into(),
+                    "incorrect result for `{expr}`"
+                );
+            }
+
+            test("'\"abc\"'", Some("abc"));
+            test(r#"'"a\\tb"'"#, Some("a\tb"));
+            test("'abc'", Some("abc"));
+            test("'[1, 2]'", Some("[1, 2]"));
+            test("'\"'", Some("\""));
+            test("null", None);
+            test("json_extract('{\"a\": \"b\"}', '$.a')", Some("b"));
 
-            test(object, "'abc'::char(3), '0'", Some("123"));
-            test(object, "'abc'::char(3), null::text", None);
+            try_eval_expr(r#"json_unquote('"\\x"')"#, MySQL).unwrap_err();
         }
 
         mod jsonb_insert {
@@ -1,9 +1,10 @@
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
-use std::mem;
+use std::{io, mem};
 
 use readyset_data::DfValue;
-use readyset_errors::{invalid_err, ReadySetError, ReadySetResult};
+use readyset_errors::{internal_err, invalid_err, ReadySetError, ReadySetResult};
+use serde::Serialize;
 use serde_json::{Number as JsonNumber, Value as JsonValue};
 
 use crate::utils;
@@ -161,11 +162,12 @@ pub(crate) fn json_strip_nulls(json: &mut JsonValue) {
 /// Returns [`DfValue::None`] if the lookup fails or
 /// [`ReadySetError`](readyset_errors::ReadySetError) for non-string keys.
 ///
-/// All key/index path extraction operations behave the same in PostgreSQL except for the return
-/// type, which is handled during expression lowering.
+/// If `as_text` is `true`, the result is converted with [`json_to_text`], as done by `#>>` and
+/// `json[b]_extract_path_text`.
 pub(crate) fn json_extract_key_path<'k>(
     mut json: &JsonValue,
     keys: impl IntoIterator<Item = &'k DfValue>,
+    as_text: bool,
 ) -> ReadySetResult<DfValue> {
     // `json` is reassigned to inner fields while looping through keys.
 
@@ -193,7 +195,11 @@ pub(crate) fn json_extract_key_path<'k>(
         }
     }
 
-    Ok(json.to_string().into())
+    if as_text {
+        Ok(json_to_text(json))
+    } else {
+        Ok(json.to_string().into())
+    }
 }
 
 pub(crate) fn json_insert<'k>(
@@ -230,6 +236,306 @@ pub(crate) fn json_insert<'k>(
 }
 
 /// Returns a mutable reference to an inner JSON value located at the end of a key path.
+/// Converts a JSON value to text the way PostgreSQL's text-returning extraction operators and
+/// functions do: strings are returned without quotes and JSON `null` becomes SQL `NULL`.
+pub(crate) fn json_to_text(json: &JsonValue) -> DfValue {
+    match json {
+        JsonValue::Null => DfValue::None,
+        JsonValue::String(s) => s.as_str().into(),
+        _ => json.to_string().into(),
+    }
+}
+
+/// Extracts values from JSON using [MySQL `JSON_EXTRACT`](https://dev.mysql.com/doc/refman/8.0/en/json-search-functions.html#function_json-extract)
+/// semantics.
+///
+/// A single path without wildcards or ranges returns the matched value as-is. Otherwise, all
+/// matches are wrapped in an array. Returns [`DfValue::None`] if nothing matches.
+pub(crate) fn json_extract_paths(json: &JsonValue, paths: &[&str]) -> ReadySetResult<DfValue> {
+    let paths = paths
+        .iter()
+        .map(|path| parse_mysql_json_path(path))
+        .collect::<ReadySetResult<Vec<_>>>()?;
+
+    let mut matches = Vec::new();
+    for path in &paths {
+        json_path_matches(json, path, &mut matches);
+    }
+
+    let wrap_matches = paths.len() > 1 || paths.iter().flatten().any(MySqlJsonPathLeg::is_wildcard);
+
+    let result = match matches.first() {
+        None => return Ok(DfValue::None),
+        Some(&first) if !wrap_matches => first.clone(),
+        Some(_) => JsonValue::Array(matches.into_iter().cloned().collect()),
+    };
+
+    Ok(mysql_json_to_string(&result)?.into())
+}
+
+/// Removes the quotes from a JSON string using [MySQL `JSON_UNQUOTE`](https://dev.mysql.com/doc/refman/8.0/en/json-modification-functions.html#function_json-unquote)
+/// semantics. Anything that isn't a quoted string is returned unchanged.
+pub(crate) fn json_unquote(json: &str) -> ReadySetResult<String> {
+    if json.len() < 2 || !json.starts_with('"') || !json.ends_with('"') {
+        return Ok(json.to_owned());
+    }
+
+    serde_json::from_str(json)
+        .map_err(|_| invalid_err!("Invalid JSON text in argument 1 to function json_unquote"))
+}
+
+/// Serializes JSON the same way MySQL displays JSON values, which separates elements with `", "`
+/// and keys from values with `": "`.
+pub(crate) fn mysql_json_to_string(json: &JsonValue) -> ReadySetResult<String> {
+    let mut buf = Vec::new();
+    json.serialize(&mut serde_json::Serializer::with_formatter(
+        &mut buf,
+        MySqlJsonFormatter,
+    ))
+    .map_err(|e| internal_err!("Could not serialize JSON: {e}"))?;
+
+    String::from_utf8(buf).map_err(|e| internal_err!("Serialized JSON is not UTF-8: {e}"))
+}
+
+struct MySqlJsonFormatter;
+
+impl serde_json::ser::Formatter for MySqlJsonFormatter {
+    fn begin_array_value<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b": ")
+    }
+}
+
+/// A single leg of a [MySQL JSON path](https://dev.mysql.com/doc/refman/8.0/en/json.html#json-path-syntax).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MySqlJsonPathLeg {
+    /// `.key` or `."key"`
+    Member(String),
+    /// `.*`
+    MemberWildcard,
+    /// `[n]`, `[last]`, or `[last-n]`
+    Index(MySqlJsonPathIndex),
+    /// `[m to n]`
+    Range(MySqlJsonPathIndex, MySqlJsonPathIndex),
+    /// `[*]`
+    IndexWildcard,
+    /// `**`
+    DoubleWildcard,
+}
+
+impl MySqlJsonPathLeg {
+    /// Returns `true` if this leg can match more than one value.
+    fn is_wildcard(&self) -> bool {
+        !matches!(self, Self::Member(_) | Self::Index(_))
+    }
+}
+
+/// An array position within a [`MySqlJsonPathLeg`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MySqlJsonPathIndex {
+    /// `n`
+    FromStart(usize),
+    /// `last-n`
+    FromEnd(usize),
+}
+
+impl MySqlJsonPathIndex {
+    /// Resolves the position within an array of length `len`, or `None` if it falls before the
+    /// start of the array.
+    fn resolve(self, len: usize) -> Option<usize> {
+        match self {
+            Self::FromStart(index) => Some(index),
+            Self::FromEnd(offset) => len.checked_sub(1)?.checked_sub(offset),
+        }
+    }
+}
+
+/// Parses a MySQL JSON path expression such as `$.a[1]."b c"`.
+fn parse_mysql_json_path(path: &str) -> ReadySetResult<Vec<MySqlJsonPathLeg>> {
+    use MySqlJsonPathLeg::*;
+
+    let invalid = || invalid_err!("Invalid JSON path expression: {path}");
+
+    let parse_index = |index: &str| -> ReadySetResult<MySqlJsonPathIndex> {
+        let index = index.trim();
+        match index.strip_prefix("last") {
+            Some("") => Ok(MySqlJsonPathIndex::FromEnd(0)),
+            Some(offset) => offset
+                .trim_start()
+                .strip_prefix('-')
+                .and_then(|offset| offset.trim_start().parse().ok())
+                .map(MySqlJsonPathIndex::FromEnd)
+                .ok_or_else(invalid),
+            None => index
+                .parse()
+                .map(MySqlJsonPathIndex::FromStart)
+                .map_err(|_| invalid()),
+        }
+    };
+
+    let mut rest = path.trim_start().strip_prefix('$').ok_or_else(invalid)?;
+    let mut legs = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+
+        if let Some(member) = rest.strip_prefix('.') {
+            let member = member.trim_start();
+
+            if let Some(after) = member.strip_prefix('*') {
+                legs.push(MemberWildcard);
+                rest = after;
+            } else if member.starts_with('"') {
+                let len = quoted_string_len(member).ok_or_else(invalid)?;
+                let key = serde_json::from_str(&member[..len]).map_err(|_| invalid())?;
+                legs.push(Member(key));
+                rest = &member[len..];
+            } else {
+                let len = member
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .unwrap_or(member.len());
+                if len == 0 {
+                    return Err(invalid());
+                }
+                legs.push(Member(member[..len].to_owned()));
+                rest = &member[len..];
+            }
+        } else if let Some(index) = rest.strip_prefix('[') {
+            let (index, after) = index.split_once(']').ok_or_else(invalid)?;
+            let index = index.trim();
+
+            legs.push(if index == "*" {
+                IndexWildcard
+            } else if let Some((from, to)) = index.split_once(" to ") {
+                Range(parse_index(from)?, parse_index(to)?)
+            } else {
+                Index(parse_index(index)?)
+            });
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("**") {
+            legs.push(DoubleWildcard);
+            rest = after;
+        } else if rest.is_empty() {
+            break;
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    // `**` must be followed by another leg.
+    if legs.last() == Some(&DoubleWildcard) {
+        return Err(invalid());
+    }
+
+    Ok(legs)
+}
+
+/// Returns the length of the double-quoted string at the start of `s`, including both quotes.
+fn quoted_string_len(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Collects all values within `json` that match the MySQL JSON path `legs`.
+fn json_path_matches<'a>(
+    json: &'a JsonValue,
+    legs: &[MySqlJsonPathLeg],
+    matches: &mut Vec<&'a JsonValue>,
+) {
+    use MySqlJsonPathLeg::*;
+
+    let Some((leg, rest)) = legs.split_first() else {
+        matches.push(json);
+        return;
+    };
+
+    // MySQL treats non-array values as single-element arrays when indexing into them.
+    let array = match json {
+        JsonValue::Array(array) => array.as_slice(),
+        _ => std::slice::from_ref(json),
+    };
+
+    match leg {
+        Member(key) => {
+            if let Some(inner) = json.as_object().and_then(|object| object.get(key)) {
+                json_path_matches(inner, rest, matches);
+            }
+        }
+        MemberWildcard => {
+            for inner in json
+                .as_object()
+                .into_iter()
+                .flat_map(|object| object.values())
+            {
+                json_path_matches(inner, rest, matches);
+            }
+        }
+        Index(index) => {
+            if let Some(inner) = index.resolve(array.len()).and_then(|i| array.get(i)) {
+                json_path_matches(inner, rest, matches);
+            }
+        }
+        Range(from, to) => {
+            let from = from.resolve(array.len()).unwrap_or(0);
+            if let Some(to) = to.resolve(array.len()) {
+                for inner in array.iter().take(to + 1).skip(from) {
+                    json_path_matches(inner, rest, matches);
+                }
+            }
+        }
+        IndexWildcard => {
+            for inner in json.as_array().into_iter().flatten() {
+                json_path_matches(inner, rest, matches);
+            }
+        }
+        DoubleWildcard => {
+            // `**` matches the current value as well as every value nested within it.
+            json_path_matches(json, rest, matches);
+            match json {
+                JsonValue::Array(array) => array
+                    .iter()
+                    .for_each(|inner| json_path_matches(inner, legs, matches)),
+                JsonValue::Object(object) => object
+                    .values()
+                    .for_each(|inner| json_path_matches(inner, legs, matches)),
+                _ => {}
+            }
+        }
+    }
+}
+
 fn json_find_mut<K>(mut json: &mut JsonValue, key_path: K) -> ReadySetResult<Option<&mut JsonValue>>
 where
     K: IntoIterator,
@@ -436,4 +742,36 @@ mod tests {
         launchpad::eq_laws!(OwnedJsonScalar);
         launchpad::hash_laws!(OwnedJsonScalar);
     }
+
+    #[test]
+    fn parse_mysql_json_path() {
+        use MySqlJsonPathIndex::*;
+        use MySqlJsonPathLeg::*;
+
+        assert_eq!(super::parse_mysql_json_path("$").unwrap(), vec![]);
+        assert_eq!(
+            super::parse_mysql_json_path(r#" $ .a."b \"c\"" [1][last - 2][*].*[0 to last]**.d"#)
+                .unwrap(),
+            vec![
+                Member("a".into()),
+                Member("b \"c\"".into()),
+                Index(FromStart(1)),
+                Index(FromEnd(2)),
+                IndexWildcard,
+                MemberWildcard,
+                Range(FromStart(0), FromEnd(0)),
+                DoubleWildcard,
+                Member("d".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn mysql_json_to_string() {
+        let json = serde_json::json!({"a": [1, "b", {"c": null}], "d": {}});
+        assert_eq!(
+            super::mysql_json_to_string(&json).unwrap(),
+            r#"{"a": [1, "b", {"c": null}], "d": {}}"#
+        );
+    }
 }
@@ -0,0 +1,64 @@
+use readyset_data::{DfType, DfValue};
+use readyset_errors::ReadySetResult;
+
+use crate::BinaryOperator;
+
+/// Returns `true` if the operand `val` of type `ty` should be interpreted as a range rather than as
+/// an element of a range.
+///
+/// Like PostgreSQL, operands of unknown type which are strings are interpreted as range literals.
+fn is_range_operand(val: &DfValue, ty: &DfType) -> bool {
+    ty.is_range()
+        || (ty.is_unknown() && (matches!(val, DfValue::Range(_)) || val.as_str().is_some()))
+}
+
+/// Coerces the operand `val` of type `ty` to a range of the given `subtype`, parsing it if it's a
+/// range literal.
+fn to_range(val: &DfValue, ty: &DfType, subtype: &DfType) -> ReadySetResult<DfValue> {
+    val.coerce_to(&DfType::Range(Box::new(subtype.clone())), ty)
+}
+
+/// Evaluates one of the range operators ([`BinaryOperator::RangeContains`],
+/// [`BinaryOperator::RangeContainedIn`], or [`BinaryOperator::RangeOverlaps`]) on the given
+/// non-null operands.
+pub(crate) fn eval_range_op(
+    op: BinaryOperator,
+    left: &DfValue,
+    left_ty: &DfType,
+    right: &DfValue,
+    right_ty: &DfType,
+) -> ReadySetResult<DfValue> {
+    // Operands of unknown type take on the subtype of the other side
+    let subtype = left_ty
+        .range_subtype()
+        .or_else(|| right_ty.range_subtype())
+        .cloned()
+        .unwrap_or_default();
+
+    // `<@` is just `@>` with its operands swapped
+    let (container, container_ty, contained, contained_ty) = match op {
+        BinaryOperator::RangeContainedIn => (right, right_ty, left, left_ty),
+        _ => (left, left_ty, right, right_ty),
+    };
+    let container = to_range(container, container_ty, &subtype)?;
+    let container = container.as_range()?;
+
+    let res = if op != BinaryOperator::RangeOverlaps && !is_range_operand(contained, contained_ty) {
+        let element = if subtype.is_known() {
+            contained.coerce_to(&subtype, contained_ty)?
+        } else {
+            contained.clone()
+        };
+        container.contains_value(&element)
+    } else {
+        let other = to_range(contained, contained_ty, &subtype)?;
+        let other = other.as_range()?;
+        if op == BinaryOperator::RangeOverlaps {
+            container.overlaps(other)
+        } else {
+            container.contains_range(other)
+        }
+    };
+
+    Ok(res.into())
+}
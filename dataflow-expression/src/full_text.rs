@@ -0,0 +1,448 @@
+//! Implementation of the semantics of full-text search predicates
+//!
+//! The supported predicates are:
+//!
+//! * MySQL's `MATCH (col) AGAINST (query)`, in either natural language or boolean mode
+//! * PostgreSQL's `to_tsvector('simple', col) @@ to_tsquery('simple', query)`, and the same with
+//!   `plainto_tsquery`
+//!
+//! Documents are split into tokens on every character which isn't alphanumeric or `_`, and tokens
+//! are lowercased. Full-text search predicates are executed by looking up a single token of the
+//! search query in a reader with a full-text index, then filtering the rows returned by that
+//! lookup with [`FullTextQuery::matches`].
+
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use itertools::Itertools;
+use nom_sql::{
+    BinaryOperator, Column, Expr as AstExpr, FunctionExpr, Literal, MatchSearchModifier,
+};
+use readyset_errors::{unsupported, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+/// Words which are ignored in MySQL full-text search queries - this is InnoDB's default stopword
+/// list
+const MYSQL_STOPWORDS: &[&str] = &[
+    "a", "about", "an", "are", "as", "at", "be", "by", "com", "de", "en", "for", "from", "how",
+    "i", "in", "is", "it", "la", "of", "on", "or", "that", "the", "this", "to", "was", "what",
+    "when", "where", "who", "will", "with", "und", "www",
+];
+
+/// Words shorter than this are ignored in MySQL full-text search queries - this is the default
+/// value of `innodb_ft_min_token_size`
+const MYSQL_MIN_TOKEN_SIZE: usize = 3;
+
+/// Split the given text into the distinct tokens used for full-text search
+pub fn tokenize(text: &str) -> HashSet<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn is_mysql_ignored_word(token: &str) -> bool {
+    token.chars().count() < MYSQL_MIN_TOKEN_SIZE || MYSQL_STOPWORDS.contains(&token)
+}
+
+/// Tokenize a single term of a search query, returning an error if it consists of more than one
+/// token
+fn single_token(term: &str) -> ReadySetResult<Option<String>> {
+    let mut tokens = tokenize(term).into_iter();
+    match (tokens.next(), tokens.next()) {
+        (token, None) => Ok(token),
+        _ => unsupported!("Unsupported term in full-text search query: {term}"),
+    }
+}
+
+/// The syntax of a full-text search query string
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FullTextSyntax {
+    /// MySQL's natural language mode, the default for `MATCH ... AGAINST`
+    MySqlNaturalLanguage,
+    /// MySQL's `IN BOOLEAN MODE`
+    MySqlBoolean,
+    /// PostgreSQL's `to_tsquery`
+    TsQuery,
+    /// PostgreSQL's `plainto_tsquery`
+    PlainTsQuery,
+}
+
+/// A parsed full-text search query
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FullTextQuery {
+    /// Tokens which must all be present in a matching document
+    required: Vec<String>,
+    /// Tokens which must not be present in a matching document
+    excluded: Vec<String>,
+    /// If non-empty, at least one of these tokens must be present in a matching document
+    optional: Vec<String>,
+}
+
+impl FullTextQuery {
+    /// Parse the given full-text search query string with the given syntax, returning an error if
+    /// the query uses features we don't support
+    pub fn parse(query: &str, syntax: FullTextSyntax) -> ReadySetResult<Self> {
+        let mut res = Self::default();
+        match syntax {
+            FullTextSyntax::MySqlNaturalLanguage => {
+                res.optional = tokenize(query)
+                    .into_iter()
+                    .filter(|token| !is_mysql_ignored_word(token))
+                    .sorted()
+                    .collect();
+            }
+            FullTextSyntax::MySqlBoolean => {
+                for word in query.split_whitespace() {
+                    let (terms, word) = match word.strip_prefix('+') {
+                        Some(word) => (&mut res.required, word),
+                        None => match word.strip_prefix('-') {
+                            Some(word) => (&mut res.excluded, word),
+                            None => (&mut res.optional, word),
+                        },
+                    };
+                    if let Some(c) = word.chars().find(|c| {
+                        matches!(c, '+' | '-' | '~' | '<' | '>' | '(' | ')' | '"' | '*' | '@')
+                    }) {
+                        unsupported!("Unsupported operator in full-text search query: {c}");
+                    }
+                    let Some(token) = single_token(word)? else { continue };
+                    if is_mysql_ignored_word(&token) {
+                        continue;
+                    }
+                    if !terms.contains(&token) {
+                        terms.push(token);
+                    }
+                }
+                // Optional terms only affect relevance ranking if there are any required terms
+                if !res.required.is_empty() {
+                    res.optional.clear();
+                }
+            }
+            FullTextSyntax::TsQuery => {
+                if query.trim().is_empty() {
+                    return Ok(res);
+                }
+                if let Some(c) = query
+                    .chars()
+                    .find(|c| matches!(c, '(' | ')' | '<' | ':' | '*' | '\'' | '\\'))
+                {
+                    unsupported!("Unsupported operator in full-text search query: {c}");
+                }
+                let disjunction = query.contains('|');
+                if disjunction && query.contains('&') {
+                    unsupported!("Full-text search queries can't mix `&` and `|`");
+                }
+                for operand in query.split(if disjunction { '|' } else { '&' }) {
+                    let operand = operand.trim();
+                    let term = operand.trim_start_matches('!');
+                    let negated = (operand.len() - term.len()) % 2 == 1;
+                    let Some(token) = single_token(term)? else {
+                        unsupported!("Empty term in full-text search query")
+                    };
+                    let terms = match (negated, disjunction) {
+                        (false, false) => &mut res.required,
+                        (false, true) => &mut res.optional,
+                        (true, false) => &mut res.excluded,
+                        (true, true) => {
+                            unsupported!("Full-text search queries can't negate terms within `|`")
+                        }
+                    };
+                    if !terms.contains(&token) {
+                        terms.push(token);
+                    }
+                }
+                if res.required.is_empty() && res.optional.is_empty() {
+                    unsupported!(
+                        "Full-text search queries must contain at least one term that isn't \
+                         negated"
+                    );
+                }
+            }
+            FullTextSyntax::PlainTsQuery => {
+                res.required = tokenize(query).into_iter().sorted().collect();
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns true if this query has no terms at all, and so can never match any document
+    pub fn is_empty(&self) -> bool {
+        self.required.is_empty() && self.excluded.is_empty() && self.optional.is_empty()
+    }
+
+    /// Returns a single token which must be present in every document matched by this query, to
+    /// look up in a full-text index, or `None` if there is no such token
+    pub fn lookup_token(&self) -> Option<&str> {
+        match (self.required.first(), self.optional.as_slice()) {
+            (Some(token), _) => Some(token),
+            (None, [token]) => Some(token),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the given document matches this query
+    pub fn matches(&self, document: &str) -> bool {
+        if self.required.is_empty() && self.optional.is_empty() {
+            return false;
+        }
+        let tokens = tokenize(document);
+        self.required.iter().all(|token| tokens.contains(token))
+            && !self.excluded.iter().any(|token| tokens.contains(token))
+            && (self.optional.is_empty() || self.optional.iter().any(|t| tokens.contains(t)))
+    }
+}
+
+impl Display for FullTextQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.required
+                .iter()
+                .map(|t| format!("+{t}"))
+                .chain(self.excluded.iter().map(|t| format!("-{t}")))
+                .chain(self.optional.iter().cloned())
+                .join(" ")
+        )
+    }
+}
+
+/// A full-text search predicate, recognized by [`full_text_predicate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FullTextPredicate<'a> {
+    /// The column being searched
+    pub column: &'a Column,
+    /// The expression for the search query string
+    pub query: &'a AstExpr,
+    /// The syntax of the search query string
+    pub syntax: FullTextSyntax,
+}
+
+fn is_function(expr: &AstExpr, function: &str) -> bool {
+    matches!(
+        expr,
+        AstExpr::Call(FunctionExpr::Call { name, .. }) if name.eq_ignore_ascii_case(function)
+    )
+}
+
+/// Returns the argument of a call to one of the PostgreSQL full-text search functions, after
+/// checking that the call specifies the `simple` text search configuration
+fn text_search_argument(args: &[AstExpr]) -> ReadySetResult<&AstExpr> {
+    match args {
+        [AstExpr::Literal(Literal::String(config)), arg]
+            if config.eq_ignore_ascii_case("simple")
+                || config.eq_ignore_ascii_case("pg_catalog.simple") =>
+        {
+            Ok(arg)
+        }
+        _ => unsupported!(
+            "Full-text search is only supported with the 'simple' text search configuration"
+        ),
+    }
+}
+
+/// If the given expression is a full-text search predicate, returns the column being searched,
+/// the search query, and its syntax. Returns an error if the given expression is a full-text
+/// search predicate that we don't support.
+pub fn full_text_predicate(expr: &AstExpr) -> ReadySetResult<Option<FullTextPredicate<'_>>> {
+    match expr {
+        AstExpr::Match {
+            columns,
+            against,
+            modifier,
+        } => {
+            let [column] = columns.as_slice() else {
+                unsupported!("Full-text search is only supported on a single column")
+            };
+            let syntax = match modifier {
+                None | Some(MatchSearchModifier::NaturalLanguage) => {
+                    FullTextSyntax::MySqlNaturalLanguage
+                }
+                Some(MatchSearchModifier::Boolean) => FullTextSyntax::MySqlBoolean,
+                Some(
+                    MatchSearchModifier::QueryExpansion
+                    | MatchSearchModifier::NaturalLanguageWithQueryExpansion,
+                ) => unsupported!("Full-text search with query expansion is not supported"),
+            };
+            Ok(Some(FullTextPredicate {
+                column,
+                query: against,
+                syntax,
+            }))
+        }
+        AstExpr::BinaryOp {
+            lhs,
+            op: BinaryOperator::AtAt,
+            rhs,
+        } => {
+            let (
+                AstExpr::Call(FunctionExpr::Call { arguments: document_args, .. }),
+                AstExpr::Call(FunctionExpr::Call { name, arguments: query_args }),
+            ) = (lhs.as_ref(), rhs.as_ref()) else {
+                unsupported!("Unsupported full-text search predicate: {expr}")
+            };
+            if !is_function(lhs, "to_tsvector") {
+                unsupported!("Unsupported full-text search predicate: {expr}");
+            }
+            let syntax = if name.eq_ignore_ascii_case("to_tsquery") {
+                FullTextSyntax::TsQuery
+            } else if name.eq_ignore_ascii_case("plainto_tsquery") {
+                FullTextSyntax::PlainTsQuery
+            } else {
+                unsupported!("Unsupported full-text search predicate: {expr}")
+            };
+            let AstExpr::Column(column) = text_search_argument(document_args)? else {
+                unsupported!("Full-text search is only supported on a single column")
+            };
+            let query = text_search_argument(query_args)?;
+            Ok(Some(FullTextPredicate {
+                column,
+                query,
+                syntax,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// If the given expression is a full-text search predicate, returns a mutable reference to the
+/// expression for its search query string
+pub fn full_text_query_mut(expr: &mut AstExpr) -> Option<&mut AstExpr> {
+    match expr {
+        AstExpr::Match { against, .. } => Some(against),
+        AstExpr::BinaryOp {
+            op: BinaryOperator::AtAt,
+            rhs,
+            ..
+        } => match rhs.as_mut() {
+            AstExpr::Call(FunctionExpr::Call { arguments, .. }) => arguments.last_mut(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_expr, Dialect};
+
+    use super::*;
+
+    fn parse(query: &str, syntax: FullTextSyntax) -> FullTextQuery {
+        FullTextQuery::parse(query, syntax).unwrap()
+    }
+
+    #[test]
+    fn tokenize_document() {
+        assert_eq!(
+            tokenize("The quick, brown fox_1 jumps over the QUICK dog!"),
+            ["the", "quick", "brown", "fox_1", "jumps", "over", "dog"]
+                .into_iter()
+                .map(String::from)
+                .collect()
+        );
+    }
+
+    #[test]
+    fn mysql_natural_language() {
+        let query = parse("the Quick fox is up", FullTextSyntax::MySqlNaturalLanguage);
+        assert_eq!(query.lookup_token(), None);
+        assert!(query.matches("a quick dog"));
+        assert!(query.matches("a red fox"));
+        assert!(!query.matches("the dog is up"));
+
+        let query = parse("fox", FullTextSyntax::MySqlNaturalLanguage);
+        assert_eq!(query.lookup_token(), Some("fox"));
+
+        let query = parse("the", FullTextSyntax::MySqlNaturalLanguage);
+        assert!(query.is_empty());
+        assert!(!query.matches("the"));
+    }
+
+    #[test]
+    fn mysql_boolean() {
+        let query = parse("+quick -dog fox", FullTextSyntax::MySqlBoolean);
+        assert_eq!(query.lookup_token(), Some("quick"));
+        assert!(query.matches("the quick fox"));
+        assert!(query.matches("quick brown things"));
+        assert!(!query.matches("the quick dog"));
+        assert!(!query.matches("the brown fox"));
+
+        let query = parse("-dog", FullTextSyntax::MySqlBoolean);
+        assert_eq!(query.lookup_token(), None);
+        assert!(!query.matches("the brown fox"));
+
+        FullTextQuery::parse("\"quick fox\"", FullTextSyntax::MySqlBoolean).unwrap_err();
+        FullTextQuery::parse("qui*", FullTextSyntax::MySqlBoolean).unwrap_err();
+        FullTextQuery::parse("+(quick fox)", FullTextSyntax::MySqlBoolean).unwrap_err();
+    }
+
+    #[test]
+    fn ts_query() {
+        let query = parse("Quick & !dog", FullTextSyntax::TsQuery);
+        assert_eq!(query.lookup_token(), Some("quick"));
+        assert!(query.matches("the quick fox"));
+        assert!(!query.matches("the quick dog"));
+
+        let query = parse("fox | dog", FullTextSyntax::TsQuery);
+        assert_eq!(query.lookup_token(), None);
+        assert!(query.matches("a dog"));
+        assert!(!query.matches("a cat"));
+
+        // Unlike MySQL, postgres' simple configuration doesn't ignore any words
+        let query = parse("a", FullTextSyntax::TsQuery);
+        assert_eq!(query.lookup_token(), Some("a"));
+
+        assert!(parse("", FullTextSyntax::TsQuery).is_empty());
+        FullTextQuery::parse("quick & fox | dog", FullTextSyntax::TsQuery).unwrap_err();
+        FullTextQuery::parse("quick <-> fox", FullTextSyntax::TsQuery).unwrap_err();
+        FullTextQuery::parse("qui:*", FullTextSyntax::TsQuery).unwrap_err();
+        FullTextQuery::parse("!dog", FullTextSyntax::TsQuery).unwrap_err();
+        FullTextQuery::parse("quick fox", FullTextSyntax::TsQuery).unwrap_err();
+    }
+
+    #[test]
+    fn plain_ts_query() {
+        let query = parse("The quick fox!", FullTextSyntax::PlainTsQuery);
+        assert!(query.lookup_token().is_some());
+        assert!(query.matches("The fox was quick, but the dog was not."));
+        assert!(!query.matches("The quick dog"));
+    }
+
+    #[test]
+    fn recognize_predicates() {
+        let expr = parse_expr(
+            Dialect::MySQL,
+            "MATCH (body) AGAINST ('foo' IN BOOLEAN MODE)",
+        )
+        .unwrap();
+        let predicate = full_text_predicate(&expr).unwrap().unwrap();
+        assert_eq!(predicate.column.name, "body");
+        assert_eq!(predicate.syntax, FullTextSyntax::MySqlBoolean);
+        assert_eq!(*predicate.query, AstExpr::Literal("foo".into()));
+
+        let expr = parse_expr(
+            Dialect::PostgreSQL,
+            "to_tsvector('simple', body) @@ plainto_tsquery('simple', $1)",
+        )
+        .unwrap();
+        let predicate = full_text_predicate(&expr).unwrap().unwrap();
+        assert_eq!(predicate.column.name, "body");
+        assert_eq!(predicate.syntax, FullTextSyntax::PlainTsQuery);
+
+        let expr = parse_expr(Dialect::MySQL, "body = 'foo'").unwrap();
+        assert!(full_text_predicate(&expr).unwrap().is_none());
+
+        for unsupported in [
+            "to_tsvector(body) @@ to_tsquery('foo')",
+            "to_tsvector('english', body) @@ to_tsquery('english', 'foo')",
+            "to_tsvector('simple', body || title) @@ to_tsquery('simple', 'foo')",
+        ] {
+            let expr = parse_expr(Dialect::PostgreSQL, unsupported).unwrap();
+            full_text_predicate(&expr).unwrap_err();
+        }
+        let expr = parse_expr(Dialect::MySQL, "MATCH (title, body) AGAINST ('foo')").unwrap();
+        full_text_predicate(&expr).unwrap_err();
+    }
+}
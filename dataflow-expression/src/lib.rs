@@ -2,6 +2,7 @@
 
 mod binary_operator;
 mod eval;
+pub mod full_text;
 pub mod like;
 mod lower;
 mod post_lookup;
@@ -10,19 +11,31 @@ pub mod utils;
 use std::fmt::{self, Display, Formatter};
 
 use itertools::Itertools;
-use nom_sql::SqlType;
+use nom_sql::{SqlType, TimestampField};
 pub use readyset_data::Dialect;
 use readyset_data::{DfType, DfValue};
 use serde::{Deserialize, Serialize};
 use vec1::Vec1;
 
 pub use crate::binary_operator::*;
+use crate::full_text::FullTextQuery;
 pub use crate::lower::LowerContext;
 pub use crate::post_lookup::{
     PostLookup, PostLookupAggregate, PostLookupAggregateFunction, PostLookupAggregates,
     PreInsertion, ReaderProcessing,
 };
 
+/// Which side(s) of a string to remove characters from in a call to one of the `trim` functions
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TrimSide {
+    /// Remove characters from the start of the string
+    Leading,
+    /// Remove characters from the end of the string
+    Trailing,
+    /// Remove characters from both ends of the string
+    Both,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum BuiltinFunction {
     /// [`convert_tz`](https://dev.mysql.com/doc/refman/8.0/en/date-and-time-functions.html#function_convert-tz)
@@ -56,6 +69,10 @@ pub enum BuiltinFunction {
     JsonExtractPath { json: Expr, keys: Vec1<Expr> },
     /// [`jsonb_insert`](https://www.postgresql.org/docs/current/functions-json.html)
     JsonbInsert(Expr, Expr, Expr, Option<Expr>),
+    /// [`json_extract`](https://dev.mysql.com/doc/refman/8.0/en/json-search-functions.html#function_json-extract)
+    JsonExtract { json: Expr, paths: Vec1<Expr> },
+    /// [`json_unquote`](https://dev.mysql.com/doc/refman/8.0/en/json-modification-functions.html#function_json-unquote)
+    JsonUnquote(Expr),
     /// [`coalesce`](https://www.postgresql.org/docs/current/functions-conditional.html#FUNCTIONS-COALESCE-NVL-IFNULL)
     Coalesce(Expr, Vec<Expr>),
     /// [`concat`](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_concat)
@@ -91,6 +108,187 @@ pub enum BuiltinFunction {
         /// actual return type of the function call.
         compare_as: DfType,
     },
+
+    /// `lower`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_lower)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Lower(Expr),
+
+    /// `upper`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_upper)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Upper(Expr),
+
+    /// The length of a string in characters - `char_length` in both MySQL and PostgreSQL, and
+    /// `length` in PostgreSQL:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_char-length)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    CharLength(Expr),
+
+    /// The length of a string in bytes - `octet_length` in both MySQL and PostgreSQL, and `length`
+    /// in MySQL:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_octet-length)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    OctetLength(Expr),
+
+    /// `trim`, `ltrim`, `rtrim`, and `btrim`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_trim)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Trim {
+        string: Expr,
+        /// The set of characters to remove. If not specified, only spaces are removed.
+        chars: Option<Expr>,
+        side: TrimSide,
+    },
+
+    /// `replace`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_replace)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Replace(Expr, Expr, Expr),
+
+    /// `lpad`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_lpad)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Lpad {
+        string: Expr,
+        len: Expr,
+        fill: Option<Expr>,
+        dialect: Dialect,
+    },
+
+    /// `rpad`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_rpad)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Rpad {
+        string: Expr,
+        len: Expr,
+        fill: Option<Expr>,
+        dialect: Dialect,
+    },
+
+    /// `left`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_left)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Left {
+        string: Expr,
+        len: Expr,
+        dialect: Dialect,
+    },
+
+    /// `right`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_right)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Right {
+        string: Expr,
+        len: Expr,
+        dialect: Dialect,
+    },
+
+    /// `md5`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/encryption-functions.html#function_md5)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Md5(Expr),
+
+    /// `regexp_replace`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/regexp.html#function_regexp-replace)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP)
+    RegexpReplace {
+        string: Expr,
+        pattern: Expr,
+        replacement: Expr,
+        /// PostgreSQL-only flags controlling the behavior of the match
+        flags: Option<Expr>,
+        dialect: Dialect,
+    },
+
+    /// `abs`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_abs)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Abs(Expr),
+
+    /// `floor`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_floor)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Floor(Expr),
+
+    /// `ceil` and `ceiling`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_ceiling)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Ceil(Expr),
+
+    /// `mod`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_mod)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Mod {
+        dividend: Expr,
+        divisor: Expr,
+        dialect: Dialect,
+    },
+
+    /// `power` and `pow`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_power)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Power {
+        base: Expr,
+        exponent: Expr,
+        dialect: Dialect,
+    },
+
+    /// Adding an interval to a date or timestamp, or subtracting an interval from one - `+` and
+    /// `-` with an `INTERVAL` operand in both MySQL and PostgreSQL, and `date_add`, `date_sub`,
+    /// `adddate`, and `subdate` in MySQL:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/date-and-time-functions.html#function_date-add)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-datetime.html)
+    DateAdd {
+        date: Expr,
+        /// The quantity of the interval, which is parsed according to `unit` and the rules of
+        /// `dialect` when the expression is evaluated
+        interval: Expr,
+        /// The unit of the interval. Always present for MySQL, and optional for PostgreSQL
+        unit: Option<TimestampField>,
+        /// If true, subtract the interval from the date rather than adding it
+        subtract: bool,
+        dialect: Dialect,
+    },
+
+    /// `extract`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/date-and-time-functions.html#function_extract)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-datetime.html#FUNCTIONS-DATETIME-EXTRACT)
+    Extract {
+        field: TimestampField,
+        expr: Expr,
+        dialect: Dialect,
+    },
+
+    /// [`date_trunc`](https://www.postgresql.org/docs/current/functions-datetime.html#FUNCTIONS-DATETIME-TRUNC)
+    DateTrunc(TimestampField, Expr),
+
+    /// Returns true if the given text matches the given full-text search query. This is never
+    /// written by users directly, but is used to filter the results of looking up a single token
+    /// of the query in a full-text index.
+    FullTextMatch {
+        document: Expr,
+        query: FullTextQuery,
+    },
 }
 
 impl BuiltinFunction {
@@ -110,12 +308,49 @@ impl BuiltinFunction {
             JsonStripNulls { .. } => "json_strip_nulls",
             JsonExtractPath { .. } => "json_extract_path",
             JsonbInsert { .. } => "jsonb_insert",
+            JsonExtract { .. } => "json_extract",
+            JsonUnquote { .. } => "json_unquote",
             Coalesce { .. } => "coalesce",
             Concat { .. } => "concat",
             Substring { .. } => "substring",
             SplitPart { .. } => "split_part",
             Greatest { .. } => "greatest",
             Least { .. } => "least",
+            Lower { .. } => "lower",
+            Upper { .. } => "upper",
+            CharLength { .. } => "char_length",
+            OctetLength { .. } => "octet_length",
+            Trim {
+                side: TrimSide::Leading,
+                ..
+            } => "ltrim",
+            Trim {
+                side: TrimSide::Trailing,
+                ..
+            } => "rtrim",
+            Trim {
+                side: TrimSide::Both,
+                ..
+            } => "trim",
+            Replace { .. } => "replace",
+            Lpad { .. } => "lpad",
+            Rpad { .. } => "rpad",
+            Left { .. } => "left",
+            Right { .. } => "right",
+            Md5 { .. } => "md5",
+            RegexpReplace { .. } => "regexp_replace",
+            Abs { .. } => "abs",
+            Floor { .. } => "floor",
+            Ceil { .. } => "ceil",
+            Mod { .. } => "mod",
+            Power { .. } => "power",
+            DateAdd {
+                subtract: false, ..
+            } => "date_add",
+            DateAdd { subtract: true, .. } => "date_sub",
+            Extract { .. } => "extract",
+            DateTrunc { .. } => "date_trunc",
+            FullTextMatch { .. } => "full_text_match",
         }
     }
 }
@@ -154,12 +389,15 @@ impl Display for BuiltinFunction {
             Round(arg1, precision) => {
                 write!(f, "({}, {})", arg1, precision)
             }
-            JsonTypeof(arg) | JsonArrayLength(arg) | JsonStripNulls(arg) => {
+            JsonTypeof(arg) | JsonArrayLength(arg) | JsonStripNulls(arg) | JsonUnquote(arg) => {
                 write!(f, "({})", arg)
             }
             JsonExtractPath { json, keys } => {
                 write!(f, "({}, {})", json, keys.iter().join(", "))
             }
+            JsonExtract { json, paths } => {
+                write!(f, "({}, {})", json, paths.iter().join(", "))
+            }
             JsonbInsert(arg1, arg2, arg3, arg4) => {
                 write!(f, "({arg1}, {arg2}, {arg3}")?;
                 if let Some(arg4) = arg4 {
@@ -187,6 +425,65 @@ impl Display for BuiltinFunction {
             Greatest { args, .. } | Least { args, .. } => {
                 write!(f, "({})", args.iter().join(", "))
             }
+            Lower(arg) | Upper(arg) | CharLength(arg) | OctetLength(arg) | Md5(arg) | Abs(arg)
+            | Floor(arg) | Ceil(arg) => {
+                write!(f, "({})", arg)
+            }
+            Trim { string, chars, .. } => {
+                write!(f, "({string}")?;
+                if let Some(chars) = chars {
+                    write!(f, ", {chars}")?;
+                }
+                write!(f, ")")
+            }
+            Replace(string, from, to) => write!(f, "({string}, {from}, {to})"),
+            Lpad {
+                string, len, fill, ..
+            }
+            | Rpad {
+                string, len, fill, ..
+            } => {
+                write!(f, "({string}, {len}")?;
+                if let Some(fill) = fill {
+                    write!(f, ", {fill}")?;
+                }
+                write!(f, ")")
+            }
+            Left { string, len, .. } | Right { string, len, .. } => {
+                write!(f, "({string}, {len})")
+            }
+            RegexpReplace {
+                string,
+                pattern,
+                replacement,
+                flags,
+                ..
+            } => {
+                write!(f, "({string}, {pattern}, {replacement}")?;
+                if let Some(flags) = flags {
+                    write!(f, ", {flags}")?;
+                }
+                write!(f, ")")
+            }
+            Mod {
+                dividend, divisor, ..
+            } => write!(f, "({dividend}, {divisor})"),
+            Power { base, exponent, .. } => write!(f, "({base}, {exponent})"),
+            DateAdd {
+                date,
+                interval,
+                unit,
+                ..
+            } => {
+                write!(f, "({date}, INTERVAL {interval}")?;
+                if let Some(unit) = unit {
+                    write!(f, " {unit}")?;
+                }
+                write!(f, ")")
+            }
+            Extract { field, expr, .. } => write!(f, "({field} FROM {expr})"),
+            DateTrunc(field, expr) => write!(f, "('{field}', {expr})"),
+            FullTextMatch { document, query } => write!(f, "({document}, '{query}')"),
         }
     }
 }
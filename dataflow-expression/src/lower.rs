@@ -1,7 +1,10 @@
 use std::iter;
 
 use launchpad::redacted::Sensitive;
-use nom_sql::{Column, Expr as AstExpr, FunctionExpr, InValue, Relation, UnaryOperator};
+use nom_sql::{
+    BinaryOperator as AstBinaryOperator, Column, Expr as AstExpr, FunctionExpr, InValue, Relation,
+    TimestampField, UnaryOperator,
+};
 use readyset_data::dialect::SqlEngine;
 use readyset_data::{DfType, DfValue};
 use readyset_errors::{
@@ -9,7 +12,7 @@ use readyset_errors::{
 };
 use vec1::Vec1;
 
-use crate::{BinaryOperator, BuiltinFunction, CaseWhenBranch, Dialect, Expr};
+use crate::{BinaryOperator, BuiltinFunction, CaseWhenBranch, Dialect, Expr, TrimSide};
 
 /// Context supplied to expression lowering to allow resolving references to objects within the
 /// schema
@@ -120,6 +123,54 @@ fn mysql_least_greatest_compare_as(arg_types: Vec<&DfType>) -> DfType {
     DfType::VarBinary(u16::MAX)
 }
 
+/// Returns the type of the result of adding an interval with the given unit to (or subtracting it
+/// from) a value of type `date_ty`
+fn type_for_date_add(date_ty: &DfType, unit: Option<TimestampField>, dialect: Dialect) -> DfType {
+    use TimestampField::*;
+
+    let default_subsecond_digits = dialect.default_subsecond_digits();
+    match (dialect.engine(), date_ty) {
+        // In MySQL, adding an interval of whole days (or larger) to a DATE returns a DATE
+        (SqlEngine::MySQL, DfType::Date)
+            if matches!(unit, Some(Day | Week | Month | Quarter | Year | YearMonth)) =>
+        {
+            DfType::Date
+        }
+        (SqlEngine::MySQL, ty @ (DfType::DateTime { .. } | DfType::Timestamp { .. })) => ty.clone(),
+        (SqlEngine::MySQL, _) => DfType::DateTime {
+            subsecond_digits: default_subsecond_digits,
+        },
+        (SqlEngine::PostgreSQL, ty @ (DfType::Timestamp { .. } | DfType::TimestampTz { .. })) => {
+            ty.clone()
+        }
+        (SqlEngine::PostgreSQL, _) => DfType::Timestamp {
+            subsecond_digits: default_subsecond_digits,
+        },
+    }
+}
+
+/// Parses the name of the field to truncate to in a call to PostgreSQL's `date_trunc` function
+fn date_trunc_field(name: &str) -> Option<TimestampField> {
+    use TimestampField::*;
+
+    Some(match name.to_ascii_lowercase().as_str() {
+        "microsecond" | "microseconds" => Microsecond,
+        "millisecond" | "milliseconds" => Millisecond,
+        "second" | "seconds" => Second,
+        "minute" | "minutes" => Minute,
+        "hour" | "hours" => Hour,
+        "day" | "days" => Day,
+        "week" | "weeks" => Week,
+        "month" | "months" => Month,
+        "quarter" | "quarters" => Quarter,
+        "year" | "years" => Year,
+        "decade" | "decades" => Decade,
+        "century" | "centuries" => Century,
+        "millennium" | "millennia" => Millennium,
+        _ => return None,
+    })
+}
+
 impl BuiltinFunction {
     pub(crate) fn from_name_and_args<A>(
         name: &str,
@@ -146,7 +197,59 @@ impl BuiltinFunction {
             }
         }
 
+        /// Returns the type for the result of a string function whose first argument is `expr`,
+        /// preserving the collation of the argument if it's textual
+        fn type_for_string_fn(expr: &Expr) -> DfType {
+            expr.ty()
+                .collation()
+                .map(DfType::Text)
+                .unwrap_or(DfType::DEFAULT_TEXT)
+        }
+
+        fn type_for_floor_or_ceil(expr: &Expr, dialect: Dialect) -> DfType {
+            use DfType::*;
+            match *expr.ty() {
+                Unknown => Unknown,
+                // MySQL returns an exact value type for exact-value numeric arguments, but
+                // PostgreSQL converts integers to double precision.
+                Numeric { prec, .. } if dialect.engine() == SqlEngine::MySQL => {
+                    Numeric { prec, scale: 0 }
+                }
+                Numeric { prec, scale } => Numeric { prec, scale },
+                ref ty if ty.is_any_int() && dialect.engine() == SqlEngine::MySQL => BigInt,
+                _ => Double,
+            }
+        }
+
+        fn type_for_abs(expr: &Expr) -> DfType {
+            match expr.ty() {
+                DfType::Unknown => DfType::Unknown,
+                ty @ DfType::Numeric { .. } => ty.clone(),
+                ty if ty.is_any_int() || ty.is_any_float() => ty.clone(),
+                _ => DfType::Double,
+            }
+        }
+
+        fn type_for_mod(dividend: &Expr, divisor: &Expr) -> DfType {
+            let (ty1, ty2) = (dividend.ty(), divisor.ty());
+            if ty1.is_unknown() && ty2.is_unknown() {
+                DfType::Unknown
+            } else if ty1.is_any_int() && ty2.is_any_int() {
+                DfType::BigInt
+            } else if ty1.is_any_float() || ty2.is_any_float() {
+                DfType::Double
+            } else if let Some(ty) = [ty1, ty2]
+                .into_iter()
+                .find(|ty| matches!(ty, DfType::Numeric { .. }))
+            {
+                ty.clone()
+            } else {
+                DfType::Double
+            }
+        }
+
         let arity_error = || ReadySetError::ArityError(name.to_owned());
+        let is_mysql = dialect.engine() == SqlEngine::MySQL;
 
         // TODO: Type-check arguments.
         let mut args = args.into_iter();
@@ -244,6 +347,19 @@ impl BuiltinFunction {
                 },
                 DfType::DEFAULT_TEXT,
             ),
+            "json_extract" if is_mysql => (
+                Self::JsonExtract {
+                    json: next_arg()?,
+                    paths: Vec1::try_from_vec(args.by_ref().collect())
+                        .map_err(|_| arity_error())?,
+                },
+                DfType::Json,
+            ),
+            "json_unquote" if is_mysql => (
+                Self::JsonUnquote(next_arg()?),
+                // MySQL returns `utf8mb4` text.
+                DfType::DEFAULT_TEXT,
+            ),
             "jsonb_insert" => (
                 Self::JsonbInsert(next_arg()?, next_arg()?, next_arg()?, args.next()),
                 DfType::Jsonb,
@@ -322,6 +438,191 @@ impl BuiltinFunction {
                     ty,
                 )
             }
+            "lower" | "lcase" => {
+                let arg = next_arg()?;
+                let ty = type_for_string_fn(&arg);
+                (Self::Lower(arg), ty)
+            }
+            "upper" | "ucase" => {
+                let arg = next_arg()?;
+                let ty = type_for_string_fn(&arg);
+                (Self::Upper(arg), ty)
+            }
+            "char_length" | "character_length" => (
+                Self::CharLength(next_arg()?),
+                if is_mysql {
+                    DfType::BigInt
+                } else {
+                    DfType::Int
+                },
+            ),
+            "octet_length" => (
+                Self::OctetLength(next_arg()?),
+                if is_mysql {
+                    DfType::BigInt
+                } else {
+                    DfType::Int
+                },
+            ),
+            // `length` counts bytes in MySQL, but characters in PostgreSQL
+            "length" if is_mysql => (Self::OctetLength(next_arg()?), DfType::BigInt),
+            "length" => (Self::CharLength(next_arg()?), DfType::Int),
+            "trim" | "ltrim" | "rtrim" | "btrim" => {
+                if name == "btrim" && is_mysql {
+                    return Err(ReadySetError::NoSuchFunction(name.to_owned()));
+                }
+                let string = next_arg()?;
+                let ty = type_for_string_fn(&string);
+                // MySQL's `trim` functions only accept a set of characters to trim via the
+                // special `TRIM(... FROM ...)` syntax
+                let chars = if is_mysql { None } else { args.next() };
+                let side = match name {
+                    "ltrim" => TrimSide::Leading,
+                    "rtrim" => TrimSide::Trailing,
+                    _ => TrimSide::Both,
+                };
+                (
+                    Self::Trim {
+                        string,
+                        chars,
+                        side,
+                    },
+                    ty,
+                )
+            }
+            "replace" => {
+                let string = next_arg()?;
+                let ty = type_for_string_fn(&string);
+                (Self::Replace(string, next_arg()?, next_arg()?), ty)
+            }
+            "lpad" | "rpad" => {
+                let string = next_arg()?;
+                let ty = type_for_string_fn(&string);
+                let len = next_arg()?;
+                // The fill string is required in MySQL, but defaults to a single space in
+                // PostgreSQL
+                let fill = if is_mysql {
+                    Some(next_arg()?)
+                } else {
+                    args.next()
+                };
+                (
+                    if name == "lpad" {
+                        Self::Lpad {
+                            string,
+                            len,
+                            fill,
+                            dialect,
+                        }
+                    } else {
+                        Self::Rpad {
+                            string,
+                            len,
+                            fill,
+                            dialect,
+                        }
+                    },
+                    ty,
+                )
+            }
+            "left" | "right" => {
+                let string = next_arg()?;
+                let ty = type_for_string_fn(&string);
+                let len = next_arg()?;
+                (
+                    if name == "left" {
+                        Self::Left {
+                            string,
+                            len,
+                            dialect,
+                        }
+                    } else {
+                        Self::Right {
+                            string,
+                            len,
+                            dialect,
+                        }
+                    },
+                    ty,
+                )
+            }
+            "md5" => (Self::Md5(next_arg()?), DfType::DEFAULT_TEXT),
+            "regexp_replace" => {
+                let string = next_arg()?;
+                let ty = type_for_string_fn(&string);
+                let pattern = next_arg()?;
+                let replacement = next_arg()?;
+                let flags = args.next();
+                if is_mysql && flags.is_some() {
+                    unsupported!(
+                        "The position, occurrence, and match_type arguments to REGEXP_REPLACE \
+                         are not supported"
+                    );
+                }
+                (
+                    Self::RegexpReplace {
+                        string,
+                        pattern,
+                        replacement,
+                        flags,
+                        dialect,
+                    },
+                    ty,
+                )
+            }
+            "abs" => {
+                let arg = next_arg()?;
+                let ty = type_for_abs(&arg);
+                (Self::Abs(arg), ty)
+            }
+            "floor" => {
+                let arg = next_arg()?;
+                let ty = type_for_floor_or_ceil(&arg, dialect);
+                (Self::Floor(arg), ty)
+            }
+            "ceil" | "ceiling" => {
+                let arg = next_arg()?;
+                let ty = type_for_floor_or_ceil(&arg, dialect);
+                (Self::Ceil(arg), ty)
+            }
+            "mod" => {
+                let dividend = next_arg()?;
+                let divisor = next_arg()?;
+                let ty = type_for_mod(&dividend, &divisor);
+                (
+                    Self::Mod {
+                        dividend,
+                        divisor,
+                        dialect,
+                    },
+                    ty,
+                )
+            }
+            "power" | "pow" => (
+                Self::Power {
+                    base: next_arg()?,
+                    exponent: next_arg()?,
+                    dialect,
+                },
+                DfType::Double,
+            ),
+            "date_trunc" if !is_mysql => {
+                let Expr::Literal { val: field, .. } = next_arg()? else {
+                    unsupported!("The field argument to date_trunc must be a literal")
+                };
+                let field = <&str>::try_from(&field)
+                    .ok()
+                    .and_then(date_trunc_field)
+                    .ok_or_else(|| invalid_err!("unit {field} not recognized for date_trunc"))?;
+                let expr = next_arg()?;
+                let ty = match expr.ty() {
+                    ty @ (DfType::Timestamp { .. } | DfType::TimestampTz { .. }) => ty.clone(),
+                    _ => DfType::Timestamp {
+                        subsecond_digits: dialect.default_subsecond_digits(),
+                    },
+                };
+                (Self::DateTrunc(field, expr), ty)
+            }
             _ => return Err(ReadySetError::NoSuchFunction(name.to_owned())),
         };
 
@@ -352,6 +653,31 @@ impl Expr {
         C: LowerContext,
     {
         match expr {
+            AstExpr::Call(FunctionExpr::Call { name, arguments })
+                if dialect.engine() == SqlEngine::MySQL
+                    && matches!(
+                        name.as_str(),
+                        "date_add" | "date_sub" | "adddate" | "subdate"
+                    ) =>
+            {
+                let [date, interval] = <[AstExpr; 2]>::try_from(arguments)
+                    .map_err(|_| ReadySetError::ArityError(name.to_string()))?;
+                let (interval, unit) = match interval {
+                    AstExpr::Interval { value, unit } => (*value, unit),
+                    // `ADDDATE(expr, days)` and `SUBDATE(expr, days)` add or subtract a number of
+                    // days
+                    days if matches!(name.as_str(), "adddate" | "subdate") => {
+                        (days, Some(TimestampField::Day))
+                    }
+                    _ => {
+                        return Err(invalid_err!(
+                            "The second argument to {name} must be an INTERVAL expression"
+                        ))
+                    }
+                };
+                let subtract = matches!(name.as_str(), "date_sub" | "subdate");
+                Self::lower_date_add(date, interval, unit, subtract, dialect, context)
+            }
             AstExpr::Call(FunctionExpr::Call {
                 name: fname,
                 arguments,
@@ -379,6 +705,39 @@ impl Expr {
                     ty,
                 })
             }
+            AstExpr::Call(FunctionExpr::Extract { field, expr }) => {
+                use TimestampField::*;
+
+                let is_mysql = dialect.engine() == SqlEngine::MySQL;
+                let valid = match field {
+                    Millisecond | Decade | Century | Millennium | Dow | IsoDow | Doy | Epoch => {
+                        !is_mysql
+                    }
+                    SecondMicrosecond | MinuteMicrosecond | MinuteSecond | HourMicrosecond
+                    | HourSecond | HourMinute | DayMicrosecond | DaySecond | DayMinute
+                    | DayHour | YearMonth => is_mysql,
+                    _ => true,
+                };
+                if !valid {
+                    return Err(invalid_err!("{field} is not a valid field for EXTRACT"));
+                }
+
+                // MySQL always returns an integer from EXTRACT, but PostgreSQL returns a numeric
+                // value since some fields (eg seconds) can be fractional
+                let ty = if is_mysql {
+                    DfType::BigInt
+                } else {
+                    DfType::DEFAULT_NUMERIC
+                };
+                Ok(Self::Call {
+                    func: Box::new(BuiltinFunction::Extract {
+                        field,
+                        expr: Self::lower(*expr, dialect, context)?,
+                        dialect,
+                    }),
+                    ty,
+                })
+            }
             AstExpr::Call(call) => internal!(
                 "Unexpected (aggregate?) call node in project expression: {:?}",
                 Sensitive(&call)
@@ -399,19 +758,52 @@ impl Expr {
                 let (index, ty) = context.resolve_column(col)?;
                 Ok(Self::Column { index, ty })
             }
+            AstExpr::BinaryOp {
+                lhs,
+                op: op @ (AstBinaryOperator::Add | AstBinaryOperator::Subtract),
+                rhs,
+            } if matches!(*rhs, AstExpr::Interval { .. })
+                || (op == AstBinaryOperator::Add && matches!(*lhs, AstExpr::Interval { .. })) =>
+            {
+                // Addition is commutative, so normalize `INTERVAL ... + date` to
+                // `date + INTERVAL ...`
+                let (date, interval) = match (*lhs, *rhs) {
+                    (interval @ AstExpr::Interval { .. }, date) => (date, interval),
+                    (date, interval) => (date, interval),
+                };
+                let AstExpr::Interval { value, unit } = interval else {
+                    internal!("Checked that one side of the operator is an INTERVAL")
+                };
+                let subtract = op == AstBinaryOperator::Subtract;
+                Self::lower_date_add(date, *value, unit, subtract, dialect, context)
+            }
+            AstExpr::BinaryOp {
+                lhs,
+                op: op @ (AstBinaryOperator::Modulo | AstBinaryOperator::Caret),
+                rhs,
+            } if op == AstBinaryOperator::Modulo || dialect.engine() == SqlEngine::PostgreSQL => {
+                // `%` and PostgreSQL's `^` behave exactly like the `mod` and `power` functions
+                let name = if op == AstBinaryOperator::Modulo {
+                    "mod"
+                } else {
+                    "power"
+                };
+                let args = vec![
+                    Self::lower(*lhs, dialect, context.clone())?,
+                    Self::lower(*rhs, dialect, context)?,
+                ];
+                let (func, ty) = BuiltinFunction::from_name_and_args(name, args, dialect)?;
+                Ok(Self::Call {
+                    func: Box::new(func),
+                    ty,
+                })
+            }
             AstExpr::BinaryOp { lhs, op, rhs } => {
                 let left = Box::new(Self::lower(*lhs, dialect, context.clone())?);
                 let right = Box::new(Self::lower(*rhs, dialect, context)?);
                 let op = BinaryOperator::from_sql_op(op, dialect, left.ty(), right.ty())?;
 
-                if matches!(
-                    op,
-                    BinaryOperator::JsonPathExtract | BinaryOperator::JsonPathExtractUnquote
-                ) {
-                    unsupported!("'{op}' operator not implemented yet for MySQL");
-                }
-
-                let ty = op.output_type(left.ty(), right.ty())?;
+                let ty = op.output_type(left.ty(), right.ty(), dialect)?;
 
                 Ok(Self::Op {
                     op,
@@ -584,13 +976,50 @@ impl Expr {
                     ty,
                 })
             }
+            AstExpr::Interval { .. } => unsupported!(
+                "INTERVAL expressions are only supported when added to or subtracted from a date"
+            ),
             AstExpr::Exists(_) => unsupported!("EXISTS not currently supported"),
             AstExpr::Variable(_) => unsupported!("Variables not currently supported"),
+            AstExpr::WindowFunction { .. } => {
+                unsupported!("Window functions are only supported in the SELECT list")
+            }
+            AstExpr::Match { .. } => {
+                unsupported!("Full-text search predicates are only supported as query parameters")
+            }
             AstExpr::Between { .. } | AstExpr::NestedSelect(_) | AstExpr::In { .. } => {
                 internal!("Expression should have been desugared earlier: {expr}")
             }
         }
     }
+
+    /// Lower an expression adding an interval of `interval` `unit`s to `date`, or subtracting it if
+    /// `subtract` is true
+    fn lower_date_add<C>(
+        date: AstExpr,
+        interval: AstExpr,
+        unit: Option<TimestampField>,
+        subtract: bool,
+        dialect: Dialect,
+        context: C,
+    ) -> ReadySetResult<Self>
+    where
+        C: LowerContext,
+    {
+        let date = Self::lower(date, dialect, context.clone())?;
+        let interval = Self::lower(interval, dialect, context)?;
+        let ty = type_for_date_add(date.ty(), unit, dialect);
+        Ok(Self::Call {
+            func: Box::new(BuiltinFunction::DateAdd {
+                date,
+                interval,
+                unit,
+                subtract,
+                dialect,
+            }),
+            ty,
+        })
+    }
 }
 
 #[cfg(test)]
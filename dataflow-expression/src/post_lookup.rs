@@ -8,6 +8,7 @@ use partial_map::InsertionOrder;
 use readyset_data::DfValue;
 use readyset_errors::{internal, ReadySetResult};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
 /// Representation of an aggregate function
 // TODO(grfn): It would be really nice to deduplicate this somehow with the grouped operator itself
@@ -26,6 +27,10 @@ pub enum PostLookupAggregateFunction {
     Max,
     /// Take the minimum input value
     Min,
+    /// Concatenate together all the input arrays
+    ArrayConcat,
+    /// Concatenate together all the input JSON arrays
+    JsonArrayConcat,
 }
 
 impl PostLookupAggregateFunction {
@@ -45,6 +50,26 @@ impl PostLookupAggregateFunction {
             .into()),
             PostLookupAggregateFunction::Max => Ok(cmp::max(val1, val2).clone()),
             PostLookupAggregateFunction::Min => Ok(cmp::min(val1, val2).clone()),
+            PostLookupAggregateFunction::ArrayConcat => match (val1, val2) {
+                (DfValue::None, v) | (v, DfValue::None) => Ok(v.clone()),
+                (DfValue::Array(arr1), DfValue::Array(arr2)) => Ok(arr1
+                    .values()
+                    .chain(arr2.values())
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into()),
+                _ => internal!("ArrayConcat can only be applied to arrays"),
+            },
+            PostLookupAggregateFunction::JsonArrayConcat => match (val1, val2) {
+                (DfValue::None, v) | (v, DfValue::None) => Ok(v.clone()),
+                _ => match (val1.to_json()?, val2.to_json()?) {
+                    (JsonValue::Array(mut arr1), JsonValue::Array(arr2)) => {
+                        arr1.extend(arr2);
+                        Ok(arr1.into())
+                    }
+                    _ => internal!("JsonArrayConcat can only be applied to JSON arrays"),
+                },
+            },
         }
     }
 }
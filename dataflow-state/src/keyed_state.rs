@@ -598,6 +598,7 @@ impl KeyedState {
 }
 
 impl From<&Index> for KeyedState {
+    #[allow(clippy::unreachable)] // Full-text indexes are only ever created in readers
     fn from(index: &Index) -> Self {
         use IndexType::*;
         match (index.len(), &index.index_type) {
@@ -615,6 +616,7 @@ impl From<&Index> for KeyedState {
             (6, HashMap) => KeyedState::SexHash(Default::default()),
             (x, HashMap) => KeyedState::MultiHash(Default::default(), x),
             (x, BTreeMap) => KeyedState::MultiBTree(Default::default(), x),
+            (_, FullText) => unreachable!("Full-text indexes are only supported in readers"),
         }
     }
 }
@@ -1091,6 +1091,7 @@ impl IndexParams {
                 Some(6) => opts.set_comparator("compare_keys_6", compare_keys_6),
                 _ => opts.set_comparator("compare_keys_multi", compare_keys_multi),
             },
+            IndexType::FullText => unreachable!("Full-text indexes are only supported in readers"),
         }
 
         opts
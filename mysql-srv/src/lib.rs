@@ -284,6 +284,9 @@ pub trait MySqlShim<W: AsyncWrite + Unpin + Send> {
     fn require_authentication(&self) -> bool {
         true
     }
+
+    /// Called once the client has successfully authenticated as the user with the given username
+    fn on_authenticated(&mut self, _username: &str) {}
 }
 
 /// Stores a preencoded result schema for a prepared MySQL statement
@@ -497,6 +500,7 @@ impl<B: MySqlShim<W> + Send, R: AsyncRead + Unpin, W: AsyncWrite + Unpin + Send>
 
         if auth_success {
             debug!(%username, "Successfully authenticated client");
+            self.shim.on_authenticated(&username);
             writers::write_ok_packet(&mut self.writer, 0, 0, StatusFlags::empty()).await?;
         } else {
             debug!(%username, ?client_auth_plugin, "Received incorrect password");
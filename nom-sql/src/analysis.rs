@@ -51,7 +51,9 @@ impl<'a> ReferredColumnsIter<'a> {
                 self.exprs_to_visit.push(lhs);
                 self.visit_expr(rhs)
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => self.visit_expr(expr),
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => self.visit_expr(expr),
             Expr::Exists { .. } => None,
             Expr::Between {
                 operand, min, max, ..
@@ -80,6 +82,22 @@ impl<'a> ReferredColumnsIter<'a> {
             }),
             Expr::NestedSelect(_) => None,
             Expr::Variable(_) => None,
+            Expr::WindowFunction {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                self.exprs_to_visit.extend(partition_by);
+                self.exprs_to_visit
+                    .extend(order_by.iter().map(|(expr, _)| expr));
+                self.visit_function_expression(function)
+            }
+            Expr::Match {
+                columns, against, ..
+            } => {
+                self.columns_to_visit.extend(columns);
+                self.visit_expr(against)
+            }
         }
     }
 
@@ -94,6 +112,9 @@ impl<'a> ReferredColumnsIter<'a> {
             Max(arg) => self.visit_expr(arg),
             Min(arg) => self.visit_expr(arg),
             GroupConcat { expr, .. } => self.visit_expr(expr),
+            Stddev { expr, .. } | Variance { expr, .. } => self.visit_expr(expr),
+            BoolAnd(arg) | BoolOr(arg) | ArrayAgg(arg) | JsonAgg(arg) => self.visit_expr(arg),
+            Extract { expr, .. } => self.visit_expr(expr),
             Call { arguments, .. } => arguments.first().and_then(|first_arg| {
                 if arguments.len() >= 2 {
                     self.exprs_to_visit.extend(arguments.iter().skip(1));
@@ -161,7 +182,9 @@ impl<'a> ReferredColumnsMut<'a> {
                 self.exprs_to_visit.push(lhs);
                 self.visit_expr(rhs)
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => self.visit_expr(expr),
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => self.visit_expr(expr),
             Expr::Exists { .. } => None,
             Expr::Between {
                 operand, min, max, ..
@@ -186,6 +209,22 @@ impl<'a> ReferredColumnsMut<'a> {
             }),
             Expr::NestedSelect(_) => None,
             Expr::Variable(_) => None,
+            Expr::WindowFunction {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                self.exprs_to_visit.extend(partition_by);
+                self.exprs_to_visit
+                    .extend(order_by.iter_mut().map(|(expr, _)| expr));
+                self.visit_function_expression(function)
+            }
+            Expr::Match {
+                columns, against, ..
+            } => {
+                self.columns_to_visit.extend(columns);
+                self.visit_expr(against)
+            }
         }
     }
 
@@ -200,6 +239,9 @@ impl<'a> ReferredColumnsMut<'a> {
             Max(arg) => self.visit_expr(arg),
             Min(arg) => self.visit_expr(arg),
             GroupConcat { expr, .. } => self.visit_expr(expr),
+            Stddev { expr, .. } | Variance { expr, .. } => self.visit_expr(expr),
+            BoolAnd(arg) | BoolOr(arg) | ArrayAgg(arg) | JsonAgg(arg) => self.visit_expr(arg),
+            Extract { expr, .. } => self.visit_expr(expr),
             Call { arguments, .. } => arguments.split_first_mut().and_then(|(first_arg, args)| {
                 self.exprs_to_visit.extend(args);
                 self.visit_expr(first_arg)
@@ -332,8 +374,15 @@ pub fn is_aggregate(function: &FunctionExpr) -> bool {
         | FunctionExpr::Sum { .. }
         | FunctionExpr::Max(_)
         | FunctionExpr::Min(_)
-        | FunctionExpr::GroupConcat { .. } => true,
+        | FunctionExpr::GroupConcat { .. }
+        | FunctionExpr::Stddev { .. }
+        | FunctionExpr::Variance { .. }
+        | FunctionExpr::BoolAnd(_)
+        | FunctionExpr::BoolOr(_)
+        | FunctionExpr::ArrayAgg(_)
+        | FunctionExpr::JsonAgg(_) => true,
         FunctionExpr::Substring { .. }
+        | FunctionExpr::Extract { .. }
         // For now, assume all "generic" function calls are not aggregates
         | FunctionExpr::Call { .. } => false,
     }
@@ -358,7 +407,9 @@ pub fn contains_aggregate(expr: &Expr) -> bool {
                     .any(|expr| contains_aggregate(expr.as_ref()))
         }
         Expr::BinaryOp { lhs, rhs, .. } => contains_aggregate(lhs) || contains_aggregate(rhs),
-        Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => contains_aggregate(expr),
+        Expr::UnaryOp { rhs: expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Interval { value: expr, .. } => contains_aggregate(expr),
         Expr::Exists(_) => false,
         Expr::Between {
             operand, min, max, ..
@@ -373,6 +424,16 @@ pub fn contains_aggregate(expr: &Expr) -> bool {
         }
         Expr::Array(exprs) => exprs.iter().any(contains_aggregate),
         Expr::Variable(_) => false,
+        Expr::WindowFunction {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            function.arguments().any(contains_aggregate)
+                || partition_by.iter().any(contains_aggregate)
+                || order_by.iter().any(|(expr, _)| contains_aggregate(expr))
+        }
+        Expr::Match { against, .. } => contains_aggregate(against),
     }
 }
 
@@ -429,9 +490,10 @@ impl Expr {
             Expr::BinaryOp { lhs, rhs, .. } => {
                 Box::new(vec![lhs, rhs].into_iter().map(AsRef::as_ref)) as _
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => {
-                Box::new(iter::once(expr.as_ref())) as _
-            }
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. }
+            | Expr::Match { against: expr, .. } => Box::new(iter::once(expr.as_ref())) as _,
             Expr::CaseWhen {
                 branches,
                 else_expr,
@@ -455,6 +517,16 @@ impl Expr {
                 ..
             } => Box::new(iter::once(lhs.as_ref())) as _,
             Expr::Array(exprs) => Box::new(exprs.iter()),
+            Expr::WindowFunction {
+                function,
+                partition_by,
+                order_by,
+            } => Box::new(
+                function
+                    .arguments()
+                    .chain(partition_by)
+                    .chain(order_by.iter().map(|(expr, _)| expr)),
+            ) as _,
         }
     }
 
@@ -17,13 +17,14 @@ use crate::transaction::{CommitStatement, RollbackStatement, StartTransactionSta
 use crate::{
     AlterColumnOperation, AlterTableDefinition, AlterTableStatement, CacheInner, CaseWhenBranch,
     Column, ColumnConstraint, ColumnSpecification, CommonTableExpr, CompoundSelectStatement,
-    CreateCacheStatement, CreateTableStatement, CreateViewStatement, DeleteStatement,
-    DropAllCachesStatement, DropCacheStatement, DropTableStatement, DropViewStatement,
-    ExplainStatement, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr, GroupByClause,
-    InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, Literal, OrderClause,
-    Relation, SelectSpecification, SelectStatement, SetNames, SetPostgresParameter, SetStatement,
-    SetVariables, ShowStatement, SqlIdentifier, SqlQuery, SqlType, TableExpr, TableExprInner,
-    TableKey, UpdateStatement, UseStatement,
+    CreateCacheStatement, CreateRoutingRuleStatement, CreateTableStatement, CreateViewStatement,
+    DeleteStatement, DropAllCachesStatement, DropCacheStatement, DropRoutingRuleStatement,
+    DropTableStatement, DropViewStatement, ExplainStatement, Expr, FieldDefinitionExpr,
+    FieldReference, FunctionExpr, GroupByClause, InValue, InsertStatement, JoinClause,
+    JoinConstraint, JoinRightSide, Literal, OrderClause, Relation, SelectSpecification,
+    SelectStatement, SetNames, SetPostgresParameter, SetStatement, SetVariables, ShowStatement,
+    SqlIdentifier, SqlQuery, SqlType, TableExpr, TableExprInner, TableKey, UpdateStatement,
+    UseStatement,
 };
 
 /// Each method of the `Visitor` trait is a hook to be potentially overridden when recursively
@@ -363,6 +364,23 @@ pub trait Visitor<'ast>: Sized {
         Ok(())
     }
 
+    fn visit_create_routing_rule_statement(
+        &mut self,
+        create_routing_rule_statement: &'ast CreateRoutingRuleStatement,
+    ) -> Result<(), Self::Error> {
+        for table in &create_routing_rule_statement.tables {
+            self.visit_table(table)?;
+        }
+        Ok(())
+    }
+
+    fn visit_drop_routing_rule_statement(
+        &mut self,
+        _drop_routing_rule_statement: &'ast DropRoutingRuleStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     fn visit_drop_view_statement(
         &mut self,
         drop_view_statement: &'ast DropViewStatement,
@@ -445,6 +463,29 @@ pub fn walk_expr<'ast, V: Visitor<'ast>>(
             Ok(())
         }
         Expr::Variable(var) => visitor.visit_variable(var),
+        Expr::Interval { value, .. } => visitor.visit_expr(value.as_ref()),
+        Expr::WindowFunction {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            visitor.visit_function_expr(function)?;
+            for expr in partition_by {
+                visitor.visit_expr(expr)?;
+            }
+            for (expr, _) in order_by {
+                visitor.visit_expr(expr)?;
+            }
+            Ok(())
+        }
+        Expr::Match {
+            columns, against, ..
+        } => {
+            for column in columns {
+                visitor.visit_column(column)?;
+            }
+            visitor.visit_expr(against.as_ref())
+        }
     }
 }
 
@@ -468,6 +509,13 @@ pub fn walk_function_expr<'ast, V: Visitor<'ast>>(
         FunctionExpr::Max(expr) => visitor.visit_expr(expr.as_ref()),
         FunctionExpr::Min(expr) => visitor.visit_expr(expr.as_ref()),
         FunctionExpr::GroupConcat { expr, .. } => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::Stddev { expr, .. } => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::Variance { expr, .. } => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::BoolAnd(expr) => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::BoolOr(expr) => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::ArrayAgg(expr) => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::JsonAgg(expr) => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::Extract { expr, .. } => visitor.visit_expr(expr.as_ref()),
         FunctionExpr::Call { arguments, .. } => {
             for arg in arguments {
                 visitor.visit_expr(arg)?;
@@ -507,7 +555,11 @@ pub fn walk_common_table_expr<'ast, V: Visitor<'ast>>(
     cte: &'ast CommonTableExpr,
 ) -> Result<(), V::Error> {
     visitor.visit_sql_identifier(&cte.name)?;
-    visitor.visit_select_statement(&cte.statement)
+    visitor.visit_select_statement(&cte.statement)?;
+    if let Some(term) = &cte.recursive_term {
+        visitor.visit_select_statement(&term.statement)?;
+    }
+    Ok(())
 }
 
 pub fn walk_field_definition_expr<'ast, V: Visitor<'ast>>(
@@ -1087,6 +1139,12 @@ pub fn walk_sql_query<'a, V: Visitor<'a>>(
         SqlQuery::CreateCache(statement) => visitor.visit_create_cache_statement(statement),
         SqlQuery::DropCache(statement) => visitor.visit_drop_cache_statement(statement),
         SqlQuery::DropAllCaches(statement) => visitor.visit_drop_all_caches_statement(statement),
+        SqlQuery::CreateRoutingRule(statement) => {
+            visitor.visit_create_routing_rule_statement(statement)
+        }
+        SqlQuery::DropRoutingRule(statement) => {
+            visitor.visit_drop_routing_rule_statement(statement)
+        }
         SqlQuery::DropView(statement) => visitor.visit_drop_view_statement(statement),
         SqlQuery::Use(statement) => visitor.visit_use_statement(statement),
         SqlQuery::Show(statement) => visitor.visit_show_statement(statement),
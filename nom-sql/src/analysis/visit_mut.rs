@@ -17,13 +17,14 @@ use crate::transaction::{CommitStatement, RollbackStatement, StartTransactionSta
 use crate::{
     AlterColumnOperation, AlterTableDefinition, AlterTableStatement, CacheInner, CaseWhenBranch,
     Column, ColumnConstraint, ColumnSpecification, CommonTableExpr, CompoundSelectStatement,
-    CreateCacheStatement, CreateTableStatement, CreateViewStatement, DeleteStatement,
-    DropAllCachesStatement, DropCacheStatement, DropTableStatement, DropViewStatement,
-    ExplainStatement, Expr, FieldDefinitionExpr, FieldReference, FunctionExpr, GroupByClause,
-    InValue, InsertStatement, JoinClause, JoinConstraint, JoinRightSide, Literal, OrderClause,
-    Relation, SelectSpecification, SelectStatement, SetNames, SetPostgresParameter, SetStatement,
-    SetVariables, ShowStatement, SqlIdentifier, SqlQuery, SqlType, TableExpr, TableExprInner,
-    TableKey, UpdateStatement, UseStatement,
+    CreateCacheStatement, CreateRoutingRuleStatement, CreateTableStatement, CreateViewStatement,
+    DeleteStatement, DropAllCachesStatement, DropCacheStatement, DropRoutingRuleStatement,
+    DropTableStatement, DropViewStatement, ExplainStatement, Expr, FieldDefinitionExpr,
+    FieldReference, FunctionExpr, GroupByClause, InValue, InsertStatement, JoinClause,
+    JoinConstraint, JoinRightSide, Literal, OrderClause, Relation, SelectSpecification,
+    SelectStatement, SetNames, SetPostgresParameter, SetStatement, SetVariables, ShowStatement,
+    SqlIdentifier, SqlQuery, SqlType, TableExpr, TableExprInner, TableKey, UpdateStatement,
+    UseStatement,
 };
 
 /// Each method of the `VisitorMut` trait is a hook to be potentially overridden when recursively
@@ -378,6 +379,23 @@ pub trait VisitorMut<'ast>: Sized {
         Ok(())
     }
 
+    fn visit_create_routing_rule_statement(
+        &mut self,
+        create_routing_rule_statement: &'ast mut CreateRoutingRuleStatement,
+    ) -> Result<(), Self::Error> {
+        for table in &mut create_routing_rule_statement.tables {
+            self.visit_table(table)?;
+        }
+        Ok(())
+    }
+
+    fn visit_drop_routing_rule_statement(
+        &mut self,
+        _drop_routing_rule_statement: &'ast mut DropRoutingRuleStatement,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     fn visit_drop_view_statement(
         &mut self,
         drop_view_statement: &'ast mut DropViewStatement,
@@ -460,6 +478,29 @@ pub fn walk_expr<'ast, V: VisitorMut<'ast>>(
             Ok(())
         }
         Expr::Variable(var) => visitor.visit_variable(var),
+        Expr::Interval { value, .. } => visitor.visit_expr(value.as_mut()),
+        Expr::WindowFunction {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            visitor.visit_function_expr(function)?;
+            for expr in partition_by {
+                visitor.visit_expr(expr)?;
+            }
+            for (expr, _) in order_by {
+                visitor.visit_expr(expr)?;
+            }
+            Ok(())
+        }
+        Expr::Match {
+            columns, against, ..
+        } => {
+            for column in columns {
+                visitor.visit_column(column)?;
+            }
+            visitor.visit_expr(against.as_mut())
+        }
     }
 }
 
@@ -483,6 +524,13 @@ pub fn walk_function_expr<'ast, V: VisitorMut<'ast>>(
         FunctionExpr::Max(expr) => visitor.visit_expr(expr.as_mut()),
         FunctionExpr::Min(expr) => visitor.visit_expr(expr.as_mut()),
         FunctionExpr::GroupConcat { expr, .. } => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::Stddev { expr, .. } => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::Variance { expr, .. } => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::BoolAnd(expr) => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::BoolOr(expr) => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::ArrayAgg(expr) => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::JsonAgg(expr) => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::Extract { expr, .. } => visitor.visit_expr(expr.as_mut()),
         FunctionExpr::Call { arguments, .. } => {
             for arg in arguments {
                 visitor.visit_expr(arg)?;
@@ -522,7 +570,11 @@ pub fn walk_common_table_expr<'ast, V: VisitorMut<'ast>>(
     cte: &'ast mut CommonTableExpr,
 ) -> Result<(), V::Error> {
     visitor.visit_sql_identifier(&mut cte.name)?;
-    visitor.visit_select_statement(&mut cte.statement)
+    visitor.visit_select_statement(&mut cte.statement)?;
+    if let Some(term) = &mut cte.recursive_term {
+        visitor.visit_select_statement(&mut term.statement)?;
+    }
+    Ok(())
 }
 
 pub fn walk_field_definition_expr<'ast, V: VisitorMut<'ast>>(
@@ -1102,6 +1154,12 @@ pub fn walk_sql_query<'a, V: VisitorMut<'a>>(
         SqlQuery::CreateCache(statement) => visitor.visit_create_cache_statement(statement),
         SqlQuery::DropCache(statement) => visitor.visit_drop_cache_statement(statement),
         SqlQuery::DropAllCaches(statement) => visitor.visit_drop_all_caches_statement(statement),
+        SqlQuery::CreateRoutingRule(statement) => {
+            visitor.visit_create_routing_rule_statement(statement)
+        }
+        SqlQuery::DropRoutingRule(statement) => {
+            visitor.visit_drop_routing_rule_statement(statement)
+        }
         SqlQuery::DropView(statement) => visitor.visit_drop_view_statement(statement),
         SqlQuery::Use(statement) => visitor.visit_use_statement(statement),
         SqlQuery::Show(statement) => visitor.visit_show_statement(statement),
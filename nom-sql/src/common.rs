@@ -8,7 +8,7 @@ use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case, take_until};
 use nom::character::complete::{digit1, line_ending};
-use nom::combinator::{map, map_res, opt};
+use nom::combinator::{map, map_res, opt, verify};
 use nom::error::{ErrorKind, ParseError};
 use nom::multi::{separated_list0, separated_list1};
 use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::column::Column;
 use crate::dialect::Dialect;
-use crate::expression::expression;
+use crate::expression::{expression, timestamp_field};
 use crate::table::Relation;
 use crate::whitespace::{whitespace0, whitespace1};
 use crate::{Expr, FunctionExpr, Literal, NomSqlResult, SqlIdentifier};
@@ -424,6 +424,86 @@ fn agg_fx_args(
     move |i| delimited(tag("("), agg_function_arguments(dialect), tag(")"))(i)
 }
 
+fn non_distinct_agg_fx_args(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Box<Expr>> {
+    move |i| {
+        map(
+            verify(agg_fx_args(dialect), |(_, distinct)| !distinct),
+            |(expr, _)| Box::new(expr),
+        )(i)
+    }
+}
+
+/// Parses the statistical, boolean, and collecting aggregate functions, none of which support
+/// `DISTINCT`
+fn other_agg_fx(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        // `STD`, `STDDEV` and `VARIANCE` compute the population statistics in MySQL, but the
+        // sample statistics in PostgreSQL
+        let sample = dialect == Dialect::PostgreSQL;
+        alt((
+            map(
+                preceded(tag_no_case("stddev_pop"), non_distinct_agg_fx_args(dialect)),
+                |expr| FunctionExpr::Stddev {
+                    expr,
+                    sample: false,
+                },
+            ),
+            map(
+                preceded(
+                    tag_no_case("stddev_samp"),
+                    non_distinct_agg_fx_args(dialect),
+                ),
+                |expr| FunctionExpr::Stddev { expr, sample: true },
+            ),
+            map(
+                preceded(
+                    alt((tag_no_case("stddev"), tag_no_case("std"))),
+                    non_distinct_agg_fx_args(dialect),
+                ),
+                move |expr| FunctionExpr::Stddev { expr, sample },
+            ),
+            map(
+                preceded(tag_no_case("var_pop"), non_distinct_agg_fx_args(dialect)),
+                |expr| FunctionExpr::Variance {
+                    expr,
+                    sample: false,
+                },
+            ),
+            map(
+                preceded(tag_no_case("var_samp"), non_distinct_agg_fx_args(dialect)),
+                |expr| FunctionExpr::Variance { expr, sample: true },
+            ),
+            map(
+                preceded(tag_no_case("variance"), non_distinct_agg_fx_args(dialect)),
+                move |expr| FunctionExpr::Variance { expr, sample },
+            ),
+            map(
+                preceded(
+                    alt((tag_no_case("bool_and"), tag_no_case("every"))),
+                    non_distinct_agg_fx_args(dialect),
+                ),
+                FunctionExpr::BoolAnd,
+            ),
+            map(
+                preceded(tag_no_case("bool_or"), non_distinct_agg_fx_args(dialect)),
+                FunctionExpr::BoolOr,
+            ),
+            map(
+                preceded(tag_no_case("array_agg"), non_distinct_agg_fx_args(dialect)),
+                FunctionExpr::ArrayAgg,
+            ),
+            map(
+                preceded(tag_no_case("json_agg"), non_distinct_agg_fx_args(dialect)),
+                FunctionExpr::JsonAgg,
+            ),
+        ))(i)
+    }
+}
+
 fn delim_fx_args(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Vec<Expr>> {
@@ -494,11 +574,52 @@ fn substring(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[
     }
 }
 
+/// Names of builtin functions which are also reserved keywords, and so would otherwise not be
+/// parsed by [`Dialect::function_identifier`]
+fn extract(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, _) = tag_no_case("extract")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag("(")(i)?;
+        let (i, _) = whitespace0(i)?;
+        // PostgreSQL also allows the field to be given as a string literal
+        let (i, field) = alt((
+            delimited(tag("'"), timestamp_field, tag("'")),
+            timestamp_field,
+        ))(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("from")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, expr) = expression(dialect)(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag(")")(i)?;
+
+        Ok((
+            i,
+            FunctionExpr::Extract {
+                field,
+                expr: Box::new(expr),
+            },
+        ))
+    }
+}
+
+fn keyword_function_name(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], &str> {
+    map_res(
+        alt((
+            tag_no_case("left"),
+            tag_no_case("right"),
+            tag_no_case("replace"),
+        )),
+        |i: LocatedSpan<&[u8]>| str::from_utf8(&i),
+    )(i)
+}
+
 fn function_call(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
     move |i| {
-        let (i, name) = dialect.function_identifier()(i)?;
+        let (i, name) = alt((dialect.function_identifier(), keyword_function_name))(i)?;
         let (i, _) = whitespace0(i)?;
         let (i, arguments) = delim_fx_args(dialect)(i)?;
         Ok((
@@ -560,7 +681,9 @@ pub fn function_expr(
                     }
                 },
             ),
+            other_agg_fx(dialect),
             substring(dialect),
+            extract(dialect),
             function_call(dialect),
             function_call_without_parens,
         ))(i)
@@ -817,7 +940,7 @@ pub fn field_reference_list(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{to_nom_result, SqlType};
+    use crate::{to_nom_result, SqlType, TimestampField};
 
     fn test_opt_delimited_fn_call(i: &str) -> IResult<&[u8], &[u8]> {
         opt_delimited(tag("("), tag("abc"), tag(")"))(i.as_bytes())
@@ -865,6 +988,58 @@ mod tests {
         assert_eq!(res.unwrap().1, expected);
     }
 
+    #[test]
+    fn statistical_aggregates() {
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"stddev(x)"),
+            FunctionExpr::Stddev {
+                expr: Box::new(Expr::Column("x".into())),
+                sample: false,
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"stddev(x)"),
+            FunctionExpr::Stddev {
+                expr: Box::new(Expr::Column("x".into())),
+                sample: true,
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"var_samp(x)"),
+            FunctionExpr::Variance {
+                expr: Box::new(Expr::Column("x".into())),
+                sample: true,
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"variance(x)"),
+            FunctionExpr::Variance {
+                expr: Box::new(Expr::Column("x".into())),
+                sample: true,
+            }
+        );
+    }
+
+    #[test]
+    fn bool_and_array_aggregates() {
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"every(x)"),
+            FunctionExpr::BoolAnd(Box::new(Expr::Column("x".into())))
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"bool_or(x)"),
+            FunctionExpr::BoolOr(Box::new(Expr::Column("x".into())))
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"array_agg(x)"),
+            FunctionExpr::ArrayAgg(Box::new(Expr::Column("x".into())))
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"json_agg(x)"),
+            FunctionExpr::JsonAgg(Box::new(Expr::Column("x".into())))
+        );
+    }
+
     #[test]
     fn simple_generic_function() {
         let qlist = [
@@ -887,6 +1062,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn keyword_named_functions() {
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"left(a, 2)"),
+            FunctionExpr::Call {
+                name: "left".into(),
+                arguments: vec![
+                    Expr::Column(Column::from("a")),
+                    Expr::Literal(Literal::UnsignedInteger(2)),
+                ],
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"replace(a, 'x', 'y')"),
+            FunctionExpr::Call {
+                name: "replace".into(),
+                arguments: vec![
+                    Expr::Column(Column::from("a")),
+                    Expr::Literal("x".into()),
+                    Expr::Literal("y".into()),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn extract() {
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::MySQL),
+                b"extract(year_month from created_at)"
+            ),
+            FunctionExpr::Extract {
+                field: TimestampField::YearMonth,
+                expr: Box::new(Expr::Column(Column::from("created_at"))),
+            }
+        );
+        let res = test_parse!(
+            function_expr(Dialect::PostgreSQL),
+            b"EXTRACT('epoch' FROM created_at)"
+        );
+        assert_eq!(
+            res,
+            FunctionExpr::Extract {
+                field: TimestampField::Epoch,
+                expr: Box::new(Expr::Column(Column::from("created_at"))),
+            }
+        );
+        assert_eq!(res.to_string(), "extract(EPOCH FROM `created_at`)");
+    }
+
     #[test]
     fn nested_function_call() {
         let res = test_parse!(function_expr(Dialect::MySQL), b"max(min(foo))");
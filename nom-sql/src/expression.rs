@@ -5,10 +5,10 @@ use concrete_iter::concrete_iter;
 use derive_more::From;
 use itertools::Itertools;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, tag_no_case};
+use nom::bytes::complete::{tag, tag_no_case, take_while1};
 use nom::character::complete::char;
-use nom::combinator::{complete, map, opt};
-use nom::multi::{many0, many1, separated_list0};
+use nom::combinator::{complete, map, not, opt, peek, value};
+use nom::multi::{many0, many1, separated_list0, separated_list1};
 use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::Parser;
 use nom_locate::LocatedSpan;
@@ -18,6 +18,7 @@ use test_strategy::Arbitrary;
 
 use crate::common::{column_identifier_no_alias, function_expr, ws_sep_comma};
 use crate::literal::literal;
+use crate::order::{order_type, OrderType};
 use crate::select::nested_selection;
 use crate::set::{variable_scope_prefix, Variable};
 use crate::sql_type::{mysql_int_cast_targets, type_identifier};
@@ -48,6 +49,25 @@ pub enum FunctionExpr {
     /// `GROUP_CONCAT` aggregation. The second argument is the separator
     GroupConcat { expr: Box<Expr>, separator: String },
 
+    /// `STDDEV_POP`/`STDDEV_SAMP` aggregation. The boolean argument is `true` for the sample
+    /// standard deviation
+    Stddev { expr: Box<Expr>, sample: bool },
+
+    /// `VAR_POP`/`VAR_SAMP` aggregation. The boolean argument is `true` for the sample variance
+    Variance { expr: Box<Expr>, sample: bool },
+
+    /// `BOOL_AND` aggregation
+    BoolAnd(Box<Expr>),
+
+    /// `BOOL_OR` aggregation
+    BoolOr(Box<Expr>),
+
+    /// `ARRAY_AGG` aggregation
+    ArrayAgg(Box<Expr>),
+
+    /// `JSON_AGG` aggregation
+    JsonAgg(Box<Expr>),
+
     /// The SQL `SUBSTRING`/`SUBSTR` function.
     ///
     /// The supported syntax is one of:
@@ -62,6 +82,12 @@ pub enum FunctionExpr {
         len: Option<Box<Expr>>,
     },
 
+    /// The SQL `EXTRACT(field FROM expr)` function
+    Extract {
+        field: TimestampField,
+        expr: Box<Expr>,
+    },
+
     /// Generic function call expression
     Call {
         name: SqlIdentifier,
@@ -80,7 +106,14 @@ impl FunctionExpr {
             | FunctionExpr::Sum { expr: arg, .. }
             | FunctionExpr::Max(arg)
             | FunctionExpr::Min(arg)
-            | FunctionExpr::GroupConcat { expr: arg, .. } => {
+            | FunctionExpr::GroupConcat { expr: arg, .. }
+            | FunctionExpr::Stddev { expr: arg, .. }
+            | FunctionExpr::Variance { expr: arg, .. }
+            | FunctionExpr::BoolAnd(arg)
+            | FunctionExpr::BoolOr(arg)
+            | FunctionExpr::ArrayAgg(arg)
+            | FunctionExpr::JsonAgg(arg)
+            | FunctionExpr::Extract { expr: arg, .. } => {
                 concrete_iter!(iter::once(arg.as_ref()))
             }
             FunctionExpr::CountStar => concrete_iter!(iter::empty()),
@@ -118,6 +151,14 @@ impl Display for FunctionExpr {
             FunctionExpr::GroupConcat { expr, separator } => {
                 write!(f, "group_concat({} separator '{}')", expr, separator)
             }
+            FunctionExpr::Stddev { expr, sample: true } => write!(f, "stddev_samp({})", expr),
+            FunctionExpr::Stddev { expr, .. } => write!(f, "stddev_pop({})", expr),
+            FunctionExpr::Variance { expr, sample: true } => write!(f, "var_samp({})", expr),
+            FunctionExpr::Variance { expr, .. } => write!(f, "var_pop({})", expr),
+            FunctionExpr::BoolAnd(expr) => write!(f, "bool_and({})", expr),
+            FunctionExpr::BoolOr(expr) => write!(f, "bool_or({})", expr),
+            FunctionExpr::ArrayAgg(expr) => write!(f, "array_agg({})", expr),
+            FunctionExpr::JsonAgg(expr) => write!(f, "json_agg({})", expr),
             FunctionExpr::Call { name, arguments } => {
                 write!(f, "{}({})", name, arguments.iter().join(", "))
             }
@@ -133,10 +174,115 @@ impl Display for FunctionExpr {
 
                 write!(f, ")")
             }
+            FunctionExpr::Extract { field, expr } => write!(f, "extract({field} FROM {expr})"),
         }
     }
 }
 
+/// A field of a date or time value, used both as the field to extract in an `EXTRACT(field FROM
+/// expr)` expression and as the unit of an `INTERVAL` expression.
+///
+/// The compound fields (eg `DAY_HOUR`) are only supported by MySQL, and the `DOW`, `ISODOW`,
+/// `DOY`, and `EPOCH` fields are only supported by PostgreSQL.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum TimestampField {
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+    /// The day of the week, from 0 (Sunday) to 6 (Saturday)
+    Dow,
+    /// The day of the week, from 1 (Monday) to 7 (Sunday)
+    IsoDow,
+    /// The day of the year, from 1 to 366
+    Doy,
+    /// The number of seconds since the Unix epoch
+    Epoch,
+    SecondMicrosecond,
+    MinuteMicrosecond,
+    MinuteSecond,
+    HourMicrosecond,
+    HourSecond,
+    HourMinute,
+    DayMicrosecond,
+    DaySecond,
+    DayMinute,
+    DayHour,
+    YearMonth,
+}
+
+impl Display for TimestampField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = match self {
+            Self::Microsecond => "MICROSECOND",
+            Self::Millisecond => "MILLISECOND",
+            Self::Second => "SECOND",
+            Self::Minute => "MINUTE",
+            Self::Hour => "HOUR",
+            Self::Day => "DAY",
+            Self::Week => "WEEK",
+            Self::Month => "MONTH",
+            Self::Quarter => "QUARTER",
+            Self::Year => "YEAR",
+            Self::Decade => "DECADE",
+            Self::Century => "CENTURY",
+            Self::Millennium => "MILLENNIUM",
+            Self::Dow => "DOW",
+            Self::IsoDow => "ISODOW",
+            Self::Doy => "DOY",
+            Self::Epoch => "EPOCH",
+            Self::SecondMicrosecond => "SECOND_MICROSECOND",
+            Self::MinuteMicrosecond => "MINUTE_MICROSECOND",
+            Self::MinuteSecond => "MINUTE_SECOND",
+            Self::HourMicrosecond => "HOUR_MICROSECOND",
+            Self::HourSecond => "HOUR_SECOND",
+            Self::HourMinute => "HOUR_MINUTE",
+            Self::DayMicrosecond => "DAY_MICROSECOND",
+            Self::DaySecond => "DAY_SECOND",
+            Self::DayMinute => "DAY_MINUTE",
+            Self::DayHour => "DAY_HOUR",
+            Self::YearMonth => "YEAR_MONTH",
+        };
+        f.write_str(field)
+    }
+}
+
+/// The search modifier of a MySQL `MATCH (col, ...) AGAINST (expr [modifier])` expression
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum MatchSearchModifier {
+    /// `IN NATURAL LANGUAGE MODE`
+    NaturalLanguage,
+    /// `IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION`
+    NaturalLanguageWithQueryExpansion,
+    /// `IN BOOLEAN MODE`
+    Boolean,
+    /// `WITH QUERY EXPANSION`
+    QueryExpansion,
+}
+
+impl Display for MatchSearchModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifier = match self {
+            Self::NaturalLanguage => "IN NATURAL LANGUAGE MODE",
+            Self::NaturalLanguageWithQueryExpansion => {
+                "IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION"
+            }
+            Self::Boolean => "IN BOOLEAN MODE",
+            Self::QueryExpansion => "WITH QUERY EXPANSION",
+        };
+        f.write_str(modifier)
+    }
+}
+
 /// Binary infix operators with [`Expr`] on both the left- and right-hand sides
 ///
 /// This type is used as the operator in [`Expr::BinaryOp`].
@@ -190,6 +336,22 @@ pub enum BinaryOperator {
     Multiply,
     /// `/`
     Divide,
+    /// `%` or `MOD`
+    Modulo,
+
+    /// `&`
+    BitwiseAnd,
+    /// `|`
+    BitwiseOr,
+    /// `<<`
+    ShiftLeft,
+    /// `>>`
+    ShiftRight,
+
+    /// `^`
+    ///
+    /// In MySQL this is bitwise XOR, whereas in Postgres it is exponentiation.
+    Caret,
 
     /// `?`
     ///
@@ -220,14 +382,14 @@ pub enum BinaryOperator {
     /// `->`
     ///
     /// This extracts JSON values as JSON:
-    /// - MySQL: `json -> jsonpath` to `json` (unimplemented)
+    /// - MySQL: `json -> jsonpath` to `json`
     /// - PostgreSQL: `json[b] -> {text,integer}` to `json[b]`
     Arrow1,
 
     /// `->>`
     ///
     /// This extracts JSON values and applies a transformation:
-    /// - MySQL: `json ->> jsonpath` to unquoted `text` (unimplemented)
+    /// - MySQL: `json ->> jsonpath` to unquoted `text`
     /// - PostgreSQL: `json[b] ->> {text,integer}` to `text`
     Arrow2,
 
@@ -243,15 +405,29 @@ pub enum BinaryOperator {
 
     /// `@>`
     ///
-    /// Postgres-specific JSONB operator. Takes two JSONB values and determines whether the
-    /// left-side values immediately contain all of the right-side values.
+    /// Postgres-specific JSONB and range operator. Takes two JSONB values and determines whether
+    /// the left-side values immediately contain all of the right-side values, or takes a range
+    /// and either a range or an element and determines whether the range contains the right
+    /// side.
     AtArrowRight,
 
     /// `<@`
     ///
-    /// Postgres-specific JSONB operator. Behaves like [`BinaryOperator::AtArrowRight`] with
-    /// switched sides for the operands.
+    /// Postgres-specific JSONB and range operator. Behaves like [`BinaryOperator::AtArrowRight`]
+    /// with switched sides for the operands.
     AtArrowLeft,
+
+    /// `&&`
+    ///
+    /// Postgres-specific range (and array) operator. Takes two ranges and determines whether they
+    /// have any elements in common.
+    DoubleAmpersand,
+
+    /// `@@`
+    ///
+    /// Postgres-specific full-text search operator. Matches a `tsvector` against a `tsquery`,
+    /// returning a boolean indicating whether the document satisfies the query.
+    AtAt,
 }
 
 impl BinaryOperator {
@@ -297,6 +473,12 @@ impl Display for BinaryOperator {
             Self::HashSubtract => "#-",
             Self::Multiply => "*",
             Self::Divide => "/",
+            Self::Modulo => "%",
+            Self::BitwiseAnd => "&",
+            Self::BitwiseOr => "|",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::Caret => "^",
             Self::QuestionMark => "?",
             Self::QuestionMarkPipe => "?|",
             Self::QuestionMarkAnd => "?&",
@@ -307,6 +489,8 @@ impl Display for BinaryOperator {
             Self::HashArrow2 => "#>>",
             Self::AtArrowRight => "@>",
             Self::AtArrowLeft => "<@",
+            Self::DoubleAmpersand => "&&",
+            Self::AtAt => "@@",
         };
         f.write_str(op)
     }
@@ -423,6 +607,30 @@ pub enum Expr {
 
     /// A variable reference
     Variable(Variable),
+
+    /// A window function call, eg `ROW_NUMBER() OVER (PARTITION BY x ORDER BY y DESC)`
+    WindowFunction {
+        function: FunctionExpr,
+        partition_by: Vec<Expr>,
+        order_by: Vec<(Expr, Option<OrderType>)>,
+    },
+
+    /// An interval expression, eg `INTERVAL 7 DAY` (MySQL) or `INTERVAL '7 days'` (PostgreSQL)
+    Interval {
+        /// The quantity of the interval. In PostgreSQL, this is always a string literal, which may
+        /// include its own units
+        value: Box<Expr>,
+        /// The unit of the interval, which is required in MySQL but optional in PostgreSQL
+        unit: Option<TimestampField>,
+    },
+
+    /// A MySQL full-text search expression, eg `MATCH (title, body) AGAINST ('foo' IN BOOLEAN
+    /// MODE)`
+    Match {
+        columns: Vec<Column>,
+        against: Box<Expr>,
+        modifier: Option<MatchSearchModifier>,
+    },
 }
 
 impl Display for Expr {
@@ -508,6 +716,55 @@ impl Display for Expr {
                 write!(f, "]")
             }
             Expr::Variable(var) => write!(f, "{}", var),
+            Expr::Interval { value, unit } => {
+                write!(f, "INTERVAL {value}")?;
+                if let Some(unit) = unit {
+                    write!(f, " {unit}")?;
+                }
+                Ok(())
+            }
+            Expr::Match {
+                columns,
+                against,
+                modifier,
+            } => {
+                write!(
+                    f,
+                    "MATCH ({}) AGAINST ({against}",
+                    columns.iter().join(", ")
+                )?;
+                if let Some(modifier) = modifier {
+                    write!(f, " {modifier}")?;
+                }
+                write!(f, ")")
+            }
+            Expr::WindowFunction {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                write!(f, "{} OVER (", function)?;
+                if !partition_by.is_empty() {
+                    write!(f, "PARTITION BY {}", partition_by.iter().join(", "))?;
+                    if !order_by.is_empty() {
+                        write!(f, " ")?;
+                    }
+                }
+                if !order_by.is_empty() {
+                    write!(
+                        f,
+                        "ORDER BY {}",
+                        order_by
+                            .iter()
+                            .map(|(expr, ot)| match ot {
+                                Some(ot) => format!("{} {}", expr, ot),
+                                None => expr.to_string(),
+                            })
+                            .join(", ")
+                    )?;
+                }
+                write!(f, ")")
+            }
         }
     }
 }
@@ -598,10 +855,18 @@ fn infix_no_and_or(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TokenTree> {
             Ok((i, BinaryOperator::IsNot))
         },
         map(pair(tag_no_case("is"), whitespace1), |_| BinaryOperator::Is),
+        map(terminated(tag_no_case("mod"), whitespace1), |_| {
+            BinaryOperator::Modulo
+        }),
         // Sigils are separated due to `alt` limit.
         //
         // NOTE: The order here matters or else some of these will be incorrectly partially parsed,
-        // such as `?` after `?|`.
+        // such as `?` after `?|`, `<` after `<<`, or `|` after `||`.
+        alt((
+            map(tag("<<"), |_| BinaryOperator::ShiftLeft),
+            map(tag(">>"), |_| BinaryOperator::ShiftRight),
+            map(tag("@@"), |_| BinaryOperator::AtAt),
+        )),
         alt((
             map(tag("@>"), |_| BinaryOperator::AtArrowRight),
             map(tag("<@"), |_| BinaryOperator::AtArrowLeft),
@@ -626,6 +891,13 @@ fn infix_no_and_or(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TokenTree> {
             map(tag("#>"), |_| BinaryOperator::HashArrow1),
         )),
         map(tag("#-"), |_| BinaryOperator::HashSubtract),
+        alt((
+            map(char('%'), |_| BinaryOperator::Modulo),
+            map(tag("&&"), |_| BinaryOperator::DoubleAmpersand),
+            map(char('&'), |_| BinaryOperator::BitwiseAnd),
+            map(char('|'), |_| BinaryOperator::BitwiseOr),
+            map(char('^'), |_| BinaryOperator::Caret),
+        )),
     ))(i)?;
 
     Ok((i, TokenTree::Infix(operator)))
@@ -808,6 +1080,12 @@ where
             Infix(Subtract) => Affix::Infix(Precedence(11), Associativity::Right),
             Infix(Multiply) => Affix::Infix(Precedence(12), Associativity::Right),
             Infix(Divide) => Affix::Infix(Precedence(12), Associativity::Right),
+            Infix(Modulo) => Affix::Infix(Precedence(12), Associativity::Right),
+            Infix(Caret) => Affix::Infix(Precedence(13), Associativity::Left),
+            Infix(ShiftLeft) => Affix::Infix(Precedence(10), Associativity::Left),
+            Infix(ShiftRight) => Affix::Infix(Precedence(10), Associativity::Left),
+            Infix(BitwiseAnd) => Affix::Infix(Precedence(9), Associativity::Left),
+            Infix(BitwiseOr) => Affix::Infix(Precedence(8), Associativity::Left),
             Prefix(Not) => Affix::Prefix(Precedence(6)),
             Prefix(Neg) => Affix::Prefix(Precedence(5)),
             Primary(_) => Affix::Nilfix,
@@ -829,7 +1107,9 @@ where
             Infix(HashArrow2) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(AtArrowRight) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(AtArrowLeft) => Affix::Infix(Precedence(8), Associativity::Left),
+            Infix(DoubleAmpersand) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(HashSubtract) => Affix::Infix(Precedence(8), Associativity::Left),
+            Infix(AtAt) => Affix::Infix(Precedence(8), Associativity::Left),
         })
     }
 
@@ -1087,6 +1367,59 @@ fn cast(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8],
     }
 }
 
+fn window_order_field(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], (Expr, Option<OrderType>)> {
+    move |i| {
+        let (i, expr) = expression(dialect)(i)?;
+        let (i, ord_typ) = opt(preceded(whitespace1, order_type))(i)?;
+        Ok((i, (expr, ord_typ)))
+    }
+}
+
+fn window_function_expr(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Expr> {
+    move |i| {
+        let (i, function) = function_expr(dialect)(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag_no_case("over")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = char('(')(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, partition_by) = opt(preceded(
+            tuple((
+                tag_no_case("partition"),
+                whitespace1,
+                tag_no_case("by"),
+                whitespace1,
+            )),
+            separated_list1(ws_sep_comma, expression(dialect)),
+        ))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, order_by) = opt(preceded(
+            tuple((
+                tag_no_case("order"),
+                whitespace1,
+                tag_no_case("by"),
+                whitespace1,
+            )),
+            separated_list1(ws_sep_comma, window_order_field(dialect)),
+        ))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = char(')')(i)?;
+
+        Ok((
+            i,
+            Expr::WindowFunction {
+                function,
+                partition_by: partition_by.unwrap_or_default(),
+                order_by: order_by.unwrap_or_default(),
+            },
+        ))
+    }
+}
+
 fn nested_select(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Expr> {
     move |i| {
         let (i, _) = char('(')(i)?;
@@ -1153,6 +1486,153 @@ fn array_expr(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&
     }
 }
 
+/// Parses a [`TimestampField`]
+pub(crate) fn timestamp_field(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TimestampField> {
+    use TimestampField::*;
+
+    terminated(
+        alt((
+            // Compound fields need to come first, so that we don't parse their prefix as a simple
+            // field
+            alt((
+                value(SecondMicrosecond, tag_no_case("second_microsecond")),
+                value(MinuteMicrosecond, tag_no_case("minute_microsecond")),
+                value(MinuteSecond, tag_no_case("minute_second")),
+                value(HourMicrosecond, tag_no_case("hour_microsecond")),
+                value(HourSecond, tag_no_case("hour_second")),
+                value(HourMinute, tag_no_case("hour_minute")),
+                value(DayMicrosecond, tag_no_case("day_microsecond")),
+                value(DaySecond, tag_no_case("day_second")),
+                value(DayMinute, tag_no_case("day_minute")),
+                value(DayHour, tag_no_case("day_hour")),
+                value(YearMonth, tag_no_case("year_month")),
+            )),
+            alt((
+                value(Microsecond, tag_no_case("microseconds")),
+                value(Microsecond, tag_no_case("microsecond")),
+                value(Millisecond, tag_no_case("milliseconds")),
+                value(Millisecond, tag_no_case("millisecond")),
+                value(Millennium, tag_no_case("millennium")),
+                value(Second, tag_no_case("second")),
+                value(Minute, tag_no_case("minute")),
+                value(Hour, tag_no_case("hour")),
+                value(Day, tag_no_case("day")),
+                value(Week, tag_no_case("week")),
+                value(Month, tag_no_case("month")),
+                value(Quarter, tag_no_case("quarter")),
+                value(Year, tag_no_case("year")),
+                value(Decade, tag_no_case("decade")),
+                value(Century, tag_no_case("century")),
+                value(Dow, tag_no_case("dow")),
+                value(IsoDow, tag_no_case("isodow")),
+                value(Doy, tag_no_case("doy")),
+                value(Epoch, tag_no_case("epoch")),
+            )),
+        )),
+        not(peek(take_while1(|c: u8| {
+            c.is_ascii_alphanumeric() || c == b'_'
+        }))),
+    )(i)
+}
+
+fn interval_expr(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Expr> {
+    move |i| {
+        let (i, _) = tag_no_case("interval")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, (value, unit)) = match dialect {
+            // PostgreSQL only supports interval literals, optionally followed by a unit
+            Dialect::PostgreSQL => pair(
+                map(literal(dialect), Expr::Literal),
+                opt(preceded(whitespace1, timestamp_field)),
+            )(i)?,
+            Dialect::MySQL => pair(
+                simple_expr(dialect),
+                map(preceded(whitespace1, timestamp_field), Some),
+            )(i)?,
+        };
+
+        Ok((
+            i,
+            Expr::Interval {
+                value: Box::new(value),
+                unit,
+            },
+        ))
+    }
+}
+
+fn with_query_expansion(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], LocatedSpan<&[u8]>> {
+    let (i, _) = tag_no_case("with")(i)?;
+    let (i, _) = whitespace1(i)?;
+    let (i, _) = tag_no_case("query")(i)?;
+    let (i, _) = whitespace1(i)?;
+    tag_no_case("expansion")(i)
+}
+
+fn match_search_modifier(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], MatchSearchModifier> {
+    alt((
+        move |i| {
+            let (i, _) = tag_no_case("in")(i)?;
+            let (i, _) = whitespace1(i)?;
+            let (i, _) = tag_no_case("natural")(i)?;
+            let (i, _) = whitespace1(i)?;
+            let (i, _) = tag_no_case("language")(i)?;
+            let (i, _) = whitespace1(i)?;
+            let (i, _) = tag_no_case("mode")(i)?;
+            let (i, expansion) = opt(preceded(whitespace1, with_query_expansion))(i)?;
+            Ok((
+                i,
+                if expansion.is_some() {
+                    MatchSearchModifier::NaturalLanguageWithQueryExpansion
+                } else {
+                    MatchSearchModifier::NaturalLanguage
+                },
+            ))
+        },
+        move |i| {
+            let (i, _) = tag_no_case("in")(i)?;
+            let (i, _) = whitespace1(i)?;
+            let (i, _) = tag_no_case("boolean")(i)?;
+            let (i, _) = whitespace1(i)?;
+            let (i, _) = tag_no_case("mode")(i)?;
+            Ok((i, MatchSearchModifier::Boolean))
+        },
+        value(MatchSearchModifier::QueryExpansion, with_query_expansion),
+    ))(i)
+}
+
+fn match_against_expr(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Expr> {
+    move |i| {
+        let (i, _) = tag_no_case("match")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = char('(')(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, columns) = separated_list1(ws_sep_comma, column_identifier_no_alias(dialect))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = char(')')(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag_no_case("against")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = char('(')(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, against) = simple_expr(dialect)(i)?;
+        let (i, modifier) = opt(preceded(whitespace1, match_search_modifier))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = char(')')(i)?;
+
+        Ok((
+            i,
+            Expr::Match {
+                columns,
+                against: Box::new(against),
+                modifier,
+            },
+        ))
+    }
+}
+
 // Expressions without (binary or unary) operators
 pub(crate) fn simple_expr(
     dialect: Dialect,
@@ -1164,6 +1644,9 @@ pub(crate) fn simple_expr(
             exists_expr(dialect),
             between_expr(dialect),
             in_expr(dialect),
+            window_function_expr(dialect),
+            interval_expr(dialect),
+            match_against_expr(dialect),
             map(function_expr(dialect), Expr::Call),
             map(literal(dialect), Expr::Literal),
             case_when_expr(dialect),
@@ -1219,6 +1702,19 @@ mod tests {
             parses_same(Dialect::MySQL, "1 + 2 * 3", "(1 + (2 * 3))");
         }
 
+        #[test]
+        fn arithmetic_and_bitwise() {
+            parses_same(
+                Dialect::MySQL,
+                "a | b & c << 1 + d % 2 ^ e",
+                "a | (b & (c << (1 + (d % (2 ^ e)))))",
+            );
+            parses_same(Dialect::MySQL, "a mod 4 = 1", "(a % 4) = 1");
+            parses_same(Dialect::MySQL, "a >> 1 >> 2", "(a >> 1) >> 2");
+            parses_same(Dialect::PostgreSQL, "a || b | c", "(a || b) | c");
+            parses_same(Dialect::PostgreSQL, "a < b << c", "a < (b << c)");
+        }
+
         #[test]
         fn between_and_or() {
             parses_same(
@@ -2043,9 +2539,192 @@ mod tests {
         );
     }
 
+    #[test]
+    fn window_function() {
+        let res = test_parse!(
+            expression(Dialect::MySQL),
+            b"row_number() over (partition by user_id order by created_at desc, id)"
+        );
+        assert_eq!(
+            res,
+            Expr::WindowFunction {
+                function: FunctionExpr::Call {
+                    name: "row_number".into(),
+                    arguments: vec![],
+                },
+                partition_by: vec![Expr::Column("user_id".into())],
+                order_by: vec![
+                    (
+                        Expr::Column("created_at".into()),
+                        Some(OrderType::OrderDescending)
+                    ),
+                    (Expr::Column("id".into()), None),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn window_function_without_partition() {
+        let res = test_parse!(expression(Dialect::MySQL), b"sum(x) OVER (ORDER BY y)");
+        assert_eq!(
+            res,
+            Expr::WindowFunction {
+                function: FunctionExpr::Sum {
+                    expr: Box::new(Expr::Column("x".into())),
+                    distinct: false,
+                },
+                partition_by: vec![],
+                order_by: vec![(Expr::Column("y".into()), None)],
+            }
+        );
+    }
+
+    #[test]
+    fn window_function_display() {
+        let expr = test_parse!(
+            expression(Dialect::MySQL),
+            b"count(*) over (partition by a, b order by c desc)"
+        );
+        assert_eq!(
+            expr.to_string(),
+            "count(*) OVER (PARTITION BY `a`, `b` ORDER BY `c` DESC)"
+        );
+    }
+
+    #[test]
+    fn mysql_interval() {
+        let expr = test_parse!(expression(Dialect::MySQL), b"created_at + INTERVAL 1 day");
+        assert_eq!(
+            expr,
+            Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("created_at".into())),
+                op: BinaryOperator::Add,
+                rhs: Box::new(Expr::Interval {
+                    value: Box::new(Expr::Literal(Literal::UnsignedInteger(1))),
+                    unit: Some(TimestampField::Day),
+                }),
+            }
+        );
+        assert_eq!(expr.to_string(), "(`created_at` + INTERVAL 1 DAY)");
+
+        let expr = test_parse!(expression(Dialect::MySQL), b"interval '1:30' hour_minute");
+        assert_eq!(
+            expr,
+            Expr::Interval {
+                value: Box::new(Expr::Literal("1:30".into())),
+                unit: Some(TimestampField::HourMinute),
+            }
+        );
+    }
+
+    #[test]
+    fn postgres_interval() {
+        let expr = test_parse!(expression(Dialect::PostgreSQL), b"interval '7 days'");
+        assert_eq!(
+            expr,
+            Expr::Interval {
+                value: Box::new(Expr::Literal("7 days".into())),
+                unit: None,
+            }
+        );
+        assert_eq!(expr.to_string(), "INTERVAL '7 days'");
+
+        let expr = test_parse!(expression(Dialect::PostgreSQL), b"INTERVAL '2' hour");
+        assert_eq!(
+            expr,
+            Expr::Interval {
+                value: Box::new(Expr::Literal("2".into())),
+                unit: Some(TimestampField::Hour),
+            }
+        );
+    }
+
     mod mysql {
         use super::*;
 
+        #[test]
+        fn match_against() {
+            let expr = test_parse!(
+                expression(Dialect::MySQL),
+                b"MATCH (title, t.body) AGAINST ('+foo -bar' IN BOOLEAN MODE)"
+            );
+            assert_eq!(
+                expr,
+                Expr::Match {
+                    columns: vec!["title".into(), "t.body".into()],
+                    against: Box::new(Expr::Literal("+foo -bar".into())),
+                    modifier: Some(MatchSearchModifier::Boolean),
+                }
+            );
+            assert_eq!(
+                expr.to_string(),
+                "MATCH (`title`, `t`.`body`) AGAINST ('+foo -bar' IN BOOLEAN MODE)"
+            );
+        }
+
+        #[test]
+        fn match_against_modifiers() {
+            let expr = test_parse!(expression(Dialect::MySQL), b"match(title) against (?)");
+            assert_eq!(
+                expr,
+                Expr::Match {
+                    columns: vec!["title".into()],
+                    against: Box::new(Expr::Literal(Literal::Placeholder(
+                        crate::ItemPlaceholder::QuestionMark
+                    ))),
+                    modifier: None,
+                }
+            );
+
+            for (modifier, expected) in [
+                (
+                    "in natural language mode",
+                    MatchSearchModifier::NaturalLanguage,
+                ),
+                (
+                    "IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION",
+                    MatchSearchModifier::NaturalLanguageWithQueryExpansion,
+                ),
+                ("with query expansion", MatchSearchModifier::QueryExpansion),
+            ] {
+                let input = format!("MATCH (title) AGAINST ('foo' {modifier})");
+                let expr = test_parse!(expression(Dialect::MySQL), input.as_bytes());
+                assert_eq!(
+                    expr,
+                    Expr::Match {
+                        columns: vec!["title".into()],
+                        against: Box::new(Expr::Literal("foo".into())),
+                        modifier: Some(expected),
+                    }
+                );
+            }
+        }
+
+        #[test]
+        fn match_against_in_condition() {
+            let expr = test_parse!(
+                expression(Dialect::MySQL),
+                b"MATCH (title) AGAINST ('foo') AND id > 3"
+            );
+            assert_eq!(
+                expr,
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Match {
+                        columns: vec!["title".into()],
+                        against: Box::new(Expr::Literal("foo".into())),
+                        modifier: None,
+                    }),
+                    op: BinaryOperator::And,
+                    rhs: Box::new(Expr::BinaryOp {
+                        lhs: Box::new(Expr::Column("id".into())),
+                        op: BinaryOperator::Greater,
+                        rhs: Box::new(Expr::Literal(Literal::UnsignedInteger(3))),
+                    }),
+                }
+            );
+        }
+
         mod precedence {
             use super::tests::precedence::parses_same;
             use crate::Dialect;
@@ -2374,6 +3053,60 @@ mod tests {
                 );
             }
 
+            #[test]
+            fn double_ampersand_operator() {
+                let cond = b"during && '[2020-01-01,2020-02-01)' and id = 1";
+                let res = test_parse!(expression(Dialect::PostgreSQL), cond);
+                assert_eq!(
+                    res,
+                    Expr::BinaryOp {
+                        lhs: Box::new(Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column("during".into())),
+                            op: BinaryOperator::DoubleAmpersand,
+                            rhs: Box::new(Expr::Literal("[2020-01-01,2020-02-01)".into())),
+                        }),
+                        op: BinaryOperator::And,
+                        rhs: Box::new(Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column("id".into())),
+                            op: BinaryOperator::Equal,
+                            rhs: Box::new(Expr::Literal(1.into())),
+                        }),
+                    }
+                );
+            }
+
+            #[test]
+            fn at_at_operator() {
+                let cond = b"to_tsvector(body) @@ to_tsquery('foo & bar') and id = 1";
+                let res = test_parse!(expression(Dialect::PostgreSQL), cond);
+                assert_eq!(
+                    res,
+                    Expr::BinaryOp {
+                        lhs: Box::new(Expr::BinaryOp {
+                            lhs: Box::new(Expr::Call(FunctionExpr::Call {
+                                name: "to_tsvector".into(),
+                                arguments: vec![Expr::Column("body".into())],
+                            })),
+                            op: BinaryOperator::AtAt,
+                            rhs: Box::new(Expr::Call(FunctionExpr::Call {
+                                name: "to_tsquery".into(),
+                                arguments: vec![Expr::Literal("foo & bar".into())],
+                            })),
+                        }),
+                        op: BinaryOperator::And,
+                        rhs: Box::new(Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column("id".into())),
+                            op: BinaryOperator::Equal,
+                            rhs: Box::new(Expr::Literal(Literal::UnsignedInteger(1))),
+                        }),
+                    }
+                );
+                assert_eq!(
+                    res.to_string(),
+                    "((to_tsvector(\"body\") @@ to_tsquery('foo & bar')) AND (\"id\" = 1))"
+                );
+            }
+
             #[test]
             fn complex_bracketing() {
                 let cond = "\"read_ribbons\".\"is_following\" = 1 \
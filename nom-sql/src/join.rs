@@ -48,6 +48,12 @@ pub enum JoinOperator {
     LeftOuterJoin,
     #[weight(0)]
     RightJoin,
+    #[weight(0)]
+    RightOuterJoin,
+    #[weight(0)]
+    FullJoin,
+    #[weight(0)]
+    FullOuterJoin,
     InnerJoin,
     #[weight(0)]
     CrossJoin,
@@ -59,6 +65,14 @@ impl JoinOperator {
     pub fn is_inner_join(&self) -> bool {
         matches!(self, JoinOperator::Join | JoinOperator::InnerJoin)
     }
+
+    pub fn is_right_join(&self) -> bool {
+        matches!(self, JoinOperator::RightJoin | JoinOperator::RightOuterJoin)
+    }
+
+    pub fn is_full_join(&self) -> bool {
+        matches!(self, JoinOperator::FullJoin | JoinOperator::FullOuterJoin)
+    }
 }
 
 impl fmt::Display for JoinOperator {
@@ -68,6 +82,9 @@ impl fmt::Display for JoinOperator {
             JoinOperator::LeftJoin => write!(f, "LEFT JOIN")?,
             JoinOperator::LeftOuterJoin => write!(f, "LEFT OUTER JOIN")?,
             JoinOperator::RightJoin => write!(f, "RIGHT JOIN")?,
+            JoinOperator::RightOuterJoin => write!(f, "RIGHT OUTER JOIN")?,
+            JoinOperator::FullJoin => write!(f, "FULL JOIN")?,
+            JoinOperator::FullOuterJoin => write!(f, "FULL OUTER JOIN")?,
             JoinOperator::InnerJoin => write!(f, "INNER JOIN")?,
             JoinOperator::CrossJoin => write!(f, "CROSS JOIN")?,
             JoinOperator::StraightJoin => write!(f, "STRAIGHT JOIN")?,
@@ -111,6 +128,13 @@ pub fn join_operator(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], JoinOperator>
             JoinOperator::LeftOuterJoin
         }),
         map(tag_no_case("right join"), |_| JoinOperator::RightJoin),
+        map(tag_no_case("right outer join"), |_| {
+            JoinOperator::RightOuterJoin
+        }),
+        map(tag_no_case("full join"), |_| JoinOperator::FullJoin),
+        map(tag_no_case("full outer join"), |_| {
+            JoinOperator::FullOuterJoin
+        }),
         map(tag_no_case("inner join"), |_| JoinOperator::InnerJoin),
         map(tag_no_case("cross join"), |_| JoinOperator::CrossJoin),
         map(tag_no_case("straight_join"), |_| JoinOperator::StraightJoin),
@@ -153,4 +177,18 @@ mod tests {
         assert_eq!(q, expected_stmt);
         assert_eq!(expected, q.to_string());
     }
+
+    #[test]
+    fn outer_join_operators() {
+        for (input, expected) in [
+            ("right join", JoinOperator::RightJoin),
+            ("RIGHT OUTER JOIN", JoinOperator::RightOuterJoin),
+            ("full join", JoinOperator::FullJoin),
+            ("FULL OUTER JOIN", JoinOperator::FullOuterJoin),
+        ] {
+            let res = test_parse!(join_operator, input.as_bytes());
+            assert_eq!(res, expected);
+            assert_eq!(res.to_string(), input.to_uppercase());
+        }
+    }
 }
@@ -28,7 +28,8 @@ pub use self::drop::{
 };
 pub use self::explain::ExplainStatement;
 pub use self::expression::{
-    BinaryOperator, CaseWhenBranch, Expr, FunctionExpr, InValue, UnaryOperator,
+    BinaryOperator, CaseWhenBranch, Expr, FunctionExpr, InValue, MatchSearchModifier,
+    TimestampField, UnaryOperator,
 };
 pub use self::insert::InsertStatement;
 pub use self::join::{JoinConstraint, JoinOperator, JoinRightSide};
@@ -38,7 +39,12 @@ pub use self::literal::{
 };
 pub use self::order::{OrderClause, OrderType};
 pub use self::parser::*;
-pub use self::select::{CommonTableExpr, GroupByClause, JoinClause, SelectStatement};
+pub use self::routing_rule::{
+    parse_routing_rules, CreateRoutingRuleStatement, DropRoutingRuleStatement, RoutingAction,
+};
+pub use self::select::{
+    CommonTableExpr, GroupByClause, JoinClause, RecursiveTerm, SelectStatement,
+};
 pub use self::set::{
     PostgresParameterScope, PostgresParameterValue, PostgresParameterValueInner, SetNames,
     SetPostgresParameter, SetPostgresParameterValue, SetStatement, SetVariables, Variable,
@@ -74,6 +80,7 @@ mod keywords;
 mod literal;
 mod order;
 mod rename;
+mod routing_rule;
 mod select;
 mod set;
 mod show;
@@ -21,6 +21,9 @@ use crate::explain::{explain_statement, ExplainStatement};
 use crate::expression::expression;
 use crate::insert::{insertion, InsertStatement};
 use crate::rename::{rename_table, RenameTableStatement};
+use crate::routing_rule::{
+    create_routing_rule, drop_routing_rule, CreateRoutingRuleStatement, DropRoutingRuleStatement,
+};
 use crate::select::{selection, SelectStatement};
 use crate::set::{set, SetStatement};
 use crate::show::{show, ShowStatement};
@@ -42,6 +45,8 @@ pub enum SqlQuery {
     CreateCache(CreateCacheStatement),
     DropCache(DropCacheStatement),
     DropAllCaches(DropAllCachesStatement),
+    CreateRoutingRule(CreateRoutingRuleStatement),
+    DropRoutingRule(DropRoutingRuleStatement),
     AlterTable(AlterTableStatement),
     Insert(InsertStatement),
     CompoundSelect(CompoundSelectStatement),
@@ -70,6 +75,8 @@ impl fmt::Display for SqlQuery {
             SqlQuery::CreateCache(ref create) => write!(f, "{}", create),
             SqlQuery::DropCache(ref drop) => write!(f, "{}", drop),
             SqlQuery::DropAllCaches(ref drop) => write!(f, "{}", drop),
+            SqlQuery::CreateRoutingRule(ref create) => write!(f, "{}", create),
+            SqlQuery::DropRoutingRule(ref drop) => write!(f, "{}", drop),
             SqlQuery::Delete(ref delete) => write!(f, "{}", delete),
             SqlQuery::DropTable(ref drop) => write!(f, "{}", drop),
             SqlQuery::DropView(ref drop) => write!(f, "{}", drop),
@@ -107,6 +114,8 @@ impl SqlQuery {
             Self::CreateCache(_) => "CREATE CACHE",
             Self::DropCache(_) => "DROP CACHE",
             Self::DropAllCaches(_) => "DROP ALL CACHES",
+            Self::CreateRoutingRule(_) => "CREATE ROUTING RULE",
+            Self::DropRoutingRule(_) => "DROP ROUTING RULE",
             Self::Delete(_) => "DELETE",
             Self::DropTable(_) => "DROP TABLE",
             Self::DropView(_) => "DROP VIEW",
@@ -145,9 +154,13 @@ pub fn sql_query(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResul
             map(updating(dialect), SqlQuery::Update),
             map(set(dialect), SqlQuery::Set),
             map(view_creation(dialect), SqlQuery::CreateView),
-            map(create_cached_query(dialect), SqlQuery::CreateCache),
-            map(drop_cached_query(dialect), SqlQuery::DropCache),
-            map(drop_all_caches, SqlQuery::DropAllCaches),
+            alt((
+                map(create_cached_query(dialect), SqlQuery::CreateCache),
+                map(drop_cached_query(dialect), SqlQuery::DropCache),
+                map(drop_all_caches, SqlQuery::DropAllCaches),
+                map(create_routing_rule(dialect), SqlQuery::CreateRoutingRule),
+                map(drop_routing_rule(dialect), SqlQuery::DropRoutingRule),
+            )),
             map(alter_table_statement(dialect), SqlQuery::AlterTable),
             map(start_transaction(dialect), SqlQuery::StartTransaction),
             map(commit(dialect), SqlQuery::Commit),
@@ -351,6 +364,17 @@ mod tests {
         assert_eq!(res, SqlQuery::DropAllCaches(DropAllCachesStatement {}));
     }
 
+    #[test]
+    fn routing_rules() {
+        let res = parse_query(Dialect::MySQL, "CREATE ROUTING RULE r FOR USER 'u' REJECT").unwrap();
+        assert!(matches!(res, SqlQuery::CreateRoutingRule(_)));
+        let res = parse_query(Dialect::MySQL, "DROP ROUTING RULE r").unwrap();
+        assert_eq!(
+            res,
+            SqlQuery::DropRoutingRule(DropRoutingRuleStatement { name: "r".into() })
+        );
+    }
+
     mod mysql {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
@@ -0,0 +1,294 @@
+use std::fmt::{self, Display};
+
+use itertools::Itertools;
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::combinator::{all_consuming, map, map_res, opt};
+use nom::multi::many0;
+use nom::sequence::{preceded, terminated, tuple};
+use nom_locate::LocatedSpan;
+use serde::{Deserialize, Serialize};
+
+use crate::common::statement_terminator;
+use crate::table::{table_list, Relation};
+use crate::whitespace::{whitespace0, whitespace1};
+use crate::{Dialect, Literal, NomSqlResult, SqlIdentifier};
+
+/// What to do with queries that match a routing rule
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum RoutingAction {
+    /// Always proxy matching queries to the upstream database
+    Proxy,
+    /// Always try to execute matching queries against ReadySet
+    ReadySet,
+    /// Reject matching queries with an error
+    Reject,
+}
+
+impl Display for RoutingAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoutingAction::Proxy => write!(f, "PROXY"),
+            RoutingAction::ReadySet => write!(f, "READYSET"),
+            RoutingAction::Reject => write!(f, "REJECT"),
+        }
+    }
+}
+
+/// `CREATE ROUTING RULE <name> [FOR USER '<user>'] [ON DATABASE '<database>']
+/// [ON TABLES <table>, ...] [MATCHING '<pattern>'] [WITH HINT '<hint>']
+/// {PROXY | READYSET | REJECT}`
+///
+/// This is a non-standard ReadySet specific extension to SQL
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct CreateRoutingRuleStatement {
+    pub name: SqlIdentifier,
+    /// Only match queries from this user
+    pub user: Option<String>,
+    /// Only match queries run against this database
+    pub database: Option<String>,
+    /// Only match queries referencing at least one of these tables
+    pub tables: Vec<Relation>,
+    /// Only match queries whose normalized text matches this regular expression
+    pub pattern: Option<String>,
+    /// Only match queries with a comment containing this string
+    pub hint: Option<String>,
+    pub action: RoutingAction,
+}
+
+impl Display for CreateRoutingRuleStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CREATE ROUTING RULE `{}` ", self.name)?;
+        if let Some(user) = &self.user {
+            write!(f, "FOR USER {} ", Literal::String(user.clone()))?;
+        }
+        if let Some(database) = &self.database {
+            write!(f, "ON DATABASE {} ", Literal::String(database.clone()))?;
+        }
+        if !self.tables.is_empty() {
+            write!(f, "ON TABLES {} ", self.tables.iter().join(", "))?;
+        }
+        if let Some(pattern) = &self.pattern {
+            write!(f, "MATCHING {} ", Literal::String(pattern.clone()))?;
+        }
+        if let Some(hint) = &self.hint {
+            write!(f, "WITH HINT {} ", Literal::String(hint.clone()))?;
+        }
+        write!(f, "{}", self.action)
+    }
+}
+
+/// `DROP ROUTING RULE <name>`
+///
+/// This is a non-standard ReadySet specific extension to SQL
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct DropRoutingRuleStatement {
+    pub name: SqlIdentifier,
+}
+
+impl Display for DropRoutingRuleStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DROP ROUTING RULE `{}`", self.name)
+    }
+}
+
+fn string(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], String> {
+    move |i| map_res(dialect.string_literal(), String::from_utf8)(i)
+}
+
+/// Parses a single clause of a routing rule of the form `<keyword> <keyword> '<string>'`,
+/// preceded by whitespace
+fn string_clause(
+    dialect: Dialect,
+    first: &'static str,
+    second: &'static str,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Option<String>> {
+    move |i| {
+        opt(preceded(
+            tuple((
+                whitespace1,
+                tag_no_case(first),
+                whitespace1,
+                tag_no_case(second),
+                whitespace1,
+            )),
+            string(dialect),
+        ))(i)
+    }
+}
+
+fn routing_action(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], RoutingAction> {
+    alt((
+        map(tag_no_case("proxy"), |_| RoutingAction::Proxy),
+        map(tag_no_case("readyset"), |_| RoutingAction::ReadySet),
+        map(tag_no_case("reject"), |_| RoutingAction::Reject),
+    ))(i)
+}
+
+/// Parse a [`CreateRoutingRuleStatement`]
+pub fn create_routing_rule(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CreateRoutingRuleStatement> {
+    move |i| {
+        let (i, _) = tag_no_case("create")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("routing")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("rule")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, name) = dialect.identifier()(i)?;
+        let (i, user) = string_clause(dialect, "for", "user")(i)?;
+        let (i, database) = string_clause(dialect, "on", "database")(i)?;
+        let (i, tables) = opt(preceded(
+            tuple((
+                whitespace1,
+                tag_no_case("on"),
+                whitespace1,
+                tag_no_case("tables"),
+                whitespace1,
+            )),
+            table_list(dialect),
+        ))(i)?;
+        let (i, pattern) = opt(preceded(
+            tuple((whitespace1, tag_no_case("matching"), whitespace1)),
+            string(dialect),
+        ))(i)?;
+        let (i, hint) = string_clause(dialect, "with", "hint")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, action) = routing_action(i)?;
+        let (i, _) = statement_terminator(i)?;
+        Ok((
+            i,
+            CreateRoutingRuleStatement {
+                name,
+                user,
+                database,
+                tables: tables.unwrap_or_default(),
+                pattern,
+                hint,
+                action,
+            },
+        ))
+    }
+}
+
+/// Parse a [`DropRoutingRuleStatement`]
+pub fn drop_routing_rule(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], DropRoutingRuleStatement> {
+    move |i| {
+        let (i, _) = tag_no_case("drop")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("routing")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("rule")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, name) = dialect.identifier()(i)?;
+        let (i, _) = statement_terminator(i)?;
+        Ok((i, DropRoutingRuleStatement { name }))
+    }
+}
+
+/// Parse a sequence of `CREATE ROUTING RULE` statements, such as the contents of a routing rules
+/// file. Comments are permitted between statements.
+pub fn parse_routing_rules(
+    dialect: Dialect,
+    input: &str,
+) -> Result<Vec<CreateRoutingRuleStatement>, String> {
+    let res = all_consuming(preceded(
+        whitespace0,
+        many0(terminated(create_routing_rule(dialect), whitespace0)),
+    ))(LocatedSpan::new(input.trim().as_bytes()));
+    match res {
+        Ok((_, rules)) => Ok(rules),
+        Err(e) => Err(format!("failed to parse routing rules: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_routing_rule_all_clauses() {
+        let res = test_parse!(
+            create_routing_rule(Dialect::MySQL),
+            b"CREATE ROUTING RULE reporting FOR USER 'reporter' ON DATABASE 'app' \
+              ON TABLES orders, app.users MATCHING '^SELECT count' WITH HINT 'report' PROXY"
+        );
+        assert_eq!(
+            res,
+            CreateRoutingRuleStatement {
+                name: "reporting".into(),
+                user: Some("reporter".into()),
+                database: Some("app".into()),
+                tables: vec![
+                    Relation::from("orders"),
+                    Relation {
+                        schema: Some("app".into()),
+                        name: "users".into(),
+                    }
+                ],
+                pattern: Some("^SELECT count".into()),
+                hint: Some("report".into()),
+                action: RoutingAction::Proxy,
+            }
+        );
+    }
+
+    #[test]
+    fn create_routing_rule_no_clauses() {
+        let res = test_parse!(
+            create_routing_rule(Dialect::PostgreSQL),
+            b"create routing rule everything readyset"
+        );
+        assert_eq!(res.name, "everything");
+        assert!(res.user.is_none());
+        assert!(res.tables.is_empty());
+        assert_eq!(res.action, RoutingAction::ReadySet);
+    }
+
+    #[test]
+    fn create_routing_rule_round_trip() {
+        let rule = test_parse!(
+            create_routing_rule(Dialect::MySQL),
+            b"CREATE ROUTING RULE r ON TABLES t MATCHING 'it''s' REJECT"
+        );
+        let res = test_parse!(
+            create_routing_rule(Dialect::MySQL),
+            rule.to_string().as_bytes()
+        );
+        assert_eq!(res, rule);
+    }
+
+    #[test]
+    fn drop_routing_rule() {
+        let res = test_parse!(
+            super::drop_routing_rule(Dialect::MySQL),
+            b"DROP ROUTING RULE reporting;"
+        );
+        assert_eq!(res.name, "reporting");
+    }
+
+    #[test]
+    fn routing_rules_file() {
+        let rules = parse_routing_rules(
+            Dialect::MySQL,
+            "-- Reports are always proxied
+            CREATE ROUTING RULE reporting FOR USER 'reporter' PROXY;
+
+            /* Never allow queries against the secrets table */
+            CREATE ROUTING RULE secrets ON TABLES secrets REJECT;
+            ",
+        )
+        .unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].action, RoutingAction::Proxy);
+        assert_eq!(rules[1].name, "secrets");
+    }
+
+    #[test]
+    fn routing_rules_file_invalid() {
+        assert!(parse_routing_rules(Dialect::MySQL, "CREATE ROUTING RULE oops DELETE").is_err());
+    }
+}
@@ -62,15 +62,36 @@ impl fmt::Display for JoinClause {
     }
 }
 
+/// The recursive part of a common table expression declared with `WITH RECURSIVE`, which is
+/// repeatedly evaluated and unioned with the rows of the CTE until no new rows are produced.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct RecursiveTerm {
+    /// Whether the term is joined to the CTE with `UNION ALL` rather than `UNION`
+    pub all: bool,
+    /// The statement for the recursive term, which may refer to the CTE itself
+    pub statement: SelectStatement,
+}
+
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct CommonTableExpr {
     pub name: SqlIdentifier,
     pub statement: SelectStatement,
+    /// If this CTE is recursive, the recursive term which follows the (non-recursive)
+    /// `statement`
+    pub recursive_term: Option<RecursiveTerm>,
 }
 
 impl fmt::Display for CommonTableExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "`{}` AS ({})", self.name, self.statement)
+        write!(f, "`{}` AS ({}", self.name, self.statement)?;
+        if let Some(term) = &self.recursive_term {
+            write!(f, " UNION ")?;
+            if term.all {
+                write!(f, "ALL ")?;
+            }
+            write!(f, "{}", term.statement)?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -102,6 +123,12 @@ impl SelectStatement {
                         | FunctionExpr::Max(_)
                         | FunctionExpr::Min(_)
                         | FunctionExpr::GroupConcat { .. }
+                        | FunctionExpr::Stddev { .. }
+                        | FunctionExpr::Variance { .. }
+                        | FunctionExpr::BoolAnd(_)
+                        | FunctionExpr::BoolOr(_)
+                        | FunctionExpr::ArrayAgg(_)
+                        | FunctionExpr::JsonAgg(_)
                 ),
                 Expr::NestedSelect(select) => select.contains_aggregate_select(),
                 _ => false,
@@ -114,7 +141,11 @@ impl SelectStatement {
 impl fmt::Display for SelectStatement {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if !self.ctes.is_empty() {
-            write!(f, "WITH {} ", self.ctes.iter().join(", "))?;
+            write!(f, "WITH ")?;
+            if self.ctes.iter().any(|cte| cte.recursive_term.is_some()) {
+                write!(f, "RECURSIVE ")?;
+            }
+            write!(f, "{} ", self.ctes.iter().join(", "))?;
         }
 
         write!(f, "SELECT ")?;
@@ -423,7 +454,30 @@ fn from_clause(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<
     }
 }
 
-fn cte(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CommonTableExpr> {
+fn recursive_term(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], RecursiveTerm> {
+    move |i| {
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag_no_case("union")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, all) = opt(terminated(tag_no_case("all"), whitespace1))(i)?;
+        let (i, statement) = nested_selection(dialect)(i)?;
+
+        Ok((
+            i,
+            RecursiveTerm {
+                all: all.is_some(),
+                statement,
+            },
+        ))
+    }
+}
+
+fn cte(
+    dialect: Dialect,
+    recursive: bool,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CommonTableExpr> {
     move |i| {
         let (i, name) = dialect.identifier()(i)?;
         let (i, _) = whitespace1(i)?;
@@ -433,10 +487,22 @@ fn cte(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], C
         let (i, _) = tag("(")(i)?;
         let (i, _) = whitespace0(i)?;
         let (i, statement) = nested_selection(dialect)(i)?;
+        let (i, recursive_term) = if recursive {
+            opt(recursive_term(dialect))(i)?
+        } else {
+            (i, None)
+        };
         let (i, _) = whitespace0(i)?;
         let (i, _) = tag(")")(i)?;
 
-        Ok((i, CommonTableExpr { name, statement }))
+        Ok((
+            i,
+            CommonTableExpr {
+                name,
+                statement,
+                recursive_term,
+            },
+        ))
     }
 }
 
@@ -446,7 +512,8 @@ fn ctes(
     move |i| {
         let (i, _) = tag_no_case("with")(i)?;
         let (i, _) = whitespace1(i)?;
-        let (i, ctes) = separated_list1(ws_sep_comma, cte(dialect))(i)?;
+        let (i, recursive) = opt(terminated(tag_no_case("recursive"), whitespace1))(i)?;
+        let (i, ctes) = separated_list1(ws_sep_comma, cte(dialect, recursive.is_some()))(i)?;
         let (i, _) = whitespace0(i)?;
 
         Ok((i, ctes))
@@ -1658,6 +1725,7 @@ mod tests {
                     tables: vec![TableExpr::from(Relation::from("t"))],
                     ..Default::default()
                 },
+                recursive_term: None,
             }],
             fields: vec![FieldDefinitionExpr::Expr {
                 expr: Expr::Column("x".into()),
@@ -1673,6 +1741,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recursive_cte() {
+        let qstr = b"WITH RECURSIVE reports AS (
+              SELECT id FROM employees WHERE manager_id = 1
+              UNION ALL
+              SELECT employees.id FROM employees
+              JOIN reports ON employees.manager_id = reports.id
+            )
+            SELECT id FROM reports";
+        let res = selection(Dialect::PostgreSQL)(LocatedSpan::new(qstr));
+        assert!(res.is_ok(), "error parsing query: {}", res.err().unwrap());
+        let (rem, query) = res.unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(query.ctes.len(), 1);
+        assert_eq!(query.ctes[0].name, "reports");
+        let term = query.ctes[0].recursive_term.as_ref().unwrap();
+        assert!(term.all);
+        assert_eq!(term.statement.join.len(), 1);
+        assert_eq!(
+            query.to_string(),
+            "WITH RECURSIVE `reports` AS (SELECT `id` FROM `employees` WHERE (`manager_id` = 1) \
+             UNION ALL SELECT `employees`.`id` FROM `employees` JOIN `reports` ON \
+             (`employees`.`manager_id` = `reports`.`id`)) SELECT `id` FROM `reports`"
+        );
+    }
+
+    #[test]
+    fn non_recursive_cte_with_recursive_keyword() {
+        let res = test_parse!(
+            selection(Dialect::MySQL),
+            b"WITH RECURSIVE foo AS (SELECT x FROM t) SELECT x FROM foo"
+        );
+        assert_eq!(res.ctes.len(), 1);
+        assert!(res.ctes[0].recursive_term.is_none());
+    }
+
     #[test]
     fn bare_having() {
         let res = test_parse!(
@@ -21,6 +21,7 @@ pub enum ShowStatement {
     ProxiedQueries(Option<QueryID>),
     ReadySetStatus,
     ReadySetVersion,
+    RoutingRules,
 }
 
 impl fmt::Display for ShowStatement {
@@ -45,6 +46,7 @@ impl fmt::Display for ShowStatement {
             }
             Self::ReadySetStatus => write!(f, "READYSET STATUS"),
             Self::ReadySetVersion => write!(f, "READYSET VERSION"),
+            Self::RoutingRules => write!(f, "ROUTING RULES"),
         }
     }
 }
@@ -112,6 +114,10 @@ pub fn show(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u
                     tuple((tag_no_case("readyset"), whitespace1, tag_no_case("version"))),
                     |_| ShowStatement::ReadySetVersion,
                 ),
+                map(
+                    tuple((tag_no_case("routing"), whitespace1, tag_no_case("rules"))),
+                    |_| ShowStatement::RoutingRules,
+                ),
             )),
             map(show_tables(dialect), ShowStatement::Tables),
             map(tag_no_case("events"), |_| ShowStatement::Events),
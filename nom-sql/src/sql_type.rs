@@ -75,6 +75,18 @@ pub enum SqlType {
     VarBit(Option<u16>),
     Serial,
     BigSerial,
+    #[weight(0)]
+    Int4Range,
+    #[weight(0)]
+    Int8Range,
+    #[weight(0)]
+    NumRange,
+    #[weight(0)]
+    TsRange,
+    #[weight(0)]
+    TsTzRange,
+    #[weight(0)]
+    DateRange,
     Array(Box<SqlType>),
 
     /// Any other named type
@@ -230,6 +242,12 @@ impl fmt::Display for SqlType {
             SqlType::VarBit(n) => write_with_len(f, "VARBIT", n),
             SqlType::Serial => write!(f, "SERIAL"),
             SqlType::BigSerial => write!(f, "BIGSERIAL"),
+            SqlType::Int4Range => write!(f, "INT4RANGE"),
+            SqlType::Int8Range => write!(f, "INT8RANGE"),
+            SqlType::NumRange => write!(f, "NUMRANGE"),
+            SqlType::TsRange => write!(f, "TSRANGE"),
+            SqlType::TsTzRange => write!(f, "TSTZRANGE"),
+            SqlType::DateRange => write!(f, "DATERANGE"),
             SqlType::Array(ref t) => write!(f, "{}[]", t),
             SqlType::Other(ref t) => write!(f, "{t}"),
         }
@@ -653,6 +671,19 @@ fn type_identifier_part3(
     }
 }
 
+// Parsed before all other types, since the names of some range types start with the names of
+// other types (eg `int` or `date`)
+fn range_type(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], SqlType> {
+    alt((
+        map(tag_no_case("int4range"), |_| SqlType::Int4Range),
+        map(tag_no_case("int8range"), |_| SqlType::Int8Range),
+        map(tag_no_case("numrange"), |_| SqlType::NumRange),
+        map(tag_no_case("tsrange"), |_| SqlType::TsRange),
+        map(tag_no_case("tstzrange"), |_| SqlType::TsTzRange),
+        map(tag_no_case("daterange"), |_| SqlType::DateRange),
+    ))(i)
+}
+
 fn other_type(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Relation> {
     move |i| match dialect {
         Dialect::PostgreSQL => relation(dialect)(i),
@@ -677,6 +708,7 @@ fn type_identifier_no_arrays(
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], SqlType> {
     move |i| {
         alt((
+            range_type,
             type_identifier_part1(dialect),
             type_identifier_part2,
             type_identifier_part3(dialect),
@@ -1055,5 +1087,30 @@ mod tests {
             let res = test_parse!(type_identifier(Dialect::PostgreSQL), b"citext");
             assert_eq!(res, SqlType::Citext);
         }
+
+        #[test]
+        fn range_types() {
+            for (input, ty) in [
+                ("int4range", SqlType::Int4Range),
+                ("INT8RANGE", SqlType::Int8Range),
+                ("numrange", SqlType::NumRange),
+                ("tsrange", SqlType::TsRange),
+                ("tstzrange", SqlType::TsTzRange),
+                ("daterange", SqlType::DateRange),
+            ] {
+                let res = test_parse!(type_identifier(Dialect::PostgreSQL), input.as_bytes());
+                assert_eq!(res, ty);
+                assert_eq!(
+                    test_parse!(
+                        type_identifier(Dialect::PostgreSQL),
+                        ty.to_string().as_bytes()
+                    ),
+                    ty
+                );
+            }
+
+            let res = test_parse!(type_identifier(Dialect::PostgreSQL), b"int4range[]");
+            assert_eq!(res, SqlType::Array(Box::new(SqlType::Int4Range)));
+        }
     }
 }
@@ -8,7 +8,7 @@ use bytes::{Buf, Bytes, BytesMut};
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use eui48::MacAddress;
 use postgres_types::{FromSql, Kind, Type};
-use readyset_data::Array;
+use readyset_data::{Array, Range};
 use rust_decimal::prelude::FromStr;
 use rust_decimal::Decimal;
 use tokio_util::codec::Decoder;
@@ -314,6 +314,7 @@ fn get_binary_value(src: &mut Bytes, t: &Type) -> Result<Value, Error> {
 
     match t.kind() {
         Kind::Array(member_type) => Ok(Value::Array(Array::from_sql(t, buf)?, member_type.clone())),
+        Kind::Range(_) => Ok(Value::Range(Range::from_sql(t, buf)?, t.clone())),
         Kind::Enum(_) => Ok(Value::Text(str::from_utf8(buf)?.into())),
         _ => match *t {
             // Postgres does not allow interior 0 bytes, even though it is valid UTF-8
@@ -377,6 +378,11 @@ fn get_text_value(src: &mut Bytes, t: &Type) -> Result<Value, Error> {
 
     let text = BytesStr::try_from(src.split_to(usize::try_from(len)?))?;
     let text_str: &str = text.borrow();
+    if let Kind::Range(_) = t.kind() {
+        // Parsing the bounds of a range requires knowing the type they'll be coerced to, so leave
+        // that to the backend
+        return Ok(Value::Text(text_str.into()));
+    }
     match *t {
         Type::BOOL => Ok(Value::Bool(text_str == BOOL_TRUE_TEXT_REP)),
         Type::VARCHAR => Ok(Value::VarChar(text_str.into())),
@@ -428,6 +428,9 @@ fn put_binary_value(val: Value, dst: &mut BytesMut) -> Result<(), Error> {
         Value::Array(arr, ty) => {
             arr.to_sql(&ty, dst)?;
         }
+        Value::Range(range, ty) => {
+            range.to_sql(&ty, dst)?;
+        }
         Value::PassThrough(p) => {
             dst.put(&p.data[..]);
         }
@@ -523,7 +526,9 @@ fn put_text_value(val: Value, dst: &mut BytesMut) -> Result<(), Error> {
         Value::Inet(ip) => write!(dst, "{}", ip)?,
         Value::Uuid(u) => write!(dst, "{}", u)?,
         Value::Json(v) => write!(dst, "{}", v)?,
-        Value::Jsonb(v) => write!(dst, "{}", v)?,
+        // PostgreSQL outputs `jsonb` values in their canonical form, with sorted keys and spaces
+        // after separators
+        Value::Jsonb(v) => write!(dst, "{}", readyset_data::jsonb_to_string(v))?,
         Value::Bit(bits) | Value::VarBit(bits) => write!(
             dst,
             "{}",
@@ -533,6 +538,7 @@ fn put_text_value(val: Value, dst: &mut BytesMut) -> Result<(), Error> {
                 .join("")
         )?,
         Value::Array(arr, _) => write!(dst, "{}", arr)?,
+        Value::Range(range, _) => write!(dst, "{}", range)?,
         Value::PassThrough(p) => {
             return Err(Error::InternalError(format!(
                 "Data of type {} unsupported in text mode",
@@ -1523,9 +1529,9 @@ mod tests {
         let mut buf = BytesMut::new();
         put_text_value(DataValue::Jsonb(json), &mut buf).unwrap();
         let mut exp = BytesMut::new();
-        exp.put_i32(67); // length (placeholder)
+        exp.put_i32(73); // length (placeholder)
         exp.extend_from_slice(
-            b"{\"age\":43,\"name\":\"John Doe\",\"phones\":[\"+44 1234567\",\"+44 2345678\"]}", // keys are sorted
+            b"{\"age\": 43, \"name\": \"John Doe\", \"phones\": [\"+44 1234567\", \"+44 2345678\"]}", // canonical jsonb
         );
         assert_eq!(buf, exp);
     }
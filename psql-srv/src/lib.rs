@@ -83,6 +83,12 @@ pub trait Backend {
     /// * `credentials` - Authentication info provided by the client
     async fn on_auth(&mut self, credentials: Credentials) -> Result<(), Error>;
 
+    /// Called once the client has been authenticated as (or, if no credentials are needed, has
+    /// claimed to be) the given user
+    ///
+    /// * `user` - The name of the user the client connected as
+    fn on_authenticated(&mut self, _user: &str) {}
+
     /// Performs the specified SQL query.
     ///
     /// * `query` - The sql query to perform.
@@ -189,6 +189,9 @@ impl Protocol {
                         .ok_or_else(|| Error::Unsupported("database is required".to_string()))?;
                     let response = match backend.on_init(database.borrow()).await? {
                         crate::CredentialsNeeded::None => {
+                            if let Some(user) = &user {
+                                backend.on_authenticated(user.borrow());
+                            }
                             self.state = State::Ready;
                             get_ready_message(backend.version())
                         }
@@ -218,6 +221,7 @@ impl Protocol {
                             password: password.to_string(),
                         })
                         .await?;
+                    backend.on_authenticated(user.borrow());
                     self.state = State::Ready;
 
                     Ok(Response::Messages(get_ready_message(backend.version())))
@@ -614,6 +618,7 @@ async fn make_field_description<B: Backend>(
     let data_type_size = match col.col_type.kind() {
         Kind::Array(_) => TYPLEN_VARLENA,
         Kind::Enum(_) => TYPLEN_VARLENA,
+        Kind::Range(_) => TYPLEN_VARLENA,
         _ => match col.col_type {
             Type::BOOL => TYPLEN_1,
             Type::BYTEA => TYPLEN_VARLENA,
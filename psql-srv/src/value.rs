@@ -3,7 +3,7 @@ use std::net::IpAddr;
 use bit_vec::BitVec;
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use eui48::MacAddress;
-use readyset_data::{Array, Text};
+use readyset_data::{Array, Range, Text};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
@@ -37,5 +37,6 @@ pub enum Value {
     Bit(BitVec),
     VarBit(BitVec),
     Array(Array, postgres_types::Type),
+    Range(Range, postgres_types::Type),
     PassThrough(readyset_data::PassThrough),
 }
@@ -165,6 +165,12 @@ fn value_of_type(typ: &SqlType) -> DfValue {
             DfValue::from(BitVec::with_capacity(size_opt.unwrap_or(1) as usize))
         }
         SqlType::VarBit(_) => DfValue::from(BitVec::new()),
+        SqlType::Int4Range
+        | SqlType::Int8Range
+        | SqlType::NumRange
+        | SqlType::TsRange
+        | SqlType::TsTzRange
+        | SqlType::DateRange => unimplemented!(),
         SqlType::Array(_) => unimplemented!(),
         SqlType::Other(_) => unimplemented!(),
     }
@@ -296,6 +302,12 @@ fn random_value_of_type(typ: &SqlType) -> DfValue {
         }
         SqlType::Serial => (rng.gen::<u32>() + 1).into(),
         SqlType::BigSerial => (rng.gen::<u64>() + 1).into(),
+        SqlType::Int4Range
+        | SqlType::Int8Range
+        | SqlType::NumRange
+        | SqlType::TsRange
+        | SqlType::TsTzRange
+        | SqlType::DateRange => unimplemented!(),
         SqlType::Array(_) => unimplemented!(),
         SqlType::Other(_) => unimplemented!(),
     }
@@ -408,6 +420,12 @@ fn unique_value_of_type(typ: &SqlType, idx: u32) -> DfValue {
         }
         SqlType::Serial => (idx + 1).into(),
         SqlType::BigSerial => ((idx + 1) as u64).into(),
+        SqlType::Int4Range
+        | SqlType::Int8Range
+        | SqlType::NumRange
+        | SqlType::TsRange
+        | SqlType::TsTzRange
+        | SqlType::DateRange => unimplemented!(),
         SqlType::Array(_) => unimplemented!(),
         SqlType::Other(_) => unimplemented!(),
     }
@@ -2809,6 +2827,7 @@ impl Subquery {
                 query.ctes.push(CommonTableExpr {
                     name: subquery_name.clone(),
                     statement: subquery,
+                    recursive_term: None,
                 });
                 (
                     JoinRightSide::Table(TableExpr::from(Relation {
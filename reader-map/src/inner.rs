@@ -36,6 +36,9 @@ pub(crate) enum Data<K, V, S> {
     BTreeMap { map: PartialMap<K, Values<V>> },
     /// Data is stored in a HashMap, only point lookups are possible
     HashMap { map: HashMap<K, Values<V>, S> },
+    /// Data is stored in an inverted index: a HashMap from each token of the indexed documents to
+    /// the values containing that token. Only point lookups by token are possible
+    FullText { map: HashMap<K, Values<V>, S> },
 }
 
 impl<K, V, S> fmt::Debug for Data<K, V, S>
@@ -47,6 +50,7 @@ where
         match self {
             Self::BTreeMap { map, .. } => f.debug_struct("BTreeMap").field("map", &map).finish(),
             Self::HashMap { map } => f.debug_struct("HashMap").field("map", &map).finish(),
+            Self::FullText { map } => f.debug_struct("FullText").field("map", &map).finish(),
         }
     }
 }
@@ -55,7 +59,7 @@ macro_rules! with_map {
     ($data: expr, |$map: ident| $body: expr) => {
         match $data {
             Data::BTreeMap { map: $map, .. } => $body,
-            Data::HashMap { map: $map } => $body,
+            Data::HashMap { map: $map } | Data::FullText { map: $map } => $body,
         }
     };
 }
@@ -72,6 +76,9 @@ impl<K, V, S> Data<K, V, S> {
             IndexType::BTreeMap => Self::BTreeMap {
                 map: Default::default(),
             },
+            IndexType::FullText => Self::FullText {
+                map: HashMap::with_hasher(hash_builder),
+            },
         }
     }
 
@@ -79,6 +86,7 @@ impl<K, V, S> Data<K, V, S> {
         match self {
             Self::BTreeMap { .. } => IndexType::BTreeMap,
             Self::HashMap { .. } => IndexType::HashMap,
+            Self::FullText { .. } => IndexType::FullText,
         }
     }
 
@@ -89,7 +97,7 @@ impl<K, V, S> Data<K, V, S> {
     pub(crate) fn len(&self) -> usize {
         match self {
             Data::BTreeMap { map } => map.num_keys(),
-            Data::HashMap { map } => map.len(),
+            Data::HashMap { map } | Data::FullText { map } => map.len(),
         }
     }
 
@@ -102,8 +110,8 @@ impl<K, V, S> Data<K, V, S> {
             Self::BTreeMap { .. } => Self::BTreeMap {
                 map: Default::default(),
             },
-            Self::HashMap { map } => {
-                Self::with_index_type_and_hasher(IndexType::HashMap, (*map.hasher()).clone())
+            Self::HashMap { map } | Self::FullText { map } => {
+                Self::with_index_type_and_hasher(self.index_type(), (*map.hasher()).clone())
             }
         }
     }
@@ -111,7 +119,7 @@ impl<K, V, S> Data<K, V, S> {
     pub(crate) fn iter(&self) -> Iter<'_, K, V> {
         match self {
             Self::BTreeMap { map, .. } => Either::Left(map.iter()),
-            Self::HashMap { map } => Either::Right(map.iter()),
+            Self::HashMap { map } | Self::FullText { map } => Either::Right(map.iter()),
         }
     }
 
@@ -120,7 +128,7 @@ impl<K, V, S> Data<K, V, S> {
             Self::BTreeMap { map, .. } => {
                 map.clear();
             }
-            Self::HashMap { map } => {
+            Self::HashMap { map } | Self::FullText { map } => {
                 map.clear();
             }
         }
@@ -137,7 +145,9 @@ impl<K, V, S> Data<K, V, S> {
     {
         match self {
             Self::BTreeMap { map, .. } => map.range(range).map_err(Miss),
-            Self::HashMap { .. } => panic!("range called on a HashMap reader_map"),
+            Self::HashMap { .. } | Self::FullText { .. } => {
+                panic!("range called on a hashed reader_map")
+            }
         }
     }
 
@@ -162,7 +172,9 @@ impl<K, V, S> Data<K, V, S> {
                 // the iterator still does all the removal)
                 let _ = map.remove_range(range);
             }
-            Self::HashMap { .. } => panic!("remove_range called on a HashMap reader_map"),
+            Self::HashMap { .. } | Self::FullText { .. } => {
+                panic!("remove_range called on a hashed reader_map")
+            }
         }
     }
 
@@ -173,7 +185,9 @@ impl<K, V, S> Data<K, V, S> {
     {
         match self {
             Self::BTreeMap { map, .. } => map.contains_range(range),
-            Self::HashMap { .. } => panic!("contains_range called on a HashMap reader_map"),
+            Self::HashMap { .. } | Self::FullText { .. } => {
+                panic!("contains_range called on a hashed reader_map")
+            }
         }
     }
 
@@ -184,7 +198,9 @@ impl<K, V, S> Data<K, V, S> {
     {
         match self {
             Self::BTreeMap { map, .. } => map.overlaps_range(range),
-            Self::HashMap { .. } => panic!("contains_range called on a HashMap reader_map"),
+            Self::HashMap { .. } | Self::FullText { .. } => {
+                panic!("contains_range called on a hashed reader_map")
+            }
         }
     }
 }
@@ -235,7 +251,7 @@ where
                 partial_map::Entry::Vacant(v) => Entry::Vacant(VacantEntry::BTreeMap(v)),
                 partial_map::Entry::Occupied(o) => Entry::Occupied(OccupiedEntry::BTreeMap(o)),
             },
-            Data::HashMap { map } => match map.entry(key) {
+            Data::HashMap { map } | Data::FullText { map } => match map.entry(key) {
                 hash_map::Entry::Vacant(v) => Entry::Vacant(VacantEntry::HashMap(v)),
                 hash_map::Entry::Occupied(o) => Entry::Occupied(OccupiedEntry::HashMap(o)),
             },
@@ -280,7 +280,7 @@ where
                     )));
                 }
             }
-            IndexType::HashMap => {
+            IndexType::HashMap | IndexType::FullText => {
                 let kvs = inner
                     .eviction_strategy
                     .pick_keys_to_evict(&inner.data, nkeys_to_evict);
@@ -1256,3 +1256,35 @@ fn eviction_range_lru() -> reader_map::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn full_text_index() -> reader_map::Result<()> {
+    let (mut w, r) = reader_map::Options::default()
+        .with_index_type(IndexType::FullText)
+        .construct();
+
+    // Values are stored once under each of the tokens they contain
+    for token in ["quick", "brown", "fox"] {
+        w.insert(token, "the quick brown fox");
+    }
+    for token in ["brown", "bear"] {
+        w.insert(token, "brown bear");
+    }
+    w.publish();
+
+    assert_eq!(r.index_type(), Some(IndexType::FullText));
+    assert_eq!(r.len(), 4);
+    assert_eq!(r.get(&"brown")?.map(|rs| rs.len()), Some(2));
+    assert_eq!(r.get(&"fox")?.map(|rs| rs.len()), Some(1));
+    assert!(r.get(&"dog")?.is_none());
+
+    for token in ["brown", "bear"] {
+        w.remove_value(token, "brown bear");
+    }
+    w.publish();
+
+    assert_eq!(r.get(&"brown")?.map(|rs| rs.len()), Some(1));
+    assert_eq!(r.get(&"bear")?.map(|rs| rs.len()), None);
+
+    Ok(())
+}
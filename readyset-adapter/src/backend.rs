@@ -82,8 +82,8 @@ use launchpad::redacted::Sensitive;
 use mysql_common::row::convert::{FromRow, FromRowError};
 use nom_sql::{
     CacheInner, CreateCacheStatement, DeleteStatement, Dialect, DropCacheStatement,
-    InsertStatement, Relation, SelectStatement, SetStatement, ShowStatement, SqlIdentifier,
-    SqlQuery, UpdateStatement, UseStatement,
+    DropRoutingRuleStatement, InsertStatement, Relation, RoutingAction, SelectStatement,
+    SetStatement, ShowStatement, SqlIdentifier, SqlQuery, UpdateStatement, UseStatement,
 };
 use readyset_client::consistency::Timestamp;
 use readyset_client::query::*;
@@ -104,14 +104,19 @@ use tracing::{error, instrument, trace, warn};
 use crate::backend::noria_connector::ExecuteSelectContext;
 use crate::query_handler::SetBehavior;
 use crate::query_status_cache::QueryStatusCache;
-use crate::upstream_database::NoriaCompare;
+use crate::read_replicas::{self, ReadReplicaConnection, ReadReplicas};
+use crate::routing_rules::{RoutedQuery, RoutingRule, RoutingRules};
 pub use crate::upstream_database::UpstreamPrepare;
+use crate::upstream_database::{IsFatalError, NoriaCompare};
+use crate::upstream_pool::PooledUpstream;
 use crate::{rewrite, QueryHandler, UpstreamDatabase, UpstreamDestination};
 
 pub mod noria_connector;
+mod upstream_writes;
 
 use self::noria_connector::MetaVariable;
 pub use self::noria_connector::NoriaConnector;
+use self::upstream_writes::UpstreamWrites;
 
 /// Query metadata used to plan query prepare
 #[allow(clippy::large_enum_variant)]
@@ -129,6 +134,8 @@ enum PrepareMeta {
     Write { stmt: SqlQuery },
     /// A read (Select; may be extended in the future)
     Select(PrepareSelectMeta),
+    /// Query matched a routing rule that rejects it
+    Rejected { rule: SqlIdentifier },
 }
 
 #[derive(Debug)]
@@ -266,6 +273,9 @@ pub struct BackendBuilder {
     query_max_failure_seconds: u64,
     fallback_recovery_seconds: u64,
     telemetry_sender: Option<TelemetrySender>,
+    routing_rules: Arc<RoutingRules>,
+    allow_routing_rule_changes: bool,
+    read_replicas: Arc<ReadReplicas>,
 }
 
 impl Default for BackendBuilder {
@@ -286,6 +296,9 @@ impl Default for BackendBuilder {
             query_max_failure_seconds: (i64::MAX / 1000) as u64,
             fallback_recovery_seconds: 0,
             telemetry_sender: None,
+            routing_rules: Default::default(),
+            allow_routing_rule_changes: false,
+            read_replicas: Default::default(),
         }
     }
 }
@@ -300,10 +313,31 @@ impl BackendBuilder {
         noria: NoriaConnector,
         upstream: Option<DB>,
         query_status_cache: &'static QueryStatusCache,
+    ) -> Backend<DB, Handler> {
+        self.build_inner(noria, upstream, None, query_status_cache)
+    }
+
+    /// Builds a [`Backend`] that leases connections to the upstream database from a pool shared
+    /// with other backends, rather than holding its own. See [`crate::upstream_pool`]
+    pub fn build_pooled<DB: UpstreamDatabase, Handler>(
+        self,
+        noria: NoriaConnector,
+        pooled_upstream: PooledUpstream<DB>,
+        query_status_cache: &'static QueryStatusCache,
+    ) -> Backend<DB, Handler> {
+        self.build_inner(noria, None, Some(pooled_upstream), query_status_cache)
+    }
+
+    fn build_inner<DB: UpstreamDatabase, Handler>(
+        self,
+        noria: NoriaConnector,
+        upstream: Option<DB>,
+        pooled_upstream: Option<PooledUpstream<DB>>,
+        query_status_cache: &'static QueryStatusCache,
     ) -> Backend<DB, Handler> {
         metrics::increment_gauge!(recorded::CONNECTED_CLIENTS, 1.0);
 
-        let proxy_state = if upstream.is_some() {
+        let proxy_state = if upstream.is_some() || pooled_upstream.is_some() {
             ProxyState::Fallback
         } else {
             ProxyState::Never
@@ -312,6 +346,7 @@ impl BackendBuilder {
         Backend {
             noria,
             upstream,
+            read_replica: None,
             users: self.users,
             query_log_sender: self.query_log_sender,
             last_query: None,
@@ -322,6 +357,13 @@ impl BackendBuilder {
                 query_status_cache,
                 ticket: self.ticket,
                 timestamp_client: self.timestamp_client,
+                upstream_writes: UpstreamWrites::default(),
+                user: None,
+                routing_rules: self.routing_rules,
+                read_replicas: self.read_replicas,
+                wrote_upstream: false,
+                session_statements: Vec::new(),
+                pooled_upstream,
             },
             settings: BackendSettings {
                 slowlog: self.slowlog,
@@ -334,6 +376,7 @@ impl BackendBuilder {
                 query_max_failure_duration: Duration::new(self.query_max_failure_seconds, 0),
                 query_log_ad_hoc_queries: self.query_log_ad_hoc_queries,
                 fallback_recovery_duration: Duration::new(self.fallback_recovery_seconds, 0),
+                allow_routing_rule_changes: self.allow_routing_rule_changes,
             },
             telemetry_sender: self.telemetry_sender,
             _query_handler: PhantomData,
@@ -415,6 +458,27 @@ impl BackendBuilder {
         self.telemetry_sender = Some(telemetry_sender);
         self
     }
+
+    /// Sets the routing rules to consult when routing queries. Routing rules are shared between
+    /// all backends built from this builder.
+    pub fn routing_rules(mut self, routing_rules: Arc<RoutingRules>) -> Self {
+        self.routing_rules = routing_rules;
+        self
+    }
+
+    /// Specifies whether clients may create and drop routing rules with `CREATE ROUTING RULE` and
+    /// `DROP ROUTING RULE`. Routing rules changed this way aren't persisted.
+    pub fn allow_routing_rule_changes(mut self, allow_routing_rule_changes: bool) -> Self {
+        self.allow_routing_rule_changes = allow_routing_rule_changes;
+        self
+    }
+
+    /// Sets the read replicas of the upstream database to send proxied reads to. Read replicas are
+    /// shared between all backends built from this builder.
+    pub fn read_replicas(mut self, read_replicas: Arc<ReadReplicas>) -> Self {
+        self.read_replicas = read_replicas;
+        self
+    }
 }
 
 /// A [`CachedPreparedStatement`] stores the data needed for an immediate
@@ -443,6 +507,8 @@ where
     /// If statement was successfully rewritten, will store all information necessary to install
     /// the view in readyset
     view_request: Option<ViewCreateRequest>,
+    /// The name of the routing rule that matched the statement when it was prepared, if any
+    routing_rule: Option<String>,
 }
 
 impl<DB> CachedPreparedStatement<DB>
@@ -483,6 +549,9 @@ where
     noria: NoriaConnector,
     /// Optional connector to the upstream DB. Used for fallback reads and all writes if it exists
     upstream: Option<DB>,
+    /// Optional connector to a read replica of the upstream DB. Used for fallback reads outside of
+    /// transactions until this connection writes to the upstream DB. See [`crate::read_replicas`]
+    read_replica: Option<ReadReplicaConnection<DB>>,
     /// Map from username to password for all users allowed to connect to the db
     pub users: HashMap<String, String>,
 
@@ -524,6 +593,86 @@ where
     /// is responsible for creating accurate RYW timestamps/tickets based on writes made by the
     /// Backend client.
     timestamp_client: Option<TimestampClient>,
+    /// Writes this connection made to the upstream database that its reads from ReadySet have to
+    /// observe. Only recorded if read-your-writes consistency is enabled for writes proxied
+    /// upstream
+    upstream_writes: UpstreamWrites,
+    /// The user this connection authenticated as, if known
+    user: Option<String>,
+    /// Rules for routing queries, shared between all connections to this adapter
+    routing_rules: Arc<RoutingRules>,
+    /// Read replicas of the upstream database to send proxied reads to, shared between all
+    /// connections to this adapter
+    read_replicas: Arc<ReadReplicas>,
+    /// Whether this connection has made (or may have made) writes to the upstream database. Once
+    /// it has, its reads are no longer sent to read replicas.
+    wrote_upstream: bool,
+    /// `SET` and `USE` statements run by this connection, which are replayed against read replica
+    /// and pooled upstream connections so that their session state matches this connection's
+    session_statements: Vec<String>,
+    /// This connection's use of a pool of upstream connections, if upstream connections are
+    /// pooled. If so, [`Backend::upstream`] holds the connection currently leased from the pool.
+    /// See [`crate::upstream_pool`]
+    pooled_upstream: Option<PooledUpstream<DB>>,
+}
+
+impl<DB> BackendState<DB>
+where
+    DB: UpstreamDatabase,
+{
+    /// Returns true if `SET` and `USE` statements need to be recorded in
+    /// [`Self::session_statements`]
+    fn tracks_session_statements(&self) -> bool {
+        !self.read_replicas.is_empty() || self.pooled_upstream.is_some()
+    }
+
+    /// Records a `SET` or `USE` statement that was run against the upstream connection
+    fn record_session_statement(&mut self, statement: &str) {
+        if !self.tracks_session_statements() {
+            return;
+        }
+        self.session_statements.push(statement.to_owned());
+        if let Some(pooled_upstream) = &mut self.pooled_upstream {
+            pooled_upstream.ran_session_statement(statement);
+        }
+    }
+
+    /// If the upstream connection leased from the pool returned the given error and it's fatal,
+    /// closes the connection rather than returning it to the pool once it's released
+    fn check_upstream_error(&mut self, error: &DB::Error) {
+        if let Some(pooled_upstream) = &mut self.pooled_upstream {
+            if error.is_fatal() {
+                pooled_upstream.discard();
+            }
+        }
+    }
+
+    /// Returns the RYW ticket to pass with a read of the given query from ReadySet: the
+    /// connection's [`ticket`](Self::ticket) if RYW is enabled for writes made through ReadySet,
+    /// and otherwise the ticket for the writes this connection made to the tables the query reads
+    /// from in the upstream database.
+    ///
+    /// If the query reads from a table whose writes can't be waited for, returns
+    /// [`ReadySetError::ReplicationOffsetNotReached`] if `can_proxy` is true, so that the read is
+    /// proxied upstream, and otherwise returns no ticket so that the read is served from ReadySet
+    /// with possibly stale results.
+    fn read_ticket(
+        &self,
+        query: Option<&SelectStatement>,
+        can_proxy: bool,
+    ) -> ReadySetResult<Option<Timestamp>> {
+        if self.ticket.is_some() {
+            return Ok(self.ticket.clone());
+        }
+
+        match query.map(|query| self.upstream_writes.ticket(query)) {
+            Some(Err(error)) if !can_proxy => {
+                warn!(%error, "Results of read may not include writes made by this connection");
+                Ok(None)
+            }
+            res => res.transpose().map(Option::flatten),
+        }
+    }
 }
 
 /// Settings that have no state and are constant for a given [`Backend`]
@@ -547,6 +696,8 @@ struct BackendSettings {
     /// repeatedly failed for query_max_failure_duration.
     fallback_recovery_duration: Duration,
     fail_invalidated_queries: bool,
+    /// Whether clients may create and drop routing rules
+    allow_routing_rule_changes: bool,
 }
 
 /// QueryInfo holds information regarding the last query that was sent along this connection
@@ -555,6 +706,8 @@ struct BackendSettings {
 pub struct QueryInfo {
     pub destination: QueryDestination,
     pub noria_error: String,
+    /// The name of the routing rule that matched the query, if any
+    pub routing_rule: Option<String>,
 }
 
 impl FromRow for QueryInfo {
@@ -573,6 +726,8 @@ impl FromRow for QueryInfo {
                     res.noria_error = std::str::from_utf8(d)
                         .map_err(|_| FromRowError(row.clone()))?
                         .to_string();
+                } else if c.name_str() == "Routing_rule" {
+                    res.routing_rule = (dest != "none").then(|| dest.to_string());
                 } else {
                     return Err(FromRowError(row.clone()));
                 }
@@ -663,6 +818,14 @@ impl<DB: UpstreamDatabase> PrepareResult<DB> {
         }
     }
 
+    /// Returns the result of preparing the statement on the upstream database, if it was
+    pub fn upstream(&self) -> Option<&UpstreamPrepare<DB>> {
+        match self {
+            Self::Upstream(res) | Self::Both(_, res) => Some(res),
+            Self::Noria(_) => None,
+        }
+    }
+
     /// If this [`PrepareResult`] is a [`PrepareResult::Both`], convert it into only a
     /// [`PrepareResult::Upstream`]
     pub fn make_upstream_only(&mut self) {
@@ -719,6 +882,9 @@ where
     Handler: 'static + QueryHandler,
 {
     pub fn version(&self) -> String {
+        if let Some(pooled_upstream) = &self.state.pooled_upstream {
+            return pooled_upstream.version().to_owned();
+        }
         self.upstream
             .as_ref()
             .map(|upstream| upstream.version())
@@ -744,20 +910,166 @@ where
     /// Internally, this will set the schema search path to a single-element vector with the
     /// database, and send a `USE` command to the upstream, if any.
     pub async fn set_database(&mut self, db: &str) -> Result<(), DB::Error> {
+        self.release_upstream().await;
+        self.lease_upstream().await?;
         if let Some(upstream) = &mut self.upstream {
-            upstream
-                .query(
-                    UseStatement {
-                        database: db.into(),
-                    }
-                    .to_string(),
-                )
-                .await?;
+            let use_statement = UseStatement {
+                database: db.into(),
+            }
+            .to_string();
+            let res = upstream.query(&use_statement).await.map(|_| ());
+            if let Err(error) = &res {
+                self.state.check_upstream_error(error);
+            }
+            res?;
+            self.state.record_session_statement(&use_statement);
         }
         self.noria.set_schema_search_path(vec![db.into()]);
         Ok(())
     }
 
+    /// If upstream connections are pooled, leases a connection from the pool into
+    /// [`Self::upstream`] unless one is already leased
+    async fn lease_upstream(&mut self) -> Result<(), DB::Error> {
+        Self::lease_pooled_upstream(
+            &mut self.upstream,
+            self.state.pooled_upstream.as_mut(),
+            &self.state.session_statements,
+        )
+        .await
+    }
+
+    /// If upstream connections are pooled, leases a connection from the pool into `upstream`
+    /// unless one is already leased, replaying the given session statements onto it
+    async fn lease_pooled_upstream(
+        upstream: &mut Option<DB>,
+        pooled_upstream: Option<&mut PooledUpstream<DB>>,
+        session_statements: &[String],
+    ) -> Result<(), DB::Error> {
+        if let Some(pooled_upstream) = pooled_upstream {
+            pooled_upstream.lease(upstream, session_statements).await?;
+        }
+        Ok(())
+    }
+
+    /// If upstream connections are pooled and this connection isn't in a transaction, returns the
+    /// upstream connection leased from the pool, if any, to the pool.
+    ///
+    /// This is called at the start of every statement, but should also be called once the results
+    /// of each statement have been sent to the client, so that idle connections don't hold on to
+    /// upstream connections.
+    pub async fn release_upstream(&mut self) {
+        let Some(pooled_upstream) = &mut self.state.pooled_upstream else {
+            return;
+        };
+        if self.upstream.is_none() || !self.state.proxy_state.is_fallback() {
+            return;
+        }
+
+        pooled_upstream.release(&mut self.upstream);
+    }
+
+    /// Returns true if reads proxied upstream from this connection can currently be sent to a read
+    /// replica: read replicas are configured, the connection is not in a transaction or proxying
+    /// all statements, and it hasn't written to the upstream database.
+    fn can_read_from_replica(&self) -> bool {
+        !self.state.read_replicas.is_empty()
+            && self.state.proxy_state.is_fallback()
+            && !self.state.wrote_upstream
+    }
+
+    /// Makes sure `read_replica` is connected to a healthy read replica with the same session state
+    /// as the upstream connection, connecting to one of `replicas` if it isn't, and returns
+    /// the connection if it is.
+    async fn ensure_read_replica<'a>(
+        read_replica: &'a mut Option<ReadReplicaConnection<DB>>,
+        replicas: &Arc<ReadReplicas>,
+        session_statements: &[String],
+    ) -> Option<&'a mut ReadReplicaConnection<DB>> {
+        if read_replica
+            .as_ref()
+            .map_or(false, |conn| !conn.replicas.get(conn.index).is_healthy())
+        {
+            *read_replica = None;
+        }
+
+        if read_replica.is_none() {
+            let index = replicas.pick()?;
+            let Some(connection) = read_replicas::connect(replicas, index).await else {
+                replicas.mark_unhealthy(index);
+                return None;
+            };
+            *read_replica = Some(ReadReplicaConnection {
+                replicas: Arc::clone(replicas),
+                index,
+                connection,
+                session_statements: 0,
+            });
+        }
+
+        loop {
+            let conn = read_replica.as_mut()?;
+            let Some(statement) = session_statements.get(conn.session_statements) else {
+                break;
+            };
+            let res = conn.connection.query(statement).await.map(|_| ());
+            if let Err(error) = res {
+                warn!(%error, "Failed to replay session statement on read replica");
+                if error.is_fatal() {
+                    conn.replicas.mark_unhealthy(conn.index);
+                }
+                *read_replica = None;
+                return None;
+            }
+            conn.session_statements += 1;
+        }
+
+        read_replica.as_mut()
+    }
+
+    /// Executes a read on a read replica if `read_replica` is given, falling back to executing it
+    /// on the upstream database if it isn't or if the read replica fails. The read replica is only
+    /// connected to (see [`Self::ensure_read_replica`]) once a read is sent to it, and if upstream
+    /// connections are pooled, an upstream connection is only leased if the read is executed on
+    /// the upstream database.
+    ///
+    /// Read replicas are only marked unhealthy if a read fails because of a fatal (connection)
+    /// error, since other errors are as likely to be caused by the query itself.
+    async fn query_upstream_read<'a>(
+        upstream: &'a mut Option<DB>,
+        pooled_upstream: Option<&mut PooledUpstream<DB>>,
+        session_statements: &[String],
+        read_replica: Option<&'a mut Option<ReadReplicaConnection<DB>>>,
+        read_replicas: &Arc<ReadReplicas>,
+        query: &'a str,
+        event: &mut QueryExecutionEvent,
+    ) -> Result<QueryResult<'a, DB>, DB::Error> {
+        let read_replica = match read_replica {
+            Some(read_replica) => {
+                Self::ensure_read_replica(read_replica, read_replicas, session_statements).await
+            }
+            None => None,
+        };
+        if let Some(read_replica) = read_replica {
+            let _t = event.start_upstream_timer();
+            match read_replica.connection.query(query).await {
+                Ok(res) => {
+                    event.destination = Some(res.destination());
+                    return Ok(QueryResult::Upstream(res));
+                }
+                Err(error) => {
+                    warn!(%error, "Error received from read replica, sending query to upstream");
+                    if error.is_fatal() {
+                        read_replica.replicas.mark_unhealthy(read_replica.index);
+                    }
+                }
+            }
+        }
+
+        Self::lease_pooled_upstream(upstream, pooled_upstream, session_statements).await?;
+        Self::query_fallback(upstream.as_mut(), query, event).await
+    }
+
     /// Executes query on the upstream database, for when it cannot be parsed or executed by noria.
     /// Returns the query result, or an error if fallback is not configured
     #[instrument_root(level = "info")]
@@ -829,6 +1141,7 @@ where
         self.last_query = destination.map(|d| QueryInfo {
             destination: d,
             noria_error: String::new(),
+            routing_rule: None,
         });
 
         // Update noria migration state for query
@@ -919,6 +1232,7 @@ where
             self.last_query = Some(QueryInfo {
                 destination: QueryDestination::Upstream,
                 noria_error: String::new(),
+                routing_rule: None,
             });
             res
         } else {
@@ -933,13 +1247,20 @@ where
             self.last_query = Some(QueryInfo {
                 destination: QueryDestination::Readyset,
                 noria_error: String::new(),
+                routing_rule: None,
             });
             Ok(PrepareResult::Noria(res))
         }
     }
 
-    /// Provides metadata required to prepare a select query
-    fn plan_prepare_select(&mut self, stmt: nom_sql::SelectStatement) -> PrepareMeta {
+    /// Provides metadata required to prepare a select query. If `always_readyset` is true, the
+    /// query will be executed against ReadySet regardless of the state of the connection, as if it
+    /// had been cached with `CREATE CACHE ALWAYS`.
+    fn plan_prepare_select(
+        &mut self,
+        stmt: nom_sql::SelectStatement,
+        always_readyset: bool,
+    ) -> PrepareMeta {
         match self.rewrite_select_and_check_noria(&stmt) {
             Some((rewritten, should_do_noria)) => {
                 let status = self
@@ -949,7 +1270,8 @@ where
                         rewritten.clone(),
                         self.noria.schema_search_path().to_owned(),
                     ));
-                if self.state.proxy_state == ProxyState::ProxyAlways && !status.always {
+                let always = status.always || always_readyset;
+                if self.state.proxy_state == ProxyState::ProxyAlways && !always {
                     PrepareMeta::Proxy
                 } else {
                     PrepareMeta::Select(PrepareSelectMeta {
@@ -960,7 +1282,7 @@ where
                         // synchronously, or if no upstream is present.
                         must_migrate: self.settings.migration_mode == MigrationMode::InRequestPath
                             || !self.has_fallback(),
-                        always: status.always,
+                        always,
                     })
                 }
             }
@@ -982,7 +1304,12 @@ where
         stmt: &nom_sql::SelectStatement,
     ) -> Option<(nom_sql::SelectStatement, bool)> {
         let mut rewritten = stmt.clone();
-        if rewrite::process_query(&mut rewritten, self.noria.server_supports_pagination()).is_err()
+        if rewrite::process_query(
+            &mut rewritten,
+            self.noria.server_supports_pagination(),
+            self.noria.max_parametrized_limit(),
+        )
+        .is_err()
         {
             None
         } else {
@@ -999,14 +1326,36 @@ where
         }
     }
 
-    /// Provides metadata required to prepare a query
-    async fn plan_prepare(&mut self, query: &str) -> PrepareMeta {
-        if self.state.proxy_state == ProxyState::ProxyAlways {
+    /// Provides metadata required to prepare a query, taking into account the routing rule that
+    /// matched the query, if any
+    async fn plan_prepare(
+        &mut self,
+        query: &str,
+        routing_rule: Option<&RoutingRule>,
+    ) -> PrepareMeta {
+        let action = routing_rule.map(|rule| rule.action());
+        match routing_rule {
+            Some(rule) if rule.action() == RoutingAction::Reject => {
+                return PrepareMeta::Rejected {
+                    rule: rule.name().clone(),
+                };
+            }
+            Some(rule) if rule.action() == RoutingAction::Proxy && self.has_fallback() => {
+                return PrepareMeta::Proxy;
+            }
+            _ => {}
+        }
+
+        if self.state.proxy_state == ProxyState::ProxyAlways
+            && action != Some(RoutingAction::ReadySet)
+        {
             return PrepareMeta::Proxy;
         }
 
         match self.parse_query(query) {
-            Ok(SqlQuery::Select(stmt)) => self.plan_prepare_select(stmt),
+            Ok(SqlQuery::Select(stmt)) => {
+                self.plan_prepare_select(stmt, action == Some(RoutingAction::ReadySet))
+            }
             Ok(
                 query @ SqlQuery::Insert(_)
                 | query @ SqlQuery::Update(_)
@@ -1051,6 +1400,7 @@ where
                 self.last_query = Some(QueryInfo {
                     destination: QueryDestination::Upstream,
                     noria_error: String::new(),
+                    routing_rule: None,
                 });
 
                 res
@@ -1059,6 +1409,10 @@ where
             PrepareMeta::Select(select_meta) => {
                 self.mirror_prepare(select_meta, query, event).await
             }
+            PrepareMeta::Rejected { rule } => Err(ReadySetError::QueryRejectedByRoutingRule {
+                rule: rule.to_string(),
+            }
+            .into()),
             _ => unsupported!(),
         }
     }
@@ -1070,9 +1424,37 @@ where
     pub async fn prepare(&mut self, query: &str) -> Result<&PrepareResult<DB>, DB::Error> {
         self.last_query = None;
         let mut query_event = QueryExecutionEvent::new(EventType::Prepare);
+        self.release_upstream().await;
+        self.lease_upstream().await?;
 
-        let meta = self.plan_prepare(query).await;
-        let res = self.do_prepare(&meta, query, &mut query_event).await?;
+        let routing_rule = if self.state.routing_rules.is_empty() {
+            None
+        } else {
+            let parsed = self.parse_query(query).ok();
+            self.route_query(query, parsed.as_ref())
+        };
+        let meta = self.plan_prepare(query, routing_rule.as_deref()).await;
+        let res = match self.do_prepare(&meta, query, &mut query_event).await {
+            Ok(res) => res,
+            Err(error) => {
+                self.state.check_upstream_error(&error);
+                return Err(error);
+            }
+        };
+
+        // Statements prepared upstream have to be prepared again on other pooled connections, so
+        // remember their query
+        let prepared_id = self.next_prepared_id();
+        if let (Some(pooled_upstream), Some(upstream_prep)) =
+            (&mut self.state.pooled_upstream, res.upstream())
+        {
+            pooled_upstream.prepared(prepared_id, query, upstream_prep.statement_id);
+        }
+
+        let routing_rule = routing_rule.map(|rule| rule.name().to_string());
+        if let Some(info) = &mut self.last_query {
+            info.routing_rule = routing_rule.clone();
+        }
 
         let (id, parsed_query, migration_state, view_request, always) = match meta {
             PrepareMeta::Write { stmt } => (
@@ -1118,6 +1500,7 @@ where
             parsed_query,
             view_request,
             always,
+            routing_rule,
         };
 
         self.state.prepared_statements.push(cache_entry);
@@ -1125,12 +1508,27 @@ where
         Ok(&self.state.prepared_statements.last().unwrap().prep)
     }
 
-    /// Executes a prepared statement on ReadySet
+    /// Builds the RYW ticket for a write to `table` that was committed upstream in the
+    /// transaction with the given GTID
+    async fn upstream_write_ticket(
+        noria: &mut NoriaConnector,
+        table: &Relation,
+        gtid: String,
+    ) -> ReadySetResult<Timestamp> {
+        let index = noria.node_index_of(table.name.as_str()).await?;
+        TimestampClient::default()
+            .append_write(WriteId::MySqlGtid(gtid), vec![WriteKey::TableIndex(index)])
+            .map_err(|e| internal_err!("{e}"))
+    }
+
+    /// Executes a prepared statement on ReadySet. See [`NoriaConnector::execute_select`] for the
+    /// meaning of `ticket` and `stale_on_timeout`
     async fn execute_noria<'a>(
         noria: &'a mut NoriaConnector,
         prep: &noria_connector::PrepareResult,
         params: &[DfValue],
         ticket: Option<Timestamp>,
+        stale_on_timeout: bool,
         event: &mut QueryExecutionEvent,
     ) -> ReadySetResult<QueryResult<'a, DB>> {
         use noria_connector::PrepareResult::*;
@@ -1143,7 +1541,9 @@ where
                 statement_id: id, ..
             } => {
                 let ctx = ExecuteSelectContext::Prepared { q_id: *id, params };
-                noria.execute_select(ctx, ticket, event).await
+                noria
+                    .execute_select(ctx, ticket, stale_on_timeout, event)
+                    .await
             }
             Insert {
                 statement_id: id, ..
@@ -1167,13 +1567,33 @@ where
     }
 
     /// Execute a prepared statement on ReadySet
+    ///
+    /// If upstream connections are pooled, leases a connection and prepares the statement with
+    /// the given `id` on it if it hasn't been yet, after checking that the statement doesn't
+    /// depend on state of a connection that isn't leased anymore (see [`crate::upstream_pool`]).
+    #[allow(clippy::too_many_arguments)]
     async fn execute_upstream<'a>(
         upstream: &'a mut Option<DB>,
+        pooled_upstream: Option<&mut PooledUpstream<DB>>,
+        session_statements: &[String],
+        id: u32,
         prep: &UpstreamPrepare<DB>,
         params: &[DfValue],
         event: &mut QueryExecutionEvent,
         is_fallback: bool,
     ) -> Result<QueryResult<'a, DB>, DB::Error> {
+        let statement_id = match pooled_upstream {
+            Some(pooled_upstream) => {
+                pooled_upstream.check_prepared(id, upstream.is_some())?;
+                pooled_upstream.lease(upstream, session_statements).await?;
+                let upstream = upstream
+                    .as_mut()
+                    .ok_or_else(|| internal_err!("No upstream connection leased"))?;
+                pooled_upstream.statement_id(upstream, id).await?
+            }
+            None => prep.statement_id,
+        };
+
         let upstream = upstream.as_mut().ok_or_else(|| {
             ReadySetError::Internal("This condition requires an upstream connector".to_string())
         })?;
@@ -1187,7 +1607,7 @@ where
         let _t = event.start_upstream_timer();
 
         upstream
-            .execute(prep.statement_id, params)
+            .execute(statement_id, params)
             .await
             .map(|r| QueryResult::Upstream(r))
     }
@@ -1197,14 +1617,22 @@ where
     async fn execute_cascade<'a>(
         noria: &'a mut NoriaConnector,
         upstream: &'a mut Option<DB>,
+        pooled_upstream: Option<&mut PooledUpstream<DB>>,
+        session_statements: &[String],
+        id: u32,
         noria_prep: &noria_connector::PrepareResult,
         upstream_prep: &UpstreamPrepare<DB>,
         params: &[DfValue],
         ex_info: Option<&mut ExecutionInfo>,
-        ticket: Option<Timestamp>,
+        ticket: ReadySetResult<Option<Timestamp>>,
         event: &mut QueryExecutionEvent,
     ) -> Result<QueryResult<'a, DB>, DB::Error> {
-        let noria_res = Self::execute_noria(noria, noria_prep, params, ticket, event).await;
+        let noria_res = match ticket {
+            Ok(ticket) => {
+                Self::execute_noria(noria, noria_prep, params, ticket, false, event).await
+            }
+            Err(error) => Err(error),
+        };
         match noria_res {
             Ok(noria_ok) => {
                 if let Some(info) = ex_info {
@@ -1228,7 +1656,17 @@ where
                           "Error received from noria, sending query to fallback");
                 }
 
-                Self::execute_upstream(upstream, upstream_prep, params, event, true).await
+                Self::execute_upstream(
+                    upstream,
+                    pooled_upstream,
+                    session_statements,
+                    id,
+                    upstream_prep,
+                    params,
+                    event,
+                    true,
+                )
+                .await
             }
         }
     }
@@ -1326,6 +1764,30 @@ where
         params: &[DfValue],
     ) -> Result<QueryResult<'_, DB>, DB::Error> {
         self.last_query = None;
+        self.release_upstream().await;
+        let (parsed_query, can_proxy) = self
+            .state
+            .prepared_statements
+            .get(id as usize)
+            .map(|stmt| {
+                (
+                    stmt.parsed_query.clone(),
+                    !matches!(stmt.prep, PrepareResult::Noria(_)),
+                )
+            })
+            .ok_or(PreparedStatementMissing { statement_id: id })?;
+        let ticket = match parsed_query.as_deref() {
+            Some(SqlQuery::Select(stmt)) => self.state.read_ticket(Some(stmt), can_proxy),
+            _ => self.state.read_ticket(None, can_proxy),
+        };
+        // The replication position of prepared writes isn't known, so reads of the tables they
+        // write to can't be served consistently from ReadySet until a later write to them
+        let untracked_write = parsed_query
+            .as_deref()
+            .and_then(written_table)
+            .filter(|_| self.noria.read_your_writes_enabled())
+            .cloned();
+
         let cached_statement = self
             .state
             .prepared_statements
@@ -1333,12 +1795,17 @@ where
             .ok_or(PreparedStatementMissing { statement_id: id })?;
 
         let mut event = QueryExecutionEvent::new(EventType::Execute);
-        event.query = cached_statement.parsed_query.clone();
+        event.query = parsed_query;
         event.query_id = cached_statement.query_id;
 
+        let may_write_upstream = (self.upstream.is_some() || self.state.pooled_upstream.is_some())
+            && !cached_statement
+                .parsed_query
+                .as_deref()
+                .map_or(false, is_read_only);
+
         let upstream = &mut self.upstream;
         let noria = &mut self.noria;
-        let ticket = self.state.ticket.clone();
 
         if cached_statement.migration_state.is_pending() {
             // We got a statement with a pending migration, we want to check if migration is
@@ -1385,17 +1852,39 @@ where
             }
         };
 
+        let pooled_upstream = self.state.pooled_upstream.as_mut();
+        let session_statements = &self.state.session_statements;
         let result = match &cached_statement.prep {
             PrepareResult::Noria(prep) => {
-                Self::execute_noria(noria, prep, params, ticket, &mut event)
+                Self::execute_noria(noria, prep, params, ticket?, true, &mut event)
                     .await
                     .map_err(Into::into)
             }
             PrepareResult::Upstream(prep) => {
-                Self::execute_upstream(upstream, prep, params, &mut event, false).await
+                Self::execute_upstream(
+                    upstream,
+                    pooled_upstream,
+                    session_statements,
+                    id,
+                    prep,
+                    params,
+                    &mut event,
+                    false,
+                )
+                .await
             }
             PrepareResult::Both(.., uprep) if should_fallback => {
-                Self::execute_upstream(upstream, uprep, params, &mut event, false).await
+                Self::execute_upstream(
+                    upstream,
+                    pooled_upstream,
+                    session_statements,
+                    id,
+                    uprep,
+                    params,
+                    &mut event,
+                    false,
+                )
+                .await
             }
             PrepareResult::Both(nprep, uprep) => {
                 if cached_statement.execution_info.is_none() {
@@ -1407,6 +1896,9 @@ where
                 Self::execute_cascade(
                     noria,
                     upstream,
+                    pooled_upstream,
+                    session_statements,
+                    id,
                     nprep,
                     uprep,
                     params,
@@ -1435,6 +1927,14 @@ where
             }
         }
 
+        if let Some(table) = untracked_write.as_ref().filter(|_| result.is_ok()) {
+            self.state.upstream_writes.record_untracked(table);
+        }
+
+        if may_write_upstream && result.is_ok() {
+            self.state.wrote_upstream = true;
+        }
+
         self.last_query = event.destination.map(|d| QueryInfo {
             destination: d,
             noria_error: event
@@ -1442,9 +1942,14 @@ where
                 .as_ref()
                 .map(|e| e.to_string())
                 .unwrap_or_default(),
+            routing_rule: cached_statement.routing_rule.clone(),
         });
         log_query(self.query_log_sender.as_ref(), event, self.settings.slowlog);
 
+        if let Err(error) = &result {
+            self.state.check_upstream_error(error);
+        }
+
         result
     }
 
@@ -1490,7 +1995,7 @@ where
 
     /// Generates response to the `EXPLAIN LAST STATEMENT` query
     fn explain_last_statement(&self) -> ReadySetResult<noria_connector::QueryResult<'static>> {
-        let (destination, error) = self
+        let (destination, error, routing_rule) = self
             .last_query
             .as_ref()
             .map(|info| {
@@ -1500,13 +2005,17 @@ where
                         s if s.is_empty() => "ok".to_string(),
                         s => s.clone(),
                     },
+                    info.routing_rule
+                        .clone()
+                        .unwrap_or_else(|| "none".to_string()),
                 )
             })
-            .unwrap_or_else(|| ("unknown".to_string(), "ok".to_string()));
+            .unwrap_or_else(|| ("unknown".to_string(), "ok".to_string(), "none".to_string()));
 
         Ok(noria_connector::QueryResult::Meta(vec![
             ("Query_destination", destination).into(),
             ("ReadySet_error", error).into(),
+            ("Routing_rule", routing_rule).into(),
         ]))
     }
 
@@ -1530,7 +2039,11 @@ where
             }
         }
         // Now migrate the new query
-        rewrite::process_query(&mut stmt, self.noria.server_supports_pagination())?;
+        rewrite::process_query(
+            &mut stmt,
+            self.noria.server_supports_pagination(),
+            self.noria.max_parametrized_limit(),
+        )?;
         self.noria
             .handle_create_cached_query(name, &stmt, override_schema_search_path, always)
             .await?;
@@ -1639,6 +2152,46 @@ where
         ))
     }
 
+    /// Responds to a `SHOW ROUTING RULES` query
+    fn show_routing_rules(&self) -> ReadySetResult<noria_connector::QueryResult<'static>> {
+        let create_column = |n: &str, column_type| ColumnSchema {
+            column: nom_sql::Column {
+                name: n.into(),
+                table: None,
+            },
+            column_type,
+            base: None,
+        };
+
+        let select_schema = SelectSchema {
+            use_bogo: false,
+            schema: Cow::Owned(vec![
+                create_column("name", DfType::DEFAULT_TEXT),
+                create_column("rule", DfType::DEFAULT_TEXT),
+                create_column("matches", DfType::UnsignedBigInt),
+            ]),
+            columns: Cow::Owned(vec!["name".into(), "rule".into(), "matches".into()]),
+        };
+
+        let data = self
+            .state
+            .routing_rules
+            .rules()
+            .into_iter()
+            .map(|rule| {
+                vec![
+                    DfValue::from(rule.name().to_string()),
+                    DfValue::from(rule.statement().to_string()),
+                    DfValue::from(rule.matches()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        Ok(noria_connector::QueryResult::from_owned(
+            select_schema,
+            vec![Results::new(data)],
+        ))
+    }
+
     async fn query_noria_extensions<'a>(
         &'a mut self,
         query: &'a SqlQuery,
@@ -1696,6 +2249,22 @@ where
             }
             SqlQuery::DropCache(DropCacheStatement { name }) => self.drop_cached_query(name).await,
             SqlQuery::DropAllCaches(_) => self.drop_all_caches().await,
+            SqlQuery::CreateRoutingRule(_) | SqlQuery::DropRoutingRule(_)
+                if !self.settings.allow_routing_rule_changes =>
+            {
+                Err(ReadySetError::RoutingRuleChangesNotAllowed)
+            }
+            SqlQuery::CreateRoutingRule(stmt) => self
+                .state
+                .routing_rules
+                .create(stmt.clone())
+                .map(|()| noria_connector::QueryResult::Empty),
+            SqlQuery::DropRoutingRule(DropRoutingRuleStatement { name }) => self
+                .state
+                .routing_rules
+                .remove(name)
+                .map(|()| noria_connector::QueryResult::Empty),
+            SqlQuery::Show(ShowStatement::RoutingRules) => self.show_routing_rules(),
             SqlQuery::Show(ShowStatement::CachedQueries(query_id)) => {
                 // Log a telemetry event
                 if let Some(ref telemetry_sender) = self.telemetry_sender {
@@ -1737,13 +2306,15 @@ where
     #[allow(clippy::too_many_arguments)]
     async fn query_adhoc_select<'a>(
         noria: &'a mut NoriaConnector,
-        upstream: Option<&'a mut DB>,
+        upstream: &'a mut Option<DB>,
+        read_replica: Option<&'a mut Option<ReadReplicaConnection<DB>>>,
         settings: &BackendSettings,
         state: &mut BackendState<DB>,
         original_query: &'a str,
         original_stmt: SelectStatement,
         view_request: &ViewCreateRequest,
         status: Option<QueryStatus>,
+        always_readyset: bool,
         event: &mut QueryExecutionEvent,
     ) -> Result<QueryResult<'a, DB>, DB::Error> {
         let mut status = status.unwrap_or(QueryStatus {
@@ -1751,6 +2322,9 @@ where
             execution_info: None,
             always: false,
         });
+        // Queries routed to ReadySet by a routing rule behave like `always` queries, without
+        // recording that in the query status cache
+        let always = status.always || always_readyset;
         let original_status = status.clone();
         let did_work = if let Some(ref mut i) = status.execution_info {
             i.reset_if_exceeded_recovery(
@@ -1761,8 +2335,9 @@ where
             false
         };
 
-        if !status.always
-            && (upstream.is_some()
+        let has_upstream = upstream.is_some() || state.pooled_upstream.is_some();
+        if !always
+            && (has_upstream
                 && (settings.migration_mode != MigrationMode::InRequestPath
                     && status.migration_state != MigrationState::Successful)
                 || (status.migration_state == MigrationState::Unsupported)
@@ -1781,20 +2356,33 @@ where
                     &status.execution_info.unwrap().last_transition_time,
                 );
             }
-            return Self::query_fallback(upstream, original_query, event).await;
+            return Self::query_upstream_read(
+                upstream,
+                state.pooled_upstream.as_mut(),
+                &state.session_statements,
+                read_replica,
+                &state.read_replicas,
+                original_query,
+                event,
+            )
+            .await;
         }
 
-        let noria_res = {
-            event.destination = Some(QueryDestination::Readyset);
-            let start = Instant::now();
-            let ctx = ExecuteSelectContext::AdHoc {
-                statement: original_stmt,
-                query: original_query,
-                create_if_missing: settings.migration_mode == MigrationMode::InRequestPath,
-            };
-            let res = noria.execute_select(ctx, state.ticket.clone(), event).await;
-            event.readyset_duration = Some(start.elapsed());
-            res
+        let can_proxy = !always && has_upstream;
+        let noria_res = match state.read_ticket(Some(&original_stmt), can_proxy) {
+            Ok(ticket) => {
+                event.destination = Some(QueryDestination::Readyset);
+                let start = Instant::now();
+                let ctx = ExecuteSelectContext::AdHoc {
+                    statement: original_stmt,
+                    query: original_query,
+                    create_if_missing: settings.migration_mode == MigrationMode::InRequestPath,
+                };
+                let res = noria.execute_select(ctx, ticket, !can_proxy, event).await;
+                event.readyset_duration = Some(start.elapsed());
+                res
+            }
+            Err(error) => Err(error),
         };
 
         if status.execution_info.is_none() {
@@ -1834,8 +2422,6 @@ where
                     status.migration_state = MigrationState::Unsupported;
                 };
 
-                let always = status.always;
-
                 if status != original_status {
                     state
                         .query_status_cache
@@ -1844,16 +2430,21 @@ where
 
                 // Try to execute on fallback if present, as long as query is not an `always`
                 // query.
-                match (always, upstream) {
-                    (true, _) | (_, None) => Err(noria_err.into()),
-                    (false, Some(fallback)) => {
-                        event.destination = Some(QueryDestination::ReadysetThenUpstream);
-                        let _t = event.start_upstream_timer();
-                        fallback
-                            .query(original_query)
-                            .await
-                            .map(QueryResult::Upstream)
-                    }
+                if !can_proxy {
+                    Err(noria_err.into())
+                } else {
+                    let res = Self::query_upstream_read(
+                        upstream,
+                        state.pooled_upstream.as_mut(),
+                        &state.session_statements,
+                        read_replica,
+                        &state.read_replicas,
+                        original_query,
+                        event,
+                    )
+                    .await;
+                    event.destination = Some(QueryDestination::ReadysetThenUpstream);
+                    res
                 }
             }
         }
@@ -1865,26 +2456,29 @@ where
     /// during processing.
     fn noria_should_try_select(&self, q: &mut ViewCreateRequest) -> (bool, Option<QueryStatus>) {
         let mut status = None;
-        let should_try =
-            if rewrite::process_query(&mut q.statement, self.noria.server_supports_pagination())
-                .is_ok()
-            {
-                let s = self.state.query_status_cache.query_status(q);
-                let should_try = if self.state.proxy_state.should_proxy() {
-                    s.always
-                } else {
-                    true
-                };
-                status = Some(s);
-                should_try
+        let should_try = if rewrite::process_query(
+            &mut q.statement,
+            self.noria.server_supports_pagination(),
+            self.noria.max_parametrized_limit(),
+        )
+        .is_ok()
+        {
+            let s = self.state.query_status_cache.query_status(q);
+            let should_try = if self.state.proxy_state.should_proxy() {
+                s.always
             } else {
-                warn!(statement = %Sensitive(&q.statement),
-                  "This statement could not be rewritten by ReadySet");
-                matches!(
-                    self.state.proxy_state,
-                    ProxyState::Never | ProxyState::Fallback
-                )
+                true
             };
+            status = Some(s);
+            should_try
+        } else {
+            warn!(statement = %Sensitive(&q.statement),
+              "This statement could not be rewritten by ReadySet");
+            matches!(
+                self.state.proxy_state,
+                ProxyState::Never | ProxyState::Fallback
+            )
+        };
 
         (should_try, status)
     }
@@ -1994,29 +2588,72 @@ where
                                 let (query_result, identifier) =
                                     upstream.handle_ryw_write(raw_query).await?;
 
-                                // TODO(andrew): Move table name to table index conversion to
-                                // timestamp service https://app.clubhouse.io/readysettech/story/331
-                                let index = noria.node_index_of(t.name.as_str()).await?;
-                                let affected_tables = vec![WriteKey::TableIndex(index)];
-
-                                let new_timestamp = timestamp_service
-                                    .append_write(WriteId::MySqlGtid(identifier), affected_tables)
-                                    .map_err(|e| internal_err!("{e}"))?;
-
-                                // TODO(andrew, justin): solidify error handling in client
-                                // https://app.clubhouse.io/readysettech/story/366
-                                let current_ticket = state.ticket.as_ref().ok_or_else(|| {
-                                    internal_err!("RYW enabled backends must have a current ticket")
-                                })?;
-
-                                state.ticket =
-                                    Some(Timestamp::join(current_ticket, &new_timestamp));
+                                // The write was committed even if it has no identifier, so it
+                                // can't fail now
+                                if let Some(identifier) = identifier {
+                                    // TODO(andrew): Move table name to table index conversion to
+                                    // timestamp service
+                                    // https://app.clubhouse.io/readysettech/story/331
+                                    let index = noria.node_index_of(t.name.as_str()).await?;
+                                    let affected_tables = vec![WriteKey::TableIndex(index)];
+
+                                    let new_timestamp = timestamp_service
+                                        .append_write(
+                                            WriteId::MySqlGtid(identifier),
+                                            affected_tables,
+                                        )
+                                        .map_err(|e| internal_err!("{e}"))?;
+
+                                    // TODO(andrew, justin): solidify error handling in client
+                                    // https://app.clubhouse.io/readysettech/story/366
+                                    let current_ticket =
+                                        state.ticket.as_ref().ok_or_else(|| {
+                                            internal_err!(
+                                                "RYW enabled backends must have a current ticket"
+                                            )
+                                        })?;
+
+                                    state.ticket =
+                                        Some(Timestamp::join(current_ticket, &new_timestamp));
+                                }
                                 Ok(query_result)
                             } else {
                                 upstream.query(raw_query).await
                             }
+                        } else if noria.read_your_writes_enabled()
+                            && state.proxy_state.is_fallback()
+                        {
+                            // Run the write in its own transaction so that we learn its GTID, and
+                            // record the ticket that reads of the table have to wait for
+                            let (query_result, gtid) = upstream.handle_ryw_write(raw_query).await?;
+                            match gtid {
+                                Some(gtid) => {
+                                    match Self::upstream_write_ticket(noria, t, gtid).await {
+                                        Ok(ticket) => state.upstream_writes.record(t, &ticket),
+                                        // Tables that aren't replicated can't be read from ReadySet
+                                        Err(e) if e.caused_by_table_not_found() => {}
+                                        Err(error) => {
+                                            warn!(
+                                                %error,
+                                                table = %t,
+                                                "Could not build read-your-writes ticket for write"
+                                            );
+                                            state.upstream_writes.record_untracked(t);
+                                        }
+                                    }
+                                }
+                                // Writes committed without a GTID can't be waited for
+                                None => state.upstream_writes.record_untracked(t),
+                            }
+                            Ok(query_result)
                         } else {
-                            upstream.query(raw_query).await
+                            let query_result = upstream.query(raw_query).await;
+                            if noria.read_your_writes_enabled() && query_result.is_ok() {
+                                // Writes in transactions don't have a known GTID until they're
+                                // committed
+                                state.upstream_writes.record_untracked(t);
+                            }
+                            query_result
                         };
 
                         query_result.map(QueryResult::Upstream)
@@ -2052,6 +2689,8 @@ where
                     SqlQuery::CreateCache(_)
                     | SqlQuery::DropCache(_)
                     | SqlQuery::DropAllCaches(_)
+                    | SqlQuery::CreateRoutingRule(_)
+                    | SqlQuery::DropRoutingRule(_)
                     | SqlQuery::Explain(_) => {
                         unreachable!("path returns prior")
                     }
@@ -2102,6 +2741,7 @@ where
     #[instrument_root(level = "info")]
     #[inline]
     pub async fn query<'a>(&'a mut self, query: &'a str) -> Result<QueryResult<'a, DB>, DB::Error> {
+        self.release_upstream().await;
         let mut event = QueryExecutionEvent::new(EventType::Query);
         let query_log_sender = self.query_log_sender.clone();
         let slowlog = self.settings.slowlog;
@@ -2110,8 +2750,44 @@ where
             let _t = event.start_parse_timer();
             self.parse_query(query)
         };
+        let may_write_upstream = self.has_fallback()
+            && !matches!(&parse_result, Ok(parsed_query) if is_read_only(parsed_query));
+        let is_session_statement = matches!(&parse_result, Ok(SqlQuery::Set(_) | SqlQuery::Use(_)));
+        let routing_rule = self.route_query(query, parse_result.as_ref().ok());
+        let routing_action = routing_rule.as_ref().map(|rule| rule.action());
+
+        if let Some(pooled_upstream) = &mut self.state.pooled_upstream {
+            pooled_upstream.check_query(query, self.upstream.is_some())?;
+        }
+
+        // If upstream connections are pooled, reads only lease one if they end up being proxied
+        // upstream, but any other statement that may be proxied upstream leases one up front
+        if self.state.pooled_upstream.is_some()
+            && routing_action != Some(RoutingAction::Reject)
+            && !matches!(
+                &parse_result,
+                Ok(parsed_query)
+                    if (matches!(parsed_query, SqlQuery::Select(_))
+                        && !Handler::requires_fallback(parsed_query))
+                        || is_readyset_extension(parsed_query)
+            )
+        {
+            self.lease_upstream().await?;
+        }
 
         let result = match parse_result {
+            // Queries rejected by a routing rule are never executed
+            _ if let Some(rule) = routing_rule
+                .as_ref()
+                .filter(|rule| rule.action() == RoutingAction::Reject) =>
+            {
+                let e = ReadySetError::QueryRejectedByRoutingRule {
+                    rule: rule.name().to_string(),
+                };
+                event.destination = Some(QueryDestination::Readyset);
+                event.set_noria_error(&e);
+                Err(e.into())
+            }
             // Parse error, but no fallback exists
             Err(e) if !self.has_fallback() => {
                 error!("{}", e);
@@ -2191,31 +2867,67 @@ where
                         .map_err(Into::into)
                 }
             }
+            // Reads matching a routing rule that proxies them always go to the upstream database
+            Ok(SqlQuery::Select(_))
+                if routing_action == Some(RoutingAction::Proxy) && self.has_fallback() =>
+            {
+                let read_replica = self
+                    .can_read_from_replica()
+                    .then_some(&mut self.read_replica);
+                Self::query_upstream_read(
+                    &mut self.upstream,
+                    self.state.pooled_upstream.as_mut(),
+                    &self.state.session_statements,
+                    read_replica,
+                    &self.state.read_replicas,
+                    query,
+                    &mut event,
+                )
+                .await
+            }
             Ok(SqlQuery::Select(stmt)) => {
                 let mut view_request = ViewCreateRequest::new(
                     stmt.clone(),
                     self.noria.schema_search_path().to_owned(),
                 );
                 let (noria_should_try, status) = self.noria_should_try_select(&mut view_request);
-                if noria_should_try {
+                // Reads matching a routing rule that routes them to ReadySet are tried against
+                // ReadySet as long as they could be rewritten
+                let always_readyset =
+                    routing_action == Some(RoutingAction::ReadySet) && status.is_some();
+                let read_replica = self
+                    .can_read_from_replica()
+                    .then_some(&mut self.read_replica);
+                if noria_should_try || always_readyset {
                     event.sql_type = SqlQueryType::Read;
                     if self.settings.query_log_ad_hoc_queries {
                         event.query = Some(Arc::new(SqlQuery::Select(stmt.clone())));
                     }
                     Self::query_adhoc_select(
                         &mut self.noria,
-                        self.upstream.as_mut(),
+                        &mut self.upstream,
+                        read_replica,
                         &self.settings,
                         &mut self.state,
                         query,
                         stmt,
                         &view_request,
                         status,
+                        always_readyset,
                         &mut event,
                     )
                     .await
                 } else {
-                    Self::query_fallback(self.upstream.as_mut(), query, &mut event).await
+                    Self::query_upstream_read(
+                        &mut self.upstream,
+                        self.state.pooled_upstream.as_mut(),
+                        &self.state.session_statements,
+                        read_replica,
+                        &self.state.read_replicas,
+                        query,
+                        &mut event,
+                    )
+                    .await
                 }
             }
             Ok(_) if self.state.proxy_state.should_proxy() => {
@@ -2235,6 +2947,18 @@ where
             }
         };
 
+        match &result {
+            Ok(_) => {
+                if may_write_upstream {
+                    self.state.wrote_upstream = true;
+                }
+                if is_session_statement {
+                    self.state.record_session_statement(query);
+                }
+            }
+            Err(error) => self.state.check_upstream_error(error),
+        }
+
         self.last_query = event.destination.map(|d| QueryInfo {
             destination: d,
             noria_error: event
@@ -2242,6 +2966,7 @@ where
                 .as_ref()
                 .map(|e| e.to_string())
                 .unwrap_or_default(),
+            routing_rule: routing_rule.map(|rule| rule.name().to_string()),
         });
 
         log_query(query_log_sender.as_ref(), event, slowlog);
@@ -2251,19 +2976,60 @@ where
 
     /// Whether or not we have fallback enabled.
     pub fn has_fallback(&self) -> bool {
-        self.upstream.is_some()
+        self.upstream.is_some() || self.state.pooled_upstream.is_some()
     }
 
     /// If we are using fallback, this will return the database that was in the original connection
     /// string, if it exists, otherwise it will return None. If we are not using fallback this will
     /// always return None.
     pub fn database(&self) -> Option<&str> {
+        if let Some(pooled_upstream) = &self.state.pooled_upstream {
+            return pooled_upstream.database();
+        }
         match &self.upstream {
             Some(db) => db.database(),
             None => None,
         }
     }
 
+    /// Records the user this connection authenticated as, for use when matching routing rules
+    pub fn set_user(&mut self, user: &str) {
+        self.state.user = Some(user.to_owned());
+    }
+
+    /// Returns the database this connection is currently using, if known
+    fn current_database(&self) -> Option<&str> {
+        match self.settings.dialect {
+            Dialect::MySQL => self
+                .noria
+                .schema_search_path()
+                .first()
+                .map(|db| db.as_str()),
+            Dialect::PostgreSQL => self.database(),
+        }
+    }
+
+    /// Returns the first routing rule that matches the given query, if any. ReadySet's own SQL
+    /// extensions and transaction boundaries are never subject to routing rules.
+    fn route_query(&self, query: &str, parsed: Option<&SqlQuery>) -> Option<Arc<RoutingRule>> {
+        if parsed.map_or(false, |parsed| {
+            is_readyset_extension(parsed)
+                || matches!(
+                    parsed,
+                    SqlQuery::StartTransaction(_) | SqlQuery::Commit(_) | SqlQuery::Rollback(_)
+                )
+        }) {
+            return None;
+        }
+
+        self.state.routing_rules.route(&RoutedQuery {
+            user: self.state.user.as_deref(),
+            database: self.current_database(),
+            query,
+            parsed,
+        })
+    }
+
     // For debugging purposes
     pub fn ticket(&self) -> &Option<Timestamp> {
         &self.state.ticket
@@ -2295,9 +3061,63 @@ where
 {
     fn drop(&mut self) {
         metrics::decrement_gauge!(recorded::CONNECTED_CLIENTS, 1.0);
+        if let Some(pooled_upstream) = &mut self.state.pooled_upstream {
+            // Pinned connections hold state that'd leak to other client connections, so they're
+            // closed rather than returned to the pool
+            if self.state.proxy_state.is_fallback() && !pooled_upstream.is_pinned() {
+                pooled_upstream.release(&mut self.upstream);
+            }
+        }
     }
 }
 
+/// Returns the table the given query writes to, if it's a write
+fn written_table(query: &SqlQuery) -> Option<&Relation> {
+    match query {
+        SqlQuery::Insert(InsertStatement { table, .. })
+        | SqlQuery::Update(UpdateStatement { table, .. })
+        | SqlQuery::Delete(DeleteStatement { table, .. }) => Some(table),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the given query can't write to the upstream database when proxied to it.
+/// Connections stop sending reads to read replicas once they've proxied any other query upstream.
+fn is_read_only(query: &SqlQuery) -> bool {
+    matches!(
+        query,
+        SqlQuery::Select(_)
+            | SqlQuery::CompoundSelect(_)
+            | SqlQuery::Show(_)
+            | SqlQuery::Set(_)
+            | SqlQuery::Use(_)
+            | SqlQuery::StartTransaction(_)
+            | SqlQuery::Commit(_)
+            | SqlQuery::Rollback(_)
+    ) || is_readyset_extension(query)
+}
+
+/// Returns `true` if the given query is one of ReadySet's own SQL extensions, which are always
+/// handled by ReadySet itself
+fn is_readyset_extension(query: &SqlQuery) -> bool {
+    matches!(
+        query,
+        SqlQuery::CreateCache(_)
+            | SqlQuery::DropCache(_)
+            | SqlQuery::DropAllCaches(_)
+            | SqlQuery::CreateRoutingRule(_)
+            | SqlQuery::DropRoutingRule(_)
+            | SqlQuery::Explain(_)
+            | SqlQuery::Show(
+                ShowStatement::CachedQueries(_)
+                    | ShowStatement::ProxiedQueries(_)
+                    | ShowStatement::ReadySetStatus
+                    | ShowStatement::ReadySetVersion
+                    | ShowStatement::RoutingRules
+            )
+    )
+}
+
 /// Offloads recording query metrics to a separate thread. Sends a
 /// message over a mpsc channel.
 fn log_query(
@@ -4,8 +4,13 @@ use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::ops::Bound;
 use std::sync::{atomic, Arc, RwLock};
+use std::time::Duration;
 
-use dataflow_expression::{BinaryOperator as DfBinaryOperator, Expr as DfExpr};
+use chrono::Utc;
+use dataflow_expression::full_text::FullTextQuery;
+use dataflow_expression::{
+    BinaryOperator as DfBinaryOperator, BuiltinFunction, Expr as DfExpr, LowerContext,
+};
 use itertools::Itertools;
 use launchpad::redacted::Sensitive;
 use nom_sql::analysis::visit_mut::VisitorMut;
@@ -29,7 +34,7 @@ use readyset_errors::{
 };
 use readyset_server::worker::readers::{CallResult, ReadRequestHandler};
 use readyset_sql_passes::anonymize::anonymize_literals;
-use tracing::{error, info, instrument, trace};
+use tracing::{error, info, instrument, trace, warn};
 use vec1::vec1;
 
 use crate::backend::SelectSchema;
@@ -385,6 +390,15 @@ pub struct NoriaConnector {
     /// supports a multi-element schema search path, the concept of "currently connected database"
     /// in MySQL can be thought of as a schema search path that only has one element.
     schema_search_path: Vec<SqlIdentifier>,
+
+    /// If set, queries with a parameterized `LIMIT` are cached with the limit replaced by this
+    /// value, and the limit provided at execute time is applied after the lookup.
+    max_parametrized_limit: Option<usize>,
+
+    /// If set, reads wait up to this long for ReadySet to replicate the writes that this
+    /// connection made to the upstream database, as described by the ticket passed to
+    /// [`Self::execute_select`].
+    read_your_writes_timeout: Option<Duration>,
 }
 
 mod request_handler {
@@ -508,9 +522,31 @@ impl NoriaConnector {
             read_request_handler: request_handler::LocalReadHandler::new(read_request_handler),
             dialect,
             schema_search_path,
+            max_parametrized_limit: None,
+            read_your_writes_timeout: None,
         }
     }
 
+    /// Configure the maximum value of a parameterized `LIMIT` that can be served from the cache.
+    /// See [`rewrite::process_query`]
+    pub fn with_max_parametrized_limit(mut self, max_parametrized_limit: Option<usize>) -> Self {
+        self.max_parametrized_limit = max_parametrized_limit;
+        self
+    }
+
+    /// Enable read-your-writes consistency for writes proxied to the upstream database, waiting up
+    /// to the given timeout for ReadySet to replicate those writes before reads.
+    pub fn with_read_your_writes_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.read_your_writes_timeout = timeout;
+        self
+    }
+
+    /// Returns `true` if read-your-writes consistency is enabled for writes proxied to the
+    /// upstream database
+    pub(crate) fn read_your_writes_enabled(&self) -> bool {
+        self.read_your_writes_timeout.is_some()
+    }
+
     pub(crate) async fn graphviz(
         &mut self,
         simplified: bool,
@@ -600,6 +636,10 @@ impl NoriaConnector {
             .unwrap_or(false)
     }
 
+    pub(crate) fn max_parametrized_limit(&self) -> Option<usize> {
+        self.max_parametrized_limit
+    }
+
     // TODO(andrew): Allow client to map table names to NodeIndexes without having to query ReadySet
     // repeatedly. Eventually, this will be responsibility of the TimestampService.
     pub async fn node_index_of(&mut self, table_name: &str) -> ReadySetResult<LocalNodeIndex> {
@@ -1363,8 +1403,11 @@ impl NoriaConnector {
             .collect();
 
         trace!("select::collapse where-in clauses");
-        let processed_query_params =
-            rewrite::process_query(&mut statement, self.server_supports_pagination())?;
+        let processed_query_params = rewrite::process_query(
+            &mut statement,
+            self.server_supports_pagination(),
+            self.max_parametrized_limit,
+        )?;
 
         // check if we already have this query prepared
         trace!("select::access view");
@@ -1410,11 +1453,19 @@ impl NoriaConnector {
         })
     }
 
+    /// Execute a select statement against ReadySet.
+    ///
+    /// If a `ticket` is given, the read only returns results that include the writes described by
+    /// the ticket. If read-your-writes consistency is enabled and ReadySet doesn't replicate those
+    /// writes within the read-your-writes timeout, either returns
+    /// [`ReadySetError::ReplicationOffsetNotReached`] or, if `stale_on_timeout` is true, results
+    /// that may not include the writes.
     #[instrument(level = "debug", skip(self, event))]
     pub(crate) async fn execute_select(
         &mut self,
         ctx: ExecuteSelectContext<'_>,
         ticket: Option<Timestamp>,
+        stale_on_timeout: bool,
         event: &mut readyset_client_metrics::QueryExecutionEvent,
     ) -> ReadySetResult<QueryResult<'_>> {
         let (qname, statement, processed_query_params, params) = match ctx {
@@ -1443,8 +1494,11 @@ impl NoriaConnector {
                 create_if_missing,
             } => {
                 verify_no_placeholders(&mut statement, query)?;
-                let processed_query_params =
-                    rewrite::process_query(&mut statement, self.server_supports_pagination())?;
+                let processed_query_params = rewrite::process_query(
+                    &mut statement,
+                    self.server_supports_pagination(),
+                    self.max_parametrized_limit,
+                )?;
                 let name = self.get_view(&statement, false, create_if_missing).await?;
                 (
                     Cow::Owned(name),
@@ -1462,18 +1516,62 @@ impl NoriaConnector {
             .get_noria_view(&qname, view_failed)
             .await?;
 
-        let res = do_read(
-            getter,
-            processed_query_params.as_ref(),
-            params,
-            statement.as_ref(),
-            ticket,
-            self.read_behavior,
-            self.read_request_handler.as_mut(),
-            event,
-            self.dialect,
-        )
-        .await;
+        let res = match (ticket, self.read_your_writes_timeout) {
+            (Some(ticket), Some(timeout)) => {
+                let read = do_read(
+                    &mut *getter,
+                    processed_query_params.as_ref(),
+                    params,
+                    statement.as_ref(),
+                    Some(ticket.clone()),
+                    self.read_behavior,
+                    self.read_request_handler.as_mut(),
+                    event,
+                    self.dialect,
+                );
+                match tokio::time::timeout(timeout, read).await {
+                    // Results are made owned so that `getter` can be used again if the read times
+                    // out
+                    Ok(res) => res.map(QueryResult::into_owned),
+                    Err(_) if stale_on_timeout => {
+                        warn!(
+                            ?ticket,
+                            "Timed out waiting for writes to be replicated, results may be stale"
+                        );
+                        do_read(
+                            getter,
+                            processed_query_params.as_ref(),
+                            params,
+                            statement.as_ref(),
+                            None,
+                            self.read_behavior,
+                            self.read_request_handler.as_mut(),
+                            event,
+                            self.dialect,
+                        )
+                        .await
+                    }
+                    Err(_) => Err(ReadySetError::ReplicationOffsetNotReached(format!(
+                        "timed out after {:?} waiting for {:?}",
+                        timeout, ticket
+                    ))),
+                }
+            }
+            (ticket, _) => {
+                do_read(
+                    getter,
+                    processed_query_params.as_ref(),
+                    params,
+                    statement.as_ref(),
+                    ticket,
+                    self.read_behavior,
+                    self.read_request_handler.as_mut(),
+                    event,
+                    self.dialect,
+                )
+                .await
+            }
+        };
 
         if let Err(e) = res.as_ref() {
             if e.is_networking_related() || e.caused_by_view_destroyed() {
@@ -1532,6 +1630,42 @@ fn verify_no_placeholders(statement: &mut SelectStatement, query: &str) -> Ready
     }
 }
 
+/// Context for lowering expressions which are evaluated against the rows returned by a reader,
+/// resolving columns by name in the reader's projected schema
+#[derive(Clone)]
+struct ReaderLowerContext<'a> {
+    schema: &'a [ColumnSchema],
+}
+
+impl<'a> LowerContext for ReaderLowerContext<'a> {
+    fn resolve_column(&self, col: nom_sql::Column) -> ReadySetResult<(usize, DfType)> {
+        // Columns in the reader's schema are named after the view, so qualified columns are
+        // matched against the base table they come from
+        let mut candidates = self.schema.iter().enumerate().filter(|(_, c)| {
+            c.column.name == col.name
+                && match (&col.table, &c.base) {
+                    (Some(table), Some(base)) => {
+                        base.column == col.name
+                            && base.table.name == table.name
+                            && (table.schema.is_none() || base.table.schema == table.schema)
+                    }
+                    _ => true,
+                }
+        });
+        let (index, column) = candidates
+            .next()
+            .ok_or_else(|| ReadySetError::NoSuchColumn(col.name.to_string()))?;
+        if candidates.next().is_some() {
+            unsupported!("Column reference {} is ambiguous", col);
+        }
+        Ok((index, column.column_type.clone()))
+    }
+
+    fn resolve_type(&self, _ty: Relation) -> Option<DfType> {
+        None
+    }
+}
+
 /// Build a [`ViewQuery`] for performing a lookup of the given `q` with the given `raw_keys`,
 /// provided `getter_schema` and `key_map` from the [`View`] itself.
 #[allow(clippy::too_many_arguments)]
@@ -1547,7 +1681,17 @@ fn build_view_query(
 ) -> ReadySetResult<ViewQuery> {
     let projected_schema = getter_schema.schema(SchemaType::ProjectedSchema);
 
-    let (limit, offset) = processed_query_params.limit_offset_params(params)?;
+    // Readers for queries with range parameters aren't keyed on the page number, so we apply the
+    // OFFSET (along with the LIMIT) to the results of the lookup instead
+    let (limit, offset) = if q.offset.is_some()
+        && !key_map
+            .iter()
+            .any(|(placeholder, _)| matches!(placeholder, ViewPlaceholder::PageNumber { .. }))
+    {
+        processed_query_params.post_lookup_limit_offset(params)?
+    } else {
+        processed_query_params.limit_offset_params(params)?
+    };
 
     let mut raw_keys = processed_query_params.make_keys(params)?;
 
@@ -1608,7 +1752,49 @@ fn build_view_query(
         binops.remove(filter_op_idx);
     }
 
-    let keys = if raw_keys.is_empty() {
+    let keys = if let Some(predicate) = utils::get_full_text_predicate(q) {
+        // Full-text search predicates look up a single token of the search query in the reader's
+        // full-text index, then filter the rows returned by that lookup with the full query
+        let (Some(key), [(ViewPlaceholder::OneToOne(1), key_column_idx)]) =
+            (raw_keys.first(), key_map) else {
+            unsupported!("Full-text search can't be combined with other query parameters")
+        };
+        if raw_keys.len() != 1 || key.len() != 1 {
+            unsupported!("Full-text search can't be combined with other query parameters");
+        }
+
+        // no from_ty since the key value is a literal
+        let query = match key[0].coerce_to(&DfType::DEFAULT_TEXT, &DfType::Unknown)? {
+            DfValue::None => FullTextQuery::default(),
+            text => FullTextQuery::parse(<&str>::try_from(&text)?, predicate.syntax)?,
+        };
+        // A query with no terms never matches anything, and tokens are never empty, so looking up
+        // the empty string returns no rows
+        let token = if query.is_empty() {
+            ""
+        } else {
+            query.lookup_token().ok_or_else(|| {
+                unsupported_err!(
+                    "Full-text search queries must contain a term which is present in every \
+                     result"
+                )
+            })?
+        };
+        let key = vec1![DfValue::from(token)];
+
+        filters.push(DfExpr::Call {
+            func: Box::new(BuiltinFunction::FullTextMatch {
+                document: DfExpr::Column {
+                    index: *key_column_idx,
+                    ty: key_types[0].clone(),
+                },
+                query,
+            }),
+            ty: DfType::Bool,
+        });
+
+        vec![KeyComparison::Equal(key)]
+    } else if raw_keys.is_empty() {
         bogo
     } else {
         let mut unique_binops = binops.iter().map(|(_, b)| *b).unique();
@@ -1758,6 +1944,23 @@ fn build_view_query(
             .collect::<ReadySetResult<Vec<_>>>()?
     };
 
+    // Reads are only ever served for sessions whose time zone is UTC, since setting the time zone
+    // to anything else is unsupported (see `QueryHandler::handle_set_statement`), so that's the
+    // time zone the current time is evaluated in
+    if let Some(conditions) =
+        processed_query_params.time_dependent_conditions(Utc::now().into(), dialect)
+    {
+        // Conditions on the current time are evaluated against the rows returned by the lookup,
+        // with the current time at the moment of the read substituted in
+        filters.push(DfExpr::lower(
+            conditions,
+            dialect,
+            ReaderLowerContext {
+                schema: projected_schema,
+            },
+        )?);
+    }
+
     trace!(?keys, ?filters, "Built view query");
 
     Ok(ViewQuery {
@@ -2005,7 +2208,7 @@ mod tests {
             };
 
             let mut q = parse_select_statement(query);
-            let pp = rewrite::process_query(&mut q, true).unwrap();
+            let pp = rewrite::process_query(&mut q, true, None).unwrap();
             build_view_query(
                 &*SCHEMA,
                 key_map,
@@ -2227,6 +2430,69 @@ mod tests {
                 .into()]
             );
         }
+
+        #[test]
+        fn paginated_range() {
+            let query = make_build_query(
+                "SELECT t.x FROM t WHERE t.x > $1 ORDER BY t.x ASC LIMIT 3 OFFSET $2",
+                &[(ViewPlaceholder::OneToOne(1), 0)],
+                &[DfValue::from(1), DfValue::from(6)],
+                Dialect::MySQL,
+            );
+
+            assert_eq!(query.key_comparisons.len(), 1);
+            assert_eq!(query.limit, Some(3));
+            assert_eq!(query.offset, Some(6));
+        }
+    }
+
+    mod reader_lower_context {
+        use nom_sql::Column;
+        use readyset_client::ColumnBase;
+
+        use super::*;
+
+        fn id_column(table: &str, ty: DfType) -> ColumnSchema {
+            ColumnSchema {
+                column: Column {
+                    name: "id".into(),
+                    table: Some("q".into()),
+                },
+                column_type: ty,
+                base: Some(ColumnBase {
+                    table: table.into(),
+                    column: "id".into(),
+                    constraints: vec![],
+                }),
+            }
+        }
+
+        #[test]
+        fn resolves_qualified_columns_by_base_table() {
+            let schema = [
+                id_column("a", DfType::Int),
+                id_column("b", DfType::DEFAULT_TEXT),
+            ];
+            let context = ReaderLowerContext { schema: &schema };
+
+            assert_eq!(
+                context.resolve_column(Column::from("a.id")).unwrap(),
+                (0, DfType::Int)
+            );
+            assert_eq!(
+                context.resolve_column(Column::from("b.id")).unwrap(),
+                (1, DfType::DEFAULT_TEXT)
+            );
+            context.resolve_column(Column::from("c.id")).unwrap_err();
+        }
+
+        #[test]
+        fn rejects_ambiguous_columns() {
+            let schema = [id_column("a", DfType::Int), id_column("b", DfType::Int)];
+            let context = ReaderLowerContext { schema: &schema };
+
+            context.resolve_column(Column::from("id")).unwrap_err();
+        }
     }
 
     #[test]
@@ -0,0 +1,150 @@
+//! Tracking of the writes a connection proxies to the upstream database, to provide
+//! read-your-writes consistency for its reads from ReadySet.
+//!
+//! After a write outside of a transaction, the adapter records a RYW ticket ([`Timestamp`]) for
+//! the table that was written to, built from the GTID of the transaction the write committed in.
+//! Since the replicator propagates the GTID of each transaction it replicates through the
+//! dataflow graph, reads of that table which pass the ticket with their lookups only return
+//! results that include the write.
+//!
+//! The GTID isn't known for writes made by prepared statements or inside explicit transactions.
+//! Until a later write to the same table records a ticket (which also covers those earlier
+//! writes, since GTIDs are assigned in commit order), reads of such tables can't be served
+//! consistently from ReadySet.
+
+use std::collections::{HashMap, HashSet};
+
+use nom_sql::analysis::visit::Visitor;
+use nom_sql::{Relation, SelectStatement, SqlIdentifier};
+use readyset_client::consistency::Timestamp;
+use readyset_errors::{ReadySetError, ReadySetResult};
+
+use crate::utils::TablesVisitor;
+
+/// The writes a connection made to tables in the upstream database that its reads from ReadySet
+/// have to observe.
+///
+/// Tables are identified by name only, since reads can refer to tables without a schema. This can
+/// only make reads wait for more writes than they have to.
+#[derive(Debug, Default)]
+pub(crate) struct UpstreamWrites {
+    /// The RYW ticket for the writes made to each table
+    tickets: HashMap<SqlIdentifier, Timestamp>,
+    /// Tables written to without a known ticket since their last ticket was recorded
+    untracked: HashSet<SqlIdentifier>,
+}
+
+impl UpstreamWrites {
+    /// Record a write to `table` described by the given RYW ticket
+    pub(crate) fn record(&mut self, table: &Relation, ticket: &Timestamp) {
+        self.untracked.remove(&table.name);
+        let current = self.tickets.entry(table.name.clone()).or_default();
+        *current = Timestamp::join(current, ticket);
+    }
+
+    /// Record a write to `table` for which a RYW ticket isn't known
+    pub(crate) fn record_untracked(&mut self, table: &Relation) {
+        self.untracked.insert(table.name.clone());
+    }
+
+    /// Returns the RYW ticket that a read of `query` from ReadySet has to pass to observe the
+    /// writes made to the tables it reads from, or `None` if it doesn't read from any table that
+    /// was written to.
+    ///
+    /// Returns [`ReadySetError::ReplicationOffsetNotReached`] if the query reads from a table that
+    /// was written to without a known ticket.
+    pub(crate) fn ticket(&self, query: &SelectStatement) -> ReadySetResult<Option<Timestamp>> {
+        if self.tickets.is_empty() && self.untracked.is_empty() {
+            return Ok(None);
+        }
+
+        let mut visitor = TablesVisitor::default();
+        let Ok(_) = visitor.visit_select_statement(query);
+
+        let mut ticket: Option<Timestamp> = None;
+        for table in visitor.tables {
+            if self.untracked.contains(&table.name) {
+                return Err(ReadySetError::ReplicationOffsetNotReached(format!(
+                    "the replication position of the last write to {} is not known",
+                    table
+                )));
+            }
+            if let Some(table_ticket) = self.tickets.get(&table.name) {
+                ticket = Some(match ticket {
+                    Some(ticket) => Timestamp::join(&ticket, table_ticket),
+                    None => table_ticket.clone(),
+                });
+            }
+        }
+
+        Ok(ticket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_select_statement, Dialect};
+    use readyset_client::internal::LocalNodeIndex;
+
+    use super::*;
+
+    fn select(query: &str) -> SelectStatement {
+        parse_select_statement(Dialect::MySQL, query).unwrap()
+    }
+
+    fn ticket(table: u32, txid: u64) -> Timestamp {
+        let mut ticket = Timestamp::default();
+        ticket.map.insert(LocalNodeIndex::make(table), txid);
+        ticket
+    }
+
+    #[test]
+    fn ticket_for_tables_read() {
+        let mut writes = UpstreamWrites::default();
+        writes.record(&"t1".into(), &ticket(1, 5));
+        writes.record(&"t1".into(), &ticket(1, 3));
+        writes.record(&"t2".into(), &ticket(2, 7));
+
+        assert_eq!(
+            writes.ticket(&select("SELECT * FROM t1")).unwrap(),
+            Some(ticket(1, 5))
+        );
+        assert_eq!(
+            writes
+                .ticket(&select("SELECT * FROM t1 JOIN db.t2 ON t1.x = t2.x"))
+                .unwrap(),
+            Some(Timestamp::join(&ticket(1, 5), &ticket(2, 7)))
+        );
+        assert_eq!(writes.ticket(&select("SELECT * FROM t3")).unwrap(), None);
+    }
+
+    #[test]
+    fn ticket_for_subqueries() {
+        let mut writes = UpstreamWrites::default();
+        writes.record(&"t1".into(), &ticket(1, 5));
+
+        assert_eq!(
+            writes
+                .ticket(&select("SELECT * FROM t2 WHERE x IN (SELECT x FROM t1)"))
+                .unwrap(),
+            Some(ticket(1, 5))
+        );
+    }
+
+    #[test]
+    fn untracked_writes() {
+        let mut writes = UpstreamWrites::default();
+        writes.record(&"t1".into(), &ticket(1, 5));
+        writes.record_untracked(&"t1".into());
+
+        writes.ticket(&select("SELECT * FROM t1")).unwrap_err();
+        assert_eq!(writes.ticket(&select("SELECT * FROM t2")).unwrap(), None);
+
+        // A later write with a known ticket also covers the untracked write
+        writes.record(&"t1".into(), &ticket(1, 8));
+        assert_eq!(
+            writes.ticket(&select("SELECT * FROM t1")).unwrap(),
+            Some(ticket(1, 8))
+        );
+    }
+}
@@ -16,13 +16,18 @@ pub mod migration_handler;
 pub mod proxied_queries_reporter;
 mod query_handler;
 pub mod query_status_cache;
+pub mod query_status_persister;
+pub mod read_replicas;
 pub mod rewrite;
+pub mod routing_rules;
 pub mod upstream_database;
+pub mod upstream_pool;
 mod utils;
 pub mod views_synchronizer;
 
 pub use crate::backend::{Backend, BackendBuilder};
 pub use crate::query_handler::{QueryHandler, SetBehavior};
+pub use crate::query_status_persister::QueryStatusPersister;
 pub use crate::upstream_database::{
     UpstreamConfig, UpstreamDatabase, UpstreamDestination, UpstreamPrepare,
 };
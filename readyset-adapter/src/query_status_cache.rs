@@ -2,15 +2,27 @@
 //! knowledge about queries, currently the migration status of a query in
 //! ReadySet.
 use std::borrow::Borrow;
+use std::fs::{self, File};
 use std::hash::Hash;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
 use std::str::FromStr;
 use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use dashmap::DashMap;
 use launchpad::hash::hash;
+use nom_sql::{parse_select_statement, Dialect, SqlIdentifier};
 use readyset_client::query::*;
-use tracing::error;
+use readyset_client::ViewCreateRequest;
+use readyset_errors::ReadySetResult;
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+
+/// The version of the format that [`QueryStatusCache::persist`] writes query statuses in. This must
+/// be incremented whenever the on-disk representation changes, so that files written by other
+/// versions of the adapter are discarded rather than misinterpreted.
+const PERSISTED_FORMAT_VERSION: u32 = 1;
 
 /// A metadata cache for all queries that have been processed by this
 /// adapter. Thread-safe.
@@ -371,6 +383,174 @@ impl QueryStatusCache {
         let id = QueryId::new(u64::from_str_radix(id.strip_prefix("q_")?, 16).ok()?);
         self.ids.get(&id).map(|r| (*r.value()).clone())
     }
+
+    /// Writes the status of every query in the cache to the file at `path`, so that it can be
+    /// reloaded with [`QueryStatusCache::restore`] after the adapter restarts.
+    ///
+    /// Queries are written as SQL text, preceded by the version of the file format, so that the
+    /// file can be read by versions of the adapter whose query representation differs. The file is
+    /// written atomically, by writing to a temporary file alongside `path` and then renaming it
+    /// into place.
+    pub fn persist(&self, path: &Path) -> ReadySetResult<()> {
+        let now = Instant::now();
+        let persisted = PersistedQueryStatuses {
+            queries: self
+                .statuses
+                .iter()
+                .map(|r| PersistedQueryStatus {
+                    query: PersistedQuery::from(r.key()),
+                    migration_state: r.migration_state,
+                    execution_info: r
+                        .execution_info
+                        .as_ref()
+                        .map(|info| PersistedExecutionInfo {
+                            state: info.state.clone(),
+                            time_since_transition: now
+                                .saturating_duration_since(info.last_transition_time),
+                        }),
+                    always: r.always,
+                })
+                .collect(),
+        };
+
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        bincode::serialize_into(&mut writer, &PERSISTED_FORMAT_VERSION)?;
+        bincode::serialize_into(&mut writer, &persisted)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Loads query statuses previously written by [`QueryStatusCache::persist`] from the file at
+    /// `path` into the cache, returning the number of queries restored. Queries that are already
+    /// present in the cache are left untouched.
+    ///
+    /// Queries that were previously migrated are restored as [`MigrationState::Pending`], since
+    /// their views may have been dropped while the adapter was down - they'll be marked as
+    /// successful again once we find out that their views still exist.
+    ///
+    /// If the file was written in a different format version, it is discarded with a warning and
+    /// no queries are restored. Queries that can no longer be parsed are skipped.
+    pub fn restore(&self, path: &Path) -> ReadySetResult<usize> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let version: u32 = bincode::deserialize_from(&mut reader)?;
+        if version != PERSISTED_FORMAT_VERSION {
+            warn!(
+                path = %path.display(),
+                version,
+                expected_version = PERSISTED_FORMAT_VERSION,
+                "Discarding persisted query statuses written in an unsupported format version"
+            );
+            return Ok(0);
+        }
+        let persisted: PersistedQueryStatuses = bincode::deserialize_from(reader)?;
+
+        let now = Instant::now();
+        let mut restored = 0;
+        for PersistedQueryStatus {
+            query,
+            migration_state,
+            execution_info,
+            always,
+        } in persisted.queries
+        {
+            let query = match query.parse() {
+                Ok(query) => query,
+                Err(error) => {
+                    warn!(%error, "Skipping persisted query that could not be parsed");
+                    continue;
+                }
+            };
+            if self.statuses.contains_key(&query) {
+                continue;
+            }
+
+            let migration_state = match migration_state {
+                MigrationState::Successful => MigrationState::Pending,
+                state => state,
+            };
+            let execution_info = execution_info.map(|info| ExecutionInfo {
+                state: info.state,
+                last_transition_time: now.checked_sub(info.time_since_transition).unwrap_or(now),
+            });
+
+            self.insert_with_status(
+                query,
+                QueryStatus {
+                    migration_state,
+                    execution_info,
+                    always,
+                },
+            );
+            restored += 1;
+        }
+
+        Ok(restored)
+    }
+}
+
+/// The on-disk representation of the contents of a [`QueryStatusCache`]
+#[derive(Serialize, Deserialize)]
+struct PersistedQueryStatuses {
+    queries: Vec<PersistedQueryStatus>,
+}
+
+/// The on-disk representation of a single query's [`QueryStatus`]
+#[derive(Serialize, Deserialize)]
+struct PersistedQueryStatus {
+    query: PersistedQuery,
+    migration_state: MigrationState,
+    execution_info: Option<PersistedExecutionInfo>,
+    always: bool,
+}
+
+/// The on-disk representation of a [`Query`], as SQL text that is parsed again when restored
+#[derive(Serialize, Deserialize)]
+enum PersistedQuery {
+    Parsed {
+        statement: String,
+        schema_search_path: Vec<SqlIdentifier>,
+    },
+    ParseFailed(String),
+}
+
+impl From<&Query> for PersistedQuery {
+    fn from(query: &Query) -> Self {
+        match query {
+            Query::Parsed(view_request) => PersistedQuery::Parsed {
+                statement: view_request.statement.to_string(),
+                schema_search_path: view_request.schema_search_path.clone(),
+            },
+            Query::ParseFailed(query) => PersistedQuery::ParseFailed((**query).clone()),
+        }
+    }
+}
+
+impl PersistedQuery {
+    fn parse(self) -> Result<Query, String> {
+        match self {
+            PersistedQuery::Parsed {
+                statement,
+                schema_search_path,
+            } => {
+                // Statements are displayed in the MySQL dialect, regardless of the dialect they
+                // were originally parsed in
+                let statement = parse_select_statement(Dialect::MySQL, statement)?;
+                Ok(ViewCreateRequest::new(statement, schema_search_path).into())
+            }
+            PersistedQuery::ParseFailed(query) => Ok(query.into()),
+        }
+    }
+}
+
+/// The on-disk representation of an [`ExecutionInfo`]. Since [`Instant`]s are only meaningful
+/// within a single process, we store the time elapsed since the last transition instead.
+#[derive(Serialize, Deserialize)]
+struct PersistedExecutionInfo {
+    state: ExecutionState,
+    time_since_transition: Duration,
 }
 
 /// MigrationStyle is used to communicate which style of managing migrations we have configured.
@@ -563,4 +743,84 @@ mod tests {
         cache.clear();
         assert_eq!(cache.allow_list().len(), 0);
     }
+
+    #[test]
+    fn persist_and_restore() {
+        let path = std::env::temp_dir().join(format!(
+            "readyset-query-status-cache-{}",
+            std::process::id()
+        ));
+
+        let cache = QueryStatusCache::new();
+        let pending = ViewCreateRequest::new(select_statement("SELECT * FROM t1").unwrap(), vec![]);
+        let successful =
+            ViewCreateRequest::new(select_statement("SELECT * FROM t2").unwrap(), vec![]);
+        let dry_run_succeeded =
+            ViewCreateRequest::new(select_statement("SELECT * FROM t3").unwrap(), vec![]);
+        let unparsed = "SELECT * FROM t4 FOR UPDATE SKIP LOCKED".to_string();
+
+        cache.insert(pending.clone());
+        cache.update_query_migration_state(&successful, MigrationState::Successful);
+        cache.update_query_migration_state(&dry_run_succeeded, MigrationState::DryRunSucceeded);
+        cache.insert(unparsed.clone());
+        cache.execute_failed(&unparsed.clone().into());
+
+        cache.persist(&path).unwrap();
+
+        let restored = QueryStatusCache::new();
+        assert_eq!(restored.restore(&path).unwrap(), 4);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            restored.query_migration_state(&pending).1,
+            MigrationState::Pending
+        );
+        assert_eq!(
+            restored.query_migration_state(&successful).1,
+            MigrationState::Pending
+        );
+        assert_eq!(
+            restored.query_migration_state(&dry_run_succeeded).1,
+            MigrationState::DryRunSucceeded
+        );
+        assert_eq!(restored.deny_list().len(), 1);
+        assert_eq!(
+            restored
+                .query_status(&unparsed)
+                .execution_info
+                .map(|info| info.state),
+            Some(ExecutionState::Failed)
+        );
+        assert_eq!(
+            restored.query(&QueryId::new(hash(&successful)).to_string()),
+            Some(successful.into())
+        );
+    }
+
+    #[test]
+    fn restore_discards_other_format_versions() {
+        let path = std::env::temp_dir().join(format!(
+            "readyset-query-status-cache-version-{}",
+            std::process::id()
+        ));
+
+        let cache = QueryStatusCache::new();
+        let query = ViewCreateRequest::new(select_statement("SELECT * FROM t1").unwrap(), vec![]);
+        cache.insert(query.clone());
+        cache.persist(&path).unwrap();
+
+        // Overwrite the format version at the start of the file
+        let mut contents = std::fs::read(&path).unwrap();
+        let other_version = bincode::serialize(&(PERSISTED_FORMAT_VERSION + 1)).unwrap();
+        contents[..other_version.len()].copy_from_slice(&other_version);
+        std::fs::write(&path, contents).unwrap();
+
+        let restored = QueryStatusCache::new();
+        assert_eq!(restored.restore(&path).unwrap(), 0);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            restored.query(&QueryId::new(hash(&query)).to_string()),
+            None
+        );
+    }
 }
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use tokio::select;
+use tracing::{debug, info, instrument, warn};
+
+use crate::query_status_cache::QueryStatusCache;
+
+/// Periodically writes the contents of the [`QueryStatusCache`] to disk, so that the adapter's
+/// knowledge about queries survives restarts.
+pub struct QueryStatusPersister {
+    /// The query status cache to persist
+    query_status_cache: &'static QueryStatusCache,
+    /// The file to persist query statuses to
+    path: PathBuf,
+    /// The interval between subsequent writes of the query status cache
+    persist_interval: std::time::Duration,
+    /// Receiver to return the shutdown signal on
+    shutdown_recv: tokio::sync::broadcast::Receiver<()>,
+}
+
+impl QueryStatusPersister {
+    pub fn new(
+        query_status_cache: &'static QueryStatusCache,
+        path: PathBuf,
+        persist_interval: std::time::Duration,
+        shutdown_recv: tokio::sync::broadcast::Receiver<()>,
+    ) -> Self {
+        QueryStatusPersister {
+            query_status_cache,
+            path,
+            persist_interval,
+            shutdown_recv,
+        }
+    }
+
+    #[instrument(level = "info", name = "query_status_persister", skip(self))]
+    pub async fn run(&mut self) {
+        let mut interval = tokio::time::interval(self.persist_interval);
+        // The first tick completes immediately, and there's nothing new to persist yet
+        interval.tick().await;
+        loop {
+            select! {
+                _ = interval.tick() => self.persist().await,
+                _ = self.shutdown_recv.recv() => {
+                    info!("Query status persister shutting down after shut down signal received");
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn persist(&self) {
+        debug!(path = %self.path.display(), "Persisting query statuses");
+        let query_status_cache = self.query_status_cache;
+        let path = self.path.clone();
+        match tokio::task::spawn_blocking(move || query_status_cache.persist(&path)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => warn!(%error, "Failed to persist query statuses"),
+            Err(error) => warn!(%error, "Query status persistence task failed"),
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Routing of reads proxied to the upstream database to read replicas of the upstream database.
+//!
+//! When read replicas are configured, reads that a connection would otherwise proxy to the primary
+//! upstream database (because they're not cached, or not supported by ReadySet) are sent to one of
+//! the replicas instead, as long as the connection isn't in a transaction and hasn't written to
+//! the primary. Once a connection writes to the primary, all of its subsequent reads are proxied
+//! to the primary, so that it always observes its own writes.
+//!
+//! Each connection picks a healthy replica round-robin the first time it proxies a read, and sticks
+//! to it until the replica becomes unhealthy. Replicas are marked unhealthy when connecting to them
+//! fails or a query against them fails with a fatal (connection) error, and their health is
+//! periodically re-checked by a [`ReadReplicaHealthChecker`].
+//!
+//! Only ad-hoc queries are sent to read replicas - prepared statements are always executed against
+//! the primary, since they're prepared on the connection to the primary, and a connection may
+//! switch between replicas (or stop reading from them) between preparing and executing a
+//! statement.
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use launchpad::redacted::RedactedString;
+use tokio::select;
+use tokio::time::timeout;
+use tracing::{debug, info, instrument, warn};
+
+use crate::upstream_database::{UpstreamConfig, UpstreamDatabase};
+
+/// Timeout for connecting to a read replica, and for running health checks against it
+const READ_REPLICA_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A single read replica of the upstream database
+#[derive(Debug)]
+pub struct ReadReplica {
+    /// The configuration used to connect to the replica
+    config: UpstreamConfig,
+    /// Whether the replica passed its last health check, and hasn't failed a query since
+    healthy: AtomicBool,
+}
+
+impl ReadReplica {
+    /// The configuration used to connect to the replica
+    pub fn config(&self) -> &UpstreamConfig {
+        &self.config
+    }
+
+    /// Returns true if the replica is currently considered healthy
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// Marks the replica as healthy or unhealthy, returning whether it was healthy before
+    pub fn set_healthy(&self, healthy: bool) -> bool {
+        self.healthy.swap(healthy, Ordering::Relaxed)
+    }
+}
+
+/// The set of read replicas configured for an adapter, shared between all connections. Thread-safe.
+#[derive(Debug, Default)]
+pub struct ReadReplicas {
+    replicas: Vec<ReadReplica>,
+    /// Index used to pick replicas round-robin
+    next: AtomicUsize,
+}
+
+impl ReadReplicas {
+    /// Constructs a new set of read replicas with the given URLs. All other configuration used to
+    /// connect to the replicas (such as SSL settings) is taken from `upstream_config`.
+    ///
+    /// All replicas are considered healthy until a query or health check against them fails.
+    pub fn new<I>(upstream_config: &UpstreamConfig, urls: I) -> Self
+    where
+        I: IntoIterator<Item = RedactedString>,
+    {
+        let replicas = urls
+            .into_iter()
+            .map(|url| ReadReplica {
+                config: UpstreamConfig {
+                    upstream_db_url: Some(url),
+                    ..upstream_config.clone()
+                },
+                healthy: AtomicBool::new(true),
+            })
+            .collect();
+
+        Self {
+            replicas,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns true if no read replicas are configured
+    pub fn is_empty(&self) -> bool {
+        self.replicas.is_empty()
+    }
+
+    /// Returns all read replicas, in the order they were configured in
+    pub fn replicas(&self) -> &[ReadReplica] {
+        &self.replicas
+    }
+
+    /// Returns the read replica with the given index
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds
+    pub fn get(&self, index: usize) -> &ReadReplica {
+        &self.replicas[index]
+    }
+
+    /// Picks a healthy read replica round-robin, returning its index, or returns `None` if no
+    /// replicas are healthy
+    pub fn pick(&self) -> Option<usize> {
+        let len = self.replicas.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|i| start.wrapping_add(i) % len)
+            .find(|&index| self.replicas[index].is_healthy())
+    }
+
+    /// Marks the read replica with the given index as unhealthy, so that it isn't picked again
+    /// until it passes a health check
+    pub fn mark_unhealthy(&self, index: usize) {
+        if self.get(index).set_healthy(false) {
+            warn!(replica = index, "Marking read replica unhealthy");
+        }
+    }
+}
+
+/// A connection's connection to one of the read replicas in a [`ReadReplicas`]
+pub(crate) struct ReadReplicaConnection<DB> {
+    pub(crate) replicas: Arc<ReadReplicas>,
+    /// The index of the replica within [`Self::replicas`]
+    pub(crate) index: usize,
+    pub(crate) connection: DB,
+    /// The number of the backend's session statements (`SET` and `USE` statements) that have been
+    /// run against this connection
+    pub(crate) session_statements: usize,
+}
+
+/// Periodically checks the health of all [`ReadReplicas`], by connecting to them and running a
+/// trivial query.
+pub struct ReadReplicaHealthChecker<DB> {
+    /// The read replicas to check
+    read_replicas: Arc<ReadReplicas>,
+    /// The interval between subsequent health checks
+    check_interval: Duration,
+    /// Receiver to return the shutdown signal on
+    shutdown_recv: tokio::sync::broadcast::Receiver<()>,
+    _upstream: PhantomData<fn() -> DB>,
+}
+
+impl<DB> ReadReplicaHealthChecker<DB>
+where
+    DB: UpstreamDatabase,
+{
+    pub fn new(
+        read_replicas: Arc<ReadReplicas>,
+        check_interval: Duration,
+        shutdown_recv: tokio::sync::broadcast::Receiver<()>,
+    ) -> Self {
+        ReadReplicaHealthChecker {
+            read_replicas,
+            check_interval,
+            shutdown_recv,
+            _upstream: PhantomData,
+        }
+    }
+
+    #[instrument(level = "info", name = "read_replica_health_checker", skip(self))]
+    pub async fn run(&mut self) {
+        let mut interval = tokio::time::interval(self.check_interval);
+        loop {
+            select! {
+                _ = interval.tick() => self.check().await,
+                _ = self.shutdown_recv.recv() => {
+                    info!("Read replica health checker shutting down after shut down signal \
+                           received");
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn check(&self) {
+        for (index, replica) in self.read_replicas.replicas().iter().enumerate() {
+            let res = timeout(READ_REPLICA_TIMEOUT, async {
+                let mut connection = DB::connect(replica.config().clone(), None).await?;
+                connection.query("SELECT 1").await?;
+                Ok::<_, DB::Error>(())
+            })
+            .await;
+
+            let healthy = match res {
+                Ok(Ok(())) => true,
+                Ok(Err(error)) => {
+                    debug!(replica = index, %error, "Read replica health check failed");
+                    false
+                }
+                Err(_) => {
+                    debug!(replica = index, "Read replica health check timed out");
+                    false
+                }
+            };
+
+            match (replica.set_healthy(healthy), healthy) {
+                (false, true) => info!(replica = index, "Read replica is healthy again"),
+                (true, false) => warn!(replica = index, "Read replica failed health check"),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Connects to the read replica with the given index, timing out after a fixed interval
+pub(crate) async fn connect<DB>(replicas: &ReadReplicas, index: usize) -> Option<DB>
+where
+    DB: UpstreamDatabase,
+{
+    let config = replicas.get(index).config().clone();
+    match timeout(READ_REPLICA_TIMEOUT, DB::connect(config, None)).await {
+        Ok(Ok(connection)) => Some(connection),
+        Ok(Err(error)) => {
+            warn!(replica = index, %error, "Failed to connect to read replica");
+            None
+        }
+        Err(_) => {
+            warn!(replica = index, "Timed out connecting to read replica");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replicas(n: usize) -> ReadReplicas {
+        ReadReplicas::new(
+            &UpstreamConfig::default(),
+            (0..n).map(|i| RedactedString::from(format!("mysql://replica-{i}/db"))),
+        )
+    }
+
+    #[test]
+    fn pick_round_robin() {
+        let replicas = replicas(3);
+        let picked = (0..6).map(|_| replicas.pick().unwrap()).collect::<Vec<_>>();
+        assert_eq!(picked, vec![0, 1, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    fn pick_skips_unhealthy() {
+        let replicas = replicas(3);
+        replicas.get(1).set_healthy(false);
+        let picked = (0..4).map(|_| replicas.pick().unwrap()).collect::<Vec<_>>();
+        assert!(!picked.contains(&1));
+
+        replicas.get(0).set_healthy(false);
+        replicas.get(2).set_healthy(false);
+        assert_eq!(replicas.pick(), None);
+
+        replicas.get(1).set_healthy(true);
+        assert_eq!(replicas.pick(), Some(1));
+    }
+
+    #[test]
+    fn uses_upstream_config() {
+        let upstream_config = UpstreamConfig {
+            disable_upstream_ssl_verification: true,
+            ..UpstreamConfig::from_url("mysql://primary/db")
+        };
+        let replicas = ReadReplicas::new(
+            &upstream_config,
+            vec![RedactedString::from("mysql://replica/db".to_owned())],
+        );
+        let config = replicas.get(0).config();
+        assert_eq!(
+            config.upstream_db_url.as_deref().map(String::as_str),
+            Some("mysql://replica/db")
+        );
+        assert!(config.disable_upstream_ssl_verification);
+    }
+
+    #[test]
+    fn empty() {
+        let replicas = ReadReplicas::default();
+        assert!(replicas.is_empty());
+        assert_eq!(replicas.pick(), None);
+    }
+}
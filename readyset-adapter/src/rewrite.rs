@@ -4,10 +4,17 @@ use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
 use std::{iter, mem};
 
+use chrono::{DateTime, FixedOffset};
+use dataflow_expression::full_text::{full_text_predicate, full_text_query_mut};
 use itertools::{Either, Itertools};
 use nom_sql::analysis::visit_mut::{self, VisitorMut};
-use nom_sql::{BinaryOperator, Expr, InValue, ItemPlaceholder, Literal, SelectStatement};
-use readyset_data::{DfType, DfValue};
+use nom_sql::analysis::ReferredColumns;
+use nom_sql::{
+    BinaryOperator, Column, Expr, FieldDefinitionExpr, FunctionExpr, InValue, ItemPlaceholder,
+    Literal, SelectStatement, SqlType,
+};
+use readyset_data::dialect::SqlEngine;
+use readyset_data::{DfType, DfValue, Dialect};
 use readyset_errors::{invalid_err, unsupported, ReadySetError, ReadySetResult};
 use tracing::trace;
 
@@ -24,6 +31,9 @@ pub struct ProcessedQueryParams {
     rewritten_in_conditions: Vec<RewrittenIn>,
     auto_parameters: Vec<(usize, Literal)>,
     pagination_parameters: AdapterPaginationParams,
+    /// Conditions which were removed from the `WHERE` clause of the query because they depend on
+    /// the current time, and so must be evaluated against the results of each lookup instead
+    time_dependent_conditions: Option<Expr>,
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +42,9 @@ struct AdapterPaginationParams {
     limit: Option<Literal>,
     offset: Option<Literal>,
     force_paginate_in_adapter: bool,
+    /// If the query had a parameterized `LIMIT` that was replaced with this maximum value in the
+    /// cached query, the actual limit is bound at execute time and applied after the lookup
+    max_parametrized_limit: Option<usize>,
 }
 
 /// This method checks if readyset-server is configured to handle LIMIT/OFFSET queries at the
@@ -56,6 +69,25 @@ fn use_fallback_pagination(
     true
 }
 
+/// Parameterized `LIMIT`s without an `OFFSET` can be cached in the dataflow graph with the limit
+/// replaced by a configured maximum, so that a single cache can serve all page sizes up to that
+/// maximum. Returns the maximum to use if that's the case for this query.
+fn parametrized_limit_max(
+    server_supports_pagination: bool,
+    max_parametrized_limit: Option<usize>,
+    limit: &Option<Literal>,
+    offset: &Option<Literal>,
+) -> Option<usize> {
+    if server_supports_pagination
+        && matches!(limit, Some(Literal::Placeholder(_)))
+        && offset.is_none()
+    {
+        max_parametrized_limit
+    } else {
+        None
+    }
+}
+
 /// This rewrite pass accomplishes the following:
 /// - Remaps dollar sign placeholders so that they appear in order
 /// - Replaces literals with placeholders when they can be used as lookup indices in the noria
@@ -63,17 +95,40 @@ fn use_fallback_pagination(
 ///   therefore cannot guarantee that the rewritten query is free of user PII.
 /// - Collapses 'WHERE <expr> IN ?, ... ?' to 'WHERE <expr> = ?'
 /// - Removes `OFFSET ?` if there isn't a `LIMIT`
+/// - Replaces `LIMIT ?` with `LIMIT <max_parametrized_limit>` if the server supports pagination and
+///   the query has no `OFFSET`
+/// - Removes conditions in the `WHERE` clause that depend on the current time (eg `expires_at >
+///   NOW()`), so they can be evaluated after the lookup instead
+/// - Replaces the search string of a full-text search predicate with a placeholder, instead of any
+///   other literals
 pub fn process_query(
     query: &mut SelectStatement,
     server_supports_pagination: bool,
+    max_parametrized_limit: Option<usize>,
 ) -> ReadySetResult<ProcessedQueryParams> {
     let reordered_placeholders = reorder_numbered_placeholders(query);
 
+    let time_dependent_conditions = extract_time_dependent_conditions(query)?;
+    // Conditions evaluated after the lookup (including full-text search predicates) must be
+    // applied before the LIMIT and OFFSET, so those have to be applied after the lookup as well
+    let server_supports_pagination = server_supports_pagination
+        && time_dependent_conditions.is_none()
+        && !has_full_text_predicate(query);
+
     let limit = query.limit.take();
     let offset = query.offset.take();
-    let force_paginate_in_adapter =
-        use_fallback_pagination(server_supports_pagination, &limit, &offset);
-    if !force_paginate_in_adapter {
+    let max_parametrized_limit = parametrized_limit_max(
+        server_supports_pagination,
+        max_parametrized_limit,
+        &limit,
+        &offset,
+    );
+    let force_paginate_in_adapter = max_parametrized_limit.is_none()
+        && use_fallback_pagination(server_supports_pagination, &limit, &offset);
+    if let Some(max) = max_parametrized_limit {
+        // Cache up to the maximum number of rows, the actual limit is applied after the lookup
+        query.limit = Some(Literal::UnsignedInteger(max as u64));
+    } else if !force_paginate_in_adapter {
         // If adapter pagination shouldn't be used reinstate the limit and offset clauses
         query.limit.clone_from(&limit);
         query.offset.clone_from(&offset);
@@ -90,16 +145,49 @@ pub fn process_query(
             limit,
             offset,
             force_paginate_in_adapter,
+            max_parametrized_limit,
         },
+        time_dependent_conditions,
     })
 }
 
 impl ProcessedQueryParams {
-    /// If the query has values for OFFSET or LIMIT, get their values, returning a tuple of `limit,
-    /// offset`
+    /// If the query has values for OFFSET or LIMIT that need to be applied after the lookup, get
+    /// their values, returning a tuple of `limit, offset`
     pub(crate) fn limit_offset_params(
         &self,
         params: &[DfValue],
+    ) -> ReadySetResult<(Option<usize>, Option<usize>)> {
+        let AdapterPaginationParams {
+            force_paginate_in_adapter,
+            max_parametrized_limit,
+            ..
+        } = &self.pagination_parameters;
+
+        let (limit, offset) = self.post_lookup_limit_offset(params)?;
+
+        if let (Some(max), Some(limit)) = (max_parametrized_limit, limit) {
+            // Not an unsupported error, since executions with a smaller LIMIT can still be served
+            // by ReadySet
+            if limit > *max {
+                return Err(ReadySetError::ParametrizedLimitExceeded { limit, max: *max });
+            }
+            Ok((Some(limit), offset))
+        } else if *force_paginate_in_adapter || limit == Some(0) {
+            Ok((limit, offset))
+        } else {
+            Ok((None, None))
+        }
+    }
+
+    /// Get the values of the LIMIT and OFFSET of the query, regardless of whether they're handled
+    /// by the dataflow graph, returning a tuple of `limit, offset`.
+    ///
+    /// This is used for queries whose readers aren't keyed on a page number, such as queries with
+    /// range parameters, for which the offset is applied after the lookup.
+    pub(crate) fn post_lookup_limit_offset(
+        &self,
+        params: &[DfValue],
     ) -> ReadySetResult<(Option<usize>, Option<usize>)> {
         let mut params_iter = self
             .reordered_placeholders
@@ -139,11 +227,7 @@ impl ProcessedQueryParams {
             }
         };
 
-        let AdapterPaginationParams {
-            limit,
-            offset,
-            force_paginate_in_adapter,
-        } = &self.pagination_parameters;
+        let AdapterPaginationParams { limit, offset, .. } = &self.pagination_parameters;
 
         // TODO(vlad): actually limit and offset can get in reverse order in MySQL if
         // LIMIT $offset, $limit syntax is used, we need to propagate that info via
@@ -151,11 +235,23 @@ impl ProcessedQueryParams {
         let offset = offset.as_ref().map(&mut get_param).transpose()?;
         let limit = limit.as_ref().map(&mut get_param).transpose()?;
 
-        if *force_paginate_in_adapter || limit == Some(0) {
-            Ok((limit, offset))
-        } else {
-            Ok((None, None))
-        }
+        Ok((limit, offset))
+    }
+
+    /// If any conditions were removed from the query because they depend on the current time,
+    /// returns those conditions combined with `AND`, with all references to the current date or
+    /// time replaced by the given value of `now`.
+    pub(crate) fn time_dependent_conditions(
+        &self,
+        now: DateTime<FixedOffset>,
+        dialect: Dialect,
+    ) -> Option<Expr> {
+        let mut conditions = self.time_dependent_conditions.clone()?;
+        #[allow(clippy::unwrap_used)] // error is !, which can never be returned
+        ReplaceCurrentTimeVisitor { now, dialect }
+            .visit_expr(&mut conditions)
+            .unwrap();
+        Some(conditions)
     }
 
     pub(crate) fn make_keys<'param, T>(
@@ -177,11 +273,12 @@ impl ProcessedQueryParams {
             limit,
             offset,
             force_paginate_in_adapter,
+            max_parametrized_limit,
         } = &self.pagination_parameters;
 
-        if *force_paginate_in_adapter {
-            // When fallback pagination is used, remove the parameters for offset and limit from the
-            // list
+        if *force_paginate_in_adapter || max_parametrized_limit.is_some() {
+            // When fallback pagination is used, or the limit is applied after the lookup, remove
+            // the parameters for offset and limit from the list
             if matches!(offset, Some(Literal::Placeholder(_))) {
                 // Skip parameter for offset
                 params = &params[..params.len() - 1];
@@ -523,6 +620,9 @@ pub fn number_placeholders(query: &mut SelectStatement) -> ReadySetResult<()> {
 struct AutoParametrizeVisitor {
     out: Vec<(usize, Literal)>,
     has_aggregates: bool,
+    /// Full-text search predicates can't be combined with other parameters, so if the query has
+    /// one we only replace its search string
+    has_full_text_predicate: bool,
     in_supported_position: bool,
     param_index: usize,
     query_depth: u8,
@@ -569,6 +669,23 @@ impl<'ast> VisitorMut<'ast> for AutoParametrizeVisitor {
         let was_supported = self.in_supported_position;
         if was_supported {
             match expression {
+                _ if self.has_full_text_predicate
+                    && !matches!(
+                        expression,
+                        Expr::BinaryOp {
+                            op: BinaryOperator::And,
+                            ..
+                        }
+                    ) =>
+                {
+                    if let Some(Expr::Literal(lit)) = full_text_query_mut(expression) {
+                        if !matches!(lit, Literal::Placeholder(_)) {
+                            self.replace_literal(lit);
+                            return Ok(());
+                        }
+                    }
+                    self.in_supported_position = false;
+                }
                 Expr::BinaryOp {
                     lhs: box Expr::Column(_),
                     op: BinaryOperator::Equal,
@@ -650,6 +767,7 @@ impl<'ast> VisitorMut<'ast> for AutoParametrizeVisitor {
     ) -> Result<(), Self::Error> {
         match offset {
             None | Some(Literal::Placeholder(_)) => {}
+            Some(_) if self.has_full_text_predicate => {}
             Some(lit) => {
                 self.replace_literal(lit);
             }
@@ -687,6 +805,7 @@ pub fn auto_parametrize_query(query: &mut SelectStatement) -> Vec<(usize, Litera
 
     let mut visitor = AutoParametrizeVisitor {
         has_aggregates: query.contains_aggregate_select(),
+        has_full_text_predicate: has_full_text_predicate(query),
         ..Default::default()
     };
     #[allow(clippy::unwrap_used)] // error is !, which can never be returned
@@ -694,6 +813,219 @@ pub fn auto_parametrize_query(query: &mut SelectStatement) -> Vec<(usize, Litera
     visitor.out
 }
 
+/// Returns true if the `WHERE` clause of the given query contains a full-text search predicate,
+/// whether or not it's one we support
+fn has_full_text_predicate(query: &SelectStatement) -> bool {
+    query.where_clause.iter().any(|expr| {
+        iter::once(expr)
+            .chain(expr.recursive_subexpressions())
+            .any(|subexpr| !matches!(full_text_predicate(subexpr), Ok(None)))
+    })
+}
+
+/// Names of functions which evaluate to the current date and/or time
+const CURRENT_TIME_FUNCTIONS: &[&str] = &[
+    "now",
+    "current_timestamp",
+    "localtimestamp",
+    "sysdate",
+    "utc_timestamp",
+    "current_date",
+    "curdate",
+    "utc_date",
+    "current_time",
+    "curtime",
+    "localtime",
+    "utc_time",
+];
+
+fn is_current_time_call(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Call(FunctionExpr::Call { name, .. })
+            if CURRENT_TIME_FUNCTIONS.iter().any(|f| name.eq_ignore_ascii_case(f))
+    )
+}
+
+fn is_time_dependent(expr: &Expr) -> bool {
+    iter::once(expr)
+        .chain(expr.recursive_subexpressions())
+        .any(is_current_time_call)
+}
+
+fn split_conjunctions(expr: Expr, out: &mut Vec<Expr>) {
+    match expr {
+        Expr::BinaryOp {
+            lhs,
+            op: BinaryOperator::And,
+            rhs,
+        } => {
+            split_conjunctions(*lhs, out);
+            split_conjunctions(*rhs, out);
+        }
+        expr => out.push(expr),
+    }
+}
+
+fn and_conjunctions(exprs: Vec<Expr>) -> Option<Expr> {
+    exprs.into_iter().reduce(|lhs, rhs| Expr::BinaryOp {
+        lhs: Box::new(lhs),
+        op: BinaryOperator::And,
+        rhs: Box::new(rhs),
+    })
+}
+
+/// Returns true if the given column is guaranteed to be returned, under the same name, by the
+/// given query
+fn is_projected(query: &SelectStatement, column: &Column) -> bool {
+    query.fields.iter().any(|field| match field {
+        FieldDefinitionExpr::All => true,
+        FieldDefinitionExpr::AllInTable(table) => {
+            column.table.as_ref().map_or(true, |t| t == table)
+        }
+        FieldDefinitionExpr::Expr {
+            expr: Expr::Column(c),
+            alias,
+        } => {
+            c.name == column.name
+                && alias.as_ref().map_or(true, |a| *a == column.name)
+                && (c.table.is_none() || column.table.is_none() || c.table == column.table)
+        }
+        FieldDefinitionExpr::Expr { .. } => false,
+    })
+}
+
+/// Remove all top-level conjunctions in the `WHERE` clause of the given query which reference the
+/// current date or time, and return them combined with `AND`.
+///
+/// The values of those conditions change over time independently of the data in the database, so
+/// they can't be maintained in the dataflow graph. Instead, the rest of the query is cached, and
+/// the removed conditions are evaluated against the result of each lookup, with the current time
+/// substituted in (see [`ProcessedQueryParams::time_dependent_conditions`]). That requires that the
+/// conditions can be evaluated against the rows returned by the query, so this returns an
+/// unsupported error if they reference columns that aren't projected, or if the query aggregates
+/// its results.
+fn extract_time_dependent_conditions(query: &mut SelectStatement) -> ReadySetResult<Option<Expr>> {
+    if !query.where_clause.iter().any(is_time_dependent) {
+        return Ok(None);
+    }
+
+    if query.distinct
+        || query.group_by.is_some()
+        || query.having.is_some()
+        || query.contains_aggregate_select()
+    {
+        unsupported!(
+            "Conditions on the current time are not supported in queries with aggregates, GROUP \
+             BY, or DISTINCT"
+        );
+    }
+
+    let mut conjunctions = vec![];
+    if let Some(where_clause) = query.where_clause.take() {
+        split_conjunctions(where_clause, &mut conjunctions);
+    }
+    let (time_dependent, rest): (Vec<_>, Vec<_>) =
+        conjunctions.into_iter().partition(is_time_dependent);
+
+    for cond in &time_dependent {
+        if cond.recursive_subexpressions().any(|expr| {
+            matches!(
+                expr,
+                Expr::Literal(Literal::Placeholder(_))
+                    | Expr::Exists(_)
+                    | Expr::NestedSelect(_)
+                    | Expr::In {
+                        rhs: InValue::Subquery(_),
+                        ..
+                    }
+            )
+        }) {
+            unsupported!(
+                "Parameters and subqueries are not supported in conditions on the current time"
+            );
+        }
+
+        if let Some(col) = cond
+            .referred_columns()
+            .find(|col| !is_projected(query, col))
+        {
+            unsupported!(
+                "Column {} must be projected to be used in a condition on the current time",
+                col
+            );
+        }
+    }
+
+    query.where_clause = and_conjunctions(rest);
+    Ok(and_conjunctions(time_dependent))
+}
+
+/// Replaces calls to functions which evaluate to the current date or time with literal values
+struct ReplaceCurrentTimeVisitor {
+    now: DateTime<FixedOffset>,
+    dialect: Dialect,
+}
+
+impl ReplaceCurrentTimeVisitor {
+    /// Returns the value of the given current time function, as a string formatted for the given
+    /// type
+    fn current_time_value(&self, function: &str) -> (String, SqlType) {
+        let is_postgres = self.dialect.engine() == SqlEngine::PostgreSQL;
+        let now = self.now;
+        let utc = now.naive_utc();
+        let local = now.naive_local();
+        match function.to_ascii_lowercase().as_str() {
+            "now" | "current_timestamp" if is_postgres => (
+                now.format("%Y-%m-%d %H:%M:%S%.6f%:z").to_string(),
+                SqlType::TimestampTz,
+            ),
+            "localtimestamp" if is_postgres => (
+                local.format("%Y-%m-%d %H:%M:%S%.6f").to_string(),
+                SqlType::Timestamp,
+            ),
+            "utc_timestamp" => (
+                utc.format("%Y-%m-%d %H:%M:%S").to_string(),
+                SqlType::DateTime(None),
+            ),
+            "current_date" | "curdate" => (local.format("%Y-%m-%d").to_string(), SqlType::Date),
+            "utc_date" => (utc.format("%Y-%m-%d").to_string(), SqlType::Date),
+            "current_time" | "curtime" if !is_postgres => {
+                (local.format("%H:%M:%S").to_string(), SqlType::Time)
+            }
+            "current_time" | "localtime" if is_postgres => {
+                (local.format("%H:%M:%S%.6f").to_string(), SqlType::Time)
+            }
+            "utc_time" => (utc.format("%H:%M:%S").to_string(), SqlType::Time),
+            // MySQL's NOW() returns a DATETIME with no fractional seconds
+            _ => (
+                local.format("%Y-%m-%d %H:%M:%S").to_string(),
+                SqlType::DateTime(None),
+            ),
+        }
+    }
+}
+
+impl<'ast> VisitorMut<'ast> for ReplaceCurrentTimeVisitor {
+    type Error = !;
+
+    fn visit_expr(&mut self, expr: &'ast mut Expr) -> Result<(), Self::Error> {
+        if is_current_time_call(expr) {
+            if let Expr::Call(FunctionExpr::Call { name, .. }) = expr {
+                let (value, ty) = self.current_time_value(name);
+                *expr = Expr::Cast {
+                    expr: Box::new(Expr::Literal(Literal::String(value))),
+                    ty,
+                    postgres_style: false,
+                };
+                return Ok(());
+            }
+        }
+
+        visit_mut::walk_expr(self, expr)
+    }
+}
+
 /// Splice the given list of extracted parameters, which should be a tuple of (placeholder position,
 /// value) as returned by [`auto_parametrize_query`] into the given list of parameters supplied by
 /// the user, by interleaving them into the params based on the placeholder position.
@@ -1184,6 +1516,24 @@ mod tests {
                 vec![(0, 1_u32.into()), (1, 6_u32.into())],
             );
         }
+
+        #[test]
+        fn full_text_search() {
+            test_auto_parametrize(
+                "SELECT * FROM t WHERE id = 1 AND MATCH (b) AGAINST ('foo') LIMIT 3 OFFSET 6",
+                "SELECT * FROM t WHERE id = 1 AND MATCH (b) AGAINST (?) LIMIT 3 OFFSET 6",
+                vec![(0, "foo".into())],
+            );
+        }
+
+        #[test]
+        fn existing_full_text_search_param() {
+            test_auto_parametrize(
+                "SELECT * FROM posts WHERE MATCH (body) AGAINST (?) AND id = 1",
+                "SELECT * FROM posts WHERE MATCH (body) AGAINST (?) AND id = 1",
+                vec![],
+            );
+        }
     }
 
     mod splice_auto_parameters {
@@ -1248,7 +1598,7 @@ mod tests {
             params: Vec<DfValue>,
         ) -> (Vec<Vec<DfValue>>, SelectStatement) {
             let mut query = parse_select_statement(query);
-            let processed = process_query(&mut query, false).unwrap();
+            let processed = process_query(&mut query, false, None).unwrap();
             (
                 processed
                     .make_keys(&params)
@@ -1268,7 +1618,7 @@ mod tests {
                 "SELECT id FROM users WHERE credit_card_number = $1 AND id = $2",
             );
 
-            process_query(&mut query, false).expect("Should be able to rewrite query");
+            process_query(&mut query, false, None).expect("Should be able to rewrite query");
             assert_eq!(query.to_string(), expected.to_string());
         }
 
@@ -1279,7 +1629,7 @@ mod tests {
             );
             let expected =
                 parse_select_statement("SELECT id + 3 FROM users WHERE credit_card_number = $1");
-            process_query(&mut query, false).expect("Should be able to rewrite query");
+            process_query(&mut query, false, None).expect("Should be able to rewrite query");
             assert_eq!(query, expected);
         }
 
@@ -1452,7 +1802,7 @@ mod tests {
         #[test]
         fn correct_offset_limit() {
             let get_lim_off = |q: &str, p: &[DfValue]| -> (Option<usize>, Option<usize>) {
-                let proc = process_query(&mut parse_select_statement(q), false).unwrap();
+                let proc = process_query(&mut parse_select_statement(q), false, None).unwrap();
                 proc.limit_offset_params(p).unwrap()
             };
 
@@ -1501,5 +1851,190 @@ mod tests {
                 (Some(4), Some(2))
             );
         }
+
+        #[test]
+        fn parametrized_limit_with_max() {
+            let mut query = parse_select_statement("SELECT * FROM t WHERE x = ? LIMIT ?");
+            let proc = process_query(&mut query, true, Some(100)).unwrap();
+
+            assert_eq!(
+                query,
+                parse_select_statement("SELECT * FROM t WHERE x = $1 LIMIT 100"),
+                "{}",
+                query
+            );
+            assert_eq!(
+                proc.make_keys::<DfValue>(&[1.into(), 10.into()])
+                    .unwrap()
+                    .into_iter()
+                    .map(|c| c.to_vec())
+                    .collect::<Vec<_>>(),
+                vec![vec![DfValue::from(1)]]
+            );
+            assert_eq!(
+                proc.limit_offset_params(&[1.into(), 10.into()]).unwrap(),
+                (Some(10), None)
+            );
+            let err = proc
+                .limit_offset_params(&[1.into(), 101.into()])
+                .unwrap_err();
+            assert!(!err.caused_by_unsupported());
+            assert_eq!(
+                err,
+                ReadySetError::ParametrizedLimitExceeded {
+                    limit: 101,
+                    max: 100
+                }
+            );
+        }
+
+        #[test]
+        fn parametrized_limit_with_offset_uses_fallback() {
+            let mut query = parse_select_statement("SELECT * FROM t WHERE x = ? LIMIT ? OFFSET ?");
+            let proc = process_query(&mut query, true, Some(100)).unwrap();
+
+            assert_eq!(
+                query,
+                parse_select_statement("SELECT * FROM t WHERE x = $1"),
+                "{}",
+                query
+            );
+            assert_eq!(
+                proc.limit_offset_params(&[1.into(), 10.into(), 20.into()])
+                    .unwrap(),
+                (Some(10), Some(20))
+            );
+        }
+    }
+
+    mod time_dependent_conditions {
+        use chrono::TimeZone;
+
+        use super::*;
+
+        fn now() -> DateTime<FixedOffset> {
+            FixedOffset::east(0).ymd(2022, 10, 18).and_hms(12, 34, 56)
+        }
+
+        fn where_clause(dialect: Dialect, q: &str) -> Expr {
+            nom_sql::parse_select_statement(dialect, q)
+                .unwrap()
+                .where_clause
+                .unwrap()
+        }
+
+        #[test]
+        fn extracts_conditions_on_now() {
+            let mut query = parse_select_statement(
+                "SELECT * FROM t WHERE x = ? AND expires_at > NOW() AND y = 1",
+            );
+            let proc = process_query(&mut query, true, None).unwrap();
+
+            assert_eq!(
+                query,
+                parse_select_statement("SELECT * FROM t WHERE x = $1 AND y = $2"),
+                "{}",
+                query
+            );
+            assert_eq!(
+                proc.time_dependent_conditions(now(), readyset_data::Dialect::DEFAULT_MYSQL)
+                    .unwrap(),
+                where_clause(
+                    Dialect::MySQL,
+                    "SELECT * FROM t WHERE expires_at > CAST('2022-10-18 12:34:56' AS DATETIME)"
+                )
+            );
+        }
+
+        #[test]
+        fn no_conditions_on_now() {
+            let mut query = parse_select_statement("SELECT * FROM t WHERE x = ?");
+            let proc = process_query(&mut query, true, None).unwrap();
+            assert_eq!(
+                proc.time_dependent_conditions(now(), readyset_data::Dialect::DEFAULT_MYSQL),
+                None
+            );
+        }
+
+        #[test]
+        fn postgres_current_time_functions() {
+            let mut query = nom_sql::parse_select_statement(
+                Dialect::PostgreSQL,
+                "SELECT * FROM t WHERE created_at < now() OR d = CURRENT_DATE",
+            )
+            .unwrap();
+            let proc = process_query(&mut query, true, None).unwrap();
+
+            assert_eq!(query.where_clause, None);
+            assert_eq!(
+                proc.time_dependent_conditions(now(), readyset_data::Dialect::DEFAULT_POSTGRESQL)
+                    .unwrap(),
+                where_clause(
+                    Dialect::PostgreSQL,
+                    "SELECT * FROM t WHERE created_at < CAST('2022-10-18 12:34:56.000000+00:00' \
+                     AS TIMESTAMPTZ) OR d = CAST('2022-10-18' AS DATE)"
+                )
+            );
+        }
+
+        #[test]
+        fn limit_applied_after_lookup() {
+            let mut query =
+                parse_select_statement("SELECT * FROM t WHERE expires_at > NOW() LIMIT 10");
+            let proc = process_query(&mut query, true, Some(100)).unwrap();
+
+            assert_eq!(query, parse_select_statement("SELECT * FROM t"));
+            assert_eq!(proc.limit_offset_params(&[]).unwrap(), (Some(10), None));
+        }
+
+        #[test]
+        fn unprojected_column() {
+            let mut query = parse_select_statement("SELECT id FROM t WHERE expires_at > NOW()");
+            process_query(&mut query, true, None).unwrap_err();
+
+            let mut query =
+                parse_select_statement("SELECT id, expires_at FROM t WHERE expires_at > NOW()");
+            process_query(&mut query, true, None).unwrap();
+        }
+
+        #[test]
+        fn aggregates() {
+            let mut query =
+                parse_select_statement("SELECT count(*) FROM t WHERE expires_at > NOW()");
+            process_query(&mut query, true, None).unwrap_err();
+        }
+
+        #[test]
+        fn placeholder_in_condition() {
+            let mut query =
+                parse_select_statement("SELECT * FROM t WHERE expires_at > NOW() - INTERVAL ? DAY");
+            process_query(&mut query, true, None).unwrap_err();
+        }
+    }
+
+    mod full_text_search {
+        use readyset_data::DfValue;
+
+        use super::*;
+
+        #[test]
+        fn limit_applied_after_lookup() {
+            let mut query = parse_select_statement(
+                "SELECT * FROM posts WHERE MATCH (body) AGAINST ('foo' IN BOOLEAN MODE) LIMIT 10",
+            );
+            let proc = process_query(&mut query, true, Some(100)).unwrap();
+
+            assert_eq!(
+                query,
+                parse_select_statement(
+                    "SELECT * FROM posts WHERE MATCH (body) AGAINST ($1 IN BOOLEAN MODE)"
+                )
+            );
+            assert_eq!(proc.limit_offset_params(&[]).unwrap(), (Some(10), None));
+            assert_eq!(
+                proc.make_keys::<DfValue>(&[]).unwrap(),
+                vec![Cow::Owned(vec![DfValue::from("foo")])]
+            );
+        }
     }
 }
@@ -0,0 +1,385 @@
+//! Declarative rules for routing queries, which can force queries matching certain criteria to
+//! always be proxied to the upstream database, always be executed against ReadySet, or be rejected
+//! outright.
+//!
+//! Routing rules are loaded at startup from a file of `CREATE ROUTING RULE` statements, and are
+//! consulted in the order they were created - the first rule that matches a query determines how
+//! it's routed. Rules are shared between all connections to an adapter.
+//!
+//! If the adapter is configured to allow it, rules can also be created and dropped at runtime with
+//! the `CREATE ROUTING RULE` and `DROP ROUTING RULE` SQL extensions. Such changes are only kept in
+//! memory, and are lost when the adapter restarts.
+//!
+//! Rules are consulted for every query other than ReadySet's own SQL extensions and transaction
+//! control statements. Rules with the `REJECT` action apply to all such queries, while rules with
+//! the `PROXY` and `READYSET` actions only change how reads are routed - writes are always sent to
+//! the upstream database if one exists. `READYSET` rules execute matching reads against ReadySet
+//! regardless of the state of the connection or the query, as if the query had been cached with
+//! `CREATE CACHE ALWAYS`.
+//!
+//! Prepared statements are routed when they're prepared, so changes to routing rules only affect
+//! statements prepared after the change.
+
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use nom_sql::analysis::visit::Visitor;
+use nom_sql::{
+    parse_routing_rules, CreateRoutingRuleStatement, Dialect, Relation, RoutingAction,
+    SqlIdentifier, SqlQuery,
+};
+use readyset_errors::{invalid_err, ReadySetError, ReadySetResult};
+use regex::Regex;
+
+use crate::utils::TablesVisitor;
+
+/// A query being routed, along with information about the connection it was received on
+#[derive(Debug, Clone, Copy)]
+pub struct RoutedQuery<'a> {
+    /// The user the connection authenticated as, if known
+    pub user: Option<&'a str>,
+    /// The database the connection is currently using, if known
+    pub database: Option<&'a str>,
+    /// The text of the query, as received from the client
+    pub query: &'a str,
+    /// The query, if it could be parsed
+    pub parsed: Option<&'a SqlQuery>,
+}
+
+/// Properties of a [`RoutedQuery`] that are expensive to compute, computed at most once while
+/// routing the query regardless of how many rules need them
+#[derive(Default)]
+struct RoutedQueryProperties<'a> {
+    normalized: Option<Cow<'a, str>>,
+    tables: Option<Vec<&'a Relation>>,
+}
+
+impl<'a> RoutedQuery<'a> {
+    /// Returns the normalized text of the query, which is used to match routing rule patterns.
+    /// Queries that could be parsed are normalized by formatting the parsed query, which removes
+    /// comments and makes whitespace, quoting and keyword case consistent.
+    fn normalized<'p>(&self, properties: &'p mut RoutedQueryProperties<'a>) -> &'p str {
+        properties
+            .normalized
+            .get_or_insert_with(|| match self.parsed {
+                Some(parsed) => parsed.to_string().into(),
+                None => self.query.into(),
+            })
+    }
+
+    /// Returns all tables referenced by the query, if it could be parsed
+    fn tables<'p>(&self, properties: &'p mut RoutedQueryProperties<'a>) -> &'p [&'a Relation] {
+        properties.tables.get_or_insert_with(|| {
+            let mut visitor = TablesVisitor::default();
+            if let Some(parsed) = self.parsed {
+                let Ok(_) = visitor.visit_sql_query(parsed);
+            }
+            visitor.tables
+        })
+    }
+
+    /// Returns the contents of all block comments (`/* ... */`) in the text of the query
+    fn comments(&self) -> impl Iterator<Item = &'a str> {
+        self.query
+            .split("/*")
+            .skip(1)
+            .filter_map(|s| s.split_once("*/").map(|(comment, _)| comment))
+    }
+}
+
+/// A single routing rule, created from a [`CreateRoutingRuleStatement`]
+#[derive(Debug)]
+pub struct RoutingRule {
+    statement: CreateRoutingRuleStatement,
+    pattern: Option<Regex>,
+    /// The number of queries this rule has matched since it was created
+    matches: AtomicU64,
+}
+
+impl TryFrom<CreateRoutingRuleStatement> for RoutingRule {
+    type Error = ReadySetError;
+
+    fn try_from(statement: CreateRoutingRuleStatement) -> ReadySetResult<Self> {
+        let pattern = statement
+            .pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| {
+                invalid_err!("Invalid pattern for routing rule {}: {}", statement.name, e)
+            })?;
+
+        Ok(Self {
+            statement,
+            pattern,
+            matches: AtomicU64::new(0),
+        })
+    }
+}
+
+impl RoutingRule {
+    /// The name of the rule
+    pub fn name(&self) -> &SqlIdentifier {
+        &self.statement.name
+    }
+
+    /// What to do with queries that match the rule
+    pub fn action(&self) -> RoutingAction {
+        self.statement.action
+    }
+
+    /// The statement the rule was created with
+    pub fn statement(&self) -> &CreateRoutingRuleStatement {
+        &self.statement
+    }
+
+    /// The number of queries this rule has matched since it was created
+    pub fn matches(&self) -> u64 {
+        self.matches.load(Ordering::Relaxed)
+    }
+
+    /// Returns true if all of the conditions of this rule hold for the given query
+    fn is_match<'a>(
+        &self,
+        query: &RoutedQuery<'a>,
+        properties: &mut RoutedQueryProperties<'a>,
+    ) -> bool {
+        let statement = &self.statement;
+
+        if statement.user.is_some() && statement.user.as_deref() != query.user {
+            return false;
+        }
+
+        if statement.database.is_some() && statement.database.as_deref() != query.database {
+            return false;
+        }
+
+        if !statement.tables.is_empty() {
+            let tables = query.tables(properties);
+            let references_table = statement.tables.iter().any(|rule_table| {
+                tables.iter().any(|table| {
+                    rule_table.name == table.name
+                        && (rule_table.schema.is_none() || rule_table.schema == table.schema)
+                })
+            });
+            if !references_table {
+                return false;
+            }
+        }
+
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(query.normalized(properties)) {
+                return false;
+            }
+        }
+
+        if let Some(hint) = &statement.hint {
+            if !query
+                .comments()
+                .any(|comment| comment.contains(hint.as_str()))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// The set of routing rules configured for an adapter. Thread-safe.
+#[derive(Debug, Default)]
+pub struct RoutingRules {
+    rules: RwLock<Vec<Arc<RoutingRule>>>,
+}
+
+impl RoutingRules {
+    /// Loads routing rules from a file containing a sequence of `CREATE ROUTING RULE` statements
+    pub fn load(path: &Path, dialect: Dialect) -> ReadySetResult<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let rules = Self::default();
+        for statement in parse_routing_rules(dialect, &contents).map_err(|e| invalid_err!("{e}"))? {
+            rules.create(statement)?;
+        }
+        Ok(rules)
+    }
+
+    /// Creates a new routing rule. If a rule with the same name already exists, it is replaced
+    /// (keeping its position in the order rules are consulted in).
+    pub fn create(&self, statement: CreateRoutingRuleStatement) -> ReadySetResult<()> {
+        let rule = Arc::new(RoutingRule::try_from(statement)?);
+        let mut rules = self.rules.write().unwrap();
+        match rules.iter_mut().find(|r| r.name() == rule.name()) {
+            Some(existing) => *existing = rule,
+            None => rules.push(rule),
+        }
+        Ok(())
+    }
+
+    /// Removes the routing rule with the given name, returning an error if no such rule exists
+    pub fn remove(&self, name: &SqlIdentifier) -> ReadySetResult<()> {
+        let mut rules = self.rules.write().unwrap();
+        let len = rules.len();
+        rules.retain(|r| r.name() != name);
+        if rules.len() == len {
+            return Err(ReadySetError::NoRoutingRule {
+                name: name.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns true if no routing rules have been created
+    pub fn is_empty(&self) -> bool {
+        self.rules.read().unwrap().is_empty()
+    }
+
+    /// Returns all routing rules, in the order they're consulted in
+    pub fn rules(&self) -> Vec<Arc<RoutingRule>> {
+        self.rules.read().unwrap().clone()
+    }
+
+    /// Returns the first routing rule that matches the given query, if any
+    pub fn route(&self, query: &RoutedQuery<'_>) -> Option<Arc<RoutingRule>> {
+        let rules = self.rules.read().unwrap();
+        let mut properties = RoutedQueryProperties::default();
+        let rule = rules
+            .iter()
+            .find(|rule| rule.is_match(query, &mut properties))?;
+        rule.matches.fetch_add(1, Ordering::Relaxed);
+        Some(Arc::clone(rule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::parse_query;
+
+    use super::*;
+
+    fn rules(rules: &str) -> RoutingRules {
+        let res = RoutingRules::default();
+        for statement in parse_routing_rules(Dialect::MySQL, rules).unwrap() {
+            res.create(statement).unwrap();
+        }
+        res
+    }
+
+    fn route(rules: &RoutingRules, user: &str, query: &str) -> Option<String> {
+        let parsed = parse_query(Dialect::MySQL, query).ok();
+        rules
+            .route(&RoutedQuery {
+                user: Some(user),
+                database: Some("app"),
+                query,
+                parsed: parsed.as_ref(),
+            })
+            .map(|rule| rule.name().to_string())
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = rules(
+            "CREATE ROUTING RULE reporting FOR USER 'reporter' PROXY;
+             CREATE ROUTING RULE everything READYSET;",
+        );
+        assert_eq!(
+            route(&rules, "reporter", "SELECT * FROM t").as_deref(),
+            Some("reporting")
+        );
+        assert_eq!(
+            route(&rules, "app", "SELECT * FROM t").as_deref(),
+            Some("everything")
+        );
+        assert_eq!(rules.rules()[0].matches(), 1);
+        assert_eq!(rules.rules()[1].matches(), 1);
+    }
+
+    #[test]
+    fn database() {
+        let rules = rules(
+            "CREATE ROUTING RULE other ON DATABASE 'other' REJECT;
+             CREATE ROUTING RULE app ON DATABASE 'app' PROXY;",
+        );
+        assert_eq!(
+            route(&rules, "app", "SELECT * FROM t").as_deref(),
+            Some("app")
+        );
+    }
+
+    #[test]
+    fn tables() {
+        let rules = rules("CREATE ROUTING RULE secrets ON TABLES app.secrets, tokens REJECT");
+        assert_eq!(
+            route(
+                &rules,
+                "app",
+                "SELECT * FROM t JOIN app.secrets ON t.id = secrets.id"
+            )
+            .as_deref(),
+            Some("secrets")
+        );
+        assert_eq!(
+            route(&rules, "app", "UPDATE tokens SET k = 1").as_deref(),
+            Some("secrets")
+        );
+        assert_eq!(route(&rules, "app", "SELECT * FROM other.secrets"), None);
+        assert_eq!(
+            route(&rules, "app", "SELECT secrets.x FROM t AS secrets"),
+            None
+        );
+    }
+
+    #[test]
+    fn pattern_matches_normalized_query() {
+        let rules =
+            rules("CREATE ROUTING RULE counts MATCHING '^SELECT count\\\\(\\\\*\\\\)' PROXY");
+        assert_eq!(
+            route(&rules, "app", "select   COUNT(*) from t").as_deref(),
+            Some("counts")
+        );
+        assert_eq!(route(&rules, "app", "SELECT * FROM t"), None);
+    }
+
+    #[test]
+    fn hint() {
+        let rules = rules("CREATE ROUTING RULE hinted WITH HINT 'route:upstream' PROXY");
+        assert_eq!(
+            route(&rules, "app", "SELECT /* route:upstream */ * FROM t").as_deref(),
+            Some("hinted")
+        );
+        assert_eq!(route(&rules, "app", "SELECT * FROM t"), None);
+    }
+
+    #[test]
+    fn create_replaces_and_remove() {
+        let rules = rules(
+            "CREATE ROUTING RULE a PROXY;
+             CREATE ROUTING RULE b REJECT;
+             CREATE ROUTING RULE a FOR USER 'someone' READYSET;",
+        );
+        let names = |rules: &RoutingRules| {
+            rules
+                .rules()
+                .iter()
+                .map(|r| r.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&rules), vec!["a", "b"]);
+        assert_eq!(rules.rules()[0].action(), RoutingAction::ReadySet);
+
+        rules.remove(&"a".into()).unwrap();
+        assert_eq!(names(&rules), vec!["b"]);
+        assert!(rules.remove(&"a".into()).is_err());
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let statement =
+            parse_routing_rules(Dialect::MySQL, "CREATE ROUTING RULE r MATCHING '(' PROXY")
+                .unwrap()
+                .remove(0);
+        assert!(RoutingRules::default().create(statement).is_err());
+    }
+}
@@ -141,12 +141,12 @@ pub trait UpstreamDatabase: Sized + Send {
         S: AsRef<str> + Send + Sync + 'a;
 
     /// Execute a raw, un-prepared write query, constructing and returning a RYW ticket for the
-    /// write
+    /// write, or `None` if the upstream database didn't report one once the write was committed
     // TODO: newtype RYW ticket, not just String
     async fn handle_ryw_write<'a, S>(
         &'a mut self,
         query: S,
-    ) -> Result<(Self::QueryResult<'a>, String), Self::Error>
+    ) -> Result<(Self::QueryResult<'a>, Option<String>), Self::Error>
     where
         S: AsRef<str> + Send + Sync + 'a;
 
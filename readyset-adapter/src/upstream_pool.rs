@@ -0,0 +1,580 @@
+//! Pooling of connections to the upstream database, shared between all connections to an adapter.
+//!
+//! By default, every client connection to the adapter holds its own dedicated upstream connection,
+//! even if all of its queries are served by ReadySet. When upstream connections are pooled, client
+//! connections instead lease a connection from a shared [`UpstreamPool`] for each statement they
+//! send to the upstream database, and return it once the statement's results have been sent to the
+//! client. Connections that are in a transaction (or have disabled autocommit) hold on to their
+//! leased connection until the transaction ends. Statements served by ReadySet never lease a
+//! connection.
+//!
+//! Since consecutive statements of a client connection may run on different upstream connections,
+//! each client connection's session state is tracked and replayed onto the connections it leases:
+//!
+//! * `SET` and `USE` statements are recorded, and replayed in order onto leased connections that
+//!   haven't run them yet. Each pooled connection remembers the session statements that have been
+//!   run on it, and a connection whose session statements aren't a prefix of the leasing client
+//!   connection's is reset (see [`UpstreamDatabase::reset`]) before being leased. Once a client
+//!   connection has run [`MAX_REPLAYED_SESSION_STATEMENTS`] session statements, replaying them
+//!   would cost more than holding on to a connection, so the connection it has leased is pinned to
+//!   it, as described below.
+//! * Prepared statements are prepared again on each leased connection the first time they're
+//!   executed on it. Pooled connections remember the statements prepared on them since their
+//!   session statements last changed, so statements with the same query are shared between client
+//!   connections with the same session state.
+//!
+//! Other session state lives only on the upstream connection it was created on, and can't be
+//! replayed (see [`connection_scope`]):
+//!
+//! * Statements that create such state, such as creating temporary tables, taking locks, assigning
+//!   user variables in a `SELECT`, or `PREPARE`, pin the connection they're run on to the client
+//!   connection, which then holds on to it until the client disconnects or the upstream connection
+//!   returns a fatal error.
+//! * Statements that read state left behind by earlier statements, such as `LAST_INSERT_ID()`,
+//!   `currval()`, `lastval()` or `EXECUTE`, are rejected unless they run on the same upstream
+//!   connection as those statements, because the client connection is in a transaction or has
+//!   pinned its connection.
+
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use nom_sql::SqlIdentifier;
+use parking_lot::Mutex;
+use readyset_errors::{internal_err, ReadySetError, ReadySetResult};
+use regex::Regex;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::timeout;
+use tracing::debug;
+
+use crate::fallback_cache::FallbackCache;
+use crate::upstream_database::{UpstreamConfig, UpstreamDatabase};
+
+/// The number of session statements a client connection can run before the connection it has
+/// leased is pinned to it, rather than replaying its session statements onto each connection it
+/// leases
+pub const MAX_REPLAYED_SESSION_STATEMENTS: usize = 32;
+
+lazy_static! {
+    /// Matches statements that create state on the upstream connection they're run on which can't
+    /// be replayed onto other connections
+    static ref CREATES_CONNECTION_STATE: Regex = Regex::new(concat!(
+        r"(?is)^\s*(CREATE\s+(GLOBAL\s+|LOCAL\s+)?TEMP(ORARY)?\s+TABLE|LOCK\s+TABLES?|PREPARE",
+        r"|LISTEN|DECLARE)\b",
+        r"|\b(GET_LOCK|PG_ADVISORY_LOCK|PG_ADVISORY_LOCK_SHARED)\s*\(",
+        r"|\bINTO\s+@|@\w+\s*:=",
+    ))
+    .unwrap();
+
+    /// Matches statements that read state left on the upstream connection they're run on by
+    /// earlier statements
+    static ref READS_CONNECTION_STATE: Regex = Regex::new(
+        r"(?is)^\s*EXECUTE\b|\b(LAST_INSERT_ID|CURRVAL|LASTVAL)\s*\("
+    )
+    .unwrap();
+}
+
+/// How a statement depends on state that only exists on the upstream connection it's run on, other
+/// than session statements and prepared statements, which are replayed onto leased connections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct ConnectionScope {
+    /// The statement creates state that can't be replayed, so the connection it's run on must be
+    /// pinned to the client connection
+    creates_state: bool,
+    /// The statement reads state created by earlier statements, so it must run on the same
+    /// connection they ran on
+    reads_state: bool,
+}
+
+/// Determines how the given statement depends on state that only exists on the upstream connection
+/// it's run on.
+///
+/// This is a conservative textual check, since the statements involved often can't be parsed.
+pub(crate) fn connection_scope(query: &str) -> ConnectionScope {
+    ConnectionScope {
+        creates_state: CREATES_CONNECTION_STATE.is_match(query),
+        reads_state: READS_CONNECTION_STATE.is_match(query),
+    }
+}
+
+/// Information about the upstream database that's the same for all connections to it, loaded from
+/// the first connection made by an [`UpstreamPool`]
+#[derive(Debug, Clone)]
+struct UpstreamInfo {
+    version: String,
+    database: Option<String>,
+    schema_search_path: Vec<SqlIdentifier>,
+}
+
+/// The session state of a pooled upstream connection
+#[derive(Debug, Default)]
+struct ConnectionState {
+    /// The `SET` and `USE` statements that have been run on the connection, in order
+    session_statements: Vec<String>,
+    /// The statements prepared on the connection, mapped from their query to their statement ID on
+    /// the connection
+    prepared_statements: HashMap<String, u32>,
+}
+
+impl ConnectionState {
+    /// Records that a session statement was run on the connection. Statements prepared before
+    /// were prepared under the previous session state (for example, against a different default
+    /// database), so they can't be reused for queries prepared under the new one.
+    fn ran_session_statement(&mut self, statement: &str) {
+        self.session_statements.push(statement.to_owned());
+        self.prepared_statements.clear();
+    }
+}
+
+/// A connection that's idle in an [`UpstreamPool`]
+struct IdleConnection<DB> {
+    connection: DB,
+    state: ConnectionState,
+}
+
+/// A lease on a connection from an [`UpstreamPool`]
+struct Lease {
+    state: ConnectionState,
+    /// Whether the connection should be closed rather than returned to the pool once released
+    discard: bool,
+    /// Whether the connection holds state that can't be replayed, so it must stay leased until
+    /// the client connection disconnects
+    pinned: bool,
+    /// The permit allowing the connection to be leased, released back to the pool along with it
+    _permit: OwnedSemaphorePermit,
+}
+
+/// Takes the idle connection that needs the least work to match the given session statements out
+/// of `idle`: the connection that has run the longest prefix of them, or any connection if none
+/// have run a prefix of them.
+fn take_idle<DB>(
+    idle: &mut Vec<IdleConnection<DB>>,
+    session_statements: &[String],
+) -> Option<IdleConnection<DB>> {
+    let index = idle
+        .iter()
+        .enumerate()
+        .filter(|(_, conn)| session_statements.starts_with(&conn.state.session_statements))
+        .max_by_key(|(_, conn)| conn.state.session_statements.len())
+        .map(|(index, _)| index)
+        .or_else(|| idle.len().checked_sub(1))?;
+    Some(idle.swap_remove(index))
+}
+
+/// A pool of connections to the upstream database, shared between all connections to an adapter.
+/// Thread-safe.
+///
+/// See [the module documentation](self) for more information.
+pub struct UpstreamPool<DB: UpstreamDatabase> {
+    config: UpstreamConfig,
+    fallback_cache: Option<FallbackCache<DB::CachedReadResult>>,
+    /// Connections that aren't currently leased
+    idle: Mutex<Vec<IdleConnection<DB>>>,
+    /// One permit per connection that may be leased at once, which bounds the number of
+    /// connections the pool opens
+    leases: Arc<Semaphore>,
+    /// How long to wait for a connection to be leased before giving up
+    lease_timeout: Duration,
+    info: Mutex<Option<UpstreamInfo>>,
+}
+
+impl<DB> UpstreamPool<DB>
+where
+    DB: UpstreamDatabase,
+{
+    /// Constructs a new, empty pool of connections to the upstream database with the given
+    /// configuration. Connections are opened as they're needed, up to `max_connections`.
+    pub fn new(
+        config: UpstreamConfig,
+        fallback_cache: Option<FallbackCache<DB::CachedReadResult>>,
+        max_connections: usize,
+        lease_timeout: Duration,
+    ) -> Self {
+        Self {
+            config,
+            fallback_cache,
+            idle: Default::default(),
+            leases: Arc::new(Semaphore::new(max_connections)),
+            lease_timeout,
+            info: Default::default(),
+        }
+    }
+
+    /// Returns the number of connections that are open but not currently leased
+    pub fn idle_connections(&self) -> usize {
+        self.idle.lock().len()
+    }
+
+    /// Opens a new connection to the upstream database, loading [`UpstreamInfo`] from it if it's
+    /// the first one
+    async fn connect(&self) -> Result<DB, DB::Error> {
+        let mut connection = DB::connect(self.config.clone(), self.fallback_cache.clone()).await?;
+        let has_info = self.info.lock().is_some();
+        if !has_info {
+            let info = UpstreamInfo {
+                version: connection.version(),
+                database: connection.database().map(ToOwned::to_owned),
+                schema_search_path: connection.schema_search_path().await?,
+            };
+            *self.info.lock() = Some(info);
+        }
+        Ok(connection)
+    }
+
+    /// Returns [`UpstreamInfo`], connecting to the upstream database to load it if no connections
+    /// have been made yet
+    async fn info(&self) -> Result<UpstreamInfo, DB::Error> {
+        let info = self.info.lock().clone();
+        if let Some(info) = info {
+            return Ok(info);
+        }
+
+        let (connection, lease) = self.lease(&[]).await?;
+        self.release(connection, lease);
+        let info = self.info.lock().clone();
+        info.ok_or_else(|| internal_err!("No info loaded from upstream connection").into())
+    }
+
+    /// Leases a connection from the pool, opening a new one if none are idle, and runs the given
+    /// session statements on it
+    async fn lease(&self, session_statements: &[String]) -> Result<(DB, Lease), DB::Error> {
+        match timeout(self.lease_timeout, self.lease_inner(session_statements)).await {
+            Ok(res) => res,
+            Err(_) => Err(ReadySetError::UpstreamPoolTimeout {
+                timeout_ms: self.lease_timeout.as_millis() as u64,
+            }
+            .into()),
+        }
+    }
+
+    async fn lease_inner(&self, session_statements: &[String]) -> Result<(DB, Lease), DB::Error> {
+        let permit = Arc::clone(&self.leases)
+            .acquire_owned()
+            .await
+            .map_err(|_| internal_err!("Upstream connection pool closed"))?;
+
+        let idle = take_idle(&mut self.idle.lock(), session_statements);
+        let (mut connection, mut state) = match idle {
+            Some(IdleConnection { connection, state }) => (connection, state),
+            None => (self.connect().await?, ConnectionState::default()),
+        };
+
+        if !session_statements.starts_with(&state.session_statements) {
+            debug!("Resetting pooled upstream connection with different session state");
+            connection.reset().await?;
+            state = ConnectionState::default();
+        }
+
+        for statement in &session_statements[state.session_statements.len()..] {
+            connection.query(statement).await.map(|_| ())?;
+            state.ran_session_statement(statement);
+        }
+
+        Ok((
+            connection,
+            Lease {
+                state,
+                discard: false,
+                pinned: false,
+                _permit: permit,
+            },
+        ))
+    }
+
+    /// Returns a leased connection to the pool
+    fn release(&self, connection: DB, lease: Lease) {
+        // Make the connection available before releasing the lease's permit, so that whoever
+        // acquires the permit next can use it
+        self.idle.lock().push(IdleConnection {
+            connection,
+            state: lease.state,
+        });
+    }
+}
+
+/// A client connection's use of an [`UpstreamPool`]: the lease on the upstream connection it
+/// currently holds, if any, and the queries of the statements it has prepared upstream.
+///
+/// The leased connection itself is stored by the [`Backend`](crate::Backend), in place of the
+/// dedicated upstream connection it would otherwise hold.
+pub struct PooledUpstream<DB: UpstreamDatabase> {
+    pool: Arc<UpstreamPool<DB>>,
+    info: UpstreamInfo,
+    /// The queries of statements prepared upstream, by the ID of the prepared statement in the
+    /// [`Backend`](crate::Backend)
+    statements: HashMap<u32, String>,
+    lease: Option<Lease>,
+    /// Whether the connection leased for the current statement should be pinned, since the
+    /// statement creates connection state that can't be replayed
+    pin_lease: bool,
+}
+
+impl<DB> PooledUpstream<DB>
+where
+    DB: UpstreamDatabase,
+{
+    /// Starts using the given pool for a new client connection. If the pool hasn't made any
+    /// connections yet, this connects to the upstream database to load information about it.
+    pub async fn new(pool: Arc<UpstreamPool<DB>>) -> Result<Self, DB::Error> {
+        let info = pool.info().await?;
+        Ok(Self {
+            pool,
+            info,
+            statements: HashMap::new(),
+            lease: None,
+            pin_lease: false,
+        })
+    }
+
+    /// The upstream database's version string, as returned by [`UpstreamDatabase::version`]
+    pub fn version(&self) -> &str {
+        &self.info.version
+    }
+
+    /// The database in the upstream database's connection string, as returned by
+    /// [`UpstreamDatabase::database`]
+    pub fn database(&self) -> Option<&str> {
+        self.info.database.as_deref()
+    }
+
+    /// The initial schema search path of connections to the upstream database
+    pub fn schema_search_path(&self) -> &[SqlIdentifier] {
+        &self.info.schema_search_path
+    }
+
+    /// Leases a connection from the pool into `upstream` with the given session statements run on
+    /// it, unless one is already leased
+    pub(crate) async fn lease(
+        &mut self,
+        upstream: &mut Option<DB>,
+        session_statements: &[String],
+    ) -> Result<(), DB::Error> {
+        if let Some(lease) = self.lease.as_mut().filter(|_| upstream.is_some()) {
+            lease.pinned |= mem::take(&mut self.pin_lease);
+            return Ok(());
+        }
+
+        let (connection, mut lease) = self.pool.lease(session_statements).await?;
+        lease.pinned = mem::take(&mut self.pin_lease)
+            || session_statements.len() >= MAX_REPLAYED_SESSION_STATEMENTS;
+        *upstream = Some(connection);
+        self.lease = Some(lease);
+        Ok(())
+    }
+
+    /// Returns the connection leased into `upstream`, if any, to the pool, unless it's pinned.
+    /// Discarded connections are closed, even if they're pinned.
+    pub(crate) fn release(&mut self, upstream: &mut Option<DB>) {
+        let Some(lease) = self.lease.take() else {
+            return;
+        };
+        if lease.pinned && !lease.discard && upstream.is_some() {
+            self.lease = Some(lease);
+            return;
+        }
+        match upstream.take() {
+            Some(connection) if !lease.discard => self.pool.release(connection, lease),
+            _ => debug!("Closing pooled upstream connection"),
+        }
+    }
+
+    /// Closes the leased connection once it's released rather than returning it to the pool, for
+    /// when it has returned a fatal error
+    pub(crate) fn discard(&mut self) {
+        if let Some(lease) = &mut self.lease {
+            lease.discard = true;
+        }
+    }
+
+    /// Returns true if the leased connection is pinned to this client connection
+    pub(crate) fn is_pinned(&self) -> bool {
+        self.lease.as_ref().map_or(false, |lease| lease.pinned)
+    }
+
+    /// Checks that the given statement can be run on a leased connection, and pins the connection
+    /// it'll be run on if it creates state that can't be replayed. `lease_held` is whether the
+    /// connection the statement will run on was already leased before the statement, so that
+    /// connection state left by earlier statements is still there.
+    ///
+    /// Must be called for each statement before leasing a connection for it.
+    pub(crate) fn check_query(&mut self, query: &str, lease_held: bool) -> ReadySetResult<()> {
+        let scope = connection_scope(query);
+        self.pin_lease = scope.creates_state;
+        if scope.reads_state && !lease_held && !self.is_pinned() {
+            return Err(ReadySetError::UpstreamConnectionStateUnavailable);
+        }
+        Ok(())
+    }
+
+    /// Like [`Self::check_query`], for executing the statement with the given ID in the
+    /// [`Backend`](crate::Backend)
+    pub(crate) fn check_prepared(&mut self, id: u32, lease_held: bool) -> ReadySetResult<()> {
+        let query = self
+            .statements
+            .get(&id)
+            .ok_or(ReadySetError::PreparedStatementMissing { statement_id: id })?
+            .clone();
+        self.check_query(&query, lease_held)
+    }
+
+    /// Records that a session statement was run on the leased connection, pinning the connection
+    /// once the client connection has run more session statements than are worth replaying
+    pub(crate) fn ran_session_statement(&mut self, statement: &str) {
+        if let Some(lease) = &mut self.lease {
+            lease.state.ran_session_statement(statement);
+            if lease.state.session_statements.len() >= MAX_REPLAYED_SESSION_STATEMENTS {
+                lease.pinned = true;
+            }
+        }
+    }
+
+    /// Records that the statement with the given ID in the [`Backend`](crate::Backend) was
+    /// prepared on the leased connection with the given query, getting `statement_id`
+    pub(crate) fn prepared(&mut self, id: u32, query: &str, statement_id: u32) {
+        self.statements.insert(id, query.to_owned());
+        if let Some(lease) = &mut self.lease {
+            lease
+                .state
+                .prepared_statements
+                .insert(query.to_owned(), statement_id);
+        }
+    }
+
+    /// Returns the ID on the leased connection `upstream` of the statement with the given ID in
+    /// the [`Backend`](crate::Backend), preparing it on the connection if it hasn't been yet
+    pub(crate) async fn statement_id(
+        &mut self,
+        upstream: &mut DB,
+        id: u32,
+    ) -> Result<u32, DB::Error> {
+        let query = self
+            .statements
+            .get(&id)
+            .ok_or(ReadySetError::PreparedStatementMissing { statement_id: id })?;
+        let lease = self
+            .lease
+            .as_mut()
+            .ok_or_else(|| internal_err!("No upstream connection leased"))?;
+
+        if let Some(statement_id) = lease.state.prepared_statements.get(query) {
+            return Ok(*statement_id);
+        }
+
+        let statement_id = upstream.prepare(query).await?.statement_id;
+        lease
+            .state
+            .prepared_statements
+            .insert(query.clone(), statement_id);
+        Ok(statement_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn idle(session_statements: &[&str]) -> IdleConnection<usize> {
+        IdleConnection {
+            connection: session_statements.len(),
+            state: ConnectionState {
+                session_statements: session_statements.iter().map(|s| s.to_string()).collect(),
+                prepared_statements: HashMap::new(),
+            },
+        }
+    }
+
+    fn statements(statements: &[&str]) -> Vec<String> {
+        statements.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn session_statements_invalidate_prepared_statements() {
+        let mut state = ConnectionState::default();
+        state
+            .prepared_statements
+            .insert("SELECT * FROM t".to_owned(), 1);
+        state.ran_session_statement("USE other");
+        assert_eq!(state.session_statements, statements(&["USE other"]));
+        assert!(state.prepared_statements.is_empty());
+    }
+
+    #[test]
+    fn take_idle_prefers_longest_prefix() {
+        let mut pool = vec![
+            idle(&[]),
+            idle(&["SET a = 1", "SET b = 2"]),
+            idle(&["SET a = 1"]),
+        ];
+        let taken = take_idle(
+            &mut pool,
+            &statements(&["SET a = 1", "SET b = 2", "SET c = 3"]),
+        );
+        assert_eq!(taken.unwrap().connection, 2);
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn take_idle_skips_different_session_state() {
+        let mut pool = vec![idle(&["SET a = 2"]), idle(&[])];
+        let taken = take_idle(&mut pool, &statements(&["SET a = 1"]));
+        assert_eq!(taken.unwrap().connection, 0);
+    }
+
+    #[test]
+    fn take_idle_falls_back_to_any_connection() {
+        let mut pool = vec![idle(&["SET a = 2"])];
+        let taken = take_idle(&mut pool, &statements(&["SET a = 1"]));
+        assert_eq!(
+            taken.unwrap().state.session_statements,
+            statements(&["SET a = 2"])
+        );
+        assert!(take_idle(&mut pool, &[]).is_none());
+    }
+
+    fn scope(query: &str) -> (bool, bool) {
+        let scope = connection_scope(query);
+        (scope.creates_state, scope.reads_state)
+    }
+
+    #[test]
+    fn connection_scope_stateless() {
+        assert_eq!(scope("SELECT x FROM t WHERE id = 1"), (false, false));
+        assert_eq!(
+            scope("INSERT INTO t (x) VALUES ('prepare')"),
+            (false, false)
+        );
+        assert_eq!(scope("SET @a = 1"), (false, false));
+        assert_eq!(scope("CREATE TABLE executed (x int)"), (false, false));
+    }
+
+    #[test]
+    fn connection_scope_creates_state() {
+        for query in [
+            "CREATE TEMPORARY TABLE tmp (x int)",
+            "create temp table tmp (x int)",
+            "SELECT GET_LOCK('lock', 10)",
+            "select pg_advisory_lock(1)",
+            "LOCK TABLES t WRITE",
+            "PREPARE stmt FROM 'SELECT 1'",
+            "SELECT @a := x FROM t",
+            "SELECT x INTO @a FROM t",
+        ] {
+            assert_eq!(scope(query), (true, false), "{query}");
+        }
+    }
+
+    #[test]
+    fn connection_scope_reads_state() {
+        for query in [
+            "SELECT LAST_INSERT_ID()",
+            "select currval('t_id_seq')",
+            "SELECT lastval()",
+            "EXECUTE stmt USING @a",
+        ] {
+            assert_eq!(scope(query), (false, true), "{query}");
+        }
+        assert_eq!(scope("SELECT @a := LAST_INSERT_ID()"), (true, true));
+    }
+}
@@ -2,11 +2,13 @@ use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::iter;
 
+use dataflow_expression::full_text::{full_text_predicate, FullTextPredicate};
 use launchpad::hash::hash;
 use nom_sql::analysis::visit::{self, Visitor};
 use nom_sql::{
     BinaryOperator, Column, ColumnConstraint, CreateTableStatement, DeleteStatement, Expr,
-    InsertStatement, Literal, SelectStatement, SqlIdentifier, SqlQuery, TableKey, UpdateStatement,
+    InsertStatement, Literal, Relation, SelectStatement, SqlIdentifier, SqlQuery, TableKey,
+    UpdateStatement,
 };
 use readyset_client::{Modification, Operation};
 use readyset_data::{DfType, DfValue, Dialect};
@@ -190,6 +192,16 @@ impl<'ast> Visitor<'ast> for BinopsParameterColumnsVisitor<'ast> {
     /// Extracts columns and binops when one side of the Expr contains [`Expr::Column`] and the
     /// other side contains [`Expr::Literal(Literal::Placeholder)`]
     fn visit_expr(&mut self, expr: &'ast Expr) -> Result<(), Self::Error> {
+        if let Ok(Some(FullTextPredicate {
+            column,
+            query: Expr::Literal(Literal::Placeholder(_)),
+            ..
+        })) = full_text_predicate(expr)
+        {
+            self.parameter_cols.push((column, BinaryOperator::AtAt));
+            return Ok(());
+        }
+
         match expr {
             Expr::BinaryOp {
                 lhs: box Expr::Column(ref c),
@@ -284,6 +296,35 @@ pub(crate) fn get_select_statement_binops(
     query.get_binops_parameter_columns()
 }
 
+/// Collects all the tables referenced by the AST it visits
+#[derive(Default)]
+pub(crate) struct TablesVisitor<'ast> {
+    pub(crate) tables: Vec<&'ast Relation>,
+}
+
+impl<'ast> Visitor<'ast> for TablesVisitor<'ast> {
+    type Error = !;
+
+    fn visit_table(&mut self, table: &'ast Relation) -> Result<(), Self::Error> {
+        self.tables.push(table);
+        visit::walk_relation(self, table)
+    }
+
+    fn visit_column(&mut self, _column: &'ast Column) -> Result<(), Self::Error> {
+        // The table of a column may be an alias rather than a table, so don't walk into it
+        Ok(())
+    }
+}
+
+/// Returns the full-text search predicate in the `WHERE` clause of the given query, if any
+pub(crate) fn get_full_text_predicate(query: &SelectStatement) -> Option<FullTextPredicate<'_>> {
+    query
+        .where_clause
+        .iter()
+        .flat_map(|expr| iter::once(expr).chain(expr.recursive_subexpressions()))
+        .find_map(|expr| full_text_predicate(expr).ok().flatten())
+}
+
 pub(crate) fn select_statement_parameter_columns(query: &SelectStatement) -> Vec<&Column> {
     query
         .get_binops_parameter_columns()
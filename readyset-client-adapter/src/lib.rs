@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use std::io;
 use std::marker::Send;
 use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::{Arc, Mutex, RwLock};
@@ -32,8 +33,12 @@ use readyset_adapter::http_router::NoriaAdapterHttpRouter;
 use readyset_adapter::migration_handler::MigrationHandler;
 use readyset_adapter::proxied_queries_reporter::ProxiedQueriesReporter;
 use readyset_adapter::query_status_cache::{MigrationStyle, QueryStatusCache};
+use readyset_adapter::query_status_persister::QueryStatusPersister;
+use readyset_adapter::read_replicas::{ReadReplicaHealthChecker, ReadReplicas};
+use readyset_adapter::routing_rules::RoutingRules;
+use readyset_adapter::upstream_pool::{PooledUpstream, UpstreamPool};
 use readyset_adapter::views_synchronizer::ViewsSynchronizer;
-use readyset_adapter::{Backend, BackendBuilder, QueryHandler, UpstreamDatabase};
+use readyset_adapter::{Backend, BackendBuilder, QueryHandler, UpstreamConfig, UpstreamDatabase};
 use readyset_client::consensus::{AuthorityControl, AuthorityType, ConsulAuthority};
 #[cfg(feature = "failure_injection")]
 use readyset_client::failpoints;
@@ -294,6 +299,63 @@ pub struct Options {
     #[clap(long, env = "OUTPUTS_POLLING_INTERVAL", default_value = "300")]
     views_polling_interval: u64,
 
+    /// Path to a file in which to persist the adapter's knowledge about queries (their migration
+    /// state, whether they're supported by ReadySet, and their execution statistics), so that it
+    /// survives restarts. If unset, this knowledge is only kept in memory.
+    #[clap(long, env = "QUERY_STATUS_PERSISTENCE_PATH")]
+    query_status_persistence_path: Option<PathBuf>,
+
+    /// Specifies the interval in seconds at which query statuses are written to
+    /// `--query-status-persistence-path`.
+    #[clap(long, env = "QUERY_STATUS_PERSISTENCE_INTERVAL", default_value = "30")]
+    query_status_persistence_interval: u64,
+
+    /// Path to a file of `CREATE ROUTING RULE` statements to load at startup. Routing rules can
+    /// force queries to always be proxied to the upstream database, always be executed against
+    /// ReadySet, or be rejected.
+    #[clap(long, env = "ROUTING_RULES_PATH")]
+    routing_rules_path: Option<PathBuf>,
+
+    /// Allow clients to create and drop routing rules at runtime with `CREATE ROUTING RULE` and
+    /// `DROP ROUTING RULE`. Since this lets any client of the adapter change how queries from all
+    /// other clients are routed, it's disabled by default. Changes made at runtime are not
+    /// persisted, and are lost when the adapter restarts unless they're also made to
+    /// `--routing-rules-path`.
+    #[clap(long, env = "ALLOW_ROUTING_RULE_CHANGES")]
+    allow_routing_rule_changes: bool,
+
+    /// Comma-separated URLs of read replicas of the upstream database. Reads that would otherwise
+    /// be proxied to the upstream database are sent to a healthy read replica instead, unless the
+    /// connection is in a transaction or has written to the upstream database.
+    ///
+    /// Only ad-hoc queries are sent to read replicas. Prepared statements are always executed
+    /// against the upstream database.
+    #[clap(
+        long,
+        env = "UPSTREAM_READ_REPLICA_URLS",
+        requires("upstream-db-url"),
+        use_delimiter = true
+    )]
+    upstream_read_replica_urls: Vec<RedactedString>,
+
+    /// Specifies the interval in seconds at which the health of the read replicas passed to
+    /// `--upstream-read-replica-urls` is checked.
+    #[clap(long, env = "READ_REPLICA_HEALTH_CHECK_INTERVAL", default_value = "5")]
+    read_replica_health_check_interval: u64,
+
+    /// Maximum number of connections to open to the upstream database. If set, client connections
+    /// share a pool of upstream connections, leasing one only while a statement or transaction is
+    /// proxied upstream, rather than each opening their own upstream connection. Client
+    /// connections that create connection state that can't be replayed onto other connections,
+    /// such as temporary tables or locks, keep their upstream connection until they disconnect.
+    #[clap(long, env = "UPSTREAM_POOL_SIZE", requires("upstream-db-url"))]
+    upstream_pool_size: Option<usize>,
+
+    /// Time in milliseconds to wait for a connection to be leased from the upstream connection
+    /// pool before returning an error to the client. Only used with `--upstream-pool-size`.
+    #[clap(long, env = "UPSTREAM_POOL_LEASE_TIMEOUT", default_value = "5000")]
+    upstream_pool_lease_timeout_ms: u64,
+
     /// The time to wait before canceling a migration request. Defaults to 30 minutes.
     #[clap(
         long,
@@ -331,6 +393,26 @@ pub struct Options {
     #[clap(long, env = "NON_BLOCKING_READS")]
     non_blocking_reads: bool,
 
+    /// Allow caching queries with a parameterized LIMIT (`LIMIT ?`), by caching up to this many
+    /// rows for each set of lookup keys and applying the limit provided at execute time to the
+    /// results. Executions with a larger limit are proxied upstream.
+    ///
+    /// Requires the experimental TopK and Paginate support to be enabled.
+    #[clap(long, env = "MAX_PARAMETRIZED_LIMIT")]
+    max_parametrized_limit: Option<usize>,
+
+    /// Provide read-your-writes consistency for writes proxied to the upstream database (mysql
+    /// only). After a connection writes to a table in the upstream database, its reads of that
+    /// table from ReadySet wait up to this many milliseconds for ReadySet to replicate the
+    /// transaction the write committed in, and are proxied to the upstream database if it
+    /// doesn't in time.
+    ///
+    /// The transaction of writes made by prepared statements or inside explicit transactions
+    /// isn't known, so reads of the tables they write to are proxied to the upstream database
+    /// until the connection makes another write to the table outside of a transaction.
+    #[clap(long, env = "READ_YOUR_WRITES_TIMEOUT_MS")]
+    read_your_writes_timeout_ms: Option<u64>,
+
     /// Run ReadySet in standalone mode, running a readyset-server and readyset-mysql instance
     /// within this adapter.
     #[clap(long, env = "STANDALONE", conflicts_with = "embedded-readers")]
@@ -486,6 +568,13 @@ where
             && options
                 .server_worker_options
                 .enable_experimental_paginate_support;
+        let max_parametrized_limit = options.max_parametrized_limit;
+        let read_your_writes_timeout = options
+            .read_your_writes_timeout_ms
+            .map(Duration::from_millis);
+        if read_your_writes_timeout.is_some() && matches!(self.database_type, DatabaseType::Psql) {
+            bail!("--read-your-writes-timeout-ms is not supported for PostgreSQL");
+        }
 
         let rh = rt.block_on(async {
             let authority = authority
@@ -611,6 +700,29 @@ where
         let query_status_cache: &'static _ =
             Box::leak(Box::new(QueryStatusCache::with_style(migration_style)));
 
+        if let Some(path) = options
+            .query_status_persistence_path
+            .as_ref()
+            .filter(|path| path.exists())
+        {
+            match query_status_cache.restore(path) {
+                Ok(restored) => rs_connect.in_scope(|| info!(%restored, "Restored query statuses")),
+                Err(error) => rs_connect
+                    .in_scope(|| warn!(%error, "Failed to restore persisted query statuses")),
+            }
+        }
+
+        let routing_rules = Arc::new(match &options.routing_rules_path {
+            Some(path) => RoutingRules::load(path, self.parse_dialect).map_err(|e| {
+                anyhow!(
+                    "Failed to load routing rules from {}: {}",
+                    path.display(),
+                    e
+                )
+            })?,
+            None => RoutingRules::default(),
+        });
+
         let telemetry_sender = rt.block_on(async {
             let proxied_queries_reporter =
                 Arc::new(ProxiedQueriesReporter::new(query_status_cache));
@@ -768,7 +880,8 @@ where
                     .instrument(connection.in_scope(|| {
                         span!(Level::DEBUG, "Building migration task noria connector")
                     }))
-                    .await;
+                    .await
+                    .with_max_parametrized_limit(max_parametrized_limit);
 
                 let controller_handle = dry_run.then(|| rh.clone());
                 let mut migration_handler = MigrationHandler::new(
@@ -811,6 +924,48 @@ where
             rt.handle().spawn(abort_on_panic(fut));
         }
 
+        if let Some(path) = options.query_status_persistence_path.clone() {
+            rs_connect
+                .in_scope(|| info!(path = %path.display(), "Spawning query status persister task"));
+            let persist_interval = options.query_status_persistence_interval;
+            let shutdown_recv = shutdown_sender.subscribe();
+            let fut = async move {
+                let mut persister = QueryStatusPersister::new(
+                    query_status_cache,
+                    path,
+                    std::time::Duration::from_secs(persist_interval),
+                    shutdown_recv,
+                );
+                persister.run().await
+            };
+            rt.handle().spawn(abort_on_panic(fut));
+        }
+
+        let read_replicas = Arc::new(ReadReplicas::new(
+            &options.server_worker_options.replicator_config,
+            options.upstream_read_replica_urls.clone(),
+        ));
+        if !read_replicas.is_empty() {
+            rs_connect.in_scope(|| {
+                info!(
+                    replicas = read_replicas.replicas().len(),
+                    "Spawning read replica health checker task"
+                )
+            });
+            let check_interval = options.read_replica_health_check_interval;
+            let shutdown_recv = shutdown_sender.subscribe();
+            let read_replicas = Arc::clone(&read_replicas);
+            let fut = async move {
+                let mut health_checker = ReadReplicaHealthChecker::<H::UpstreamDatabase>::new(
+                    read_replicas,
+                    std::time::Duration::from_secs(check_interval),
+                    shutdown_recv,
+                );
+                health_checker.run().await
+            };
+            rt.handle().spawn(abort_on_panic(fut));
+        }
+
         // Spin up async task that is in charge of creating a session with the authority,
         // regularly updating the heartbeat to keep the session live, and registering the adapters
         // http endpoint.
@@ -833,7 +988,18 @@ where
         // from readers on the adapter rather than across a network hop.
         let readers: Readers = Arc::new(Mutex::new(Default::default()));
 
-        let upstream_config = options.server_worker_options.replicator_config.clone();
+        let upstream_config = UpstreamConfig {
+            track_session_gtids: read_your_writes_timeout.is_some(),
+            ..options.server_worker_options.replicator_config.clone()
+        };
+        let upstream_pool = options.upstream_pool_size.map(|max_connections| {
+            Arc::new(UpstreamPool::<H::UpstreamDatabase>::new(
+                upstream_config.clone(),
+                fallback_cache.clone(),
+                max_connections,
+                Duration::from_millis(options.upstream_pool_lease_timeout_ms),
+            ))
+        });
 
         // Run a readyset-server instance within this adapter.
         let internal_server_handle = if options.standalone || options.embedded_readers {
@@ -909,7 +1075,10 @@ where
                 .migration_mode(migration_mode)
                 .query_max_failure_seconds(options.query_max_failure_seconds)
                 .telemetry_sender(telemetry_sender.clone())
-                .fallback_recovery_seconds(options.fallback_recovery_seconds);
+                .fallback_recovery_seconds(options.fallback_recovery_seconds)
+                .routing_rules(routing_rules.clone())
+                .allow_routing_rule_changes(options.allow_routing_rule_changes)
+                .read_replicas(read_replicas.clone());
             let telemetry_sender = telemetry_sender.clone();
 
             // Initialize the reader layer for the adapter.
@@ -924,8 +1093,21 @@ where
             let query_status_cache = query_status_cache;
             let upstream_config = upstream_config.clone();
             let fallback_cache = fallback_cache.clone();
+            let upstream_pool = upstream_pool.clone();
             let fut = async move {
-                let upstream_res = if upstream_config.upstream_db_url.is_some() {
+                let upstream_res = if let Some(upstream_pool) = upstream_pool {
+                    set_failpoint!(failpoints::UPSTREAM);
+                    timeout(
+                        UPSTREAM_CONNECTION_TIMEOUT,
+                        PooledUpstream::new(upstream_pool),
+                    )
+                    .instrument(debug_span!("Connecting to upstream database"))
+                    .await
+                    .map_err(|_| "Connection timed out".to_owned())
+                    .and_then(|r| r.map_err(|e| e.to_string()))
+                    .map_err(|e| format!("Error connecting to upstream database: {}", e))
+                    .map(|pooled_upstream| (None, Some(pooled_upstream)))
+                } else if upstream_config.upstream_db_url.is_some() {
                     set_failpoint!(failpoints::UPSTREAM);
                     timeout(
                         UPSTREAM_CONNECTION_TIMEOUT,
@@ -936,13 +1118,13 @@ where
                     .map_err(|_| "Connection timed out".to_owned())
                     .and_then(|r| r.map_err(|e| e.to_string()))
                     .map_err(|e| format!("Error connecting to upstream database: {}", e))
-                    .map(Some)
+                    .map(|upstream| (Some(upstream), None))
                 } else {
-                    Ok(None)
+                    Ok((None, None))
                 };
 
                 match upstream_res {
-                    Ok(mut upstream) => {
+                    Ok((mut upstream, pooled_upstream)) => {
                         if let Err(e) =
                             telemetry_sender.send_event(TelemetryEvent::UpstreamConnected)
                         {
@@ -953,7 +1135,10 @@ where
                         //
                         // NOTE: when we start tracking all configuration parameters, this should be
                         // folded into whatever loads those initially
-                        let schema_search_path_res = if let Some(upstream) = &mut upstream {
+                        let schema_search_path_res = if let Some(pooled_upstream) = &pooled_upstream
+                        {
+                            Ok(pooled_upstream.schema_search_path().to_vec())
+                        } else if let Some(upstream) = &mut upstream {
                             upstream.schema_search_path().await.map(|ssp| {
                                 debug!(
                                     schema_search_path = ?ssp,
@@ -978,13 +1163,22 @@ where
                                     server_supports_pagination,
                                 )
                                 .instrument(debug_span!("Building noria connector"))
-                                .await;
-
-                                let backend = backend_builder.clone().build(
-                                    noria,
-                                    upstream,
-                                    query_status_cache,
-                                );
+                                .await
+                                .with_max_parametrized_limit(max_parametrized_limit)
+                                .with_read_your_writes_timeout(read_your_writes_timeout);
+
+                                let backend = match pooled_upstream {
+                                    Some(pooled_upstream) => backend_builder.clone().build_pooled(
+                                        noria,
+                                        pooled_upstream,
+                                        query_status_cache,
+                                    ),
+                                    None => backend_builder.clone().build(
+                                        noria,
+                                        upstream,
+                                        query_status_cache,
+                                    ),
+                                };
                                 connection_handler.process_connection(s, backend).await;
                             }
                             Err(error) => {
@@ -1030,6 +1224,13 @@ where
         rs_shutdown.in_scope(|| info!("Dropping controller handle"));
         drop(rh);
 
+        if let Some(path) = &options.query_status_persistence_path {
+            rs_shutdown.in_scope(|| info!(path = %path.display(), "Persisting query statuses"));
+            if let Err(error) = query_status_cache.persist(path) {
+                rs_shutdown.in_scope(|| warn!(%error, "Failed to persist query statuses"));
+            }
+        }
+
         // Send shutdown telemetry events
         if internal_server_handle.is_some() {
             let _ = telemetry_sender.send_event(TelemetryEvent::ServerStop);
@@ -11,6 +11,7 @@ use nom_sql::Relation;
 use readyset_adapter::backend::noria_connector::{NoriaConnector, ReadBehavior};
 use readyset_adapter::backend::{BackendBuilder, MigrationMode};
 use readyset_adapter::query_status_cache::QueryStatusCache;
+use readyset_adapter::upstream_pool::{PooledUpstream, UpstreamPool};
 use readyset_adapter::{Backend, QueryHandler, UpstreamConfig, UpstreamDatabase};
 use readyset_client::consensus::{Authority, LocalAuthorityStore};
 use readyset_client::ViewCreateRequest;
@@ -39,8 +40,8 @@ pub trait Adapter: Send {
     fn connection_opts_with_port(port: u16) -> Self::ConnectionOpts;
     fn url() -> String;
 
-    async fn make_upstream(addr: String) -> Self::Upstream {
-        Self::Upstream::connect(UpstreamConfig::from_url(addr), None)
+    async fn make_upstream(upstream_config: UpstreamConfig) -> Self::Upstream {
+        Self::Upstream::connect(upstream_config, None)
             .await
             .unwrap()
     }
@@ -64,6 +65,9 @@ pub struct TestBuilder {
     migration_mode: MigrationMode,
     recreate_database: bool,
     query_status_cache: Option<&'static QueryStatusCache>,
+    read_your_writes_timeout: Option<Duration>,
+    upstream_pool: Option<(usize, Duration)>,
+    max_parametrized_limit: Option<usize>,
 }
 
 impl Default for TestBuilder {
@@ -84,6 +88,9 @@ impl TestBuilder {
             migration_mode: MigrationMode::InRequestPath,
             recreate_database: true,
             query_status_cache: None,
+            read_your_writes_timeout: None,
+            upstream_pool: None,
+            max_parametrized_limit: None,
         }
     }
 
@@ -123,6 +130,24 @@ impl TestBuilder {
         self
     }
 
+    pub fn read_your_writes_timeout(mut self, timeout: Duration) -> Self {
+        self.read_your_writes_timeout = Some(timeout);
+        self
+    }
+
+    pub fn max_parametrized_limit(mut self, max_parametrized_limit: usize) -> Self {
+        self.max_parametrized_limit = Some(max_parametrized_limit);
+        self
+    }
+
+    /// Lease connections to the upstream database from a pool of at most `max_connections`
+    /// connections shared between all connections to the adapter, rather than opening one per
+    /// connection
+    pub fn upstream_pool(mut self, max_connections: usize, lease_timeout: Duration) -> Self {
+        self.upstream_pool = Some((max_connections, lease_timeout));
+        self
+    }
+
     pub async fn build<A>(self) -> (A::ConnectionOpts, Handle)
     where
         A: Adapter + 'static,
@@ -170,6 +195,20 @@ impl TestBuilder {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
 
+        let upstream_pool = fallback_url.as_ref().zip(self.upstream_pool).map(
+            |(f, (max_connections, lease_timeout))| {
+                Arc::new(UpstreamPool::<A::Upstream>::new(
+                    UpstreamConfig {
+                        track_session_gtids: self.read_your_writes_timeout.is_some(),
+                        ..UpstreamConfig::from_url(f)
+                    },
+                    None,
+                    max_connections,
+                    lease_timeout,
+                ))
+            },
+        );
+
         tokio::spawn(async move {
             loop {
                 let (s, _) = listener.accept().await.unwrap();
@@ -178,15 +217,30 @@ impl TestBuilder {
                 let auto_increments = auto_increments.clone();
                 let authority = authority.clone();
 
+                let pooled_upstream = match &upstream_pool {
+                    Some(pool) => Some(PooledUpstream::new(Arc::clone(pool)).await.unwrap()),
+                    None => None,
+                };
+
                 // backend either has upstream or noria writer
-                let mut upstream = if let Some(f) = &fallback_url {
-                    Some(A::make_upstream(f.clone()).await)
+                let mut upstream = if pooled_upstream.is_some() {
+                    None
+                } else if let Some(f) = &fallback_url {
+                    Some(
+                        A::make_upstream(UpstreamConfig {
+                            track_session_gtids: self.read_your_writes_timeout.is_some(),
+                            ..UpstreamConfig::from_url(f)
+                        })
+                        .await,
+                    )
                 } else {
                     None
                 };
 
                 let schema_search_path = if let Some(upstream) = &mut upstream {
                     upstream.schema_search_path().await.unwrap()
+                } else if let Some(pooled_upstream) = &pooled_upstream {
+                    pooled_upstream.schema_search_path().to_vec()
                 } else {
                     Default::default()
                 };
@@ -202,12 +256,19 @@ impl TestBuilder {
                     schema_search_path,
                     server_supports_pagination,
                 )
-                .await;
+                .await
+                .with_max_parametrized_limit(self.max_parametrized_limit)
+                .with_read_your_writes_timeout(self.read_your_writes_timeout);
 
-                let backend = backend_builder
+                let backend_builder = backend_builder
                     .dialect(A::DIALECT)
-                    .migration_mode(self.migration_mode)
-                    .build(noria, upstream, query_status_cache);
+                    .migration_mode(self.migration_mode);
+                let backend = match pooled_upstream {
+                    Some(pooled_upstream) => {
+                        backend_builder.build_pooled(noria, pooled_upstream, query_status_cache)
+                    }
+                    None => backend_builder.build(noria, upstream, query_status_cache),
+                };
 
                 tokio::spawn(A::run_backend(backend, s));
             }
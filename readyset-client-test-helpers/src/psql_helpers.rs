@@ -90,9 +90,14 @@ pub async fn last_query_info(conn: &Client) -> QueryInfo {
 
     let destination = QueryDestination::try_from(row.get("Query_destination").unwrap()).unwrap();
     let noria_error = row.get("ReadySet_error").unwrap().to_owned();
+    let routing_rule = row
+        .get("Routing_rule")
+        .filter(|rule| *rule != "none")
+        .map(|rule| rule.to_owned());
 
     QueryInfo {
         destination,
         noria_error,
+        routing_rule,
     }
 }
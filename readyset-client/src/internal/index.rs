@@ -17,17 +17,23 @@ pub enum IndexType {
     HashMap,
     /// An index backed by a [`BTreeMap`](std::collections::BTreeMap)
     BTreeMap,
+    /// An inverted index from each token of a text column to the rows containing that token, used
+    /// to serve full-text search predicates. Only supported in readers.
+    FullText,
 }
 
 /// An index type it₁ is > it₂ iff it₁ can support all lookup operations it₂ can support.
+///
+/// [`FullText`](IndexType::FullText) indexes are keyed on tokens rather than whole column values,
+/// so they can't support any of the lookups the other index types can, and are ordered below both.
 impl Ord for IndexType {
     fn cmp(&self, other: &Self) -> Ordering {
         use IndexType::*;
 
         match (self, other) {
-            (HashMap, HashMap) | (BTreeMap, BTreeMap) => Ordering::Equal,
-            (BTreeMap, HashMap) => Ordering::Greater,
-            (HashMap, BTreeMap) => Ordering::Less,
+            (HashMap, HashMap) | (BTreeMap, BTreeMap) | (FullText, FullText) => Ordering::Equal,
+            (BTreeMap, HashMap) | (BTreeMap | HashMap, FullText) => Ordering::Greater,
+            (HashMap, BTreeMap) | (FullText, HashMap | BTreeMap) => Ordering::Less,
         }
     }
 }
@@ -45,6 +51,7 @@ impl IndexType {
         match operator {
             Equal | Is => Some(Self::HashMap),
             Greater | GreaterOrEqual | Less | LessOrEqual => Some(Self::BTreeMap),
+            AtAt => Some(Self::FullText),
             _ => None,
         }
     }
@@ -76,7 +83,7 @@ impl IndexType {
     /// Return true if this index type can support lookups for the given `key`
     pub fn supports_key(&self, key: &KeyComparison) -> bool {
         match self {
-            IndexType::HashMap => key.is_equal(),
+            IndexType::HashMap | IndexType::FullText => key.is_equal(),
             IndexType::BTreeMap => true,
         }
     }
@@ -530,6 +530,7 @@ pub fn shard_by(dt: &DfValue, shards: usize) -> usize {
         | DfValue::Numeric(_)
         | DfValue::BitVector(_)
         | DfValue::Array(_)
+        | DfValue::Range(_)
         | DfValue::PassThrough(_) => {
             use std::hash::{Hash, Hasher};
             let mut hasher = ahash::AHasher::new_with_keys(0x3306, 0x6033);
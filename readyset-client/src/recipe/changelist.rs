@@ -266,8 +266,11 @@ impl ChangeList {
 pub enum AlterTypeChange {
     /// Set the variants of this custom type to the given list of variants.
     ///
-    /// Currently, if the change does not involve exclusively adding variants to the end of the
-    /// existing list of variants, an error will be returned
+    /// Any caches which depend on the type are re-created with its new definition. If the change
+    /// does not exclusively add variants to the end of the existing list of variants or rename
+    /// existing variants (eg if a variant is added in the middle of the list), the values already
+    /// stored for the type would change meaning, so [`ReadySetError::ResnapshotNeeded`] is
+    /// returned to signal that tables using the type have to be snapshotted again.
     SetVariants(Vec<String>),
 }
 
@@ -284,7 +287,9 @@ pub enum Change {
     AlterTable(AlterTableStatement),
     /// Add a new custom type
     ///
-    /// Internally, custom types are just represented as aliases for a [`DfType`].
+    /// Internally, custom types are just represented as aliases for a [`DfType`]. If a custom type
+    /// with the same name already exists with a different definition (which can happen when
+    /// resnapshotting), it's redefined, and everything depending on it is re-created.
     CreateType {
         /// The name of the type
         name: Relation,
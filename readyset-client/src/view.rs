@@ -614,6 +614,27 @@ impl KeyComparison {
             }
         }
     }
+
+    /// Like [`map_endpoints`](Self::map_endpoints), but with a fallible function, returning the
+    /// first error it returns
+    pub fn try_map_endpoints<F, E>(self, mut f: F) -> Result<Self, E>
+    where
+        F: FnMut(Vec1<DfValue>) -> Result<Vec1<DfValue>, E>,
+    {
+        match self {
+            KeyComparison::Equal(k) => Ok(KeyComparison::Equal(f(k)?)),
+            KeyComparison::Range((lower, upper)) => {
+                let mut map_bound = |bound| -> Result<_, E> {
+                    Ok(match bound {
+                        Bound::Included(k) => Bound::Included(f(k)?),
+                        Bound::Excluded(k) => Bound::Excluded(f(k)?),
+                        Bound::Unbounded => Bound::Unbounded,
+                    })
+                };
+                Ok(KeyComparison::Range((map_bound(lower)?, map_bound(upper)?)))
+            }
+        }
+    }
 }
 
 impl PartialEq for KeyComparison {
@@ -244,7 +244,9 @@ impl Arbitrary for Array {
 
         (
             (1_usize..=3_usize),
-            any::<DfValueKind>().prop_filter("Nested Array", |dtk| *dtk != DfValueKind::Array),
+            any::<DfValueKind>().prop_filter("Nested Array or Range", |dtk| {
+                !matches!(dtk, DfValueKind::Array | DfValueKind::Range)
+            }),
         )
             .prop_flat_map(|(ndims, kind)| {
                 (
@@ -5,6 +5,8 @@ use std::hash::{Hash, Hasher};
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumCount, FromRepr};
 use test_strategy::Arbitrary;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 /// Description for how string values should be compared against each other for ordering and
 /// equality.
@@ -42,9 +44,77 @@ pub enum Collation {
     /// [PostgreSQL `CITEXT` type](https://www.postgresql.org/docs/current/citext.html) with the
     /// locale set to `en_US.utf8`.
     Citext,
+
+    /// The case- and accent-insensitive Unicode collation.
+    ///
+    /// This collation corresponds to MySQL's `utf8mb4_0900_ai_ci` collation (the default
+    /// collation for MySQL 8.0). Strings are compared after lowercasing, removing diacritics, and
+    /// expanding ligatures such as `ß` to `ss`. Trailing spaces are significant (`NO PAD`).
+    Utf8AiCi,
+
+    /// The legacy case-insensitive MySQL collation.
+    ///
+    /// This collation corresponds to MySQL's `utf8mb4_general_ci` collation. Like
+    /// [`Utf8AiCi`][Collation::Utf8AiCi] it ignores case and diacritics, but compares characters
+    /// one at a time (so `ß` is equal to `s`), and ignores trailing spaces (`PAD SPACE`).
+    Utf8GeneralCi,
+
+    /// Byte-wise comparison of strings.
+    ///
+    /// This collation corresponds to MySQL's `binary` collation and the `_bin` collations for the
+    /// `utf8mb4` character set.
+    Binary,
+
+    /// The older case- and accent-insensitive Unicode collation.
+    ///
+    /// This collation corresponds to MySQL's `utf8mb4_unicode_ci` and `utf8mb4_unicode_520_ci`
+    /// collations. Like [`Utf8AiCi`][Collation::Utf8AiCi] it ignores case and diacritics and
+    /// expands ligatures, but ignores trailing spaces (`PAD SPACE`).
+    Utf8UnicodeCi,
+}
+
+/// Fold the given string by lowercasing it and removing all diacritics, expanding any characters
+/// which lowercase to multiple characters if `expand` is true.
+fn fold_case_and_accents(s: &str, expand: bool) -> String {
+    s.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(|c| match c {
+            'ß' | 'ẞ' if expand => "ss".chars().collect(),
+            'ß' | 'ẞ' => vec!['s'],
+            'æ' | 'Æ' if expand => "ae".chars().collect(),
+            'œ' | 'Œ' if expand => "oe".chars().collect(),
+            c if expand => c.to_lowercase().collect::<Vec<_>>(),
+            // Compare characters one at a time, without expansions
+            c => vec![c.to_lowercase().next().unwrap_or(c)],
+        })
+        .collect()
 }
 
 impl Collation {
+    /// Look up a collation by name, returning `None` if the collation is unknown or unsupported.
+    ///
+    /// Names are matched case-insensitively. Both MySQL collation names (eg
+    /// `utf8mb4_0900_ai_ci`) and the legacy `utf8`/`utf8mb3` aliases for them are recognized.
+    pub fn get(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "utf8mb4_0900_ai_ci" => Some(Self::Utf8AiCi),
+            "utf8mb4_unicode_ci"
+            | "utf8mb4_unicode_520_ci"
+            | "utf8_unicode_ci"
+            | "utf8_unicode_520_ci"
+            | "utf8mb3_unicode_ci"
+            | "utf8mb3_unicode_520_ci" => Some(Self::Utf8UnicodeCi),
+            "utf8mb4_general_ci" | "utf8_general_ci" | "utf8mb3_general_ci" => {
+                Some(Self::Utf8GeneralCi)
+            }
+            "binary" | "utf8mb4_bin" | "utf8mb4_0900_bin" | "utf8_bin" | "utf8mb3_bin" => {
+                Some(Self::Binary)
+            }
+            "c" | "posix" | "default" => Some(Self::Utf8),
+            _ => None,
+        }
+    }
+
     /// Normalize the given string according to this collation.
     ///
     /// It will always be the case that two normalized strings compare in the same way as
@@ -53,8 +123,13 @@ impl Collation {
     /// [`compare_strs`]: Collation::compare_strs
     pub(crate) fn normalize(self, s: &str) -> Cow<str> {
         match self {
-            Collation::Utf8 => s.into(),
+            Collation::Utf8 | Collation::Binary => s.into(),
             Collation::Citext => s.to_lowercase().into(),
+            Collation::Utf8AiCi => fold_case_and_accents(s, true).into(),
+            Collation::Utf8UnicodeCi => fold_case_and_accents(s.trim_end_matches(' '), true).into(),
+            Collation::Utf8GeneralCi => {
+                fold_case_and_accents(s.trim_end_matches(' '), false).into()
+            }
         }
     }
 
@@ -64,19 +139,25 @@ impl Collation {
         H: Hasher,
     {
         match self {
-            Collation::Utf8 => s.hash(state),
+            Collation::Utf8 | Collation::Binary => s.hash(state),
             Collation::Citext => s.to_lowercase().hash(state),
+            Collation::Utf8AiCi | Collation::Utf8UnicodeCi | Collation::Utf8GeneralCi => {
+                self.normalize(s).hash(state)
+            }
         }
     }
 
     /// Compare the given strings according to this collation
     pub(crate) fn compare_strs(self, s1: &str, s2: &str) -> Ordering {
         match self {
-            Collation::Utf8 => s1.cmp(s2),
+            Collation::Utf8 | Collation::Binary => s1.cmp(s2),
             Collation::Citext => s1
                 .chars()
                 .map(|c| c.to_lowercase())
                 .cmp_by(s2.chars().map(|c| c.to_lowercase()), |c1, c2| c1.cmp(c2)),
+            Collation::Utf8AiCi | Collation::Utf8UnicodeCi | Collation::Utf8GeneralCi => {
+                self.normalize(s1).cmp(&self.normalize(s2))
+            }
         }
     }
 }
@@ -141,6 +222,100 @@ mod tests {
         citext_strings_equal("Į", "į");
     }
 
+    #[test]
+    fn utf8_ai_ci_equal() {
+        #[track_caller]
+        fn strings_equal(s1: &str, s2: &str) {
+            assert_eq!(Collation::Utf8AiCi.compare_strs(s1, s2), Ordering::Equal)
+        }
+
+        #[track_caller]
+        fn strings_inequal(s1: &str, s2: &str) {
+            assert_ne!(Collation::Utf8AiCi.compare_strs(s1, s2), Ordering::Equal)
+        }
+
+        strings_equal("abcdef", "ABCDEF");
+        strings_equal("résumé", "RESUME");
+        strings_equal("aÄbø", "AAbo");
+        strings_equal("ß", "ss");
+        strings_inequal("a", "a ");
+        strings_inequal("abc", "abd");
+    }
+
+    #[test]
+    fn utf8_unicode_ci_equal() {
+        #[track_caller]
+        fn strings_equal(s1: &str, s2: &str) {
+            assert_eq!(
+                Collation::Utf8UnicodeCi.compare_strs(s1, s2),
+                Ordering::Equal
+            )
+        }
+
+        #[track_caller]
+        fn strings_inequal(s1: &str, s2: &str) {
+            assert_ne!(
+                Collation::Utf8UnicodeCi.compare_strs(s1, s2),
+                Ordering::Equal
+            )
+        }
+
+        strings_equal("résumé", "RESUME");
+        strings_equal("ß", "ss");
+        strings_equal("a", "a  ");
+        strings_inequal(" a", "a");
+        strings_inequal("abc", "abd");
+    }
+
+    #[test]
+    fn utf8_general_ci_equal() {
+        #[track_caller]
+        fn strings_equal(s1: &str, s2: &str) {
+            assert_eq!(
+                Collation::Utf8GeneralCi.compare_strs(s1, s2),
+                Ordering::Equal
+            )
+        }
+
+        #[track_caller]
+        fn strings_inequal(s1: &str, s2: &str) {
+            assert_ne!(
+                Collation::Utf8GeneralCi.compare_strs(s1, s2),
+                Ordering::Equal
+            )
+        }
+
+        strings_equal("abcdef", "ABCDEF");
+        strings_equal("Ñandú", "nandu");
+        strings_equal("ß", "s");
+        strings_equal("a", "a  ");
+        strings_inequal("ß", "ss");
+    }
+
+    #[test]
+    fn binary_is_case_sensitive() {
+        assert_eq!(Collation::Binary.compare_strs("a", "A"), Ordering::Greater);
+        assert_eq!(Collation::Binary.compare_strs("a", "a"), Ordering::Equal);
+    }
+
+    #[test]
+    fn get_by_name() {
+        assert_eq!(
+            Collation::get("utf8mb4_0900_ai_ci"),
+            Some(Collation::Utf8AiCi)
+        );
+        assert_eq!(
+            Collation::get("UTF8MB4_GENERAL_CI"),
+            Some(Collation::Utf8GeneralCi)
+        );
+        assert_eq!(
+            Collation::get("utf8mb4_unicode_ci"),
+            Some(Collation::Utf8UnicodeCi)
+        );
+        assert_eq!(Collation::get("binary"), Some(Collation::Binary));
+        assert_eq!(Collation::get("latin1_swedish_ci"), None);
+    }
+
     #[test]
     fn citext_ordering() {
         #[track_caller]
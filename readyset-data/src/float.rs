@@ -160,7 +160,8 @@ pub(crate) fn coerce_f64(val: f64, to_ty: &DfType, from_ty: &DfType) -> ReadySet
         | DfType::Uuid
         | DfType::Bit(_)
         | DfType::VarBit(_)
-        | DfType::Array(_) => Err(err("not allowed")),
+        | DfType::Array(_)
+        | DfType::Range(_) => Err(err("not allowed")),
     }
 }
 
@@ -250,7 +251,8 @@ pub(crate) fn coerce_decimal(
         | DfType::Uuid
         | DfType::Bit(_)
         | DfType::VarBit(_)
-        | DfType::Array(_) => Err(ReadySetError::DfValueConversionError {
+        | DfType::Array(_)
+        | DfType::Range(_) => Err(ReadySetError::DfValueConversionError {
             src_type: "Decimal".to_string(),
             target_type: to_ty.to_string(),
             details: "Not allowed".to_string(),
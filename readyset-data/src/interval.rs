@@ -0,0 +1,448 @@
+use std::fmt;
+use std::ops::Neg;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use nom_sql::TimestampField;
+use readyset_errors::{invalid_err, ReadySetResult};
+use serde::{Deserialize, Serialize};
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+
+/// A span of time, as represented by SQL `INTERVAL` values.
+///
+/// Like the [PostgreSQL interval type][pg], this stores months, days, and microseconds
+/// separately, since neither the number of days in a month nor the number of hours in a day are
+/// constant. Adding an interval to a timestamp adds the months first (clamping the day of the
+/// month to the last day of the resulting month), then the days, then the microseconds.
+///
+/// [pg]: https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-INTERVAL-INPUT
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    microseconds: i64,
+}
+
+impl Interval {
+    /// Construct a new interval from the given number of months, days, and microseconds
+    pub fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    /// Returns the months component of this interval
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    /// Returns the days component of this interval
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns the microseconds component of this interval
+    pub fn microseconds(&self) -> i64 {
+        self.microseconds
+    }
+
+    /// Construct an interval from (possibly fractional) numbers of months, days, and
+    /// microseconds, spilling fractional months into days (assuming 30 days per month) and
+    /// fractional days into microseconds, the same way PostgreSQL does.
+    fn from_parts(months: f64, days: f64, microseconds: f64) -> ReadySetResult<Self> {
+        let whole_months = months.trunc();
+        let days = days + (months - whole_months) * 30.0;
+        let whole_days = days.trunc();
+        let microseconds = microseconds + (days - whole_days) * MICROS_PER_DAY as f64;
+
+        let out_of_range = || invalid_err!("interval out of range");
+        if !microseconds.is_finite() || microseconds.abs() >= i64::MAX as f64 {
+            return Err(out_of_range());
+        }
+        Ok(Self {
+            months: i32::try_from(whole_months as i64).map_err(|_| out_of_range())?,
+            days: i32::try_from(whole_days as i64).map_err(|_| out_of_range())?,
+            microseconds: microseconds.round() as i64,
+        })
+    }
+
+    /// Construct an interval of `quantity` of the given (non-compound) unit
+    fn from_quantity(quantity: f64, unit: TimestampField) -> ReadySetResult<Self> {
+        use TimestampField::*;
+
+        let micros_per = |micros: i64| (0.0, 0.0, quantity * micros as f64);
+        let (months, days, microseconds) = match unit {
+            Millennium => (quantity * 12_000.0, 0.0, 0.0),
+            Century => (quantity * 1_200.0, 0.0, 0.0),
+            Decade => (quantity * 120.0, 0.0, 0.0),
+            Year => (quantity * 12.0, 0.0, 0.0),
+            Quarter => (quantity * 3.0, 0.0, 0.0),
+            Month => (quantity, 0.0, 0.0),
+            Week => (0.0, quantity * 7.0, 0.0),
+            Day => (0.0, quantity, 0.0),
+            Hour => micros_per(MICROS_PER_HOUR),
+            Minute => micros_per(MICROS_PER_MINUTE),
+            Second => micros_per(MICROS_PER_SECOND),
+            Millisecond => micros_per(1_000),
+            Microsecond => micros_per(1),
+            _ => return Err(invalid_err!("Invalid interval unit: {unit}")),
+        };
+        Self::from_parts(months, days, microseconds)
+    }
+
+    /// Parse an interval from its PostgreSQL string representation, such as `'7 days'`,
+    /// `'1 year 2 mons 3 days 04:05:06'` or `'@ 2 hours ago'`.
+    ///
+    /// If the string is a bare number, it's interpreted as a quantity of `unit` (or of seconds, if
+    /// no unit is given). Otherwise, `unit` is ignored.
+    pub fn from_postgres_str(s: &str, unit: Option<TimestampField>) -> ReadySetResult<Self> {
+        let s = s.trim();
+        if let Ok(quantity) = s.parse::<f64>() {
+            return Self::from_quantity(quantity, unit.unwrap_or(TimestampField::Second));
+        }
+
+        let invalid = || invalid_err!("Invalid interval: {s}");
+        let mut months = 0.0;
+        let mut days = 0.0;
+        let mut microseconds = 0.0;
+        let mut negate = false;
+        let mut tokens = s
+            .strip_prefix('@')
+            .unwrap_or(s)
+            .split_ascii_whitespace()
+            .peekable();
+
+        if tokens.peek().is_none() {
+            return Err(invalid());
+        }
+
+        while let Some(token) = tokens.next() {
+            if token.eq_ignore_ascii_case("ago") {
+                negate = true;
+                continue;
+            }
+
+            if token.contains(':') {
+                microseconds += parse_postgres_time(token).ok_or_else(invalid)? as f64;
+                continue;
+            }
+
+            // The unit can either be attached to the quantity (`7days`) or be the next token
+            let unit_start = token
+                .find(|c: char| c.is_ascii_alphabetic())
+                .unwrap_or(token.len());
+            let (quantity, unit) = match token.split_at(unit_start) {
+                (quantity, "") => (quantity, tokens.next().ok_or_else(invalid)?),
+                (quantity, unit) => (quantity, unit),
+            };
+            let quantity = quantity.parse::<f64>().map_err(|_| invalid())?;
+            let unit = postgres_unit(unit).ok_or_else(invalid)?;
+
+            let interval = Self::from_quantity(quantity, unit)?;
+            months += interval.months as f64;
+            days += interval.days as f64;
+            microseconds += interval.microseconds as f64;
+        }
+
+        let interval = Self::from_parts(months, days, microseconds)?;
+        Ok(if negate { -interval } else { interval })
+    }
+
+    /// Parse an interval from the value and unit of a MySQL `INTERVAL expr unit` expression, as
+    /// described in [the MySQL documentation][mysql].
+    ///
+    /// For compound units such as `DAY_HOUR`, the value is a string with one number per
+    /// component of the unit, separated by any non-digit characters (eg `'1 12'` or `'1:12'`).
+    /// If fewer numbers are given than the unit has components, the numbers are assigned to the
+    /// rightmost components.
+    ///
+    /// [mysql]: https://dev.mysql.com/doc/refman/8.0/en/expressions.html#temporal-intervals
+    pub fn from_mysql_str(value: &str, unit: TimestampField) -> ReadySetResult<Self> {
+        use TimestampField::*;
+
+        let value = value.trim();
+        let invalid = || invalid_err!("Invalid interval value for unit {unit}: {value}");
+
+        let components: &[TimestampField] = match unit {
+            Microsecond | Second | Minute | Hour | Day | Week | Month | Quarter | Year => {
+                let quantity = value.parse::<f64>().map_err(|_| invalid())?;
+                // Only seconds can have a fractional part - everything else is rounded to the
+                // nearest integer
+                let quantity = if unit == Second {
+                    quantity
+                } else {
+                    quantity.round()
+                };
+                return Self::from_quantity(quantity, unit);
+            }
+            SecondMicrosecond => &[Second, Microsecond],
+            MinuteMicrosecond => &[Minute, Second, Microsecond],
+            MinuteSecond => &[Minute, Second],
+            HourMicrosecond => &[Hour, Minute, Second, Microsecond],
+            HourSecond => &[Hour, Minute, Second],
+            HourMinute => &[Hour, Minute],
+            DayMicrosecond => &[Day, Hour, Minute, Second, Microsecond],
+            DaySecond => &[Day, Hour, Minute, Second],
+            DayMinute => &[Day, Hour, Minute],
+            DayHour => &[Day, Hour],
+            YearMonth => &[Year, Month],
+            Millisecond | Decade | Century | Millennium | Dow | IsoDow | Doy | Epoch => {
+                return Err(invalid_err!("Invalid interval unit for MySQL: {unit}"))
+            }
+        };
+
+        let (negate, digits) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let parts = digits
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+        if parts.is_empty() || parts.len() > components.len() {
+            return Err(invalid());
+        }
+
+        let mut interval = Self::default();
+        let skipped = components.len() - parts.len();
+        for (part, component) in parts.iter().zip(&components[skipped..]) {
+            let mut quantity = part.parse::<i64>().map_err(|_| invalid())?;
+            // Like MySQL, treat the microseconds component as a fraction of a second, so that
+            // `'1.5' SECOND_MICROSECOND` is one and a half seconds
+            if *component == Microsecond && part.len() < 6 {
+                quantity *= 10_i64.pow(6 - part.len() as u32);
+            }
+            let component = Self::from_quantity(quantity as f64, *component)?;
+            interval.months += component.months;
+            interval.days += component.days;
+            interval.microseconds += component.microseconds;
+        }
+
+        Ok(if negate { -interval } else { interval })
+    }
+
+    /// Add this interval to the given datetime, returning `None` if the result is out of range.
+    pub fn add_to_datetime(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = datetime.date();
+        let months = date.year() as i64 * 12 + date.month0() as i64 + self.months as i64;
+        let year = i32::try_from(months.div_euclid(12)).ok()?;
+        let month = months.rem_euclid(12) as u32 + 1;
+        // Clamp the day to the last day of the resulting month
+        let date = (1..=date.day())
+            .rev()
+            .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))?;
+
+        date.and_time(datetime.time())
+            .checked_add_signed(Duration::days(self.days as i64))?
+            .checked_add_signed(Duration::microseconds(self.microseconds))
+    }
+}
+
+/// Parse a time of the form `[-]hh:mm[:ss[.ffffff]]` within a PostgreSQL interval string into a
+/// number of microseconds
+fn parse_postgres_time(s: &str) -> Option<i64> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let mut parts = s.split(':');
+    let hours = parts.next()?.parse::<i64>().ok()?;
+    let minutes = parts.next()?.parse::<i64>().ok()?;
+    let seconds = parts.next().map(|s| s.parse::<f64>()).transpose().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let micros = hours * MICROS_PER_HOUR
+        + minutes * MICROS_PER_MINUTE
+        + (seconds.unwrap_or(0.0) * MICROS_PER_SECOND as f64).round() as i64;
+    Some(if negative { -micros } else { micros })
+}
+
+/// Parse the name of a unit within a PostgreSQL interval string
+fn postgres_unit(s: &str) -> Option<TimestampField> {
+    use TimestampField::*;
+
+    Some(match s.to_ascii_lowercase().as_str() {
+        "millennium" | "millennia" | "millenniums" | "mil" | "mils" => Millennium,
+        "century" | "centuries" | "c" | "cent" => Century,
+        "decade" | "decades" | "dec" | "decs" => Decade,
+        "year" | "years" | "y" | "yr" | "yrs" => Year,
+        "month" | "months" | "mon" | "mons" => Month,
+        "week" | "weeks" | "w" => Week,
+        "day" | "days" | "d" => Day,
+        "hour" | "hours" | "h" | "hr" | "hrs" => Hour,
+        "minute" | "minutes" | "m" | "min" | "mins" => Minute,
+        "second" | "seconds" | "s" | "sec" | "secs" => Second,
+        "millisecond" | "milliseconds" | "ms" | "msec" | "msecs" => Millisecond,
+        "microsecond" | "microseconds" | "us" | "usec" | "usecs" => Microsecond,
+        _ => return None,
+    })
+}
+
+impl Neg for Interval {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            months: -self.months,
+            days: -self.days,
+            microseconds: -self.microseconds,
+        }
+    }
+}
+
+/// Formats the interval the same way as PostgreSQL's default (`postgres`) interval output style
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |n: i32| if n == 1 { "" } else { "s" };
+
+        let mut parts = vec![];
+        let (years, months) = (self.months / 12, self.months % 12);
+        if years != 0 {
+            parts.push(format!("{years} year{}", plural(years)));
+        }
+        if months != 0 {
+            parts.push(format!("{months} mon{}", plural(months)));
+        }
+        if self.days != 0 {
+            parts.push(format!("{} day{}", self.days, plural(self.days)));
+        }
+        if self.microseconds != 0 || parts.is_empty() {
+            let sign = if self.microseconds < 0 { "-" } else { "" };
+            let micros = self.microseconds.unsigned_abs();
+            let hours = micros / MICROS_PER_HOUR as u64;
+            let minutes = micros % MICROS_PER_HOUR as u64 / MICROS_PER_MINUTE as u64;
+            let seconds = micros % MICROS_PER_MINUTE as u64 / MICROS_PER_SECOND as u64;
+            let fraction = micros % MICROS_PER_SECOND as u64;
+
+            let mut time = format!("{sign}{hours:02}:{minutes:02}:{seconds:02}");
+            if fraction != 0 {
+                time.push('.');
+                time.push_str(format!("{fraction:06}").trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn postgres_strings() {
+        assert_eq!(
+            Interval::from_postgres_str("7 days", None).unwrap(),
+            Interval::new(0, 7, 0)
+        );
+        assert_eq!(
+            Interval::from_postgres_str("1 year 2 mons 3 days 04:05:06.5", None).unwrap(),
+            Interval::new(
+                14,
+                3,
+                4 * MICROS_PER_HOUR + 5 * MICROS_PER_MINUTE + 6_500_000
+            )
+        );
+        assert_eq!(
+            Interval::from_postgres_str("@ 2 hours ago", None).unwrap(),
+            Interval::new(0, 0, -2 * MICROS_PER_HOUR)
+        );
+        assert_eq!(
+            Interval::from_postgres_str("1.5 days", None).unwrap(),
+            Interval::new(0, 1, 12 * MICROS_PER_HOUR)
+        );
+        assert_eq!(
+            Interval::from_postgres_str("3weeks", None).unwrap(),
+            Interval::new(0, 21, 0)
+        );
+        assert_eq!(
+            Interval::from_postgres_str("2", Some(TimestampField::Hour)).unwrap(),
+            Interval::new(0, 0, 2 * MICROS_PER_HOUR)
+        );
+        assert_eq!(
+            Interval::from_postgres_str("30", None).unwrap(),
+            Interval::new(0, 0, 30 * MICROS_PER_SECOND)
+        );
+        Interval::from_postgres_str("7 fortnights", None).unwrap_err();
+        Interval::from_postgres_str("", None).unwrap_err();
+        Interval::from_postgres_str("days", None).unwrap_err();
+    }
+
+    #[test]
+    fn mysql_values() {
+        use TimestampField::*;
+
+        assert_eq!(
+            Interval::from_mysql_str("7", Day).unwrap(),
+            Interval::new(0, 7, 0)
+        );
+        assert_eq!(
+            Interval::from_mysql_str("-1", Quarter).unwrap(),
+            Interval::new(-3, 0, 0)
+        );
+        assert_eq!(
+            Interval::from_mysql_str("1.5", Second).unwrap(),
+            Interval::new(0, 0, 1_500_000)
+        );
+        assert_eq!(
+            Interval::from_mysql_str("1:30", HourMinute).unwrap(),
+            Interval::new(0, 0, 90 * MICROS_PER_MINUTE)
+        );
+        assert_eq!(
+            Interval::from_mysql_str("-1 2", DayHour).unwrap(),
+            Interval::new(0, -1, -2 * MICROS_PER_HOUR)
+        );
+        assert_eq!(
+            Interval::from_mysql_str("5", DayMinute).unwrap(),
+            Interval::new(0, 0, 5 * MICROS_PER_MINUTE)
+        );
+        assert_eq!(
+            Interval::from_mysql_str("1.5", SecondMicrosecond).unwrap(),
+            Interval::new(0, 0, 1_500_000)
+        );
+        assert_eq!(
+            Interval::from_mysql_str("2-3", YearMonth).unwrap(),
+            Interval::new(27, 0, 0)
+        );
+        Interval::from_mysql_str("1 2 3", DayHour).unwrap_err();
+        Interval::from_mysql_str("1", Epoch).unwrap_err();
+    }
+
+    #[test]
+    fn add_to_datetime() {
+        assert_eq!(
+            Interval::new(1, 0, 0).add_to_datetime(datetime("2022-01-31 12:00:00")),
+            Some(datetime("2022-02-28 12:00:00"))
+        );
+        assert_eq!(
+            Interval::new(-13, 0, 0).add_to_datetime(datetime("2024-03-31 00:00:00")),
+            Some(datetime("2023-02-28 00:00:00"))
+        );
+        assert_eq!(
+            Interval::new(0, 1, MICROS_PER_HOUR).add_to_datetime(datetime("2022-12-31 23:30:00")),
+            Some(datetime("2023-01-02 00:30:00"))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Interval::default().to_string(), "00:00:00");
+        assert_eq!(Interval::new(0, 7, 0).to_string(), "7 days");
+        assert_eq!(
+            Interval::new(14, 1, -(4 * MICROS_PER_HOUR + 500_000)).to_string(),
+            "1 year 2 mons 1 day -04:00:00.5"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Support for the canonical representation of values of the PostgreSQL `jsonb` type.
+//!
+//! Unlike `json`, which preserves the exact text it was given, `jsonb` values are normalized on
+//! input - object keys are deduplicated and sorted, insignificant whitespace is discarded, and
+//! numbers are stored as `numeric`. We store `jsonb` values as text in that normalized form, so
+//! that two `jsonb` values which are equal in PostgreSQL are also equal (and hash equally) as
+//! [`DfValue`](crate::DfValue)s.
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde_json::{Number as JsonNumber, Value as JsonValue};
+
+/// Serialize the given JSON value in the same format PostgreSQL uses to output values of the
+/// `jsonb` type.
+///
+/// Object keys are sorted shortest first then bytewise (the order PostgreSQL stores them in),
+/// a single space is written after every `,` and `:`, and numbers are written in their canonical
+/// `numeric` form. Note that, as in PostgreSQL, the scale of numbers is preserved, so `1.0` and
+/// `1.00` are written differently.
+///
+/// # Examples
+///
+/// ```
+/// use readyset_data::jsonb_to_string;
+/// use serde_json::json;
+///
+/// assert_eq!(
+///     jsonb_to_string(&json!({"bb": [1, 2e2], "a": {"c": null}})),
+///     r#"{"a": {"c": null}, "bb": [1, 200]}"#
+/// );
+/// ```
+pub fn jsonb_to_string(json: &JsonValue) -> String {
+    let mut out = String::new();
+    write_jsonb(json, &mut out);
+    out
+}
+
+fn write_jsonb(json: &JsonValue, out: &mut String) {
+    match json {
+        JsonValue::Null => out.push_str("null"),
+        JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        JsonValue::Number(n) => out.push_str(&canonical_number(n)),
+        JsonValue::String(s) => write_jsonb_string(s, out),
+        JsonValue::Array(elems) => {
+            out.push('[');
+            for (i, elem) in elems.iter().enumerate() {
+                if i != 0 {
+                    out.push_str(", ");
+                }
+                write_jsonb(elem, out);
+            }
+            out.push(']');
+        }
+        JsonValue::Object(obj) => {
+            out.push('{');
+            for (i, (k, v)) in sorted_entries(obj).into_iter().enumerate() {
+                if i != 0 {
+                    out.push_str(", ");
+                }
+                write_jsonb_string(k, out);
+                out.push_str(": ");
+                write_jsonb(v, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_jsonb_string(s: &str, out: &mut String) {
+    // Serializing a string can't fail
+    #[allow(clippy::unwrap_used)]
+    out.push_str(&serde_json::to_string(s).unwrap());
+}
+
+/// Returns the entries of the given object in the order PostgreSQL stores (and outputs) them in:
+/// shorter keys first, and keys of equal length sorted bytewise.
+fn sorted_entries(obj: &serde_json::Map<String, JsonValue>) -> Vec<(&String, &JsonValue)> {
+    let mut entries = obj.iter().collect::<Vec<_>>();
+    entries.sort_by(|(k1, _), (k2, _)| k1.len().cmp(&k2.len()).then_with(|| k1.cmp(k2)));
+    entries
+}
+
+/// Returns the text of the given JSON number as it would be written by PostgreSQL's `numeric`
+/// type.
+///
+/// Numbers which can't be represented as a [`Decimal`] are written as they were given.
+fn canonical_number(n: &JsonNumber) -> String {
+    let s = n.to_string();
+    if s.contains(['e', 'E']) {
+        match Decimal::from_scientific(&s) {
+            Ok(mut d) => {
+                if d.is_zero() {
+                    d.set_sign_positive(true);
+                }
+                d.to_string()
+            }
+            Err(_) => s,
+        }
+    } else if let Some(abs) = s
+        .strip_prefix('-')
+        .filter(|abs| abs.bytes().all(|b| b == b'0' || b == b'.'))
+    {
+        // `numeric` has no negative zero
+        abs.to_owned()
+    } else {
+        s
+    }
+}
+
+/// The ordering of the different kinds of JSON values relative to each other, as defined by
+/// PostgreSQL for `jsonb`.
+fn kind_rank(json: &JsonValue) -> u8 {
+    match json {
+        JsonValue::Null => 0,
+        JsonValue::String(_) => 1,
+        JsonValue::Number(_) => 2,
+        JsonValue::Bool(_) => 3,
+        JsonValue::Array(_) => 4,
+        JsonValue::Object(_) => 5,
+    }
+}
+
+fn number_cmp(n1: &JsonNumber, n2: &JsonNumber) -> Ordering {
+    let parse = |n: &JsonNumber| {
+        let s = n.to_string();
+        Decimal::from_str_exact(&s).or_else(|_| Decimal::from_scientific(&s))
+    };
+
+    match (parse(n1), parse(n2)) {
+        (Ok(d1), Ok(d2)) => d1.cmp(&d2),
+        // Fall back to comparing as floats for numbers too large (or too precise) to be decimals
+        (d1, d2) => {
+            let as_f64 = |n: &JsonNumber, d: Result<Decimal, rust_decimal::Error>| {
+                n.as_f64()
+                    .or_else(|| d.ok().and_then(|d| d.to_f64()))
+                    .or_else(|| f64::from_str(&n.to_string()).ok())
+                    .unwrap_or(f64::NAN)
+            };
+            as_f64(n1, d1).total_cmp(&as_f64(n2, d2))
+        }
+    }
+}
+
+/// Compare two JSON values using the ordering PostgreSQL defines for the `jsonb` type:
+///
+/// * `Object > Array > Boolean > Number > String > Null`
+/// * Objects with more key/value pairs are greater than objects with fewer pairs. Objects with the
+///   same number of pairs are compared by key then value, in the order the keys are stored (see
+///   [`jsonb_to_string`])
+/// * Arrays with more elements are greater than arrays with fewer elements. Arrays with the same
+///   number of elements are compared element by element
+/// * Numbers are compared numerically, strings bytewise, and `false` is less than `true`
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use readyset_data::jsonb_cmp;
+/// use serde_json::json;
+///
+/// assert_eq!(jsonb_cmp(&json!(1.0), &json!(1)), Ordering::Equal);
+/// assert_eq!(jsonb_cmp(&json!([1, 2]), &json!([3])), Ordering::Greater);
+/// assert_eq!(jsonb_cmp(&json!({}), &json!([1])), Ordering::Greater);
+/// ```
+pub fn jsonb_cmp(a: &JsonValue, b: &JsonValue) -> Ordering {
+    match (a, b) {
+        (JsonValue::Null, JsonValue::Null) => Ordering::Equal,
+        (JsonValue::String(s1), JsonValue::String(s2)) => s1.cmp(s2),
+        (JsonValue::Number(n1), JsonValue::Number(n2)) => number_cmp(n1, n2),
+        (JsonValue::Bool(b1), JsonValue::Bool(b2)) => b1.cmp(b2),
+        (JsonValue::Array(elems1), JsonValue::Array(elems2)) => elems1
+            .len()
+            .cmp(&elems2.len())
+            .then_with(|| elems1.iter().cmp_by(elems2, |e1, e2| jsonb_cmp(e1, e2))),
+        (JsonValue::Object(obj1), JsonValue::Object(obj2)) => {
+            obj1.len().cmp(&obj2.len()).then_with(|| {
+                sorted_entries(obj1).into_iter().cmp_by(
+                    sorted_entries(obj2),
+                    |(k1, v1), (k2, v2)| {
+                        k1.len()
+                            .cmp(&k2.len())
+                            .then_with(|| k1.cmp(k2))
+                            .then_with(|| jsonb_cmp(v1, v2))
+                    },
+                )
+            })
+        }
+        _ => kind_rank(a).cmp(&kind_rank(b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn canonical(s: &str) -> String {
+        jsonb_to_string(&serde_json::from_str(s).unwrap())
+    }
+
+    #[test]
+    fn canonical_objects() {
+        assert_eq!(
+            canonical(r#"{"bbb":1,"a":2,"bb":3,"ba":4}"#),
+            r#"{"a": 2, "ba": 4, "bb": 3, "bbb": 1}"#
+        );
+        assert_eq!(canonical(r#"{"a":1,"a":2}"#), r#"{"a": 2}"#);
+        assert_eq!(canonical("{}"), "{}");
+        assert_eq!(canonical("[ ]"), "[]");
+        assert_eq!(
+            canonical(r#"  {"x" : [ 1,{"z":null, "y": true}] }"#),
+            r#"{"x": [1, {"y": true, "z": null}]}"#
+        );
+    }
+
+    #[test]
+    fn canonical_strings() {
+        assert_eq!(canonical(r#""aA\n\"\/é""#), r#""aA\n\"/é""#);
+    }
+
+    #[test]
+    fn canonical_numbers() {
+        assert_eq!(canonical("1e2"), "100");
+        assert_eq!(canonical("1.50E1"), "15.0");
+        assert_eq!(canonical("25e-2"), "0.25");
+        assert_eq!(canonical("-0"), "0");
+        assert_eq!(canonical("-0.00"), "0.00");
+        assert_eq!(canonical("-0e5"), "0");
+        assert_eq!(canonical("1.0"), "1.0");
+        assert_eq!(canonical("-12.5"), "-12.5");
+        assert_eq!(
+            canonical("123456789012345678901234567890123"),
+            "123456789012345678901234567890123"
+        );
+    }
+
+    #[test]
+    fn cmp_kinds() {
+        let ordered = [
+            json!(null),
+            json!("a"),
+            json!(1),
+            json!(false),
+            json!([]),
+            json!({}),
+        ];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(jsonb_cmp(a, b), i.cmp(&j), "{} <=> {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn cmp_numbers() {
+        let n = |s: &str| serde_json::from_str::<JsonValue>(s).unwrap();
+        assert_eq!(jsonb_cmp(&n("1.0"), &n("1")), Ordering::Equal);
+        assert_eq!(jsonb_cmp(&n("1e2"), &n("99.5")), Ordering::Greater);
+        assert_eq!(jsonb_cmp(&n("-3"), &n("2")), Ordering::Less);
+        assert_eq!(jsonb_cmp(&n("1e100"), &n("1e99")), Ordering::Greater);
+    }
+
+    #[test]
+    fn cmp_collections() {
+        assert_eq!(jsonb_cmp(&json!([5]), &json!([1, 2])), Ordering::Less);
+        assert_eq!(jsonb_cmp(&json!([1, 3]), &json!([1, 2])), Ordering::Greater);
+        assert_eq!(
+            jsonb_cmp(&json!({"bb": 1}), &json!({"a": 1, "b": 1})),
+            Ordering::Less
+        );
+        // Shorter keys sort first, regardless of their contents
+        assert_eq!(
+            jsonb_cmp(&json!({"z": 1}), &json!({"aa": 1})),
+            Ordering::Less
+        );
+        assert_eq!(
+            jsonb_cmp(&json!({"a": 1, "b": 2}), &json!({"b": 2, "a": 1})),
+            Ordering::Equal
+        );
+    }
+}
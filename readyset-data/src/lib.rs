@@ -35,6 +35,9 @@ pub mod dialect;
 mod r#enum;
 mod float;
 mod integer;
+mod interval;
+mod json;
+mod range;
 mod serde;
 mod text;
 mod timestamp;
@@ -45,7 +48,10 @@ pub use ndarray::{ArrayD, IxDyn};
 pub use crate::array::Array;
 pub use crate::collation::Collation;
 pub use crate::dialect::Dialect;
+pub use crate::interval::Interval;
+pub use crate::json::{jsonb_cmp, jsonb_to_string};
 pub use crate::r#type::{DfType, PgEnumMetadata, PgTypeCategory};
+pub use crate::range::Range;
 pub use crate::text::{Text, TinyText};
 pub use crate::timestamp::{TimestampTz, TIMESTAMP_FORMAT, TIMESTAMP_PARSE_FORMAT};
 
@@ -105,6 +111,8 @@ pub enum DfValue {
     BitVector(Arc<BitVec>),
     /// An array of [`DfValue`]s.
     Array(Arc<Array>),
+    /// A value of a PostgreSQL range type.
+    Range(Arc<Range>),
     /// Container type for arbitrary unserialized, unsupported types
     PassThrough(Arc<PassThrough>),
     /// A sentinel maximal value.
@@ -151,6 +159,7 @@ impl fmt::Display for DfValue {
                 )
             }
             DfValue::Array(ref arr) => write!(f, "{}", arr),
+            DfValue::Range(ref range) => write!(f, "{}", range),
             DfValue::PassThrough(ref p) => {
                 write!(f, "[{}:{:x?}]", p.ty.name(), p.data)
             }
@@ -210,6 +219,7 @@ impl DfValue {
             DfValue::Numeric(_) => DfValue::from(Decimal::MIN),
             DfValue::BitVector(_) => DfValue::from(BitVec::new()),
             DfValue::Array(_) => DfValue::empty_array(),
+            DfValue::Range(_) => DfValue::from(Range::Empty),
             DfValue::PassThrough(p) => DfValue::PassThrough(Arc::new(PassThrough {
                 ty: p.ty.clone(),
                 data: [].into(),
@@ -238,6 +248,7 @@ impl DfValue {
             | DfValue::ByteArray(_)
             | DfValue::BitVector(_)
             | DfValue::Array(_)
+            | DfValue::Range(_)
             | DfValue::PassThrough(_)
             | DfValue::Max => DfValue::Max,
         }
@@ -315,7 +326,7 @@ impl DfValue {
             // Truthiness only matters for mysql, and mysql doesn't have arrays, so we can kind of
             // pick whatever we want here - but it makes the most sense to try to limit falsiness to
             // only the things that mysql considers falsey
-            DfValue::Array(_) | DfValue::PassThrough(_) => true,
+            DfValue::Array(_) | DfValue::Range(_) | DfValue::PassThrough(_) => true,
         }
     }
 
@@ -383,6 +394,13 @@ impl DfValue {
             Self::Array(vs) => Some(SqlType::Array(Box::new(
                 vs.values().find_map(|v| v.sql_type())?,
             ))),
+            Self::Range(range) => match range.infer_subtype() {
+                DfType::BigInt => Some(Int8Range),
+                DfType::Numeric { .. } => Some(NumRange),
+                DfType::Timestamp { .. } => Some(TsRange),
+                DfType::TimestampTz { .. } => Some(TsTzRange),
+                _ => None,
+            },
         }
     }
 
@@ -420,6 +438,7 @@ impl DfValue {
                     .find(DfType::is_known)
                     .unwrap_or_default(),
             )),
+            Self::Range(range) => DfType::Range(Box::new(range.infer_subtype())),
         }
     }
 
@@ -517,6 +536,14 @@ impl DfValue {
                 )),
                 _ => Err(mk_err()),
             },
+            DfValue::Range(range) => match to_ty {
+                DfType::Range(subtype) => Ok(DfValue::from(range.coerce_to(subtype)?)),
+                DfType::Text(collation) => Ok(DfValue::from_str_and_collation(
+                    &range.to_string(),
+                    *collation,
+                )),
+                _ => Err(mk_err()),
+            },
             _ if is_clone_coercible() => Ok(self.clone()),
             DfValue::Text(t) => t.coerce_to(to_ty, from_ty),
             DfValue::TinyText(tt) => tt.coerce_to(to_ty, from_ty),
@@ -562,6 +589,14 @@ impl DfValue {
                 .unwrap_or(DfValue::Int(0));
         } else if col_ty.is_array() && col_ty.innermost_array_type().is_enum() {
             *self = self.coerce_to(col_ty, &DfType::Unknown)?;
+        } else if let Some(collation) = col_ty.collation() {
+            // Store text values with the collation of their column, so that comparisons and
+            // hashing (and hence lookups) respect the column's collation
+            if let Some((s, c)) = self.as_str_and_collation() {
+                if c != collation {
+                    *self = DfValue::from_str_and_collation(s, collation);
+                }
+            }
         }
 
         Ok(())
@@ -615,6 +650,22 @@ impl DfValue {
         }
     }
 
+    /// If `self` is [`DfValue::Range`], return a reference to the underlying [`Range`], otherwise
+    /// return a [`ReadySetError::DfValueConversionError`] for all other [`DfValue`] variants.
+    pub fn as_range(&self) -> ReadySetResult<&Range> {
+        match self {
+            DfValue::Range(range) => Ok(range),
+            _ => Err(ReadySetError::DfValueConversionError {
+                src_type: match self.sql_type() {
+                    Some(ty) => ty.to_string(),
+                    None => "Null".to_string(),
+                },
+                target_type: "Range".to_string(),
+                details: "".to_string(),
+            }),
+        }
+    }
+
     /// Transform this [`DfValue`] in preparation for being serialized to disk as part of an index.
     ///
     /// This function has the property that if `d1 == d2`, then
@@ -682,9 +733,38 @@ impl DfValue {
     /// assert!(DfValue::from("🤯").to_json().is_err()); // Not a valid JSON string
     /// ```
     pub fn to_json(&self) -> ReadySetResult<JsonValue> {
-        let text_val = self.coerce_to(&DfType::DEFAULT_TEXT, &DfType::Unknown)?;
-        let json_str = <&str>::try_from(&text_val)?;
-        serde_json::from_str(json_str).map_err(|e| {
+        self.as_json().map(Cow::into_owned)
+    }
+
+    /// Attempts to convert self to a str and parse as JSON, like [`DfValue::to_json`], but
+    /// borrowing the result when possible.
+    ///
+    /// [`DfValue::Text`] values cache the result of parsing their contents as JSON, so calling this
+    /// method repeatedly on the same (or a cloned) value only parses it once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use readyset_data::DfValue;
+    /// use serde_json::json;
+    ///
+    /// let val = DfValue::from(r#"{"name": "a long enough string to be a Text"}"#);
+    /// assert_eq!(
+    ///     *val.as_json().unwrap(),
+    ///     json!({"name": "a long enough string to be a Text"})
+    /// );
+    /// ```
+    pub fn as_json(&self) -> ReadySetResult<Cow<'_, JsonValue>> {
+        let res = match self {
+            DfValue::Text(t) => t.as_json().map(Cow::Borrowed),
+            DfValue::TinyText(tt) => serde_json::from_str(tt.as_str()).map(Cow::Owned),
+            _ => {
+                let text_val = self.coerce_to(&DfType::DEFAULT_TEXT, &DfType::Unknown)?;
+                serde_json::from_str(<&str>::try_from(&text_val)?).map(Cow::Owned)
+            }
+        };
+
+        res.map_err(|e| {
             invalid_err!(
                 "Could not convert value to JSON: {}. JSON error: {}",
                 Sensitive(self),
@@ -692,6 +772,43 @@ impl DfValue {
             )
         })
     }
+
+    /// Construct a new [`DfValue`] representing the given value of the PostgreSQL `jsonb` type.
+    ///
+    /// The value is stored in the canonical text form of `jsonb` (see [`jsonb_to_string`]), so
+    /// that `jsonb` values which are equal are also equal as [`DfValue`]s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use readyset_data::DfValue;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(
+    ///     DfValue::from_jsonb(json!({"b": 1, "a": [1, 2]})),
+    ///     DfValue::from(r#"{"a": [1, 2], "b": 1}"#)
+    /// );
+    /// ```
+    pub fn from_jsonb(json: JsonValue) -> Self {
+        Self::from_json_and_string(jsonb_to_string(&json), json)
+    }
+
+    /// Construct a new [`DfValue`] from a JSON value and its serialized form, prepopulating the
+    /// parsed JSON cache of the resulting value if it's a [`DfValue::Text`].
+    fn from_json_and_string(s: String, json: JsonValue) -> Self {
+        match TinyText::try_from(s.as_str()) {
+            Ok(tt) => Self::TinyText(tt),
+            Err(_) => Self::Text(Text::from_json(&s, json)),
+        }
+    }
+
+    /// Compare `self` and `other` as values of the PostgreSQL `jsonb` type, using the same
+    /// ordering as PostgreSQL (see [`jsonb_cmp`]).
+    ///
+    /// Returns an error if either value is not valid JSON.
+    pub fn jsonb_cmp(&self, other: &Self) -> ReadySetResult<Ordering> {
+        Ok(jsonb_cmp(&*self.as_json()?, &*other.as_json()?))
+    }
 }
 
 impl PartialEq for DfValue {
@@ -794,6 +911,7 @@ impl PartialEq for DfValue {
                 bits_a.as_ref() == bits_b.as_ref()
             }
             (&DfValue::Array(ref vs_a), &DfValue::Array(ref vs_b)) => vs_a == vs_b,
+            (&DfValue::Range(ref r_a), &DfValue::Range(ref r_b)) => r_a == r_b,
             (&DfValue::None, &DfValue::None) => true,
             (&DfValue::Max, &DfValue::Max) => true,
             _ => false,
@@ -956,6 +1074,7 @@ impl Ord for DfValue {
                 bits_a.cmp(bits_b)
             }
             (&DfValue::Array(ref vs_a), &DfValue::Array(ref vs_b)) => vs_a.cmp(vs_b),
+            (&DfValue::Range(ref r_a), &DfValue::Range(ref r_b)) => r_a.cmp(r_b),
 
             // for all other kinds of data types, just compare the variants in order
             (_, _) => DfValueKind::from(self).cmp(&DfValueKind::from(other)),
@@ -990,6 +1109,7 @@ impl Hash for DfValue {
             DfValue::Numeric(ref d) => d.hash(state),
             DfValue::BitVector(ref bits) => bits.hash(state),
             DfValue::Array(ref vs) => vs.hash(state),
+            DfValue::Range(ref r) => r.hash(state),
             DfValue::PassThrough(ref p) => p.hash(state),
         }
     }
@@ -1225,6 +1345,7 @@ impl TryFrom<DfValue> for Literal {
             DfValue::Numeric(ref d) => Ok(Literal::Numeric(d.mantissa(), d.scale())),
             DfValue::BitVector(ref bits) => Ok(Literal::BitVector(bits.as_ref().to_bytes())),
             DfValue::Array(_) => unsupported!("Arrays not implemented yet"),
+            DfValue::Range(ref range) => Ok(Literal::String(range.to_string())),
             DfValue::PassThrough(_) => internal!("PassThrough has no representation as a literal"),
             DfValue::Max => internal!("MAX has no representation as a literal"),
         }
@@ -1554,6 +1675,12 @@ impl From<Array> for DfValue {
     }
 }
 
+impl From<Range> for DfValue {
+    fn from(range: Range) -> Self {
+        Self::Range(Arc::new(range))
+    }
+}
+
 impl From<Vec<DfValue>> for DfValue {
     fn from(vs: Vec<DfValue>) -> Self {
         Self::from(Array::from(vs))
@@ -1562,7 +1689,7 @@ impl From<Vec<DfValue>> for DfValue {
 
 impl From<JsonValue> for DfValue {
     fn from(value: JsonValue) -> Self {
-        (&value).into()
+        Self::from_json_and_string(value.to_string(), value)
     }
 }
 
@@ -1715,7 +1842,9 @@ impl ToSql for DfValue {
                     .and_then(|m| m.to_sql(ty, out))
             }
             (Self::Text(_) | Self::TinyText(_), &Type::JSON | &Type::JSONB) => {
-                serde_json::from_str::<serde_json::Value>(<&str>::try_from(self).unwrap())
+                // Use `as_json` rather than parsing here, to take advantage of the parsed JSON
+                // cached on `Text` values
+                self.as_json()
                     .map_err(|e| {
                         Box::<dyn Error + Send + Sync>::from(format!(
                             "Could not convert Text into a JSON: {}",
@@ -1736,6 +1865,7 @@ impl ToSql for DfValue {
             (Self::ByteArray(ref array), _) => array.as_ref().to_sql(ty, out),
             (Self::BitVector(ref bits), _) => bits.as_ref().to_sql(ty, out),
             (Self::Array(ref array), _) => array.as_ref().to_sql(ty, out),
+            (Self::Range(ref range), _) => range.as_ref().to_sql(ty, out),
             (Self::PassThrough(p), _) => p.data.as_ref().to_sql(&p.ty, out),
         }
     }
@@ -1762,6 +1892,7 @@ impl<'a> FromSql<'a> for DfValue {
         }
         match ty.kind() {
             Kind::Array(_) => mk_from_sql!(Array),
+            Kind::Range(_) => mk_from_sql!(Range),
             Kind::Enum(variants) => {
                 let variant_str = str::from_utf8(raw)?;
                 Ok(DfValue::from(
@@ -1806,9 +1937,10 @@ impl<'a> FromSql<'a> for DfValue {
                 )),
                 Type::INET => Ok(DfValue::from(IpAddr::from_sql(ty, raw)?.to_string())),
                 Type::UUID => Ok(DfValue::from(Uuid::from_sql(ty, raw)?.to_string())),
-                Type::JSON | Type::JSONB => Ok(DfValue::from(
+                Type::JSON => Ok(DfValue::from(
                     serde_json::Value::from_sql(ty, raw)?.to_string(),
                 )),
+                Type::JSONB => Ok(DfValue::from_jsonb(serde_json::Value::from_sql(ty, raw)?)),
                 Type::BIT | Type::VARBIT => mk_from_sql!(BitVec),
                 ref ty if ty.name() == "citext" => Ok(DfValue::from_str_and_collation(
                     <&str>::from_sql(ty, raw)?,
@@ -1869,6 +2001,7 @@ impl TryFrom<&DfValue> for mysql_common::value::Value {
             }
             DfValue::BitVector(_) => internal!("MySQL does not support bit vector types"),
             DfValue::Array(_) => internal!("MySQL does not support array types"),
+            DfValue::Range(_) => internal!("MySQL does not support range types"),
         }
     }
 }
@@ -2038,6 +2171,7 @@ impl Arbitrary for DfValue {
                 .prop_map(|bs| DfValue::BitVector(Arc::new(BitVec::from_bytes(&bs))))
                 .boxed(),
             Some(DfValueKind::Array) => any::<Array>().prop_map(DfValue::from).boxed(),
+            Some(DfValueKind::Range) => any::<Range>().prop_map(DfValue::from).boxed(),
             Some(DfValueKind::PassThrough) => any::<(u32, Vec<u8>)>()
                 .prop_map(|(oid, data)| {
                     DfValue::PassThrough(Arc::new(PassThrough {
@@ -2065,7 +2199,8 @@ impl Arbitrary for DfValue {
                     .prop_map(DfValue::Time),
                 any::<Vec<u8>>().prop_map(|b| DfValue::ByteArray(Arc::new(b))),
                 arbitrary_decimal().prop_map(DfValue::from),
-                any::<Array>().prop_map(DfValue::from)
+                any::<Array>().prop_map(DfValue::from),
+                any::<Range>().prop_map(DfValue::from)
             ]
             .boxed(),
         }
@@ -2199,6 +2334,7 @@ mod tests {
             | DfValue::Numeric(_)
             | DfValue::BitVector(_)
             | DfValue::Array(_)
+            | DfValue::Range(_)
             | DfValue::Max => false,
             _ => true,
         });
@@ -3369,8 +3505,26 @@ mod tests {
             let result = input.coerce_to(&DfType::Json, &DfType::Unknown).unwrap();
             assert_eq!(input, result);
 
+            // jsonb values are converted to their canonical form
             let result = input.coerce_to(&DfType::Jsonb, &DfType::Unknown).unwrap();
-            assert_eq!(input, result);
+            assert_eq!(
+                result,
+                DfValue::from(
+                    "{\"age\": 43, \"name\": \"John Doe\", \"phones\": [\"+44 1234567\", \"+44 2345678\"]}"
+                )
+            );
+            assert_eq!(
+                result.coerce_to(&DfType::Jsonb, &DfType::Unknown).unwrap(),
+                result
+            );
+            assert_eq!(
+                DfValue::from("{\"b\":1,\"a\":2}")
+                    .coerce_to(&DfType::Jsonb, &DfType::Unknown)
+                    .unwrap(),
+                DfValue::from("{\"b\": 1, \"a\": 2}")
+                    .coerce_to(&DfType::Jsonb, &DfType::Unknown)
+                    .unwrap()
+            );
 
             let input = DfValue::from("not a json");
             let result = input.coerce_to(&DfType::Json, &DfType::Unknown);
@@ -3380,6 +3534,41 @@ mod tests {
             result.unwrap_err();
         }
 
+        #[test]
+        fn text_to_range() {
+            let int4range = DfType::Range(Box::new(DfType::Int));
+            let result = DfValue::from("(1,5]")
+                .coerce_to(&int4range, &DfType::Unknown)
+                .unwrap();
+            assert_eq!(
+                result,
+                DfValue::from(
+                    Range::new(
+                        std::ops::Bound::Included(2.into()),
+                        std::ops::Bound::Excluded(6.into()),
+                        &DfType::Int
+                    )
+                    .unwrap()
+                )
+            );
+            assert_eq!(result.to_string(), "[2,6)");
+            assert_eq!(
+                result.coerce_to(&DfType::DEFAULT_TEXT, &int4range).unwrap(),
+                DfValue::from("[2,6)")
+            );
+            assert_eq!(
+                result.infer_dataflow_type(),
+                DfType::Range(Box::new(DfType::BigInt))
+            );
+
+            DfValue::from("[5,1)")
+                .coerce_to(&int4range, &DfType::Unknown)
+                .unwrap_err();
+            DfValue::from("[1,a)")
+                .coerce_to(&int4range, &DfType::Unknown)
+                .unwrap_err();
+        }
+
         #[test]
         fn text_to_macaddr() {
             let input = DfValue::from("12:34:56:ab:cd:ef");
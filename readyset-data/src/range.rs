@@ -0,0 +1,653 @@
+//! Support for [PostgreSQL range types][pg-ranges], such as `int4range` or `tstzrange`.
+//!
+//! [pg-ranges]: https://www.postgresql.org/docs/current/rangetypes.html
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::Bound;
+
+use bytes::BytesMut;
+use postgres_protocol::types::RangeBound;
+use proptest::arbitrary::Arbitrary;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use readyset_errors::{invalid_err, ReadySetResult};
+use serde::{Deserialize, Serialize};
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, Kind, ToSql, Type};
+
+use crate::{DfType, DfValue, TimestampTz};
+
+/// Internal representation of values of PostgreSQL range types.
+///
+/// Like in PostgreSQL, ranges are always stored in a canonical form, so that two ranges which
+/// contain the same set of values are also equal (and hash equally) as [`Range`]s:
+///
+/// * Ranges containing no values are always [`Range::Empty`]
+/// * Ranges over discrete subtypes (integers and dates) always have an inclusive lower bound and an
+///   exclusive upper bound, eg `[1,3]` is stored as `[1,4)`
+///
+/// Null bound values are treated as unbounded, also as in PostgreSQL.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Range {
+    /// The range containing no values
+    Empty,
+    /// A range containing all values between (and possibly including) its lower and upper bounds
+    NonEmpty {
+        lower: Bound<DfValue>,
+        upper: Bound<DfValue>,
+    },
+}
+
+/// Returns the value of the given bound, if it's bounded
+fn bound_value(bound: &Bound<DfValue>) -> Option<&DfValue> {
+    match bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(v),
+        Bound::Unbounded => None,
+    }
+}
+
+/// Compares two lower bounds, where lesser bounds include more values below the bound
+fn cmp_lower(b1: &Bound<DfValue>, b2: &Bound<DfValue>) -> Ordering {
+    match (b1, b2) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(v1), Bound::Included(v2)) | (Bound::Excluded(v1), Bound::Excluded(v2)) => {
+            v1.cmp(v2)
+        }
+        (Bound::Included(v1), Bound::Excluded(v2)) => v1.cmp(v2).then(Ordering::Less),
+        (Bound::Excluded(v1), Bound::Included(v2)) => v1.cmp(v2).then(Ordering::Greater),
+    }
+}
+
+/// Compares two upper bounds, where greater bounds include more values above the bound
+fn cmp_upper(b1: &Bound<DfValue>, b2: &Bound<DfValue>) -> Ordering {
+    match (b1, b2) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(v1), Bound::Included(v2)) | (Bound::Excluded(v1), Bound::Excluded(v2)) => {
+            v1.cmp(v2)
+        }
+        (Bound::Included(v1), Bound::Excluded(v2)) => v1.cmp(v2).then(Ordering::Greater),
+        (Bound::Excluded(v1), Bound::Included(v2)) => v1.cmp(v2).then(Ordering::Less),
+    }
+}
+
+/// Returns true if there is any value which is both above the given lower bound and below the
+/// given upper bound
+fn lower_below_upper(lower: &Bound<DfValue>, upper: &Bound<DfValue>) -> bool {
+    match (lower, upper) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Included(l), Bound::Included(u)) => l <= u,
+        (Bound::Included(l) | Bound::Excluded(l), Bound::Included(u) | Bound::Excluded(u)) => l < u,
+    }
+}
+
+/// Returns the value directly after `val` if `subtype` is a discrete type (one where every value
+/// has a well-defined successor), or `None` if `subtype` is continuous.
+fn successor(val: &DfValue, subtype: &DfType) -> ReadySetResult<Option<DfValue>> {
+    match (subtype, val) {
+        (DfType::Int | DfType::BigInt, DfValue::Int(i)) => {
+            let next = i
+                .checked_add(1)
+                .ok_or_else(|| invalid_err!("integer out of range"))?;
+            Ok(Some(
+                DfValue::Int(next).coerce_to(subtype, &DfType::BigInt)?,
+            ))
+        }
+        (DfType::Date, DfValue::TimestampTz(ts)) => {
+            let next = ts
+                .to_chrono()
+                .naive_local()
+                .date()
+                .succ_opt()
+                .ok_or_else(|| invalid_err!("date out of range"))?;
+            Ok(Some(DfValue::TimestampTz(TimestampTz::from(next))))
+        }
+        _ => Ok(None),
+    }
+}
+
+impl Range {
+    /// Construct a new range with the given bounds, coercing the bound values to `subtype` and
+    /// converting the range into its canonical form.
+    ///
+    /// Returns an error if the lower bound of the range is greater than its upper bound, or if
+    /// either of the bound values can't be coerced to `subtype`.
+    pub fn new(
+        lower: Bound<DfValue>,
+        upper: Bound<DfValue>,
+        subtype: &DfType,
+    ) -> ReadySetResult<Self> {
+        let coerce = |bound: Bound<DfValue>| -> ReadySetResult<Bound<DfValue>> {
+            let coerce_val = |v: DfValue| {
+                if subtype.is_known() {
+                    v.coerce_to(subtype, &v.infer_dataflow_type())
+                } else {
+                    Ok(v)
+                }
+            };
+            Ok(match bound {
+                Bound::Included(DfValue::None) | Bound::Excluded(DfValue::None) => Bound::Unbounded,
+                Bound::Included(v) => Bound::Included(coerce_val(v)?),
+                Bound::Excluded(v) => Bound::Excluded(coerce_val(v)?),
+                Bound::Unbounded => Bound::Unbounded,
+            })
+        };
+        let mut lower = coerce(lower)?;
+        let mut upper = coerce(upper)?;
+
+        if let (Some(l), Some(u)) = (bound_value(&lower), bound_value(&upper)) {
+            match l.cmp(u) {
+                Ordering::Greater => {
+                    return Err(invalid_err!(
+                        "range lower bound must be less than or equal to range upper bound"
+                    ))
+                }
+                Ordering::Equal
+                    if !matches!((&lower, &upper), (Bound::Included(_), Bound::Included(_))) =>
+                {
+                    return Ok(Self::Empty)
+                }
+                _ => {}
+            }
+        }
+
+        if let Bound::Excluded(l) = &lower {
+            if let Some(next) = successor(l, subtype)? {
+                lower = Bound::Included(next);
+            }
+        }
+        if let Bound::Included(u) = &upper {
+            if let Some(next) = successor(u, subtype)? {
+                upper = Bound::Excluded(next);
+            }
+        }
+
+        // Canonicalizing discrete bounds can leave us with a range like `[2,2)`
+        if matches!(
+            (&lower, &upper),
+            (Bound::Included(l), Bound::Excluded(u)) if l == u
+        ) {
+            return Ok(Self::Empty);
+        }
+
+        Ok(Self::NonEmpty { lower, upper })
+    }
+
+    /// Parse a range from its PostgreSQL text representation (eg `[1,5)`, `(,"2020-01-01"]` or
+    /// `empty`), coercing the bound values to the given `subtype`.
+    pub fn parse(s: &str, subtype: &DfType) -> ReadySetResult<Self> {
+        let malformed = || invalid_err!("malformed range literal: \"{s}\"");
+
+        let trimmed = s.trim();
+        if trimmed.eq_ignore_ascii_case("empty") {
+            return Ok(Self::Empty);
+        }
+
+        let mut chars = trimmed.chars();
+        let lower_inclusive = match chars.next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => return Err(malformed()),
+        };
+
+        // Parses the text of a single bound value, up to (and consuming) one of the given
+        // terminator characters. Returns `None` for an omitted (unbounded) value, and the
+        // terminator that was found.
+        let mut parse_bound = |terminators: &[char]| -> ReadySetResult<(Option<String>, char)> {
+            let mut val = String::new();
+            let mut present = false;
+            let mut in_quotes = false;
+            loop {
+                let c = chars.next().ok_or_else(malformed)?;
+                match c {
+                    '\\' => {
+                        val.push(chars.next().ok_or_else(malformed)?);
+                        present = true;
+                    }
+                    '"' if in_quotes => {
+                        if chars.as_str().starts_with('"') {
+                            chars.next();
+                            val.push('"');
+                        } else {
+                            in_quotes = false;
+                        }
+                    }
+                    '"' => {
+                        in_quotes = true;
+                        present = true;
+                    }
+                    c if !in_quotes && terminators.contains(&c) => {
+                        return Ok((present.then_some(val), c));
+                    }
+                    c if !in_quotes && matches!(c, '(' | ')' | '[' | ']' | ',') => {
+                        return Err(malformed());
+                    }
+                    c => {
+                        val.push(c);
+                        present = true;
+                    }
+                }
+            }
+        };
+
+        let (lower, _) = parse_bound(&[','])?;
+        let (upper, terminator) = parse_bound(&[')', ']'])?;
+        if !chars.as_str().trim().is_empty() {
+            return Err(malformed());
+        }
+
+        let mk_bound = |val: Option<String>, inclusive: bool| match val {
+            None => Bound::Unbounded,
+            Some(v) if inclusive => Bound::Included(DfValue::from(v)),
+            Some(v) => Bound::Excluded(DfValue::from(v)),
+        };
+        Self::new(
+            mk_bound(lower, lower_inclusive),
+            mk_bound(upper, terminator == ']'),
+            subtype,
+        )
+    }
+
+    /// Returns `true` if this range contains no values
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// Returns `true` if the given value is within this range.
+    ///
+    /// The value is assumed to already be of the subtype of the range.
+    pub fn contains_value(&self, val: &DfValue) -> bool {
+        match self {
+            Self::Empty => false,
+            Self::NonEmpty { lower, upper } => {
+                let above_lower = match lower {
+                    Bound::Included(l) => val >= l,
+                    Bound::Excluded(l) => val > l,
+                    Bound::Unbounded => true,
+                };
+                let below_upper = match upper {
+                    Bound::Included(u) => val <= u,
+                    Bound::Excluded(u) => val < u,
+                    Bound::Unbounded => true,
+                };
+                above_lower && below_upper
+            }
+        }
+    }
+
+    /// Returns `true` if every value in `other` is also within this range.
+    ///
+    /// The empty range is contained in every range, including itself.
+    pub fn contains_range(&self, other: &Range) -> bool {
+        match (self, other) {
+            (_, Self::Empty) => true,
+            (Self::Empty, _) => false,
+            (
+                Self::NonEmpty { lower, upper },
+                Self::NonEmpty {
+                    lower: other_lower,
+                    upper: other_upper,
+                },
+            ) => cmp_lower(lower, other_lower).is_le() && cmp_upper(upper, other_upper).is_ge(),
+        }
+    }
+
+    /// Returns `true` if this range and `other` have any values in common.
+    pub fn overlaps(&self, other: &Range) -> bool {
+        match (self, other) {
+            (Self::Empty, _) | (_, Self::Empty) => false,
+            (
+                Self::NonEmpty { lower, upper },
+                Self::NonEmpty {
+                    lower: other_lower,
+                    upper: other_upper,
+                },
+            ) => lower_below_upper(lower, other_upper) && lower_below_upper(other_lower, upper),
+        }
+    }
+
+    /// Coerce the bound values of this range to the given new subtype.
+    pub(crate) fn coerce_to(&self, new_subtype: &DfType) -> ReadySetResult<Self> {
+        match self {
+            Self::Empty => Ok(Self::Empty),
+            Self::NonEmpty { lower, upper } => Self::new(lower.clone(), upper.clone(), new_subtype),
+        }
+    }
+
+    /// Returns a guess for the [`DfType`] of the bounds of this range, or [`DfType::Unknown`] if
+    /// this range has no bound values
+    pub fn infer_subtype(&self) -> DfType {
+        match self {
+            Self::Empty => DfType::Unknown,
+            Self::NonEmpty { lower, upper } => bound_value(lower)
+                .or_else(|| bound_value(upper))
+                .map(DfValue::infer_dataflow_type)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Ord for Range {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Like PostgreSQL, the empty range sorts before all other ranges, and non-empty ranges are
+        // compared by their lower bounds first, then their upper bounds
+        match (self, other) {
+            (Self::Empty, Self::Empty) => Ordering::Equal,
+            (Self::Empty, _) => Ordering::Less,
+            (_, Self::Empty) => Ordering::Greater,
+            (
+                Self::NonEmpty { lower, upper },
+                Self::NonEmpty {
+                    lower: other_lower,
+                    upper: other_upper,
+                },
+            ) => cmp_lower(lower, other_lower).then_with(|| cmp_upper(upper, other_upper)),
+        }
+    }
+}
+
+impl PartialOrd for Range {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_bound_value(f: &mut fmt::Formatter<'_>, val: &DfValue) -> fmt::Result {
+            let s = val.to_string();
+            let needs_quotes = s.is_empty()
+                || s.chars().any(|c| {
+                    matches!(c, '"' | '\\' | '(' | ')' | '[' | ']' | ',') || c.is_whitespace()
+                });
+            if needs_quotes {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    if c == '"' || c == '\\' {
+                        write!(f, "{c}")?;
+                    }
+                    write!(f, "{c}")?;
+                }
+                write!(f, "\"")
+            } else {
+                write!(f, "{s}")
+            }
+        }
+
+        match self {
+            Self::Empty => write!(f, "empty"),
+            Self::NonEmpty { lower, upper } => {
+                match lower {
+                    Bound::Included(v) => {
+                        write!(f, "[")?;
+                        write_bound_value(f, v)?;
+                    }
+                    Bound::Excluded(v) => {
+                        write!(f, "(")?;
+                        write_bound_value(f, v)?;
+                    }
+                    Bound::Unbounded => write!(f, "(")?,
+                }
+                write!(f, ",")?;
+                match upper {
+                    Bound::Included(v) => {
+                        write_bound_value(f, v)?;
+                        write!(f, "]")
+                    }
+                    Bound::Excluded(v) => {
+                        write_bound_value(f, v)?;
+                        write!(f, ")")
+                    }
+                    Bound::Unbounded => write!(f, ")"),
+                }
+            }
+        }
+    }
+}
+
+impl Arbitrary for Range {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        // Keep the bound values away from the limits of `i32`, so canonicalizing them can't
+        // overflow
+        let bound = || {
+            prop_oneof![
+                (-1000..1000i32).prop_map(|i| Bound::Included(DfValue::from(i))),
+                (-1000..1000i32).prop_map(|i| Bound::Excluded(DfValue::from(i))),
+                Just(Bound::Unbounded),
+            ]
+        };
+
+        (bound(), bound())
+            .prop_map(|(b1, b2)| {
+                let (lower, upper) = match (bound_value(&b1), bound_value(&b2)) {
+                    (Some(v1), Some(v2)) if v1 > v2 => (b2, b1),
+                    _ => (b1, b2),
+                };
+                // We ordered the bounds above, so this can't fail
+                #[allow(clippy::unwrap_used)]
+                Range::new(lower, upper, &DfType::Int).unwrap()
+            })
+            .boxed()
+    }
+}
+
+impl<'a> FromSql<'a> for Range {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let subtype = match ty.kind() {
+            Kind::Range(subtype) => subtype,
+            _ => return Err(format!("Expected range type, got {ty}").into()),
+        };
+
+        let bound = |b: RangeBound<Option<&'a [u8]>>| -> Result<_, Box<dyn Error + Sync + Send>> {
+            Ok(match b {
+                RangeBound::Inclusive(Some(v)) => Bound::Included(DfValue::from_sql(subtype, v)?),
+                RangeBound::Exclusive(Some(v)) => Bound::Excluded(DfValue::from_sql(subtype, v)?),
+                RangeBound::Inclusive(None)
+                | RangeBound::Exclusive(None)
+                | RangeBound::Unbounded => Bound::Unbounded,
+            })
+        };
+
+        // Ranges sent by PostgreSQL are already in their canonical form
+        Ok(match postgres_protocol::types::range_from_sql(raw)? {
+            postgres_protocol::types::Range::Empty => Self::Empty,
+            postgres_protocol::types::Range::Nonempty(lower, upper) => Self::NonEmpty {
+                lower: bound(lower)?,
+                upper: bound(upper)?,
+            },
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match ty.kind() {
+            Kind::Range(subtype) => <DfValue as FromSql>::accepts(subtype),
+            _ => false,
+        }
+    }
+}
+
+impl ToSql for Range {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let subtype = match ty.kind() {
+            Kind::Range(subtype) => subtype,
+            _ => return Err(format!("Expected range type, got {ty}").into()),
+        };
+
+        let bound_to_sql = |bound: &Bound<DfValue>, buf: &mut BytesMut| {
+            let val_to_sql =
+                |v: &DfValue, buf: &mut BytesMut| -> Result<_, Box<dyn Error + Sync + Send>> {
+                    Ok(match v.to_sql(subtype, buf)? {
+                        IsNull::Yes => postgres_protocol::IsNull::Yes,
+                        IsNull::No => postgres_protocol::IsNull::No,
+                    })
+                };
+            Ok(match bound {
+                Bound::Included(v) => RangeBound::Inclusive(val_to_sql(v, buf)?),
+                Bound::Excluded(v) => RangeBound::Exclusive(val_to_sql(v, buf)?),
+                Bound::Unbounded => RangeBound::Unbounded,
+            })
+        };
+
+        match self {
+            Self::Empty => postgres_protocol::types::empty_range_to_sql(out),
+            Self::NonEmpty { lower, upper } => postgres_protocol::types::range_to_sql(
+                |buf| bound_to_sql(lower, buf),
+                |buf| bound_to_sql(upper, buf),
+                out,
+            )?,
+        }
+
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match ty.kind() {
+            Kind::Range(subtype) => <DfValue as ToSql>::accepts(subtype),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use test_strategy::proptest;
+
+    use super::*;
+
+    fn int_range(s: &str) -> Range {
+        Range::parse(s, &DfType::Int).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(int_range("[1,5)").to_string(), "[1,5)");
+        assert_eq!(int_range(" ( 1 , 5 ] ").to_string(), "[2,6)");
+        assert_eq!(int_range("(,5]").to_string(), "(,6)");
+        assert_eq!(int_range("[1,)").to_string(), "[1,)");
+        assert_eq!(int_range("(,)").to_string(), "(,)");
+        assert_eq!(int_range("[\"1\",\"3\"]").to_string(), "[1,4)");
+        assert_eq!(int_range("EMPTY"), Range::Empty);
+        assert_eq!(int_range("[3,3)"), Range::Empty);
+        assert_eq!(int_range("(3,4)"), Range::Empty);
+        assert_eq!(int_range("[3,3]").to_string(), "[3,4)");
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in ["", "1,5", "[1,5", "[1,5)x", "[5,1)", "[a,5)", "[1,2,3)"] {
+            assert!(Range::parse(s, &DfType::Int).is_err(), "{s}");
+        }
+        assert!(Range::parse("[1,2147483647]", &DfType::Int).is_err());
+    }
+
+    #[test]
+    fn parse_continuous() {
+        let r = Range::parse("(1.5,2.5]", &DfType::DEFAULT_NUMERIC).unwrap();
+        assert_eq!(r.to_string(), "(1.5,2.5]");
+        assert!(!r.contains_value(&Decimal::new(15, 1).into()));
+        assert!(r.contains_value(&Decimal::new(25, 1).into()));
+
+        let r = Range::parse(
+            "[\"2020-01-01 00:00:00\",\"2020-01-02 00:00:00\")",
+            &DfType::Timestamp {
+                subsecond_digits: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            r.to_string(),
+            "[\"2020-01-01 00:00:00\",\"2020-01-02 00:00:00\")"
+        );
+    }
+
+    #[test]
+    fn dates_are_canonicalized() {
+        let r = Range::parse("[2020-01-01,2020-01-31]", &DfType::Date).unwrap();
+        assert_eq!(r.to_string(), "[2020-01-01,2020-02-01)");
+        assert!(r.contains_value(&NaiveDate::from_ymd(2020, 1, 31).into()));
+        assert!(!r.contains_value(&NaiveDate::from_ymd(2020, 2, 1).into()));
+    }
+
+    #[test]
+    fn quoted_text_bounds() {
+        let r = Range::parse(r#"["a b","c""d\\e")"#, &DfType::DEFAULT_TEXT).unwrap();
+        assert_eq!(
+            r,
+            Range::NonEmpty {
+                lower: Bound::Included("a b".into()),
+                upper: Bound::Excluded("c\"d\\e".into()),
+            }
+        );
+        assert_eq!(r.to_string(), r#"["a b","c""d\\e")"#);
+    }
+
+    #[test]
+    fn contains() {
+        let r = int_range("[1,10)");
+        assert!(r.contains_value(&DfValue::from(1)));
+        assert!(r.contains_value(&DfValue::from(9)));
+        assert!(!r.contains_value(&DfValue::from(10)));
+        assert!(!r.contains_value(&DfValue::from(0)));
+
+        assert!(r.contains_range(&int_range("[2,5)")));
+        assert!(r.contains_range(&int_range("[1,10)")));
+        assert!(r.contains_range(&Range::Empty));
+        assert!(!r.contains_range(&int_range("[0,5)")));
+        assert!(!r.contains_range(&int_range("[5,)")));
+        assert!(int_range("(,)").contains_range(&r));
+        assert!(!Range::Empty.contains_range(&r));
+        assert!(!Range::Empty.contains_value(&DfValue::from(1)));
+    }
+
+    #[test]
+    fn overlaps() {
+        let r = int_range("[1,10)");
+        assert!(r.overlaps(&int_range("[9,20)")));
+        assert!(!r.overlaps(&int_range("[10,20)")));
+        assert!(r.overlaps(&int_range("(,1]")));
+        assert!(!r.overlaps(&int_range("(,1)")));
+        assert!(!r.overlaps(&Range::Empty));
+
+        let r = Range::parse("[1.0,2.0]", &DfType::DEFAULT_NUMERIC).unwrap();
+        assert!(r.overlaps(&Range::parse("[2.0,3.0]", &DfType::DEFAULT_NUMERIC).unwrap()));
+        assert!(!r.overlaps(&Range::parse("(2.0,3.0]", &DfType::DEFAULT_NUMERIC).unwrap()));
+    }
+
+    #[test]
+    fn ordering() {
+        let ordered = [
+            Range::Empty,
+            int_range("(,5)"),
+            int_range("[1,5)"),
+            int_range("[1,6)"),
+            int_range("[1,)"),
+            int_range("[2,3)"),
+        ];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{a} <=> {b}");
+            }
+        }
+    }
+
+    #[proptest]
+    fn display_parse_round_trip(r: Range) {
+        assert_eq!(Range::parse(&r.to_string(), &DfType::Int).unwrap(), r);
+    }
+}
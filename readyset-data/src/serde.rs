@@ -27,6 +27,7 @@ enum Variant {
     BitVector,
     TimestampTz,
     Array,
+    Range,
     Max,
 }
 
@@ -86,6 +87,7 @@ impl serde::ser::Serialize for DfValue {
                 serialize_variant(serializer, Variant::TimestampTz, &(ts, extra))
             }
             DfValue::Array(vs) => serialize_variant(serializer, Variant::Array, &vs),
+            DfValue::Range(r) => serialize_variant(serializer, Variant::Range, &r),
             DfValue::PassThrough(v) => Err(serde::ser::Error::custom(format_args!(
                 "PassThrough value of type {} not supported in dataflow graph",
                 v.ty
@@ -120,7 +122,7 @@ impl<'de> Deserialize<'de> for DfValue {
                 } else {
                     Err(serde::de::Error::invalid_value(
                         serde::de::Unexpected::Unsigned(val),
-                        &"variant index 0 <= i < 11",
+                        &"variant index 0 <= i < 13",
                     ))
                 }
             }
@@ -220,6 +222,9 @@ impl<'de> Deserialize<'de> for DfValue {
                     (Variant::Array, variant) => {
                         VariantAccess::newtype_variant(variant).map(DfValue::Array)
                     }
+                    (Variant::Range, variant) => {
+                        VariantAccess::newtype_variant(variant).map(DfValue::Range)
+                    }
                     (Variant::Max, variant) => {
                         VariantAccess::unit_variant(variant).map(|_| DfValue::Max)
                     }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
@@ -7,9 +8,11 @@ use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
 
+use once_cell::race::OnceBox;
 use readyset_errors::{ReadySetError, ReadySetResult};
+use serde_json::Value as JsonValue;
 
-use crate::{Array, Collation, DfType, DfValue};
+use crate::{Array, Collation, DfType, DfValue, Range};
 
 pub(crate) const TINYTEXT_WIDTH: usize = 14;
 
@@ -58,6 +61,9 @@ pub struct TinyText {
 #[derive(Debug)]
 struct TextHeader {
     valid: AtomicBool,
+    /// The contents of the text parsed as JSON, populated the first time the value is used as
+    /// JSON so that repeated JSON operations over the same value don't have to re-parse it
+    json: OnceBox<JsonValue>,
     collation: Collation,
 }
 
@@ -240,6 +246,7 @@ impl Text {
             inner: triomphe::ThinArc::from_header_and_slice(
                 TextHeader {
                     valid: AtomicBool::new(valid),
+                    json: OnceBox::new(),
                     collation,
                 },
                 v,
@@ -247,6 +254,30 @@ impl Text {
         }
     }
 
+    /// Create a new `Text` from the given string, which must be the serialized form of `json`.
+    ///
+    /// The returned value will not need to re-parse its contents the first time it is used as
+    /// JSON.
+    pub(crate) fn from_json(s: &str, json: JsonValue) -> Self {
+        // SAFETY: `s` is guaranteed to contain valid UTF-8
+        let res = unsafe { Self::new(true, Default::default(), s.as_bytes()) };
+        // Can't fail, since we just created the header
+        let _ = res.inner.header.header.json.set(Box::new(json));
+        res
+    }
+
+    /// Returns the contents of this `Text` parsed as JSON.
+    ///
+    /// The result of parsing is cached (and shared between all clones of this value), so calling
+    /// this repeatedly only pays the cost of parsing once.
+    pub fn as_json(&self) -> Result<&JsonValue, serde_json::Error> {
+        self.inner
+            .header
+            .header
+            .json
+            .get_or_try_init(|| serde_json::from_str(self.as_str()).map(Box::new))
+    }
+
     /// Return the configured collation on this [`Text`] value
     pub fn collation(&self) -> Collation {
         self.inner.header.header.collation
@@ -331,6 +362,9 @@ pub(crate) trait TextCoerce: Sized + Clone + Into<DfValue> {
     /// Get the inner str from the DfValue
     fn try_str(&self) -> ReadySetResult<&str>;
 
+    /// Parse the inner str as JSON
+    fn try_json(&self) -> Result<Cow<'_, JsonValue>, serde_json::Error>;
+
     /// Print the DfValue name for error reporting
     fn type_name() -> String;
 
@@ -444,16 +478,24 @@ pub(crate) trait TextCoerce: Sized + Clone + Into<DfValue> {
             DfType::BigInt => Self::parse_int::<i64>(str, to_ty),
             DfType::UnsignedBigInt => Self::parse_int::<u64>(str, to_ty),
 
-            DfType::Json | DfType::Jsonb => {
-                // Currently just validates the json
-                // TODO: this is very very wrong as there is no gurantee two equal json objects will
-                // be string equal, quite the opposite actually. And we can't just "normalize the
-                // json" as we do for MAC and UUID.
-                str.parse::<serde_json::Value>()
-                    .map_err(|e| Self::coerce_err(to_ty, e))?;
+            DfType::Json => {
+                // `json` values preserve the exact text they were written with, so just validate
+                self.try_json().map_err(|e| Self::coerce_err(to_ty, e))?;
                 Ok(self.clone().into())
             }
 
+            DfType::Jsonb => {
+                // `jsonb` values are stored in their canonical text form, so that equal JSON
+                // values are also equal (and hash equally) as text - just like we do for MAC and
+                // UUID below
+                let json = self.try_json().map_err(|e| Self::coerce_err(to_ty, e))?;
+                if crate::json::jsonb_to_string(&json) == str {
+                    Ok(self.clone().into())
+                } else {
+                    Ok(DfValue::from_jsonb(json.into_owned()))
+                }
+            }
+
             DfType::MacAddr => {
                 // Since MAC addresses can be represented in many ways, if we want to store them as
                 // a string, we have to at least normalize to the same representation.
@@ -536,6 +578,10 @@ pub(crate) trait TextCoerce: Sized + Clone + Into<DfValue> {
             )
             .coerce_to(to_ty, from_ty),
 
+            DfType::Range(ref subtype) => Ok(Range::parse(str, subtype)
+                .map_err(|e| Self::coerce_err(to_ty, e))?
+                .into()),
+
             DfType::Enum { ref variants, .. } => {
                 if let Some(i) = variants.iter().position(|variant| variant == str) {
                     // MySQL enums use 1-based indexing since a value of 0 is reserved for string
@@ -561,6 +607,10 @@ impl TextCoerce for TinyText {
         Ok(self.as_str())
     }
 
+    fn try_json(&self) -> Result<Cow<'_, JsonValue>, serde_json::Error> {
+        serde_json::from_str(self.as_str()).map(Cow::Owned)
+    }
+
     fn type_name() -> String {
         "TinyText".into()
     }
@@ -571,6 +621,10 @@ impl TextCoerce for Text {
         Ok(self.as_str())
     }
 
+    fn try_json(&self) -> Result<Cow<'_, JsonValue>, serde_json::Error> {
+        self.as_json().map(Cow::Borrowed)
+    }
+
     fn type_name() -> String {
         "Text".into()
     }
@@ -640,6 +694,18 @@ mod tests {
         t.as_str();
     }
 
+    #[test]
+    fn text_json_parsed_once() {
+        let t = Text::from(r#"{"key": "a value long enough for a Text"}"#);
+        let cloned = t.clone();
+        let json = t.as_json().unwrap();
+        assert_eq!(json["key"], "a value long enough for a Text");
+        // Clones share the cached parse
+        assert!(std::ptr::eq(json, cloned.as_json().unwrap()));
+
+        Text::from("not valid json at all").as_json().unwrap_err();
+    }
+
     #[test]
     #[should_panic]
     fn tiny_text_panics_non_utf8() {
@@ -179,6 +179,12 @@ pub enum DfType {
 
     /// [PostgreSQL `jsonb`](https://www.postgresql.org/docs/current/datatype-json.html).
     Jsonb,
+
+    /// [PostgreSQL range types](https://www.postgresql.org/docs/current/rangetypes.html), such as
+    /// `int4range` or `tstzrange`.
+    ///
+    /// Holds the subtype of the range, i.e. the type of its bounds.
+    Range(Box<DfType>),
 }
 
 /// Defaults.
@@ -296,6 +302,18 @@ impl DfType {
             MacAddr => Self::MacAddr,
             Inet => Self::Inet,
             Citext => Self::Text(Collation::Citext),
+
+            Int4Range => Self::Range(Box::new(Self::Int)),
+            Int8Range => Self::Range(Box::new(Self::BigInt)),
+            // PostgreSQL `numrange`s are ranges over unconstrained `numeric`s
+            NumRange => Self::Range(Box::new(Self::DEFAULT_NUMERIC)),
+            TsRange => Self::Range(Box::new(Self::Timestamp {
+                subsecond_digits: dialect.default_subsecond_digits(),
+            })),
+            TsTzRange => Self::Range(Box::new(Self::TimestampTz {
+                subsecond_digits: dialect.default_subsecond_digits(),
+            })),
+            DateRange => Self::Range(Box::new(Self::Date)),
             Other(ref id) => resolve_custom_type(id.clone())
                 .ok_or_else(|| unsupported_err!("Unsupported type: {id}"))?,
         })
@@ -351,6 +369,7 @@ impl DfType {
             DfType::Uuid | DfType::Enum { .. } | DfType::Json | DfType::Jsonb => {
                 PgTypeCategory::UserDefined
             }
+            DfType::Range(_) => PgTypeCategory::Range,
         }
     }
 
@@ -390,7 +409,7 @@ impl DfType {
     pub fn is_strictly_known(&self) -> bool {
         match self {
             Self::Unknown => false,
-            Self::Array(ty) => ty.is_strictly_known(),
+            Self::Array(ty) | Self::Range(ty) => ty.is_strictly_known(),
             _ => true,
         }
     }
@@ -453,6 +472,29 @@ impl DfType {
         matches!(self, Self::Text(..) | Self::VarChar(..) | Self::Char(..))
     }
 
+    /// Returns the collation of this type, if it is any `text` type
+    #[inline]
+    pub fn collation(&self) -> Option<Collation> {
+        match *self {
+            Self::Text(collation) | Self::VarChar(_, collation) | Self::Char(_, collation, _) => {
+                Some(collation)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns this type with its collation replaced by `collation`, if it is any `text` type.
+    /// Otherwise, returns the type unchanged.
+    #[must_use]
+    pub fn with_collation(self, collation: Collation) -> Self {
+        match self {
+            Self::Text(_) => Self::Text(collation),
+            Self::VarChar(len, _) => Self::VarChar(len, collation),
+            Self::Char(len, _, dialect) => Self::Char(len, collation, dialect),
+            ty => ty,
+        }
+    }
+
     /// Returns `true` if this is any IEEE 754 floating-point type.
     #[inline]
     pub fn is_any_float(&self) -> bool {
@@ -465,6 +507,21 @@ impl DfType {
         matches!(self, Self::Array { .. })
     }
 
+    /// Returns `true` if this is any PostgreSQL range type.
+    #[inline]
+    pub fn is_range(&self) -> bool {
+        matches!(self, Self::Range(_))
+    }
+
+    /// Returns the subtype of the range if this is a [`DfType::Range`], otherwise [`None`].
+    #[inline]
+    pub fn range_subtype(&self) -> Option<&Self> {
+        match self {
+            Self::Range(ty) => Some(ty),
+            _ => None,
+        }
+    }
+
     /// Returns the deepest nested type in [`DfType::Array`], otherwise returns `self`.
     #[inline]
     pub fn innermost_array_type(&self) -> &Self {
@@ -600,6 +657,7 @@ impl fmt::Display for DfType {
             | Self::Jsonb => write!(f, "{kind:?}"),
 
             Self::Array(ref ty) => write!(f, "{ty}[]"),
+            Self::Range(ref ty) => write!(f, "{kind:?}({ty})"),
 
             Self::Char(n, ..)
             | Self::VarChar(n, ..)
@@ -296,7 +296,7 @@ impl WriteHandle {
     where
         I: IntoIterator<Item = Record>,
     {
-        let mem_delta = self.handle.add(&self.index.columns, self.cols, rs);
+        let mem_delta = self.handle.add(&self.index, self.cols, rs);
         match mem_delta.cmp(&0) {
             Ordering::Greater => {
                 self.mem_size += mem_delta as usize;
@@ -373,11 +373,11 @@ impl WriteHandle {
 
         #[allow(clippy::unreachable)] // Documented invariant.
         let range = match (self.index.index_type, &key) {
-            (IndexType::HashMap, KeyComparison::Equal(equal)) => {
+            (IndexType::HashMap | IndexType::FullText, KeyComparison::Equal(equal)) => {
                 return self.mut_with_key(equal.as_vec()).mark_filled();
             }
-            (IndexType::HashMap, KeyComparison::Range(_)) => {
-                unreachable!("Range key with a HashMap index")
+            (IndexType::HashMap | IndexType::FullText, KeyComparison::Range(_)) => {
+                unreachable!("Range key with a hashed index")
             }
             (IndexType::BTreeMap, KeyComparison::Equal(equal)) => (
                 Bound::Included(equal.as_vec()),
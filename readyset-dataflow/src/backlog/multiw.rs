@@ -1,7 +1,7 @@
 use std::ops::{Bound, RangeBounds};
 
 use ahash::RandomState;
-use dataflow_expression::PreInsertion;
+use dataflow_expression::{full_text, PreInsertion};
 use readyset_client::consistency::Timestamp;
 
 use super::{key_to_single, Key};
@@ -114,12 +114,37 @@ impl Handle {
         }
     }
 
-    pub fn add<I>(&mut self, key: &[usize], cols: usize, rs: I) -> isize
+    pub fn add<I>(&mut self, index: &Index, cols: usize, rs: I) -> isize
     where
         I: IntoIterator<Item = Record>,
     {
+        let key = &index.columns;
         let mut memory_delta = 0isize;
         match *self {
+            Handle::Single(ref mut h) if index.index_type == IndexType::FullText => {
+                // Full-text indexes store each record under every distinct token of the indexed
+                // column. Values which aren't text (including NULL) have no tokens.
+                assert_eq!(key.len(), 1);
+                for r in rs {
+                    debug_assert!(r.len() >= cols);
+                    let tokens = <&str>::try_from(&r[key[0]])
+                        .map(full_text::tokenize)
+                        .unwrap_or_default();
+                    for token in tokens {
+                        let token = DfValue::from(token);
+                        match &r {
+                            Record::Positive(r) => {
+                                memory_delta += r.deep_size_of() as isize;
+                                h.insert(token, r.clone().into_boxed_slice());
+                            }
+                            Record::Negative(r) => {
+                                memory_delta -= r.deep_size_of() as isize;
+                                h.remove_value(token, r.clone().into_boxed_slice());
+                            }
+                        }
+                    }
+                }
+            }
             Handle::Single(ref mut h) => {
                 assert_eq!(key.len(), 1);
                 for r in rs {
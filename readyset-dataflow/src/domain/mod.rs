@@ -240,7 +240,7 @@ impl RequestedKeys {
     /// Create a new set of requested keys for storing requests to the given [`IndexType`].
     fn new(index_type: IndexType) -> Self {
         match index_type {
-            IndexType::HashMap => Self::Points(Default::default()),
+            IndexType::HashMap | IndexType::FullText => Self::Points(Default::default()),
             IndexType::BTreeMap => Self::Ranges(Default::default()),
         }
     }
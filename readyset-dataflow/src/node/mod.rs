@@ -1,8 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
-use nom_sql::{ColumnSpecification, Relation, SqlIdentifier};
+use nom_sql::{ColumnConstraint, ColumnSpecification, Relation, SqlIdentifier};
 use readyset_client::consistency::Timestamp;
-use readyset_data::{DfType, Dialect};
+use readyset_data::{Collation, DfType, Dialect};
 use serde::{Deserialize, Serialize};
 
 use crate::ops;
@@ -48,6 +48,10 @@ impl Column {
     }
 
     /// Creates a dataflow column from the [`nom_sql`] specification.
+    ///
+    /// If the column specification has a `COLLATE` constraint naming a supported collation, the
+    /// resulting column's type will use that collation. Unsupported collations fall back to the
+    /// default collation for the type.
     #[inline]
     pub fn from_spec<F>(
         spec: ColumnSpecification,
@@ -57,11 +61,15 @@ impl Column {
     where
         F: Fn(Relation) -> Option<DfType>,
     {
-        Ok(Self::new(
-            spec.column.name,
-            DfType::from_sql_type(&spec.sql_type, dialect, resolve_type)?,
-            spec.column.table,
-        ))
+        let mut ty = DfType::from_sql_type(&spec.sql_type, dialect, resolve_type)?;
+        if let Some(collation) = spec.constraints.iter().find_map(|c| match c {
+            ColumnConstraint::Collation(name) => Collation::get(name),
+            _ => None,
+        }) {
+            ty = ty.with_collation(collation);
+        }
+
+        Ok(Self::new(spec.column.name, ty, spec.column.table))
     }
 
     /// Column name
@@ -22,6 +22,19 @@ pub enum Aggregation {
     Avg,
     /// Concatenates using the given separator between values.
     GroupConcat { separator: String },
+    /// Count the number of distinct non-null values. Maintains a count of each value in each
+    /// group in a HashMap
+    CountDistinct,
+    /// The standard deviation of the `over` column, either of the sample or of the population.
+    /// Maintains count, sum, and sum of squares in HashMap
+    Stddev { sample: bool },
+    /// The variance of the `over` column, either of the sample or of the population. Maintains
+    /// count, sum, and sum of squares in HashMap
+    Variance { sample: bool },
+    /// Collects all the values of the `over` column into an array.
+    ArrayAgg,
+    /// Collects all the values of the `over` column into a JSON array.
+    JsonAgg,
 }
 
 impl Aggregation {
@@ -38,7 +51,7 @@ impl Aggregation {
         over_col_ty: &DfType,
     ) -> ReadySetResult<GroupedOperator<Aggregator>> {
         let out_ty = match &self {
-            Aggregation::Count { .. } => DfType::BigInt,
+            Aggregation::Count { .. } | Aggregation::CountDistinct => DfType::BigInt,
             // The SUM() and AVG() functions return a DECIMAL value for exact-value arguments
             // (integer or DECIMAL), and a DOUBLE value for approximate-value arguments (FLOAT or
             // DOUBLE).
@@ -50,6 +63,9 @@ impl Aggregation {
                 }
             }
             Aggregation::GroupConcat { .. } => DfType::Text(/* TODO */ Collation::default()),
+            Aggregation::Stddev { .. } | Aggregation::Variance { .. } => DfType::Double,
+            Aggregation::ArrayAgg => DfType::Array(Box::new(over_col_ty.clone())),
+            Aggregation::JsonAgg => DfType::Json,
         };
 
         Ok(GroupedOperator::new(
@@ -59,6 +75,8 @@ impl Aggregation {
                 over,
                 group: group_by.into(),
                 count_sum_map: RefCell::new(Default::default()),
+                distinct_values_map: RefCell::new(Default::default()),
+                moments_map: RefCell::new(Default::default()),
                 over_else: None,
                 out_ty,
             },
@@ -87,6 +105,13 @@ pub struct Aggregator {
     // We skip serde since we don't want the state, just the configuration.
     #[serde(skip)]
     count_sum_map: RefCell<HashMap<GroupHash, AverageDataPair>>,
+    // only needed for COUNT(DISTINCT). Stores the number of occurrences of each value in each
+    // group
+    #[serde(skip)]
+    distinct_values_map: RefCell<HashMap<GroupHash, HashMap<DfValue, usize>>>,
+    // only needed for STDDEV and VARIANCE.
+    #[serde(skip)]
+    moments_map: RefCell<HashMap<GroupHash, Moments>>,
     over_else: Option<Literal>,
     // Output type of this column
     out_ty: DfType,
@@ -130,6 +155,43 @@ impl AverageDataPair {
     }
 }
 
+/// For storing (Count, Sum, Sum of squares) in additional state for Stddev and Variance.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Moments {
+    count: u64,
+    sum: f64,
+    sum_of_squares: f64,
+}
+
+impl Moments {
+    fn apply_diff(&mut self, d: NumericalDiff) -> ReadySetResult<()> {
+        let value = f64::try_from(&d.value)?;
+        if d.positive {
+            self.count += 1;
+            self.sum += value;
+            self.sum_of_squares += value * value;
+        } else {
+            self.count = self.count.saturating_sub(1);
+            self.sum -= value;
+            self.sum_of_squares -= value * value;
+        }
+        Ok(())
+    }
+
+    /// Returns the variance of the values in the group, or None if there are too few values to
+    /// compute it
+    fn variance(&self, sample: bool) -> Option<f64> {
+        let degrees_of_freedom = u64::from(sample);
+        if self.count <= degrees_of_freedom {
+            return None;
+        }
+        let count = self.count as f64;
+        // Clamp to zero to avoid reporting tiny negative variances due to rounding errors
+        let squared_deviations = (self.sum_of_squares - self.sum * self.sum / count).max(0.0);
+        Some(squared_deviations / (count - degrees_of_freedom as f64))
+    }
+}
+
 impl Aggregator {
     fn group_hash(&self, rec: &[DfValue]) -> GroupHash {
         let mut hasher = DefaultHasher::new();
@@ -152,6 +214,78 @@ impl Aggregator {
             _ => internal!(),
         }
     }
+
+    fn apply_count_distinct(
+        &self,
+        current: Option<&DfValue>,
+        diffs: &mut dyn Iterator<Item = NumericalDiff>,
+    ) -> ReadySetResult<Option<DfValue>> {
+        let mut diffs = diffs.peekable();
+        let group_hash = match diffs.peek() {
+            Some(diff) => diff.group_hash,
+            None => return Ok(current.cloned()),
+        };
+
+        let mut distinct_values_map = self.distinct_values_map.borrow_mut();
+        let mut values = match (current, distinct_values_map.remove(&group_hash)) {
+            (Some(_), Some(values)) => values,
+            // We've lost the state for this group, so we need to recompute it
+            (Some(_), None) => return Ok(None),
+            (None, _) => HashMap::new(),
+        };
+
+        for diff in diffs {
+            if diff.value.is_none() {
+                continue;
+            }
+            if diff.positive {
+                *values.entry(diff.value).or_default() += 1;
+            } else if let Some(count) = values.get_mut(&diff.value) {
+                *count -= 1;
+                if *count == 0 {
+                    values.remove(&diff.value);
+                }
+            }
+        }
+
+        let count = DfValue::from(values.len() as i64);
+        distinct_values_map.insert(group_hash, values);
+        Ok(Some(count))
+    }
+
+    fn apply_moments(
+        &self,
+        current: Option<&DfValue>,
+        diffs: &mut dyn Iterator<Item = NumericalDiff>,
+    ) -> ReadySetResult<Option<DfValue>> {
+        let mut diffs = diffs.peekable();
+        let group_hash = match diffs.peek() {
+            Some(diff) => diff.group_hash,
+            None => return Ok(current.cloned()),
+        };
+
+        let mut moments_map = self.moments_map.borrow_mut();
+        let mut moments = match (current, moments_map.remove(&group_hash)) {
+            (Some(_), Some(moments)) => moments,
+            // We've lost the state for this group, so we need to recompute it
+            (Some(_), None) => return Ok(None),
+            (None, _) => Moments::default(),
+        };
+
+        for diff in diffs {
+            if !diff.value.is_none() {
+                moments.apply_diff(diff)?;
+            }
+        }
+
+        let value = match self.op {
+            Aggregation::Stddev { sample } => moments.variance(sample).map(f64::sqrt),
+            Aggregation::Variance { sample } => moments.variance(sample),
+            _ => internal!("Only STDDEV and VARIANCE keep track of moments"),
+        };
+        moments_map.insert(group_hash, moments);
+        Ok(Some(value.map(DfValue::Double).unwrap_or(DfValue::None)))
+    }
 }
 
 impl GroupedOperation for Aggregator {
@@ -188,6 +322,14 @@ impl GroupedOperation for Aggregator {
         current: Option<&DfValue>,
         diffs: &mut dyn Iterator<Item = Self::Diff>,
     ) -> ReadySetResult<Option<DfValue>> {
+        match self.op {
+            Aggregation::CountDistinct => return self.apply_count_distinct(current, diffs),
+            Aggregation::Stddev { .. } | Aggregation::Variance { .. } => {
+                return self.apply_moments(current, diffs)
+            }
+            _ => {}
+        }
+
         let apply_count = |curr: DfValue, diff: Self::Diff| -> ReadySetResult<DfValue> {
             if diff.positive {
                 &curr + &DfValue::Int(1)
@@ -225,9 +367,16 @@ impl GroupedOperation for Aggregator {
                     Aggregation::Count { .. } => apply_count(curr?, diff),
                     Aggregation::Sum => apply_sum(curr?, diff),
                     Aggregation::Avg => apply_avg(curr?, diff),
-                    Aggregation::GroupConcat { separator: _ } => internal!(
+                    Aggregation::GroupConcat { .. }
+                    | Aggregation::ArrayAgg
+                    | Aggregation::JsonAgg => internal!(
                         "GroupConcats are separate from the other aggregations in the dataflow."
                     ),
+                    Aggregation::CountDistinct
+                    | Aggregation::Stddev { .. }
+                    | Aggregation::Variance { .. } => {
+                        internal!("Aggregations with per-group state are applied separately")
+                    }
                 }
             };
 
@@ -245,6 +394,11 @@ impl GroupedOperation for Aggregator {
                 Aggregation::GroupConcat { separator: ref s } => {
                     format!("||({})", s)
                 }
+                Aggregation::CountDistinct => "+DISTINCT".to_owned(),
+                Aggregation::Stddev { .. } => "StdDev".to_owned(),
+                Aggregation::Variance { .. } => "Var".to_owned(),
+                Aggregation::ArrayAgg => "ArrayAgg".to_owned(),
+                Aggregation::JsonAgg => "JsonAgg".to_owned(),
             };
         }
 
@@ -253,6 +407,13 @@ impl GroupedOperation for Aggregator {
             Aggregation::Sum => format!("𝛴({})", self.over),
             Aggregation::Avg => format!("Avg({})", self.over),
             Aggregation::GroupConcat { separator: ref s } => format!("||({}, {})", s, self.over),
+            Aggregation::CountDistinct => format!("|DISTINCT {}|", self.over),
+            Aggregation::Stddev { sample: true } => format!("StdDevSamp({})", self.over),
+            Aggregation::Stddev { sample: false } => format!("StdDevPop({})", self.over),
+            Aggregation::Variance { sample: true } => format!("VarSamp({})", self.over),
+            Aggregation::Variance { sample: false } => format!("VarPop({})", self.over),
+            Aggregation::ArrayAgg => format!("ArrayAgg({})", self.over),
+            Aggregation::JsonAgg => format!("JsonAgg({})", self.over),
         };
         let group_cols = self
             .group
@@ -273,16 +434,16 @@ impl GroupedOperation for Aggregator {
 
     fn empty_value(&self) -> Option<DfValue> {
         match self.op {
-            Aggregation::Count { .. } => Some(0.into()),
+            Aggregation::Count { .. } | Aggregation::CountDistinct => Some(0.into()),
             _ => None,
         }
     }
 
     fn emit_empty(&self) -> bool {
         match self.op {
-            Aggregation::Count { .. } | Aggregation::GroupConcat { .. } => {
-                self.group_by().is_empty()
-            }
+            Aggregation::Count { .. }
+            | Aggregation::CountDistinct
+            | Aggregation::GroupConcat { .. } => self.group_by().is_empty(),
             _ => false,
         }
     }
@@ -492,6 +653,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn count_distinct() {
+        let mut c = setup(Aggregation::CountDistinct, true);
+
+        let rs = c.narrow_one_row(vec![1.into(), 1.into()], true);
+        assert_eq!(
+            rs,
+            vec![Record::Positive(vec![1.into(), 1.into(), 1.into()])].into()
+        );
+
+        // A duplicate value doesn't change the count
+        let rs = c.narrow_one_row(vec![1.into(), 1.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), 1.into(), 1.into()]),
+                Record::Positive(vec![1.into(), 1.into(), 2.into()]),
+            ]
+            .into()
+        );
+
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), 1.into(), 2.into()]),
+                Record::Positive(vec![1.into(), 2.into(), 3.into()]),
+            ]
+            .into()
+        );
+
+        // Removing one of the duplicates doesn't change the count either...
+        let rs = c.narrow_one_row((vec![1.into(), 1.into()], false), true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), 2.into(), 3.into()]),
+                Record::Positive(vec![1.into(), 2.into(), 2.into()]),
+            ]
+            .into()
+        );
+
+        // ...but removing the last one does
+        let rs = c.narrow_one_row((vec![1.into(), 1.into()], false), true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), 2.into(), 2.into()]),
+                Record::Positive(vec![1.into(), 1.into(), 1.into()]),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn population_variance() {
+        let mut c = setup(Aggregation::Variance { sample: false }, true);
+
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(
+            rs,
+            vec![Record::Positive(vec![
+                1.into(),
+                DfValue::Double(0.0),
+                1.into()
+            ])]
+            .into()
+        );
+
+        let rs = c.narrow_one_row(vec![1.into(), 4.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), DfValue::Double(0.0), 1.into()]),
+                Record::Positive(vec![1.into(), DfValue::Double(1.0), 2.into()]),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn sample_stddev() {
+        let mut c = setup(Aggregation::Stddev { sample: true }, true);
+
+        // The sample standard deviation of a single value is NULL
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(
+            rs,
+            vec![Record::Positive(vec![1.into(), DfValue::None, 1.into()])].into()
+        );
+
+        let rs = c.narrow_one_row(vec![1.into(), 4.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                Record::Negative(vec![1.into(), DfValue::None, 1.into()]),
+                Record::Positive(vec![1.into(), DfValue::Double(2f64.sqrt()), 2.into()]),
+            ]
+            .into()
+        );
+    }
+
     /// Testing SUM emits correct records with single column group and single over column
     /// Records are in the form of (GroupCol, OverCol)
     /// Includes adding and removing records from different groups independently and in batch.
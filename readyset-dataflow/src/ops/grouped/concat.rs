@@ -1,4 +1,4 @@
-//! Kinda (s)crappy group_concat() implementation
+//! Kinda (s)crappy group_concat() implementation, which also backs array_agg() and json_agg()
 
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -10,6 +10,7 @@ use launchpad::Indices;
 use readyset_data::{Collation, DfType};
 use readyset_errors::invariant_eq;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
 use crate::node::Node;
 use crate::ops::grouped::{GroupedOperation, GroupedOperator};
@@ -18,8 +19,8 @@ use crate::prelude::*;
 /// The last stored state for a given group.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct LastState {
-    /// The string representation we last emitted for this group.
-    string_repr: String,
+    /// The value we last emitted for this group.
+    repr: DfValue,
     /// A vector containing the actual data
     data: Vec<DfValue>,
 }
@@ -27,23 +28,39 @@ struct LastState {
 impl Default for LastState {
     fn default() -> Self {
         Self {
-            string_repr: "".to_owned(),
+            repr: DfValue::None,
             data: vec![],
         }
     }
 }
 
+/// The kind of value a [`GroupConcat`] aggregates the values in each group into
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ConcatFormat {
+    /// A string representation of the values, separated by a user-defined separator
+    /// (`GROUP_CONCAT`)
+    Text { separator: String },
+    /// An array of the values (`ARRAY_AGG`)
+    Array,
+    /// A JSON array of the values (`JSON_AGG`)
+    Json,
+}
+
 /// `GroupConcat` partially implements the `GROUP_CONCAT` SQL aggregate function, which
 /// aggregates a set of arbitrary `DfValue`s into a string representation separated by
 /// a user-defined separator.
+///
+/// The same operator also implements the `ARRAY_AGG` and `JSON_AGG` aggregate functions, which
+/// collect the values into an array or a JSON array respectively, depending on its
+/// [`ConcatFormat`].
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GroupConcat {
     /// Which column to aggregate.
     source_col: usize,
     /// The columns to group by.
     group_by: Vec<usize>,
-    /// The user-defined separator.
-    separator: String,
+    /// The kind of value to aggregate into.
+    format: ConcatFormat,
     /// Cached state for each group (set of data corresponding to the columns of `group_by`).
     // We skip serde since we don't want the state of the node, just the configuration.
     #[serde(skip)]
@@ -61,6 +78,18 @@ fn concat_fmt<F: Write>(f: &mut F, dt: &DfValue) -> ReadySetResult<()> {
     Ok(())
 }
 
+/// Converts a single value to JSON, for inclusion in the output of `JSON_AGG`
+fn json_value(dt: &DfValue) -> ReadySetResult<JsonValue> {
+    Ok(match dt {
+        DfValue::None => JsonValue::Null,
+        DfValue::Int(i) => (*i).into(),
+        DfValue::UnsignedInt(i) => (*i).into(),
+        DfValue::Float(_) | DfValue::Double(_) | DfValue::Numeric(_) => f64::try_from(dt)?.into(),
+        DfValue::Text(..) | DfValue::TinyText(..) => <&str>::try_from(dt)?.into(),
+        x => x.to_string().into(),
+    })
+}
+
 impl GroupConcat {
     /// Construct a new `GroupConcat`, aggregating the provided `source_col` and separating
     /// aggregated data with the provided `separator`.
@@ -69,13 +98,24 @@ impl GroupConcat {
         source_col: usize,
         group_by: Vec<usize>,
         separator: String,
+    ) -> ReadySetResult<GroupedOperator<GroupConcat>> {
+        Self::with_format(src, source_col, group_by, ConcatFormat::Text { separator })
+    }
+
+    /// Construct a new `GroupConcat`, aggregating the provided `source_col` into a value of the
+    /// given `format`.
+    pub fn with_format(
+        src: NodeIndex,
+        source_col: usize,
+        group_by: Vec<usize>,
+        format: ConcatFormat,
     ) -> ReadySetResult<GroupedOperator<GroupConcat>> {
         Ok(GroupedOperator::new(
             src,
             GroupConcat {
                 source_col,
                 group_by,
-                separator,
+                format,
                 last_state: RefCell::new(HashMap::new()),
             },
         ))
@@ -120,9 +160,7 @@ impl GroupedOperation for GroupConcat {
         current: Option<&DfValue>,
         diffs: &mut dyn Iterator<Item = Self::Diff>,
     ) -> ReadySetResult<Option<DfValue>> {
-        let current: Option<&str> = current
-            .filter(|dt| matches!(dt, &DfValue::Text(..) | &DfValue::TinyText(..)))
-            .and_then(|dt| <&str>::try_from(dt).ok());
+        let current = current.filter(|dt| !dt.is_none());
 
         let mut diffs = diffs.peekable();
 
@@ -131,19 +169,16 @@ impl GroupedOperation for GroupConcat {
             .ok_or_else(|| internal_err!("group_concat got no diffs"))?;
         let group = first_diff.group_by.clone();
 
-        let mut ls = self.last_state.borrow_mut().remove(&group);
-        let mut prev_state = match current {
-            #[allow(clippy::unwrap_used)] // check for is_some() before unwrapping
-            Some(text) if ls.is_some() && text == ls.as_ref().unwrap().string_repr => {
-                // if state matches, use it
-                ls.take().unwrap()
-            }
+        let ls = self.last_state.borrow_mut().remove(&group);
+        let mut prev_state = match (current, ls) {
+            // if state matches, use it
+            (Some(current), Some(ls)) if *current == ls.repr => ls,
             // if state doesn't match, need to recreate it
-            Some(_) => {
+            (Some(_), _) => {
                 return Ok(None);
             }
             // if we're recreating or this is the first record for the group, make a new state
-            None => LastState::default(),
+            (None, _) => LastState::default(),
         };
         for ConcatDiff {
             value,
@@ -174,29 +209,52 @@ impl GroupedOperation for GroupConcat {
                 prev_state.data.remove(item_pos);
             }
         }
-        // what I *really* want here is Haskell's "intercalate" ~eta
-        let mut out_str = String::new();
-        for (i, piece) in prev_state.data.iter().enumerate() {
-            // TODO(eta): not unwrap, maybe
-            concat_fmt(&mut out_str, piece)?;
-            if i < prev_state.data.len() - 1 {
-                write!(&mut out_str, "{}", self.separator).unwrap();
+        let out = match &self.format {
+            ConcatFormat::Text { separator } => {
+                // what I *really* want here is Haskell's "intercalate" ~eta
+                let mut out_str = String::new();
+                for (i, piece) in prev_state.data.iter().enumerate() {
+                    // TODO(eta): not unwrap, maybe
+                    concat_fmt(&mut out_str, piece)?;
+                    if i < prev_state.data.len() - 1 {
+                        write!(&mut out_str, "{}", separator).unwrap();
+                    }
+                }
+                DfValue::from(out_str)
             }
-        }
-        prev_state.string_repr = out_str.clone();
+            ConcatFormat::Array => DfValue::from(prev_state.data.clone()),
+            ConcatFormat::Json => DfValue::from(
+                prev_state
+                    .data
+                    .iter()
+                    .map(json_value)
+                    .collect::<ReadySetResult<Vec<_>>>()?,
+            ),
+        };
+        prev_state.repr = out.clone();
         self.last_state.borrow_mut().insert(group, prev_state);
-        Ok(Some(out_str.into()))
+        Ok(Some(out))
     }
 
     fn description(&self, detailed: bool) -> String {
-        if !detailed {
-            return "CONCAT2".try_into().unwrap();
+        match &self.format {
+            ConcatFormat::Text { separator } => {
+                if !detailed {
+                    return "CONCAT2".try_into().unwrap();
+                }
+
+                format!(
+                    "||({}, {:?}) γ{:?}",
+                    self.source_col, separator, self.group_by
+                )
+            }
+            ConcatFormat::Array if !detailed => "ARRAY_AGG".to_owned(),
+            ConcatFormat::Array => {
+                format!("array_agg({}) γ{:?}", self.source_col, self.group_by)
+            }
+            ConcatFormat::Json if !detailed => "JSON_AGG".to_owned(),
+            ConcatFormat::Json => format!("json_agg({}) γ{:?}", self.source_col, self.group_by),
         }
-
-        format!(
-            "||({}, {:?}) γ{:?}",
-            self.source_col, self.separator, self.group_by
-        )
     }
 
     fn over_column(&self) -> usize {
@@ -204,11 +262,18 @@ impl GroupedOperation for GroupConcat {
     }
 
     fn output_col_type(&self) -> DfType {
-        DfType::Text(/* TODO */ Collation::default())
+        match self.format {
+            ConcatFormat::Text { .. } => DfType::Text(/* TODO */ Collation::default()),
+            ConcatFormat::Array => DfType::Array(Box::new(DfType::Unknown)),
+            ConcatFormat::Json => DfType::Json,
+        }
     }
 
     fn empty_value(&self) -> Option<DfValue> {
-        Some("".into())
+        match self.format {
+            ConcatFormat::Text { .. } => Some("".into()),
+            ConcatFormat::Array | ConcatFormat::Json => None,
+        }
     }
 }
 
@@ -235,6 +300,44 @@ mod tests {
         assert_eq!(c.node().description(true), "||(1, \"#\") γ[0]",);
     }
 
+    #[test]
+    fn it_forwards_array_agg() {
+        let mut c = ops::test::MockGraph::new();
+        let s = c.add_base("source", &["x", "y"]);
+        let op = GroupConcat::with_format(s.as_global(), 1, vec![0], ConcatFormat::Array).unwrap();
+        c.set_op("array_agg", &["x", "ys"], op, true);
+
+        let rs = c.narrow_one_row(vec![1.into(), 1.into()], true);
+        assert_eq!(
+            rs,
+            vec![(
+                vec![1.into(), DfValue::from(vec![DfValue::from(1)]), 1.into()],
+                true
+            )]
+            .into()
+        );
+
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(
+            rs,
+            vec![
+                (
+                    vec![1.into(), DfValue::from(vec![DfValue::from(1)]), 1.into()],
+                    false
+                ),
+                (
+                    vec![
+                        1.into(),
+                        DfValue::from(vec![DfValue::from(1), DfValue::from(2)]),
+                        2.into()
+                    ],
+                    true
+                ),
+            ]
+            .into()
+        );
+    }
+
     #[test]
     #[allow(clippy::cognitive_complexity)]
     fn it_forwards() {
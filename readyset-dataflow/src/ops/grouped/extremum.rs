@@ -12,6 +12,12 @@ pub enum Extremum {
     Min,
     /// The maximum value of the `over` column for all records of each group.
     Max,
+    /// Whether the `over` column is true for all records of each group - the minimum truth value
+    /// of the column.
+    BoolAnd,
+    /// Whether the `over` column is true for any record of each group - the maximum truth value of
+    /// the column.
+    BoolOr,
 }
 
 impl Extremum {
@@ -85,11 +91,16 @@ impl GroupedOperation for ExtremumOperator {
         #[allow(clippy::indexing_slicing)] // Invariant documented.
         let v = &r[self.over];
         if let DfValue::None = *v {
-            Ok(DiffType::None)
-        } else if pos {
-            Ok(DiffType::Insert(v.clone()))
+            return Ok(DiffType::None);
+        }
+        let v = match self.op {
+            Extremum::BoolAnd | Extremum::BoolOr => DfValue::from(v.is_truthy()),
+            Extremum::Min | Extremum::Max => v.clone(),
+        };
+        if pos {
+            Ok(DiffType::Insert(v))
         } else {
-            Ok(DiffType::Remove(v.clone()))
+            Ok(DiffType::Remove(v))
         }
     }
 
@@ -108,8 +119,8 @@ impl GroupedOperation for ExtremumOperator {
         let is_extreme_value = |x: &DfValue| {
             if let Some(n) = current {
                 match self.op {
-                    Extremum::Max => x >= n,
-                    Extremum::Min => x <= n,
+                    Extremum::Max | Extremum::BoolOr => x >= n,
+                    Extremum::Min | Extremum::BoolAnd => x <= n,
                 }
             } else {
                 true
@@ -129,8 +140,8 @@ impl GroupedOperation for ExtremumOperator {
         }
 
         let extreme = match self.op {
-            Extremum::Min => extreme_values.into_iter().min(),
-            Extremum::Max => extreme_values.into_iter().max(),
+            Extremum::Min | Extremum::BoolAnd => extreme_values.into_iter().min(),
+            Extremum::Max | Extremum::BoolOr => extreme_values.into_iter().max(),
         };
 
         Ok(extreme)
@@ -141,12 +152,16 @@ impl GroupedOperation for ExtremumOperator {
             return String::from(match self.op {
                 Extremum::Min => "MIN",
                 Extremum::Max => "MAX",
+                Extremum::BoolAnd => "BOOL_AND",
+                Extremum::BoolOr => "BOOL_OR",
             });
         }
 
         let op_string = match self.op {
             Extremum::Min => format!("min({})", self.over),
             Extremum::Max => format!("max({})", self.over),
+            Extremum::BoolAnd => format!("bool_and({})", self.over),
+            Extremum::BoolOr => format!("bool_or({})", self.over),
         };
         let group_cols = self
             .group
@@ -162,8 +177,11 @@ impl GroupedOperation for ExtremumOperator {
     }
 
     fn output_col_type(&self) -> DfType {
-        // Type of extremum relies on col type.
-        DfType::Unknown
+        match self.op {
+            Extremum::BoolAnd | Extremum::BoolOr => DfType::Bool,
+            // Type of extremum relies on col type.
+            Extremum::Min | Extremum::Max => DfType::Unknown,
+        }
     }
 }
 
@@ -336,6 +354,36 @@ mod tests {
         assert_record_change(key, 7.into(), 5.into(), out);
     }
 
+    #[test]
+    fn it_forwards_bool_and() {
+        let mut c = setup(Extremum::BoolAnd, true);
+        let key = 1;
+
+        let out = c.narrow_one_row(vec![key.into(), 4.into()], true);
+        assert_positive_record(key, 1, out);
+
+        // Any false value makes the whole group false
+        let out = c.narrow_one_row(vec![key.into(), 0.into()], true);
+        assert_record_change(key, true.into(), false.into(), out);
+
+        // Until it's removed
+        let out = c.narrow_one_row((vec![key.into(), 0.into()], false), true);
+        assert_record_change(key, false.into(), true.into(), out);
+    }
+
+    #[test]
+    fn it_forwards_bool_or() {
+        let mut c = setup(Extremum::BoolOr, true);
+        let key = 1;
+
+        let out = c.narrow_one_row(vec![key.into(), 0.into()], true);
+        assert_positive_record(key, 0, out);
+
+        // Any true value makes the whole group true
+        let out = c.narrow_one_row(vec![key.into(), 7.into()], true);
+        assert_record_change(key, false.into(), true.into(), out);
+    }
+
     #[test]
     fn it_cancels_out_opposite_records() {
         let mut c = setup(Extremum::Max, true);
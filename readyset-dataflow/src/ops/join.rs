@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 
+use dataflow_expression::Expr;
 use dataflow_state::PointKey;
 use itertools::Itertools;
 use launchpad::intervals::into_bound_endpoint;
@@ -24,6 +25,8 @@ pub enum JoinType {
     Left,
     /// Inner join between two views
     Inner,
+    /// Full outer join between two views
+    Full,
 }
 
 /// Where to source a join column
@@ -61,6 +64,13 @@ pub struct Join {
     generated_column_buffer: HashMap<(Vec<usize>, Side), Records>,
 
     kind: JoinType,
+
+    /// An optional predicate which must also hold for a pair of rows matching on the join key for
+    /// those rows to be joined, evaluated against the concatenation of the row from the left
+    /// parent and the row from the right parent. For left joins, a row in the left parent is
+    /// joined to NULLs if no rows in the right parent both match on the join key and satisfy this
+    /// predicate.
+    residual: Option<Expr>,
 }
 
 impl Join {
@@ -133,6 +143,26 @@ impl Join {
             in_place_right_emit,
             generated_column_buffer: Default::default(),
             kind,
+            residual: None,
+        }
+    }
+
+    /// Set the residual predicate for this join, which is evaluated against the concatenation of
+    /// each pair of rows from the left and right parents that match on the join key.
+    pub fn with_residual(mut self, residual: Expr) -> Self {
+        self.residual = Some(residual);
+        self
+    }
+
+    /// Returns true if the given rows from the left and right parents satisfy the residual
+    /// predicate of this join, if any
+    fn residual_matches(&self, left: &[DfValue], right: &[DfValue]) -> ReadySetResult<bool> {
+        match &self.residual {
+            None => Ok(true),
+            Some(residual) => {
+                let row = left.iter().chain(right).cloned().collect::<Vec<_>>();
+                Ok(residual.eval(&row)?.is_truthy())
+            }
         }
     }
 
@@ -174,19 +204,23 @@ impl Join {
                 .iter()
                 .filter(|r| rec.indices(from_key.clone()) == r.indices(other_key.clone()))
             {
-                ret.push(Record::Positive(self.generate_row(&rec, other_rec.row())))
+                if self.residual_matches(&rec, other_rec.row())? {
+                    ret.push(Record::Positive(self.generate_row(&rec, other_rec.row())))
+                }
             }
         }
         Ok(ret.into())
     }
 
+    /// Generate a row joining the given row from `side` to NULLs for all the columns from the
+    /// other side
     // TODO: make non-allocating
-    fn generate_null(&self, left: &[DfValue]) -> Vec<DfValue> {
+    fn generate_null(&self, side: Side, row: &[DfValue]) -> Vec<DfValue> {
         self.emit
             .iter()
-            .map(|&(side, col)| {
-                if side == Side::Left {
-                    left[col].clone()
+            .map(|&(from_side, col)| {
+                if from_side == side {
+                    row[col].clone()
                 } else {
                     DfValue::None
                 }
@@ -194,9 +228,28 @@ impl Join {
             .collect()
     }
 
+    /// Returns true if rows from the given side of this join which don't match any rows in the
+    /// other side should be emitted joined to NULLs
+    fn preserves(&self, side: Side) -> bool {
+        match self.kind {
+            JoinType::Inner => false,
+            JoinType::Left => side == Side::Left,
+            JoinType::Full => true,
+        }
+    }
+
     fn resolve_col(&self, col: usize) -> (Option<usize>, Option<usize>) {
         let (side, pcol) = self.emit[col];
 
+        if self.kind == JoinType::Full {
+            // Rows in a full join which don't match the other side have NULLs for the join columns
+            // from that side, so we can't source a join column from both parents
+            return match side {
+                Side::Left => (Some(pcol), None),
+                Side::Right => (None, Some(pcol)),
+            };
+        }
+
         if let Some((on_l, on_r)) = self
             .on
             .iter()
@@ -235,6 +288,14 @@ impl Ingredient for Join {
     }
 
     fn must_replay_among(&self) -> Option<HashSet<NodeIndex>> {
+        if self.kind == JoinType::Full {
+            // Rows in the right which don't match anything in the left can only be found by
+            // replaying the right
+            return Some(HashSet::from([
+                self.left.as_global(),
+                self.right.as_global(),
+            ]));
+        }
         Some(Some(self.left.as_global()).into_iter().collect())
     }
 
@@ -260,6 +321,13 @@ impl Ingredient for Join {
         let mut lookups = Vec::new();
 
         let from_left = from == *self.left;
+        let from_side = if from_left { Side::Left } else { Side::Right };
+
+        // During a full replay of a full join, we receive rows from both parents. Rows which match
+        // on the join key are emitted when the left is replayed, so when replaying the right we
+        // only need to emit rows which don't match anything in the left.
+        let full_replay = matches!(replay, ReplayContext::Full { .. });
+        let only_unmatched = full_replay && self.kind == JoinType::Full && !from_left;
 
         let other = if from_left { *self.right } else { *self.left };
 
@@ -381,15 +449,19 @@ impl Ingredient for Join {
             // type.
             let nulls = join_key.iter().any(|v| v.is_none());
 
-            // The difference between a left join and an inner join, is that for the former we must
-            // emit rows with nulls even if we later get no match in the other side.
+            // The difference between an outer join and an inner join, is that for the former we
+            // must emit rows with nulls even if we later get no match in the other side.
 
-            let mut new_right_count = None;
+            let mut new_from_count = None;
+            // If we have a residual predicate, whether we need to emit NULLs depends on the rows
+            // in this side that satisfy the predicate for each individual row in the other side,
+            // rather than just the number of rows in this side
+            let mut new_from_rows = None;
 
-            if self.kind == JoinType::Left && !from_left {
+            if self.preserves(from_side.other_side()) && !full_replay {
                 let rc = self.lookup(
-                    *self.right,
-                    &self.on_right(),
+                    from,
+                    &from_key,
                     &PointKey::from(join_key.iter().cloned()),
                     nodes,
                     state,
@@ -400,8 +472,8 @@ impl Ingredient for Join {
                     IngredientLookupResult::Records(rc) => {
                         if replay_key_cols.is_some() && !nulls {
                             lookups.push(Lookup {
-                                on: *self.right,
-                                cols: self.on_right(),
+                                on: from,
+                                cols: from_key.clone(),
                                 key: join_key
                                     .clone()
                                     .try_into()
@@ -409,11 +481,15 @@ impl Ingredient for Join {
                             });
                         }
 
-                        let rc = rc.count();
-                        new_right_count = Some(rc);
+                        if self.residual.is_some() {
+                            new_from_rows = Some(rc.collect::<Result<Vec<_>, _>>()?);
+                        } else {
+                            new_from_count = Some(rc.count());
+                        }
                     }
                     IngredientLookupResult::Miss => {
                         // we got something from right, but that row's key is not in right??
+                        // (or vice versa, for full joins)
                         //
                         // this *can* happen! imagine if you have two partial indices on right,
                         // one on column a and one on column b. imagine that a is the join key.
@@ -467,40 +543,78 @@ impl Ingredient for Join {
             }
 
             let other_rows = other_records.collect::<Result<Vec<_>, _>>()?;
+            let group = group.collect::<Vec<_>>();
 
             let mut rc_diff = 0isize;
-            for r in group {
-                let (row, positive) = r.extract();
+            for r in &group {
+                let (row, positive) = (r.rec(), r.is_positive());
 
                 rc_diff += if positive { 1 } else { -1 };
 
-                if other_rows.is_empty() {
-                    if self.kind == JoinType::Left && from_left {
-                        // left join, got a thing from left, no rows in right == NULL
-                        ret.push((self.generate_null(&row), positive).into());
-                    }
-                } else {
-                    for other in other_rows.iter() {
-                        if from == *self.left {
-                            ret.push((self.generate_row(&row, other), positive).into());
-                        } else {
-                            ret.push((self.generate_row(other, &row), positive).into());
+                let mut matched = false;
+                for other in other_rows.iter() {
+                    let (left, right) = if from_left {
+                        (row, &**other)
+                    } else {
+                        (&**other, row)
+                    };
+                    if self.residual_matches(left, right)? {
+                        matched = true;
+                        if !only_unmatched {
+                            ret.push((self.generate_row(left, right), positive).into());
                         }
                     }
                 }
+
+                if !matched && self.preserves(from_side) {
+                    // outer join, no matching rows in the other side == NULL
+                    ret.push((self.generate_null(from_side, row), positive).into());
+                }
             }
 
-            // For a left join with updates from the right side, we also have to emit/delete NULL
-            // rows if row count changed to/from zero
-            if let Some(new_rc) = new_right_count {
+            // For an outer join with updates from the side opposite a preserved side, we also have
+            // to emit/delete NULL rows if row count changed to/from zero
+            let other_side = from_side.other_side();
+            if let Some(new_rc) = new_from_count {
                 let old_rc = new_rc as isize - rc_diff;
                 if new_rc == 0 && old_rc != 0 {
                     for other in other_rows.iter() {
-                        ret.push((self.generate_null(other), true).into());
+                        ret.push((self.generate_null(other_side, other), true).into());
                     }
                 } else if new_rc != 0 && old_rc == 0 {
                     for other in other_rows.iter() {
-                        ret.push((self.generate_null(other), false).into());
+                        ret.push((self.generate_null(other_side, other), false).into());
+                    }
+                }
+            }
+
+            if let Some(new_from_rows) = new_from_rows {
+                let residual_matches = |other: &[DfValue], row: &[DfValue]| {
+                    if from_left {
+                        self.residual_matches(row, other)
+                    } else {
+                        self.residual_matches(other, row)
+                    }
+                };
+
+                for other in other_rows.iter().map(|other| &**other) {
+                    let mut new_rc = 0isize;
+                    for row in new_from_rows.iter().map(|row| &**row) {
+                        if residual_matches(other, row)? {
+                            new_rc += 1;
+                        }
+                    }
+                    let mut rc_diff = 0isize;
+                    for r in &group {
+                        if residual_matches(other, r.rec())? {
+                            rc_diff += if r.is_positive() { 1 } else { -1 };
+                        }
+                    }
+                    let old_rc = new_rc - rc_diff;
+                    if new_rc == 0 && old_rc != 0 {
+                        ret.push((self.generate_null(other_side, other), true).into());
+                    } else if new_rc != 0 && old_rc == 0 {
+                        ret.push((self.generate_null(other_side, other), false).into());
                     }
                 }
             }
@@ -529,6 +643,7 @@ impl Ingredient for Join {
             return String::from(match self.kind {
                 JoinType::Left => "⋉",
                 JoinType::Inner => "⋈",
+                JoinType::Full => "⟗",
             });
         }
 
@@ -548,10 +663,17 @@ impl Ingredient for Join {
         let op = match self.kind {
             JoinType::Left => "⋉",
             JoinType::Inner => "⋈",
+            JoinType::Full => "⟗",
         };
 
+        let residual = self
+            .residual
+            .as_ref()
+            .map(|residual| format!(" σ[{}]", residual))
+            .unwrap_or_default();
+
         format!(
-            "[{}] {}:({}) {} {}:({})",
+            "[{}] {}:({}) {} {}:({}){}",
             emit,
             self.left.as_global().index(),
             self.on_left().into_iter().map(|i| i.to_string()).join(", "),
@@ -560,7 +682,8 @@ impl Ingredient for Join {
             self.on_right()
                 .into_iter()
                 .map(|i| i.to_string())
-                .join(", ")
+                .join(", "),
+            residual
         )
     }
 
@@ -962,6 +1085,212 @@ mod tests {
         assert_eq!(res, vec![(l.as_global(), Some(0))]);
     }
 
+    #[test]
+    fn full_join() {
+        let mut g = ops::test::MockGraph::new();
+        let l = g.add_base("left", &["l0", "l1"]);
+        let r = g.add_base("right", &["r0", "r1"]);
+
+        use self::JoinSource::*;
+        let j = Join::new(
+            l.as_global(),
+            r.as_global(),
+            JoinType::Full,
+            vec![B(0, 0), L(1), R(1)],
+        );
+        g.set_op("join", &["j0", "j1", "j2"], j, false);
+
+        // nothing in the left, so a row from the right is joined to NULLs
+        let r_1x = vec![1.into(), "x".try_into().unwrap()];
+        g.seed(r, r_1x.clone());
+        let rs = g.one_row(r, r_1x, false);
+        assert_eq!(
+            rs,
+            vec![(
+                vec![DfValue::None, DfValue::None, "x".try_into().unwrap()],
+                true
+            )]
+            .into()
+        );
+
+        // a matching row from the left should revoke the NULLs for the row in the right
+        let l_1a = vec![1.into(), "a".try_into().unwrap()];
+        g.seed(l, l_1a.clone());
+        let rs = g.one_row(l, l_1a.clone(), false);
+        assert_eq!(
+            rs,
+            vec![
+                (
+                    vec![1.into(), "a".try_into().unwrap(), "x".try_into().unwrap()],
+                    true
+                ),
+                (
+                    vec![DfValue::None, DfValue::None, "x".try_into().unwrap()],
+                    false
+                ),
+            ]
+            .into()
+        );
+
+        // a row from the left with no match in the right is joined to NULLs
+        let l_2b = vec![2.into(), "b".try_into().unwrap()];
+        g.seed(l, l_2b.clone());
+        let rs = g.one_row(l, l_2b.clone(), false);
+        assert_eq!(
+            rs,
+            vec![(vec![2.into(), "b".try_into().unwrap(), DfValue::None], true)].into()
+        );
+
+        // removing the only matching row in the left brings back the NULLs for the right
+        g.unseed(l);
+        g.seed(l, l_2b);
+        let rs = g.one_row(l, (l_1a, false), false);
+        assert_eq!(
+            rs,
+            vec![
+                (
+                    vec![1.into(), "a".try_into().unwrap(), "x".try_into().unwrap()],
+                    false
+                ),
+                (
+                    vec![DfValue::None, DfValue::None, "x".try_into().unwrap()],
+                    true
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn full_join_column_source() {
+        let mut g = ops::test::MockGraph::new();
+        let l = g.add_base("left", &["l0", "l1"]);
+        let r = g.add_base("right", &["r0", "r1"]);
+
+        use self::JoinSource::*;
+        let j = Join::new(
+            l.as_global(),
+            r.as_global(),
+            JoinType::Full,
+            vec![B(0, 0), L(1), R(0), R(1)],
+        );
+        g.set_op("join", &["j0", "j1", "j2", "j3"], j, false);
+
+        assert_eq!(
+            g.node().column_source(&[0]),
+            ColumnSource::exact_copy(l.as_global(), vec1![0])
+        );
+        assert_eq!(
+            g.node().column_source(&[2]),
+            ColumnSource::exact_copy(r.as_global(), vec1![0])
+        );
+    }
+
+    mod residual {
+        use dataflow_expression::utils::column_with_type;
+        use dataflow_expression::BinaryOperator;
+        use readyset_data::DfType;
+
+        use super::*;
+
+        /// Set up a join with the residual predicate `l1 < r1`
+        fn setup(kind: JoinType) -> (ops::test::MockGraph, IndexPair, IndexPair) {
+            let mut g = ops::test::MockGraph::new();
+            let l = g.add_base("left", &["l0", "l1"]);
+            let r = g.add_base("right", &["r0", "r1"]);
+
+            use self::JoinSource::*;
+            let j = Join::new(
+                l.as_global(),
+                r.as_global(),
+                kind,
+                vec![B(0, 0), L(1), R(1)],
+            )
+            .with_residual(Expr::Op {
+                op: BinaryOperator::Less,
+                left: Box::new(column_with_type(1, DfType::Int)),
+                right: Box::new(column_with_type(3, DfType::Int)),
+                ty: DfType::Bool,
+            });
+
+            g.set_op("join", &["j0", "j1", "j2"], j, false);
+            (g, l, r)
+        }
+
+        #[test]
+        fn inner_filters_matches() {
+            let (mut j, l, r) = setup(JoinType::Inner);
+
+            let r_1_5 = vec![1.into(), 5.into()];
+            let r_1_10 = vec![1.into(), 10.into()];
+            j.seed(r, r_1_5.clone());
+            j.one_row(r, r_1_5, false);
+            j.seed(r, r_1_10.clone());
+            j.one_row(r, r_1_10, false);
+
+            let l_1_7 = vec![1.into(), 7.into()];
+            j.seed(l, l_1_7.clone());
+            let rs = j.one_row(l, l_1_7, false);
+            assert_eq!(rs, vec![(vec![1.into(), 7.into(), 10.into()], true)].into());
+
+            let r_1_3 = vec![1.into(), 3.into()];
+            j.seed(r, r_1_3.clone());
+            let rs = j.one_row(r, r_1_3, false);
+            assert!(rs.is_empty());
+        }
+
+        #[test]
+        fn left_emits_nulls_without_residual_match() {
+            let (mut j, l, r) = setup(JoinType::Left);
+
+            let r_1_5 = vec![1.into(), 5.into()];
+            j.seed(r, r_1_5.clone());
+            j.one_row(r, r_1_5, false);
+
+            // matches on the join key, but not on the residual
+            let l_1_7 = vec![1.into(), 7.into()];
+            j.seed(l, l_1_7.clone());
+            let rs = j.one_row(l, l_1_7, false);
+            assert_eq!(
+                rs,
+                vec![(vec![1.into(), 7.into(), DfValue::None], true)].into()
+            );
+
+            // a row in the right satisfying the residual should revoke the nulls
+            let r_1_10 = vec![1.into(), 10.into()];
+            j.seed(r, r_1_10.clone());
+            let rs = j.one_row(r, r_1_10.clone(), false);
+            assert_eq!(
+                rs,
+                vec![
+                    (vec![1.into(), 7.into(), 10.into()], true),
+                    (vec![1.into(), 7.into(), DfValue::None], false),
+                ]
+                .into()
+            );
+
+            // another row in the right not satisfying the residual should have no effect
+            let r_1_2 = vec![1.into(), 2.into()];
+            j.seed(r, r_1_2.clone());
+            let rs = j.one_row(r, r_1_2, false);
+            assert!(rs.is_empty());
+
+            // removing the only row satisfying the residual should bring the nulls back
+            j.unseed(r);
+            j.seed(r, vec![1.into(), 5.into()]);
+            j.seed(r, vec![1.into(), 2.into()]);
+            let rs = j.one_row(r, (r_1_10, false), false);
+            assert_eq!(
+                rs,
+                vec![
+                    (vec![1.into(), 7.into(), 10.into()], false),
+                    (vec![1.into(), 7.into(), DfValue::None], true),
+                ]
+                .into()
+            );
+        }
+    }
+
     mod handle_upquery {
         use std::ops::Bound;
 
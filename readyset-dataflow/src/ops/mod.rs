@@ -15,9 +15,11 @@ pub mod join;
 pub mod latest;
 pub mod paginate;
 pub mod project;
+pub mod recursive;
 pub mod topk;
 pub mod union;
 pub(crate) mod utils;
+pub mod window;
 
 use crate::ops::grouped::concat::GroupConcat;
 use crate::processing::{
@@ -56,6 +58,8 @@ pub enum NodeOperator {
     Identity(identity::Identity),
     Filter(filter::Filter),
     TopK(topk::TopK),
+    Window(window::Window),
+    Recursive(recursive::Recursive),
 }
 
 impl ToString for NodeOperator {
@@ -72,6 +76,8 @@ impl ToString for NodeOperator {
             NodeOperator::Identity(_) => "Identity",
             NodeOperator::Filter(_) => "Filter",
             NodeOperator::TopK(_) => "TopK",
+            NodeOperator::Window(_) => "Window",
+            NodeOperator::Recursive(_) => "Recursive",
         }
         .to_string()
     }
@@ -91,6 +97,8 @@ macro_rules! impl_ingredient_fn_mut {
             NodeOperator::Identity(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Filter(ref mut i) => i.$fn($($arg),*),
             NodeOperator::TopK(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::Window(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::Recursive(ref mut i) => i.$fn($($arg),*),
         }
     }
 }
@@ -109,6 +117,8 @@ macro_rules! impl_ingredient_fn_ref {
             NodeOperator::Identity(ref i) => i.$fn($($arg),*),
             NodeOperator::Filter(ref i) => i.$fn($($arg),*),
             NodeOperator::TopK(ref i) => i.$fn($($arg),*),
+            NodeOperator::Window(ref i) => i.$fn($($arg),*),
+            NodeOperator::Recursive(ref i) => i.$fn($($arg),*),
         }
     }
 }
@@ -463,6 +473,27 @@ pub mod test {
             self.input(src, u, remember).results
         }
 
+        /// Like [`Self::one`] without remembering the results, but returns errors from processing
+        /// the input rather than panicking
+        pub fn try_one<U: Into<Records>>(
+            &mut self,
+            src: IndexPair,
+            u: U,
+        ) -> ReadySetResult<Records> {
+            let id = self.nut.unwrap();
+            let mut n = self.nodes[*id].borrow_mut();
+            match n.as_mut_internal().unwrap().on_input_raw(
+                *src,
+                u.into(),
+                ReplayContext::None,
+                &self.nodes,
+                &self.states,
+            )? {
+                RawProcessingResult::Regular(res) => Ok(res.results),
+                _ => unreachable!(),
+            }
+        }
+
         pub fn one_row<R: Into<Record>>(
             &mut self,
             src: IndexPair,
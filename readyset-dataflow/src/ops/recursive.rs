@@ -0,0 +1,704 @@
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use dataflow_expression::Expr;
+use dataflow_state::{MaterializedNodeState, PointKey};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::processing::{ColumnSource, IngredientLookupResult, LookupIndex, LookupMode};
+
+/// The maximum number of times the recursive term will be applied to a row derived from the
+/// anchor, mirroring the default value of MySQL's `cte_max_recursion_depth`. Exceeding it fails
+/// processing of the input that caused it, rather than maintaining a truncated result.
+pub const MAX_RECURSION_DEPTH: usize = 1000;
+
+/// Recursive implements a dataflow node that maintains the result of a recursive common table
+/// expression (`WITH RECURSIVE`) by computing the fixpoint of a recursive term over the rows of
+/// its anchor.
+///
+/// The node has two parents: the *anchor*, which provides the initial rows of the CTE, and the
+/// *step* relation, which the recursive term joins against the rows of the CTE. For every row `r`
+/// of the CTE and every row `s` of the step relation where the `step_key` columns of `s` are
+/// equal to the `cte_key` columns of `r` (and the optional `condition` holds), the row given by
+/// evaluating the `emit` expressions is also a row of the CTE. Both the condition and the `emit`
+/// expressions are evaluated against the concatenation of `s` and `r`.
+///
+/// If `distinct` is set (for `UNION`), each row is emitted at most once. Otherwise (for `UNION
+/// ALL`), a row is emitted once for every way it can be derived from the anchor.
+///
+/// Insertions are maintained incrementally, by only applying the recursive term to rows which are
+/// new to the CTE. Deletions from a `UNION ALL` CTE are maintained the same way, but since a row
+/// of a `UNION` CTE can have more than one derivation, deleting a row from either parent of a
+/// `UNION` CTE recomputes the whole result and emits the difference.
+///
+/// The node keeps a copy of the rows of its anchor to be able to recompute its result, and so
+/// must always be fully materialized.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Recursive {
+    /// The parent providing the initial rows of the CTE
+    anchor: IndexPair,
+    /// The parent the recursive term joins against the rows of the CTE
+    step: IndexPair,
+    /// The index of this node. Used to look up into our own state
+    our_index: Option<IndexPair>,
+    /// Columns in `step` which are compared to `cte_key`
+    step_key: Vec<usize>,
+    /// Columns in the CTE which are compared to `step_key`
+    cte_key: Vec<usize>,
+    /// Optional extra condition for the recursive term, evaluated against the concatenation of a
+    /// row from `step` and a row of the CTE
+    condition: Option<Expr>,
+    /// Expressions computing the columns of a derived row of the CTE, evaluated against the
+    /// concatenation of a row from `step` and a row of the CTE
+    emit: Vec<Expr>,
+    /// Whether the CTE was declared with `UNION` (rather than `UNION ALL`)
+    distinct: bool,
+    /// The rows we've received from the anchor, along with how many times we've received them
+    #[serde(skip)]
+    anchor_rows: HashMap<Vec<DfValue>, usize>,
+}
+
+impl Recursive {
+    /// Construct a new recursive operator, deriving rows of the CTE from the rows of `anchor` by
+    /// repeatedly joining them against `step`.
+    pub fn new(
+        anchor: NodeIndex,
+        step: NodeIndex,
+        step_key: Vec<usize>,
+        cte_key: Vec<usize>,
+        condition: Option<Expr>,
+        emit: Vec<Expr>,
+        distinct: bool,
+    ) -> ReadySetResult<Self> {
+        if anchor == step {
+            internal!("Recursive node must have distinct anchor and step parents");
+        }
+        if step_key.is_empty() || step_key.len() != cte_key.len() {
+            internal!("Recursive node must have the same number of key columns on both sides");
+        }
+
+        Ok(Recursive {
+            anchor: anchor.into(),
+            step: step.into(),
+            our_index: None,
+            step_key,
+            cte_key,
+            condition,
+            emit,
+            distinct,
+            anchor_rows: Default::default(),
+        })
+    }
+
+    /// Apply the recursive term to the given row from `step` and row of the CTE, returning the
+    /// derived row of the CTE if the condition holds
+    fn derive(
+        &self,
+        step_row: &[DfValue],
+        cte_row: &[DfValue],
+    ) -> ReadySetResult<Option<Vec<DfValue>>> {
+        let row = step_row.iter().chain(cte_row).cloned().collect::<Vec<_>>();
+
+        if let Some(condition) = &self.condition {
+            if !condition.eval(&row)?.is_truthy() {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(
+            self.emit
+                .iter()
+                .map(|expr| expr.eval(&row))
+                .collect::<ReadySetResult<_>>()?,
+        ))
+    }
+
+    /// Look up all the rows of `step` which join against the given row of the CTE
+    fn step_rows(
+        &self,
+        cte_row: &[DfValue],
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<Vec<Vec<DfValue>>> {
+        let key = self
+            .cte_key
+            .iter()
+            .map(|i| cte_row.get(*i).cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ReadySetError::InvalidRecordLength)?;
+        // See [note: null-join-keys]
+        if key.iter().any(|v| v.is_none()) {
+            return Ok(vec![]);
+        }
+
+        match self.lookup(
+            *self.step,
+            &self.step_key,
+            &PointKey::from(key),
+            nodes,
+            state,
+            LookupMode::Strict,
+        )? {
+            IngredientLookupResult::Records(rs) => rs.map(|r| r.map(|r| r.into_owned())).collect(),
+            IngredientLookupResult::Miss => {
+                internal!("Recursive node's step parent must be fully materialized")
+            }
+        }
+    }
+
+    /// Look up all the rows currently in our own state which join against the given row of `step`
+    fn cte_rows(
+        &self,
+        step_row: &[DfValue],
+        state: &StateMap,
+    ) -> ReadySetResult<Vec<Vec<DfValue>>> {
+        let key = self
+            .step_key
+            .iter()
+            .map(|i| step_row.get(*i).cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ReadySetError::InvalidRecordLength)?;
+        if key.iter().any(|v| v.is_none()) {
+            return Ok(vec![]);
+        }
+
+        match self
+            .own_state(state)?
+            .lookup(&self.cte_key, &PointKey::from(key))
+        {
+            LookupResult::Some(rs) => Ok(rs.into_iter().map(|r| r.into_owned()).collect()),
+            LookupResult::Missing => {
+                internal!("Recursive node must be fully materialized")
+            }
+        }
+    }
+
+    /// Returns true if the given row is currently in our own state
+    fn contains(&self, row: &[DfValue], state: &StateMap) -> ReadySetResult<bool> {
+        let key = self
+            .cte_key
+            .iter()
+            .map(|i| row.get(*i).cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ReadySetError::InvalidRecordLength)?;
+
+        match self
+            .own_state(state)?
+            .lookup(&self.cte_key, &PointKey::from(key))
+        {
+            LookupResult::Some(rs) => Ok(rs.into_iter().any(|r| *r == *row)),
+            LookupResult::Missing => {
+                internal!("Recursive node must be fully materialized")
+            }
+        }
+    }
+
+    fn own_state<'a>(&self, state: &'a StateMap) -> ReadySetResult<&'a MaterializedNodeState> {
+        let us = self
+            .our_index
+            .ok_or_else(|| internal_err!("Recursive node used before on_commit"))?;
+        state
+            .get(*us)
+            .ok_or_else(|| internal_err!("Recursive node must have its own state materialized"))
+    }
+
+    /// Repeatedly apply the recursive term to the rows in `frontier` and the rows derived from
+    /// them until no new rows are derived, returning all of the rows in `frontier` along with all
+    /// the rows derived from them.
+    ///
+    /// Each derived row is passed to `is_new`, and is only returned (and derived from further) if
+    /// it returns true.
+    fn fixpoint<F>(
+        &self,
+        mut frontier: Vec<Vec<DfValue>>,
+        mut is_new: F,
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<Vec<Vec<DfValue>>>
+    where
+        F: FnMut(&[DfValue]) -> ReadySetResult<bool>,
+    {
+        let mut res = vec![];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            // Stopping here would leave out the rows derived from the frontier, so fail rather
+            // than maintain an incomplete result
+            if depth == MAX_RECURSION_DEPTH {
+                return Err(ReadySetError::RecursionDepthExceeded {
+                    max_depth: MAX_RECURSION_DEPTH,
+                });
+            }
+            depth += 1;
+
+            let mut next = vec![];
+            for cte_row in &frontier {
+                for step_row in self.step_rows(cte_row, nodes, state)? {
+                    if let Some(row) = self.derive(&step_row, cte_row)? {
+                        if is_new(&row)? {
+                            next.push(row);
+                        }
+                    }
+                }
+            }
+            res.append(&mut mem::replace(&mut frontier, next));
+        }
+        Ok(res)
+    }
+
+    /// Recompute the entire result of the CTE from our copy of the anchor rows, and return
+    /// records for the difference between that and our current state.
+    fn recompute(&self, nodes: &DomainNodes, state: &StateMap) -> ReadySetResult<Vec<Record>> {
+        let mut seen = HashSet::new();
+        let anchor_rows = self
+            .anchor_rows
+            .iter()
+            .flat_map(|(row, count)| {
+                let count = if self.distinct { 1 } else { *count };
+                std::iter::repeat(row).take(count)
+            })
+            .filter(|row| !self.distinct || seen.insert((*row).clone()))
+            .cloned()
+            .collect();
+        let new_rows = self.fixpoint(
+            anchor_rows,
+            |row| Ok(!self.distinct || seen.insert(row.to_vec())),
+            nodes,
+            state,
+        )?;
+
+        let mut diff: HashMap<Vec<DfValue>, isize> = HashMap::new();
+        for row in new_rows {
+            *diff.entry(row).or_default() += 1;
+        }
+        for row in self.own_state(state)?.cloned_records() {
+            *diff.entry(row).or_default() -= 1;
+        }
+
+        Ok(diff
+            .into_iter()
+            .flat_map(|(row, count)| std::iter::repeat((row, count > 0)).take(count.unsigned_abs()))
+            .map(Record::from)
+            .collect())
+    }
+
+    /// Handle a batch of records from the anchor, updating our copy of the anchor rows
+    fn on_anchor_input(
+        &mut self,
+        rs: Records,
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<Vec<Record>> {
+        let (positives, negatives): (Vec<_>, Vec<_>) =
+            rs.into_iter().partition(|r| r.is_positive());
+
+        for r in &positives {
+            *self.anchor_rows.entry(r.rec().to_vec()).or_default() += 1;
+        }
+        for r in &negatives {
+            if let Some(count) = self.anchor_rows.get_mut(r.rec()) {
+                *count -= 1;
+                if *count == 0 {
+                    self.anchor_rows.remove(r.rec());
+                }
+            }
+        }
+
+        if self.distinct {
+            if !negatives.is_empty() {
+                return self.recompute(nodes, state);
+            }
+
+            let mut pending = HashSet::new();
+            let mut is_new = |row: &[DfValue]| -> ReadySetResult<bool> {
+                Ok(!self.contains(row, state)? && pending.insert(row.to_vec()))
+            };
+            let mut frontier = vec![];
+            for r in positives {
+                if is_new(r.rec())? {
+                    frontier.push(r.into_row());
+                }
+            }
+            return Ok(self
+                .fixpoint(frontier, is_new, nodes, state)?
+                .into_iter()
+                .map(Record::Positive)
+                .collect());
+        }
+
+        // Each derivation of a row in a UNION ALL CTE starts at exactly one row of the anchor, so
+        // inserting or deleting an anchor row inserts or deletes all the rows derived from it.
+        let positives = positives.into_iter().map(Record::into_row).collect();
+        let negatives = negatives.into_iter().map(Record::into_row).collect();
+        Ok(self
+            .fixpoint(positives, |_| Ok(true), nodes, state)?
+            .into_iter()
+            .map(Record::Positive)
+            .chain(
+                self.fixpoint(negatives, |_| Ok(true), nodes, state)?
+                    .into_iter()
+                    .map(Record::Negative),
+            )
+            .collect())
+    }
+
+    /// Handle a batch of records from the step relation.
+    ///
+    /// By the time we receive the batch, the state of the step relation already reflects it.
+    fn on_step_input(
+        &self,
+        rs: Records,
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<Vec<Record>> {
+        let positive = match rs.iter().map(|r| r.is_positive()).dedup().exactly_one() {
+            Ok(positive) => positive,
+            // Mixed insertions and deletions
+            Err(_) => return self.recompute(nodes, state),
+        };
+        if self.distinct && !positive {
+            return self.recompute(nodes, state);
+        }
+
+        // Every derivation which uses one of the rows in the batch can be split at the first use
+        // of a row in the batch (for insertions) or at the last use (for deletions): the rows
+        // before that are already in our state, and the rest are derived through the current
+        // contents of the step relation.
+        let mut pending = HashSet::new();
+        let mut is_new = |row: &[DfValue]| -> ReadySetResult<bool> {
+            Ok(!self.distinct || (!self.contains(row, state)? && pending.insert(row.to_vec())))
+        };
+        let mut frontier = vec![];
+        for step_row in rs.iter() {
+            for cte_row in self.cte_rows(step_row.rec(), state)? {
+                if let Some(row) = self.derive(step_row.rec(), &cte_row)? {
+                    if is_new(&row)? {
+                        frontier.push(row);
+                    }
+                }
+            }
+        }
+
+        Ok(self
+            .fixpoint(frontier, is_new, nodes, state)?
+            .into_iter()
+            .map(|row| (row, positive).into())
+            .collect())
+    }
+}
+
+impl Ingredient for Recursive {
+    fn take(&mut self) -> NodeOperator {
+        self.clone().into()
+    }
+
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.anchor.as_global(), self.step.as_global()]
+    }
+
+    fn must_replay_among(&self) -> Option<HashSet<NodeIndex>> {
+        // Rows are only ever derived from the anchor, so a full replay of the anchor (with lookups
+        // into the step relation) produces all of our rows
+        Some(HashSet::from([self.anchor.as_global()]))
+    }
+
+    impl_replace_sibling!(anchor, step);
+
+    fn on_commit(&mut self, us: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        self.anchor.remap(remap);
+        self.step.remap(remap);
+        self.our_index = Some(remap[&us]);
+    }
+
+    fn on_input(
+        &mut self,
+        from: LocalNodeIndex,
+        rs: Records,
+        _replay: &ReplayContext,
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        if rs.is_empty() {
+            return Ok(ProcessingResult {
+                results: rs,
+                ..Default::default()
+            });
+        }
+
+        let results = if from == *self.anchor {
+            self.on_anchor_input(rs, nodes, state)?
+        } else if from == *self.step {
+            self.on_step_input(rs, nodes, state)?
+        } else {
+            internal!("Recursive node received input from an unknown parent")
+        };
+
+        Ok(ProcessingResult {
+            results: results.into(),
+            ..Default::default()
+        })
+    }
+
+    fn suggest_indexes(&self, this: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
+        HashMap::from([
+            (
+                this,
+                LookupIndex::Strict(Index::hash_map(self.cte_key.clone())),
+            ),
+            (
+                self.step.as_global(),
+                LookupIndex::Strict(Index::hash_map(self.step_key.clone())),
+            ),
+        ])
+    }
+
+    fn column_source(&self, _cols: &[usize]) -> ColumnSource {
+        ColumnSource::RequiresFullReplay(vec1![self.anchor.as_global()])
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return "Recursive".into();
+        }
+
+        format!(
+            "{} ⟲ {}:({}) = ({}){}",
+            self.anchor,
+            self.step,
+            self.step_key.iter().join(", "),
+            self.cte_key.iter().join(", "),
+            if self.distinct { " DISTINCT" } else { "" }
+        )
+    }
+
+    fn requires_full_materialization(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dataflow_expression::utils::make_int_column;
+
+    use super::*;
+    use crate::ops::test::MockGraph;
+
+    /// Set up a recursive CTE over an `edges (src, dst)` table equivalent to:
+    ///
+    /// ```sql
+    /// WITH RECURSIVE reachable AS (
+    ///     SELECT node FROM roots
+    ///     UNION [ALL]
+    ///     SELECT edges.dst FROM edges JOIN reachable ON edges.src = reachable.node
+    /// )
+    /// ```
+    fn setup(distinct: bool) -> (MockGraph, IndexPair, IndexPair) {
+        let mut g = MockGraph::new();
+        let roots = g.add_base("roots", &["node"]);
+        let edges = g.add_base("edges", &["src", "dst"]);
+
+        g.set_op(
+            "reachable",
+            &["node"],
+            Recursive::new(
+                roots.as_global(),
+                edges.as_global(),
+                vec![0],
+                vec![0],
+                None,
+                vec![make_int_column(1)],
+                distinct,
+            )
+            .unwrap(),
+            true,
+        );
+        (g, roots, edges)
+    }
+
+    fn edge(src: i32, dst: i32) -> Vec<DfValue> {
+        vec![src.into(), dst.into()]
+    }
+
+    fn sorted(rs: Records) -> Vec<Record> {
+        let mut rs: Vec<_> = rs.into();
+        rs.sort();
+        rs
+    }
+
+    #[test]
+    fn derives_from_anchor() {
+        let (mut g, roots, edges) = setup(false);
+        g.seed(edges, edge(1, 2));
+        g.seed(edges, edge(2, 3));
+        g.seed(edges, edge(4, 5));
+
+        let res = g.one_row(roots, vec![1.into()], true);
+        assert_eq!(
+            sorted(res),
+            vec![
+                Record::Positive(vec![1.into()]),
+                Record::Positive(vec![2.into()]),
+                Record::Positive(vec![3.into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn new_step_rows_extend_existing_rows() {
+        let (mut g, roots, edges) = setup(false);
+        g.seed(edges, edge(1, 2));
+        g.one_row(roots, vec![1.into()], true);
+
+        g.seed(edges, edge(2, 3));
+        g.seed(edges, edge(3, 4));
+        let res = g.one_row(edges, edge(2, 3), true);
+        assert_eq!(
+            sorted(res),
+            vec![
+                Record::Positive(vec![3.into()]),
+                Record::Positive(vec![4.into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_all_counts_every_derivation() {
+        let (mut g, roots, edges) = setup(false);
+        g.seed(edges, edge(1, 2));
+        g.seed(edges, edge(1, 3));
+        g.seed(edges, edge(2, 4));
+        g.seed(edges, edge(3, 4));
+
+        let res = g.one_row(roots, vec![1.into()], true);
+        assert_eq!(
+            sorted(res),
+            vec![
+                Record::Positive(vec![1.into()]),
+                Record::Positive(vec![2.into()]),
+                Record::Positive(vec![3.into()]),
+                Record::Positive(vec![4.into()]),
+                Record::Positive(vec![4.into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_all_deleting_step_row_deletes_derived_rows() {
+        let (mut g, roots, edges) = setup(false);
+        g.seed(edges, edge(1, 2));
+        g.seed(edges, edge(2, 3));
+        g.one_row(roots, vec![1.into()], true);
+
+        g.unseed(edges);
+        g.seed(edges, edge(2, 3));
+        let res = g.one_row(edges, (edge(1, 2), false), true);
+        assert_eq!(
+            sorted(res),
+            vec![
+                Record::Negative(vec![2.into()]),
+                Record::Negative(vec![3.into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_all_cycle_exceeds_max_depth() {
+        let (mut g, roots, edges) = setup(false);
+        g.seed(edges, edge(1, 2));
+        g.seed(edges, edge(2, 1));
+
+        let res = g.try_one(roots, vec![vec![1.into()]]);
+        assert_eq!(
+            res.unwrap_err(),
+            ReadySetError::RecursionDepthExceeded {
+                max_depth: MAX_RECURSION_DEPTH
+            }
+        );
+    }
+
+    #[test]
+    fn union_terminates_on_cycles() {
+        let (mut g, roots, edges) = setup(true);
+        g.seed(edges, edge(1, 2));
+        g.seed(edges, edge(2, 1));
+
+        let res = g.one_row(roots, vec![1.into()], true);
+        assert_eq!(
+            sorted(res),
+            vec![
+                Record::Positive(vec![1.into()]),
+                Record::Positive(vec![2.into()]),
+            ]
+        );
+
+        // Closing another path to an existing row doesn't emit it again
+        g.seed(edges, edge(2, 3));
+        g.seed(edges, edge(3, 1));
+        let res = g.one(edges, vec![(edge(2, 3), true), (edge(3, 1), true)], true);
+        assert_eq!(sorted(res), vec![Record::Positive(vec![3.into()])]);
+    }
+
+    #[test]
+    fn union_deletion_keeps_rows_with_other_derivations() {
+        let (mut g, roots, edges) = setup(true);
+        g.seed(edges, edge(1, 2));
+        g.seed(edges, edge(1, 3));
+        g.seed(edges, edge(3, 2));
+        g.seed(edges, edge(2, 4));
+        g.one_row(roots, vec![1.into()], true);
+
+        g.unseed(edges);
+        g.seed(edges, edge(1, 3));
+        g.seed(edges, edge(3, 2));
+        g.seed(edges, edge(2, 4));
+        let res = g.one_row(edges, (edge(1, 2), false), true);
+        assert!(res.is_empty(), "{:?}", res);
+
+        g.unseed(edges);
+        g.seed(edges, edge(1, 3));
+        g.seed(edges, edge(2, 4));
+        let res = g.one_row(edges, (edge(3, 2), false), true);
+        assert_eq!(
+            sorted(res),
+            vec![
+                Record::Negative(vec![2.into()]),
+                Record::Negative(vec![4.into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn deleting_anchor_row() {
+        for distinct in [false, true] {
+            let (mut g, roots, edges) = setup(distinct);
+            g.seed(edges, edge(1, 2));
+            g.seed(edges, edge(5, 6));
+            g.one(roots, vec![vec![1.into()], vec![5.into()]], true);
+
+            let res = g.one_row(roots, (vec![1.into()], false), true);
+            assert_eq!(
+                sorted(res),
+                vec![
+                    Record::Negative(vec![1.into()]),
+                    Record::Negative(vec![2.into()]),
+                ],
+                "distinct: {}",
+                distinct
+            );
+        }
+    }
+
+    #[test]
+    fn suggest_indexes() {
+        let (g, _, edges) = setup(false);
+        let me = g.node_index().as_global();
+        let res = g.node().suggest_indexes(me);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[&me], LookupIndex::Strict(Index::hash_map(vec![0])));
+        assert_eq!(
+            res[&edges.as_global()],
+            LookupIndex::Strict(Index::hash_map(vec![0]))
+        );
+    }
+}
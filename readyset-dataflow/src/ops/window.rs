@@ -0,0 +1,729 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt::{self, Display};
+use std::mem;
+
+use dataflow_state::PointKey;
+use itertools::Itertools;
+use launchpad::Indices;
+use nom_sql::OrderType;
+use readyset_data::DfType;
+use serde::{Deserialize, Serialize};
+
+use crate::ops::utils::Order;
+use crate::prelude::*;
+use crate::processing::{ColumnMiss, LookupIndex};
+
+/// Supported window functions.
+///
+/// Aggregate window functions are computed over the default SQL window frame - all the rows from
+/// the start of the partition up to and including the last peer of the current row (the rows
+/// which compare equal to the current row according to the window's ordering). If the window has
+/// no ordering, every row in the partition is a peer of every other row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WindowFunction {
+    /// The number of the current row within its partition, starting at 1. Peers are numbered in
+    /// an unspecified (but deterministic) order.
+    RowNumber,
+    /// The rank of the current row within its partition, with gaps for peers.
+    Rank,
+    /// The rank of the current row within its partition, without gaps for peers.
+    DenseRank,
+    /// Count the number of non-null values of the `over` column, or the number of rows if there is
+    /// no `over` column.
+    Count,
+    /// Sum the values of the `over` column.
+    Sum,
+    /// The minimum value of the `over` column.
+    Min,
+    /// The maximum value of the `over` column.
+    Max,
+}
+
+impl WindowFunction {
+    /// Returns true if this window function must be computed over the value of a column
+    pub fn requires_over(&self) -> bool {
+        matches!(
+            self,
+            WindowFunction::Sum | WindowFunction::Min | WindowFunction::Max
+        )
+    }
+
+    /// Returns the type of the values computed by this window function, given the type of the
+    /// column it is computed over (if any)
+    pub fn output_type(&self, over_col_ty: Option<&DfType>) -> DfType {
+        match self {
+            WindowFunction::RowNumber
+            | WindowFunction::Rank
+            | WindowFunction::DenseRank
+            | WindowFunction::Count => DfType::BigInt,
+            // Mirror the output type of the SUM aggregate
+            WindowFunction::Sum => {
+                if over_col_ty.iter().any(|ty| ty.is_any_float()) {
+                    DfType::Double
+                } else {
+                    DfType::DEFAULT_NUMERIC
+                }
+            }
+            WindowFunction::Min | WindowFunction::Max => {
+                over_col_ty.cloned().unwrap_or(DfType::Unknown)
+            }
+        }
+    }
+}
+
+impl Display for WindowFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFunction::RowNumber => write!(f, "ROW_NUMBER"),
+            WindowFunction::Rank => write!(f, "RANK"),
+            WindowFunction::DenseRank => write!(f, "DENSE_RANK"),
+            WindowFunction::Count => write!(f, "COUNT"),
+            WindowFunction::Sum => write!(f, "SUM"),
+            WindowFunction::Min => write!(f, "MIN"),
+            WindowFunction::Max => write!(f, "MAX"),
+        }
+    }
+}
+
+/// Window implements a dataflow node that computes a [window function](WindowFunction) over
+/// partitions of its input, emitting all of its parent's columns plus one extra column containing
+/// the result of the window function for each row.
+///
+/// When a record arrives, the window operator loads the current contents of that record's
+/// partition out of its own (materialized) state, applies the record to the partition,
+/// recomputes the window function for every row in the partition, and emits negative and
+/// positive records for each row whose value changed.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Window {
+    /// The direct Ingredient or Base ancestor of this node
+    src: IndexPair,
+    /// The index of this node. Used to look up into our own state
+    our_index: Option<IndexPair>,
+    /// The column index of the window function result column emitted by this node
+    ///
+    /// This is always equal to the number of columns in the parent node (there is one more output
+    /// column than input columns)
+    ///
+    /// Set during [`Ingredient::on_connected`]
+    output_col: Option<usize>,
+    /// The window function we are computing
+    function: WindowFunction,
+    /// The column the window function is computed over, if any
+    over: Option<usize>,
+    /// The list of column indices that we're partitioning by
+    partition_by: Vec<usize>,
+    /// The ordering for rows within a partition
+    order: Order,
+    /// The type of the values computed by the window function
+    out_ty: DfType,
+}
+
+impl Window {
+    /// Construct a new window operator which computes `function` (over the column `over`, if
+    /// given) for each partition of the rows of `src` grouped by `partition_by`, ordered within
+    /// each partition by `order`.
+    pub fn new(
+        src: NodeIndex,
+        function: WindowFunction,
+        over: Option<usize>,
+        partition_by: Vec<usize>,
+        order: Vec<(usize, OrderType)>,
+        over_col_ty: Option<&DfType>,
+    ) -> ReadySetResult<Self> {
+        if function.requires_over() && over.is_none() {
+            internal!(
+                "{} window function requires a column to compute over",
+                function
+            );
+        }
+
+        Ok(Window {
+            src: src.into(),
+            our_index: None,
+            output_col: None,
+            function,
+            over,
+            partition_by,
+            order: order.into(),
+            out_ty: function.output_type(over_col_ty),
+        })
+    }
+
+    /// Returns the type of the column computed by this node
+    pub fn output_col_type(&self) -> &DfType {
+        &self.out_ty
+    }
+
+    /// Project the columns we are partitioning by out of the given record
+    fn project_partition<'rec, R>(&self, rec: &'rec R) -> ReadySetResult<Vec<&'rec DfValue>>
+    where
+        R: Indices<'static, usize, Output = DfValue> + ?Sized,
+    {
+        rec.indices(self.partition_by.clone())
+            .map_err(|_| ReadySetError::InvalidRecordLength)
+    }
+
+    /// Return the column index of the window function result column output by this node (which
+    /// will always be the last column)
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Ingredient::on_connected`]
+    fn output_column(&self) -> usize {
+        self.output_col
+            .expect("output_column called before Ingredient::on_connected")
+    }
+
+    /// Compare two rows within a partition, first according to the window's ordering and then by
+    /// the values of the rows themselves, so that rows always have a consistent position within
+    /// their partition.
+    fn cmp_rows(&self, a: &[DfValue], b: &[DfValue]) -> Ordering {
+        let n = self.output_column();
+        self.order
+            .cmp(a, b)
+            .then_with(|| a[..n.min(a.len())].cmp(&b[..n.min(b.len())]))
+    }
+
+    /// Compute the value of the window function for each row in the given partition, which must
+    /// already be sorted according to [`Self::cmp_rows`]
+    fn compute(&self, partition: &[Cow<[DfValue]>]) -> ReadySetResult<Vec<DfValue>> {
+        let mut values = Vec::with_capacity(partition.len());
+        let mut count = 0i64;
+        let mut dense_rank = 0i64;
+        let mut acc: Option<DfValue> = None;
+
+        let mut peers_start = 0;
+        while peers_start < partition.len() {
+            let first_peer = &partition[peers_start];
+            let peers_end = peers_start
+                + partition[peers_start..]
+                    .iter()
+                    .take_while(|row| self.order.cmp(row, first_peer) == Ordering::Equal)
+                    .count();
+            dense_rank += 1;
+
+            for row in &partition[peers_start..peers_end] {
+                let value = match self.over {
+                    Some(over) => row.get(over).ok_or(ReadySetError::InvalidRecordLength)?,
+                    None => {
+                        count += 1;
+                        continue;
+                    }
+                };
+                if value.is_none() {
+                    continue;
+                }
+                count += 1;
+
+                acc = Some(match (self.function, acc.take()) {
+                    (WindowFunction::Sum, None) => {
+                        let zero = if self.out_ty.is_any_float() {
+                            DfValue::Double(0.0)
+                        } else {
+                            DfValue::Numeric(Default::default())
+                        };
+                        (&zero + value)?
+                    }
+                    (WindowFunction::Sum, Some(acc)) => (&acc + value)?,
+                    (WindowFunction::Min, Some(acc)) if acc <= *value => acc,
+                    (WindowFunction::Max, Some(acc)) if acc >= *value => acc,
+                    _ => value.clone(),
+                });
+            }
+
+            for row_number in peers_start..peers_end {
+                values.push(match self.function {
+                    WindowFunction::RowNumber => DfValue::from(row_number as i64 + 1),
+                    WindowFunction::Rank => DfValue::from(peers_start as i64 + 1),
+                    WindowFunction::DenseRank => DfValue::from(dense_rank),
+                    WindowFunction::Count => DfValue::from(count),
+                    WindowFunction::Sum | WindowFunction::Min | WindowFunction::Max => {
+                        acc.clone().unwrap_or(DfValue::None)
+                    }
+                });
+            }
+
+            peers_start = peers_end;
+        }
+
+        Ok(values)
+    }
+
+    /// Recompute the window function for all the rows in the given partition, emitting records
+    /// for all rows that are new to the partition or whose value has changed.
+    ///
+    /// Rows loaded from our state will have the value of the window function as their last
+    /// column, whereas new rows received in the batch will be one column shorter.
+    fn post_partition<'state>(
+        &self,
+        out: &mut Vec<Record>,
+        current_partition: &mut Vec<Cow<'state, [DfValue]>>,
+    ) -> ReadySetResult<()> {
+        let mut partition = mem::take(current_partition);
+        partition.sort_by(|a, b| self.cmp_rows(a, b));
+        let values = self.compute(&partition)?;
+
+        for (mut row, value) in partition.into_iter().zip(values) {
+            if let Some(current_value) = row.get(self.output_column()) {
+                if *current_value != value {
+                    out.push(Record::Negative(row.clone().into()));
+                    row.to_mut()[self.output_column()] = value;
+                    out.push(Record::Positive(row.into()));
+                }
+            } else {
+                row.to_mut().push(value);
+                out.push(Record::Positive(row.into()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Ingredient for Window {
+    fn take(&mut self) -> NodeOperator {
+        self.clone().into()
+    }
+
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.src.as_global()]
+    }
+
+    impl_replace_sibling!(src);
+
+    fn on_connected(&mut self, graph: &Graph) {
+        self.output_col = Some(graph[self.src.as_global()].columns().len());
+    }
+
+    fn on_commit(&mut self, us: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        self.src.remap(remap);
+        self.our_index = Some(remap[&us]);
+    }
+
+    fn on_input<'a>(
+        &mut self,
+        from: LocalNodeIndex,
+        rs: Records,
+        replay: &ReplayContext,
+        _nodes: &DomainNodes,
+        state: &'a StateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        debug_assert_eq!(from, *self.src);
+
+        if rs.is_empty() {
+            return Ok(ProcessingResult {
+                results: rs,
+                ..Default::default()
+            });
+        }
+
+        let mut rs = Vec::from(rs);
+        rs.sort_by(|a: &Record, b: &Record| {
+            self.project_partition(&***a)
+                .unwrap_or_default()
+                .cmp(&self.project_partition(&***b).unwrap_or_default())
+        });
+
+        let us = self
+            .our_index
+            .ok_or_else(|| internal_err!("on_input called on Window before on_commit"))?;
+        let db = state
+            .get(*us)
+            .ok_or_else(|| internal_err!("window must have its own state materialized"))?;
+
+        let mut current_partition_key: Vec<DfValue> = vec![];
+        let mut current_partition: Vec<Cow<[DfValue]>> = vec![];
+        let mut partition_missed = false;
+
+        let mut out = vec![];
+        let mut lookups = vec![];
+        let mut misses = vec![];
+
+        for r in rs {
+            let record_partition = self.project_partition(r.rec())?;
+            if current_partition_key
+                .iter()
+                .cmp(record_partition.iter().copied())
+                != Ordering::Equal
+            {
+                // New partition!
+                if !current_partition_key.is_empty() {
+                    self.post_partition(&mut out, &mut current_partition)?;
+                }
+
+                // Clear and extend to reuse the allocation
+                current_partition_key.clear();
+                current_partition_key.extend(record_partition.into_iter().cloned());
+
+                // Load all the rows in the partition into memory
+                match db.lookup(
+                    &self.partition_by,
+                    &PointKey::from(current_partition_key.clone()),
+                ) {
+                    LookupResult::Some(local_records) => {
+                        if replay.is_partial() {
+                            lookups.push(Lookup {
+                                on: *us,
+                                cols: self.partition_by.clone(),
+                                key: current_partition_key.clone().try_into().map_err(|_| {
+                                    internal_err!("Window partition key must not be empty")
+                                })?,
+                            });
+                        }
+
+                        partition_missed = false;
+                        current_partition.extend(local_records);
+                    }
+                    LookupResult::Missing => {
+                        partition_missed = true;
+                    }
+                }
+            }
+
+            if partition_missed {
+                misses.push(
+                    Miss::builder()
+                        .on(*us)
+                        .lookup_idx(self.partition_by.clone())
+                        .lookup_key(self.partition_by.clone())
+                        .replay(replay)
+                        .record(r.into_row())
+                        .build(),
+                );
+                continue;
+            }
+
+            match r {
+                Record::Positive(r) => current_partition.push(Cow::Owned(r)),
+                Record::Negative(r) => {
+                    let pos = current_partition
+                        .iter()
+                        .position(|row| row[..self.output_column()] == *r)
+                        .ok_or_else(|| {
+                            internal_err!(
+                                "Window received a negative for a row not in its partition"
+                            )
+                        })?;
+                    let row = current_partition.swap_remove(pos);
+                    if row.len() > self.output_column() {
+                        // Only retract rows we've previously emitted
+                        out.push(Record::Negative(row.into()));
+                    }
+                }
+            }
+        }
+
+        if !current_partition.is_empty() {
+            self.post_partition(&mut out, &mut current_partition)?;
+        }
+
+        Ok(ProcessingResult {
+            results: out.into(),
+            lookups,
+            misses,
+        })
+    }
+
+    fn suggest_indexes(&self, this: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
+        HashMap::from([(
+            this,
+            LookupIndex::Strict(Index::hash_map(self.partition_by.clone())),
+        )])
+    }
+
+    fn column_source(&self, cols: &[usize]) -> ColumnSource {
+        if cols.contains(&self.output_column()) {
+            if cols.len() == 1 {
+                // Lookups on only the window function result require a full replay
+                return ColumnSource::RequiresFullReplay(vec1![self.src.as_global()]);
+            }
+
+            #[allow(clippy::unwrap_used)]
+            // Once we remove the output column, we have to have at least one column left
+            // (because we just checked len > 1)
+            let columns = cols
+                .iter()
+                .copied()
+                .filter(|c| *c != self.output_column())
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap();
+            ColumnSource::GeneratedFromColumns(vec1![ColumnRef {
+                node: self.our_index.unwrap().as_global(),
+                columns,
+            }])
+        } else {
+            ColumnSource::ExactCopy(ColumnRef {
+                node: self.src.as_global(),
+                columns: cols.to_vec().try_into().unwrap(),
+            })
+        }
+    }
+
+    fn handle_upquery(&mut self, miss: ColumnMiss) -> ReadySetResult<Vec<ColumnMiss>> {
+        let output_column = miss
+            .column_indices
+            .iter()
+            .position(|ci| *ci == self.output_column())
+            .ok_or_else(|| internal_err!("Window upquery must include the output column"))?;
+        let us = self
+            .our_index
+            .ok_or_else(|| internal_err!("handle_upquery called on Window before on_commit"))?;
+
+        Ok(vec![ColumnMiss {
+            node: *us,
+            column_indices: self
+                .partition_by
+                .clone()
+                .try_into()
+                .map_err(|_| internal_err!("Window partition columns must not be empty"))?,
+            missed_keys: miss.missed_keys.try_mapped(|k| {
+                k.try_map_endpoints(|mut r| {
+                    r.remove(output_column).map_err(|_| {
+                        internal_err!("Window upquery key must include a partition column")
+                    })?;
+                    Ok::<_, ReadySetError>(r)
+                })
+            })?,
+        }])
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return "Window".into();
+        }
+
+        format!(
+            "{}({}) OVER γ[{}] o[{}]",
+            self.function,
+            self.over.iter().join(", "),
+            self.partition_by.iter().join(", "),
+            self.order
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test::MockGraph;
+
+    fn setup(function: WindowFunction, over: Option<usize>) -> (MockGraph, IndexPair) {
+        let mut g = MockGraph::new();
+        let s = g.add_base("source", &["x", "y"]);
+
+        // function(..) OVER (PARTITION BY y ORDER BY x ASC)
+        g.set_op(
+            "window",
+            &["x", "y", "w"],
+            Window::new(
+                s.as_global(),
+                function,
+                over,
+                vec![1],
+                vec![(0, OrderType::OrderAscending)],
+                None,
+            )
+            .unwrap(),
+            true,
+        );
+        (g, s)
+    }
+
+    fn with_value<V: Into<DfValue>>(row: &[DfValue], value: V) -> Vec<DfValue> {
+        let mut res = row.to_vec();
+        res.push(value.into());
+        res
+    }
+
+    #[test]
+    fn column_source_for_partition_by() {
+        let (g, s) = setup(WindowFunction::RowNumber, None);
+        let src = g.node().column_source(&[1]);
+        assert_eq!(
+            src,
+            ColumnSource::ExactCopy(ColumnRef {
+                node: s.as_global(),
+                columns: vec1![1]
+            })
+        );
+    }
+
+    #[test]
+    fn column_source_for_output_only_lookup() {
+        let (g, s) = setup(WindowFunction::RowNumber, None);
+        let src = g.node().column_source(&[2]);
+        assert_eq!(src, ColumnSource::RequiresFullReplay(vec1![s.as_global()]));
+    }
+
+    #[test]
+    fn suggest_indexes() {
+        let (g, _) = setup(WindowFunction::RowNumber, None);
+        let res = g.node().suggest_indexes(g.node_index().as_global());
+        assert_eq!(res.len(), 1);
+        assert_eq!(
+            res[&g.node_index().as_global()],
+            LookupIndex::Strict(Index::hash_map(vec![1]))
+        );
+    }
+
+    #[test]
+    fn row_number_multiple_partitions() {
+        let (mut g, _) = setup(WindowFunction::RowNumber, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r1b = vec![1.into(), "b".into()];
+
+        let res = g.narrow_one(vec![r2a.clone(), r1b.clone(), r1a.clone()], true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&r1a, 1i64),
+                with_value(&r2a, 2i64),
+                with_value(&r1b, 1i64),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn row_number_insert_shifts_later_rows() {
+        let (mut g, _) = setup(WindowFunction::RowNumber, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r3a = vec![3.into(), "a".into()];
+
+        g.narrow_one(vec![r1a, r3a.clone()], true);
+
+        let res = g.narrow_one_row(r2a.clone(), true);
+        assert_eq!(
+            res,
+            vec![
+                (with_value(&r2a, 2i64), true),
+                (with_value(&r3a, 2i64), false),
+                (with_value(&r3a, 3i64), true),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn row_number_delete_shifts_later_rows() {
+        let (mut g, _) = setup(WindowFunction::RowNumber, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r3a = vec![3.into(), "a".into()];
+
+        g.narrow_one(vec![r1a.clone(), r2a.clone(), r3a.clone()], true);
+
+        let res = g.narrow_one_row((r1a.clone(), false), true);
+        assert_eq!(
+            res,
+            vec![
+                (with_value(&r1a, 1i64), false),
+                (with_value(&r2a, 2i64), false),
+                (with_value(&r2a, 1i64), true),
+                (with_value(&r3a, 3i64), false),
+                (with_value(&r3a, 2i64), true),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn rank_and_dense_rank_with_peers() {
+        let rows: Vec<Vec<DfValue>> = vec![
+            vec![1.into(), "a".into()],
+            vec![1.into(), "a".into()],
+            vec![2.into(), "a".into()],
+        ];
+
+        let (mut g, _) = setup(WindowFunction::Rank, None);
+        let res = g.narrow_one(rows.clone(), true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&rows[0], 1i64),
+                with_value(&rows[1], 1i64),
+                with_value(&rows[2], 3i64),
+            ]
+            .into()
+        );
+
+        let (mut g, _) = setup(WindowFunction::DenseRank, None);
+        let res = g.narrow_one(rows.clone(), true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&rows[0], 1i64),
+                with_value(&rows[1], 1i64),
+                with_value(&rows[2], 2i64),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn running_count() {
+        let (mut g, _) = setup(WindowFunction::Count, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r2a_dup = vec![2.into(), "a".into()];
+
+        let res = g.narrow_one(vec![r1a.clone(), r2a.clone(), r2a_dup.clone()], true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&r1a, 1i64),
+                with_value(&r2a, 3i64),
+                with_value(&r2a_dup, 3i64),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn running_max() {
+        let (mut g, _) = setup(WindowFunction::Max, Some(0));
+
+        let r1a = vec![1.into(), "a".into()];
+        let r3a = vec![3.into(), "a".into()];
+
+        let res = g.narrow_one(vec![r3a.clone(), r1a.clone()], true);
+        assert_eq!(res, vec![with_value(&r1a, 1), with_value(&r3a, 3)].into());
+    }
+
+    #[test]
+    fn running_sum_updates_later_rows() {
+        let (mut g, _) = setup(WindowFunction::Sum, Some(0));
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r3a = vec![3.into(), "a".into()];
+
+        let res = g.narrow_one(vec![r1a.clone(), r3a.clone()], true);
+        let sum = |n: i32| (&DfValue::Numeric(Default::default()) + &DfValue::from(n)).unwrap();
+        assert_eq!(
+            res,
+            vec![with_value(&r1a, sum(1)), with_value(&r3a, sum(4))].into()
+        );
+
+        let res = g.narrow_one_row(r2a.clone(), true);
+        assert_eq!(
+            res,
+            vec![
+                (with_value(&r2a, sum(3)), true),
+                (with_value(&r3a, sum(4)), false),
+                (with_value(&r3a, sum(6)), true),
+            ]
+            .into()
+        );
+    }
+}
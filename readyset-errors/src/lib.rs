@@ -78,6 +78,19 @@ pub enum ReadySetError {
     #[error("No query known by id {id}")]
     NoQueryForId { id: String },
 
+    /// The adapter received a `DROP ROUTING RULE` for a routing rule that does not exist
+    #[error("No routing rule named {name}")]
+    NoRoutingRule { name: String },
+
+    /// The adapter received a `CREATE ROUTING RULE` or `DROP ROUTING RULE` statement, but isn't
+    /// configured to allow routing rules to be changed at runtime
+    #[error("Changing routing rules at runtime is not allowed by this adapter")]
+    RoutingRuleChangesNotAllowed,
+
+    /// The query matched a routing rule that rejects all queries it matches
+    #[error("Query rejected by routing rule {rule}")]
+    QueryRejectedByRoutingRule { rule: String },
+
     /// The adapter will return this error on any set statement that is not
     /// explicitly allowed.
     #[error("Set statement disallowed: {}", Sensitive(statement))]
@@ -90,6 +103,23 @@ pub enum ReadySetError {
     #[error("Could not connect to the upstream database provided")]
     InvalidUpstreamDatabase,
 
+    /// No connection to the upstream database could be leased from the adapter's upstream
+    /// connection pool in time
+    #[error("Timed out after {timeout_ms}ms leasing a connection from the upstream pool")]
+    UpstreamPoolTimeout {
+        /// The lease timeout of the pool, in milliseconds
+        timeout_ms: u64,
+    },
+
+    /// A statement reads state left on an upstream connection by earlier statements, but the
+    /// connection it would run on was leased from the adapter's upstream connection pool for it
+    #[error(
+        "Statement depends on upstream connection state that isn't kept between statements when \
+         upstream connections are pooled; run it in a transaction with the statements it depends \
+         on"
+    )]
+    UpstreamConnectionStateUnavailable,
+
     /// An intra-ReadySet RPC call failed.
     #[error("Error during RPC ({during}): {source}")]
     RpcFailed {
@@ -278,6 +308,26 @@ pub enum ReadySetError {
     #[error("the queries lookup key is not found at the reader")]
     ReaderMissingKey,
 
+    /// Maintaining a recursive common table expression took more iterations of its recursive
+    /// term than allowed, likely because the recursion doesn't terminate.
+    #[error(
+        "Recursive common table expression exceeded the maximum recursion depth of {max_depth}"
+    )]
+    RecursionDepthExceeded {
+        /// The maximum number of times the recursive term may be applied
+        max_depth: usize,
+    },
+
+    /// The LIMIT passed when executing a cached query with a parameterized LIMIT exceeds the
+    /// maximum LIMIT the query was cached with, so the execution can't be served by ReadySet.
+    #[error("LIMIT {limit} exceeds the maximum parameterized LIMIT of {max} for cached queries")]
+    ParametrizedLimitExceeded {
+        /// The LIMIT passed when executing the query
+        limit: usize,
+        /// The maximum parameterized LIMIT for cached queries
+        max: usize,
+    },
+
     /// A prepared statement is missing.
     #[error("Prepared statement with ID {statement_id} not found")]
     PreparedStatementMissing {
@@ -531,6 +581,12 @@ pub enum ReadySetError {
     )]
     ReplicationOffsetLogDifferent(String, String),
 
+    /// A read with read-your-writes consistency could not observe writes the connection made to
+    /// the upstream database, either because it timed out waiting for ReadySet to replicate them
+    /// or because the replication position of the writes isn't known
+    #[error("Writes to the upstream database have not been replicated: {0}")]
+    ReplicationOffsetNotReached(String),
+
     /// An error that was encountered during snapshot/binlog/wal replication proccess
     #[error("Error during replication: {0}")]
     ReplicationFailed(String),
@@ -672,6 +728,17 @@ impl ReadySetError {
         self.any_cause(|e| e.is_table_not_found())
     }
 
+    /// Returns `true` if self is [`ResnapshotNeeded`].
+    pub fn is_resnapshot_needed(&self) -> bool {
+        matches!(self, Self::ResnapshotNeeded)
+    }
+
+    /// Returns `true` if self either *is* [`ResnapshotNeeded`], or was *caused by*
+    /// [`ResnapshotNeeded`].
+    pub fn caused_by_resnapshot_needed(&self) -> bool {
+        self.any_cause(|e| e.is_resnapshot_needed())
+    }
+
     /// Returns `true` if the error could have been caused by a networking problem.
     pub fn is_networking_related(&self) -> bool {
         self.any_cause(|e| {
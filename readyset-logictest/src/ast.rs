@@ -428,6 +428,7 @@ impl TryFrom<DfValue> for Value {
             DfValue::Numeric(ref d) => Ok(Value::Numeric(*d.as_ref())),
             DfValue::BitVector(ref b) => Ok(Value::BitVector(b.as_ref().clone())),
             DfValue::Array(_) => bail!("Arrays not supported"),
+            DfValue::Range(ref r) => Ok(Value::Text(r.to_string())),
             DfValue::PassThrough(_) => unimplemented!(),
         }
     }
@@ -109,7 +109,9 @@ fn is_ddl(query: &SqlQuery) -> bool {
         | SqlQuery::Use(_)
         | SqlQuery::CreateCache(_)
         | SqlQuery::DropCache(_)
-        | SqlQuery::DropAllCaches(_) => true,
+        | SqlQuery::DropAllCaches(_)
+        | SqlQuery::CreateRoutingRule(_)
+        | SqlQuery::DropRoutingRule(_) => true,
     }
 }
 
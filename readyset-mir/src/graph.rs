@@ -158,6 +158,19 @@ impl MirGraph {
                 );
                 columns
             }
+            MirNodeInner::LeftJoin {
+                residual: Some(residual),
+                ..
+            } => {
+                // Left joins with a residual predicate also need the columns it references
+                let mut columns = self.columns(node);
+                for c in residual.referred_columns() {
+                    if !columns.iter().any(|col| col == c) {
+                        columns.push(c.clone().into())
+                    }
+                }
+                columns
+            }
             MirNodeInner::Filter { conditions } => {
                 let mut columns = self.columns(node);
                 for c in conditions.referred_columns() {
@@ -167,6 +180,53 @@ impl MirGraph {
                 }
                 columns
             }
+            MirNodeInner::Window {
+                over,
+                partition_by,
+                order_by,
+                output_column,
+                ..
+            } => {
+                // Windows need all the columns they emit (other than the one they compute), along
+                // with the columns they partition by, order by, and compute over
+                let mut columns = self.columns(node);
+                columns.retain(|c| c != output_column);
+                for c in over
+                    .iter()
+                    .chain(partition_by)
+                    .chain(order_by.iter().map(|(c, _)| c))
+                {
+                    if !columns.contains(c) {
+                        columns.push(c.clone());
+                    }
+                }
+                columns
+            }
+            MirNodeInner::Recursive {
+                on,
+                condition,
+                emit,
+                ..
+            } => {
+                // Recursive nodes need all the columns they emit, along with the columns they join
+                // on and the columns referenced by the recursive term
+                let mut columns = self.columns(node);
+                for c in on.iter().flat_map(|(s, c)| [s, c]) {
+                    if !columns.contains(c) {
+                        columns.push(c.clone());
+                    }
+                }
+                for c in condition
+                    .iter()
+                    .chain(emit)
+                    .flat_map(|expr| expr.referred_columns())
+                {
+                    if !columns.iter().any(|col| col == c) {
+                        columns.push(c.clone().into())
+                    }
+                }
+                columns
+            }
             _ => self.columns(node),
         }
     }
@@ -188,7 +248,8 @@ impl MirGraph {
             | MirNodeInner::Leaf { .. }
             | MirNodeInner::Identity
             | MirNodeInner::Latest { .. }
-            | MirNodeInner::TopK { .. } => parent_columns(),
+            | MirNodeInner::TopK { .. }
+            | MirNodeInner::Recursive { .. } => parent_columns(),
             MirNodeInner::AliasTable { table } => parent_columns()
                 .iter()
                 .map(|c| MirColumn {
@@ -213,7 +274,9 @@ impl MirGraph {
                 .collect(),
             MirNodeInner::Join { project, .. }
             | MirNodeInner::LeftJoin { project, .. }
-            | MirNodeInner::DependentJoin { project, .. } => project.clone(),
+            | MirNodeInner::FullJoin { project, .. }
+            | MirNodeInner::DependentJoin { project, .. }
+            | MirNodeInner::DependentLeftJoin { project, .. } => project.clone(),
             MirNodeInner::JoinAggregates => {
                 let cols = self
                     // see note [edge-ordering]
@@ -252,6 +315,10 @@ impl MirGraph {
                 .into_iter()
                 .chain(iter::once(MirColumn::named(&*PAGE_NUMBER_COL)))
                 .collect(),
+            MirNodeInner::Window { output_column, .. } => parent_columns()
+                .into_iter()
+                .chain(iter::once(output_column.clone()))
+                .collect(),
             MirNodeInner::Distinct { group_by } => group_by
                 .iter()
                 .cloned()
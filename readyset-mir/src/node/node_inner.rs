@@ -4,6 +4,7 @@ use common::{DfValue, IndexType};
 use dataflow::ops::grouped::aggregate::Aggregation;
 use dataflow::ops::grouped::extremum::Extremum;
 use dataflow::ops::union;
+use dataflow::ops::window::WindowFunction;
 use dataflow::PostLookupAggregates;
 use itertools::Itertools;
 use nom_sql::{ColumnSpecification, Expr, OrderType, Relation, SqlIdentifier};
@@ -105,7 +106,8 @@ pub enum MirNodeInner {
     /// version of each group_by column in the final join.
     JoinAggregates,
     /// Node which computes a *left* join on its two parents by finding all rows in the right where
-    /// the values in `on_right` are equal to the values of `on_left` on the left
+    /// the values in `on_right` are equal to the values of `on_left` on the left, and (if present)
+    /// `residual` holds for the pair of rows
     ///
     /// Converted to [`Join`] with [`JoinType::Left`] when lowering to dataflow.
     ///
@@ -117,6 +119,24 @@ pub enum MirNodeInner {
         on: Vec<(Column, Column)>,
         /// Columns (from both parents) to project in the output.
         project: Vec<Column>,
+        /// Optional predicate, referencing columns in both parents, which must also hold for a
+        /// pair of rows to be joined
+        residual: Option<Expr>,
+    },
+    /// Node which computes a *full outer* join on its two parents by finding all rows in the right
+    /// where the values in `on_right` are equal to the values of `on_left` on the left, and
+    /// emitting rows from either side which don't match any rows in the other joined to NULLs
+    ///
+    /// Converted to [`Join`] with [`JoinType::Full`] when lowering to dataflow.
+    ///
+    /// [`Join`]: dataflow::ops::join::Join
+    /// [`JoinType::Full`]: dataflow::ops::join::JoinType::Full
+    FullJoin {
+        /// Columns to use as the join keys. Each tuple corresponds to a column in the left parent
+        /// and column in the right parent.
+        on: Vec<(Column, Column)>,
+        /// Columns (from both parents) to project in the output.
+        project: Vec<Column>,
     },
     /// Join where nodes in the right-hand side depend on columns in the left-hand side
     /// (referencing tables in `dependent_tables`). These are created during compilation for
@@ -135,6 +155,18 @@ pub enum MirNodeInner {
         /// Columns (from both parents) to project in the output.
         project: Vec<Column>,
     },
+    /// *Left* join where nodes in the right-hand side depend on columns in the left-hand side.
+    /// These are created during compilation for correlated scalar subqueries, and like
+    /// [`DependentJoin`]s must be removed entirely by rewrite passes before lowering to dataflow.
+    ///
+    /// [`DependentJoin`]: MirNodeInner::DependentJoin
+    DependentLeftJoin {
+        /// Columns to use as the join keys. Each tuple corresponds to a column in the left parent
+        /// and column in the right parent.
+        on: Vec<(Column, Column)>,
+        /// Columns (from both parents) to project in the output.
+        project: Vec<Column>,
+    },
     /// group columns
     // currently unused
     #[allow(dead_code)]
@@ -205,6 +237,46 @@ pub enum MirNodeInner {
         /// LIMIT clause
         limit: usize,
     },
+    /// Node which computes a window function over partitions of its input, emitting all of its
+    /// parent's columns plus one extra column for the result of the window function.
+    ///
+    /// Converted to [`Window`] when lowering to dataflow.
+    ///
+    /// [`Window`]: dataflow::ops::window::Window
+    Window {
+        /// Which window function we are computing
+        function: WindowFunction,
+        /// Column to compute the window function over, if any
+        over: Option<Column>,
+        /// List of columns to partition by
+        partition_by: Vec<Column>,
+        /// Set of columns used to order the rows within each partition
+        order_by: Vec<(Column, OrderType)>,
+        /// The column name to use for the result of the window function, which will always be the
+        /// last column
+        output_column: Column,
+    },
+    /// Node which maintains the result of a recursive common table expression, by repeatedly
+    /// joining the rows of the CTE against another relation, starting from the rows of an anchor.
+    ///
+    /// The first parent of this node is the anchor, and the second is the relation the recursive
+    /// term joins against. Emits the same columns as the anchor.
+    ///
+    /// Converted to [`Recursive`] when lowering to dataflow.
+    ///
+    /// [`Recursive`]: dataflow::ops::recursive::Recursive
+    Recursive {
+        /// Pairs of `(step column, CTE column)` which must be equal for a row of the second parent
+        /// to join against a row of the CTE
+        on: Vec<(Column, Column)>,
+        /// Optional extra condition for a row of the second parent to join against a row of the
+        /// CTE
+        condition: Option<Expr>,
+        /// Expressions for each of the columns of the rows derived by the recursive term
+        emit: Vec<Expr>,
+        /// Whether each row is emitted at most once (for `UNION`, rather than `UNION ALL`)
+        distinct: bool,
+    },
     /// Node which emits only distinct rows per some group.
     ///
     /// Converted to [`Aggregator`] with [`Aggregation::Count`] when lowering to dataflow.
@@ -279,7 +351,9 @@ impl MirNodeInner {
             }
             MirNodeInner::Join { project, .. }
             | MirNodeInner::LeftJoin { project, .. }
-            | MirNodeInner::DependentJoin { project, .. } => {
+            | MirNodeInner::FullJoin { project, .. }
+            | MirNodeInner::DependentJoin { project, .. }
+            | MirNodeInner::DependentLeftJoin { project, .. } => {
                 if !project.contains(&c) {
                     project.push(c);
                 }
@@ -311,11 +385,15 @@ impl MirNodeInner {
         }
     }
 
-    /// Returns `true` if self is a [`DependentJoin`].
+    /// Returns `true` if self is a [`DependentJoin`] or a [`DependentLeftJoin`].
     ///
     /// [`DependentJoin`]: MirNodeInner::DependentJoin
+    /// [`DependentLeftJoin`]: MirNodeInner::DependentLeftJoin
     pub fn is_dependent_join(&self) -> bool {
-        matches!(self, Self::DependentJoin { .. })
+        matches!(
+            self,
+            Self::DependentJoin { .. } | Self::DependentLeftJoin { .. }
+        )
     }
 
     pub(crate) fn description(&self) -> String {
@@ -333,6 +411,19 @@ impl MirNodeInner {
                     Aggregation::GroupConcat { separator: ref s } => {
                         format!("||([{}], \"{}\")", on.name.as_str(), s.as_str())
                     }
+                    Aggregation::CountDistinct => format!("|DISTINCT|({})", on.name.as_str()),
+                    Aggregation::Stddev { sample } => format!(
+                        "STDDEV_{}({})",
+                        if sample { "SAMP" } else { "POP" },
+                        on.name.as_str()
+                    ),
+                    Aggregation::Variance { sample } => format!(
+                        "VAR_{}({})",
+                        if sample { "SAMP" } else { "POP" },
+                        on.name.as_str()
+                    ),
+                    Aggregation::ArrayAgg => format!("ARRAY_AGG({})", on.name.as_str()),
+                    Aggregation::JsonAgg => format!("JSON_AGG({})", on.name.as_str()),
                 };
                 let group_cols = group_by
                     .iter()
@@ -370,6 +461,8 @@ impl MirNodeInner {
                 let op_string = match *kind {
                     Extremum::Min => format!("min({})", on.name.as_str()),
                     Extremum::Max => format!("max({})", on.name.as_str()),
+                    Extremum::BoolAnd => format!("bool_and({})", on.name.as_str()),
+                    Extremum::BoolOr => format!("bool_or({})", on.name.as_str()),
                 };
                 let group_cols = group_by
                     .iter()
@@ -414,11 +507,12 @@ impl MirNodeInner {
             MirNodeInner::LeftJoin {
                 ref on,
                 ref project,
-                ..
+                ref residual,
             } => {
                 let jc = on
                     .iter()
                     .map(|(l, r)| format!("{}:{}", l.name, r.name))
+                    .chain(residual.iter().map(|r| r.to_string()))
                     .collect::<Vec<_>>()
                     .join(", ");
                 format!(
@@ -431,6 +525,25 @@ impl MirNodeInner {
                     jc
                 )
             }
+            MirNodeInner::FullJoin {
+                ref on,
+                ref project,
+            } => {
+                let jc = on
+                    .iter()
+                    .map(|(l, r)| format!("{}:{}", l.name, r.name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "⟗ [{} on {}]",
+                    project
+                        .iter()
+                        .map(|c| c.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    jc
+                )
+            }
             MirNodeInner::DependentJoin {
                 ref on,
                 ref project,
@@ -444,6 +557,18 @@ impl MirNodeInner {
                         .join(", ")
                 )
             }
+            MirNodeInner::DependentLeftJoin {
+                ref on,
+                ref project,
+            } => {
+                format!(
+                    "⟕⧑ | {} on: {}",
+                    project.iter().map(|c| &c.name).join(", "),
+                    on.iter()
+                        .map(|(l, r)| format!("{}:{}", l.name, r.name))
+                        .join(", ")
+                )
+            }
             MirNodeInner::Latest { ref group_by } => {
                 let key_cols = group_by
                     .iter()
@@ -495,6 +620,39 @@ impl MirNodeInner {
             } => {
                 format!("TopK [k: {}, {:?}]", limit, order)
             }
+            MirNodeInner::Window {
+                ref function,
+                ref over,
+                ref partition_by,
+                ref order_by,
+                ..
+            } => {
+                let partition_cols = partition_by
+                    .iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "{}({}) OVER [γ: {}, {:?}]",
+                    function,
+                    over.iter().map(|c| c.name.as_str()).join(", "),
+                    partition_cols,
+                    order_by
+                )
+            }
+            MirNodeInner::Recursive {
+                ref on,
+                ref distinct,
+                ..
+            } => {
+                format!(
+                    "⟲ [on: {}{}]",
+                    on.iter()
+                        .map(|(s, c)| format!("{}:{}", s.name.as_str(), c.name.as_str()))
+                        .join(", "),
+                    if *distinct { ", distinct" } else { "" }
+                )
+            }
             MirNodeInner::Union {
                 ref emit,
                 ref duplicate_mode,
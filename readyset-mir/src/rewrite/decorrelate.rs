@@ -22,6 +22,9 @@ use crate::Column;
 ///   filters with impunity
 /// - Grouped nodes ([`Aggregation`] and [`Extremum`]) require adding any *non* dependent columns
 ///   mentioned in the filter to the group-by of the node.
+/// - Only equality conditions can be turned into join keys when the filter depends on a
+///   [`DependentLeftJoin`] - lifting any other filter above a left join would filter out rows on
+///   the left-hand side, so we return an [unsupported error][] for those
 /// - All other nodes currently return an [unsupported error][] - it *is* theoretically possible to
 ///   push below any node, but currently we don't have that ability
 ///
@@ -31,6 +34,7 @@ use crate::Column;
 /// [filter]: MirNodeInner::Filter
 /// [`Project`]: MirNodeInner::Project
 /// [`Join`]: MirNodeInner::LeftJoin
+/// [`DependentLeftJoin`]: MirNodeInner::DependentLeftJoin
 /// [`Aggregation`]: MirNodeInner::Aggregation
 /// [`Extremum`]: MirNodeInner::Extremum
 /// [unsupported error]: noria_errors::ReadySetError::Unsupported
@@ -95,11 +99,28 @@ fn push_dependent_filter(
                 DependentCondition::FullyDependent { .. } => true,
             }
         }
+        MirNodeInner::DependentLeftJoin { on, .. } if child_idx == dependent_join_idx => {
+            match dependency {
+                DependentCondition::JoinKey { lhs, rhs } => {
+                    on.push((lhs.clone(), rhs.clone()));
+                    query.graph.add_column(child_idx, lhs)?;
+                    query.graph.add_column(child_idx, rhs)?;
+                    false
+                }
+                // Lifting the filter above a left join would remove rows from the left-hand side
+                // rather than just the right, which changes the semantics of the query
+                DependentCondition::FullyDependent { .. } => unsupported!(
+                    "Correlated scalar subqueries may only reference outer columns in equality \
+                     conditions"
+                ),
+            }
+        }
         MirNodeInner::Project { .. }
         | MirNodeInner::Filter { .. }
         | MirNodeInner::Join { .. }
         | MirNodeInner::LeftJoin { .. }
         | MirNodeInner::DependentJoin { .. }
+        | MirNodeInner::DependentLeftJoin { .. }
         | MirNodeInner::AliasTable { .. } => true,
         MirNodeInner::Aggregation { .. } | MirNodeInner::Extremum { .. } => {
             for col in dependency.non_dependent_columns() {
@@ -273,6 +294,11 @@ pub(crate) fn eliminate_dependent_joins(query: &mut MirQuery<'_>) -> ReadySetRes
                     on: on.clone(),
                     project: project.clone(),
                 },
+                MirNodeInner::DependentLeftJoin { on, project } => MirNodeInner::LeftJoin {
+                    on: on.clone(),
+                    project: project.clone(),
+                    residual: None,
+                },
                 _ => unreachable!("Already checked is_dependent_join above"),
             };
             query.get_node_mut(join).unwrap().inner = new_inner;
@@ -756,4 +782,172 @@ mod tests {
 
         pull_all_required_columns(&mut query).unwrap();
     }
+
+    /// Build a graph for a query that looks something like:
+    ///
+    /// ```sql
+    /// SELECT t1.a, (SELECT count(t2.a) FROM t2 WHERE <condition>) FROM t1
+    /// ```
+    ///
+    /// Returning the graph, the query's leaf, and the dependent left join
+    fn scalar_subquery_graph(condition: Expr) -> (MirGraph, NodeIndex, NodeIndex) {
+        let mut graph = MirGraph::new();
+        let query_name = Relation::from("q");
+
+        let t2 = graph.add_node(MirNode::new(
+            "t2".into(),
+            MirNodeInner::Base {
+                column_specs: vec![ColumnSpecification {
+                    column: nom_sql::Column::from("t2.a"),
+                    sql_type: SqlType::Int(None),
+                    constraints: vec![],
+                    comment: None,
+                }],
+                primary_key: Some([Column::new(Some("t2"), "a")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t2].add_owner(query_name.clone());
+
+        // -> σ[<condition>]
+        let t2_filter = graph.add_node(MirNode::new(
+            "t2_filter".into(),
+            MirNodeInner::Filter {
+                conditions: condition,
+            },
+        ));
+        graph[t2_filter].add_owner(query_name.clone());
+        graph.add_edge(t2, t2_filter, 0);
+
+        // -> π[t2.a, lit: 0]
+        let grp_proj = graph.add_node(MirNode::new(
+            "q_prj_hlpr".into(),
+            MirNodeInner::Project {
+                emit: vec![Column::new(Some("t2"), "a")],
+                expressions: vec![],
+                literals: vec![("grp".into(), DfValue::from(0u32))],
+            },
+        ));
+        graph[grp_proj].add_owner(query_name.clone());
+        graph.add_edge(t2_filter, grp_proj, 0);
+
+        // -> |*|(t2.a) γ[grp]
+        let t2_count = graph.add_node(MirNode::new(
+            "t2_count".into(),
+            MirNodeInner::Aggregation {
+                on: Column::new(Some("t2"), "a"),
+                group_by: vec![Column::named("grp")],
+                output_column: Column::named("count(t2.a)"),
+                kind: Aggregation::Count,
+            },
+        ));
+        graph[t2_count].add_owner(query_name.clone());
+        graph.add_edge(grp_proj, t2_count, 0);
+
+        // -> π[count(t2.a), lit: 0]
+        let subquery_proj = graph.add_node(MirNode::new(
+            "subquery_prj".into(),
+            MirNodeInner::Project {
+                emit: vec![Column::named("count(t2.a)")],
+                expressions: vec![],
+                literals: vec![("__subquery_0_grp".into(), DfValue::from(0u32))],
+            },
+        ));
+        graph[subquery_proj].add_owner(query_name.clone());
+        graph.add_edge(t2_count, subquery_proj, 0);
+
+        let t1 = graph.add_node(MirNode::new(
+            "t1".into(),
+            MirNodeInner::Base {
+                column_specs: vec![ColumnSpecification {
+                    column: nom_sql::Column::from("t1.a"),
+                    sql_type: SqlType::Int(None),
+                    constraints: vec![],
+                    comment: None,
+                }],
+                primary_key: Some([Column::from("a")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t1].add_owner(query_name.clone());
+
+        // -> π[t1.a, lit: 0]
+        let left_key_proj = graph.add_node(MirNode::new(
+            "t1_join_key".into(),
+            MirNodeInner::Project {
+                emit: vec![Column::new(Some("t1"), "a")],
+                expressions: vec![],
+                literals: vec![("__subquery_0_key".into(), DfValue::from(0u32))],
+            },
+        ));
+        graph[left_key_proj].add_owner(query_name.clone());
+        graph.add_edge(t1, left_key_proj, 0);
+
+        // -> ⟕⧑ on: l.__subquery_0_key ≡ r.__subquery_0_grp
+        let subquery_join = graph.add_node(MirNode::new(
+            "subquery_join".into(),
+            MirNodeInner::DependentLeftJoin {
+                on: vec![(
+                    Column::named("__subquery_0_key"),
+                    Column::named("__subquery_0_grp"),
+                )],
+                project: vec![
+                    Column::new(Some("t1"), "a"),
+                    Column::named("__subquery_0_key"),
+                    Column::named("__subquery_0_grp"),
+                    Column::named("count(t2.a)"),
+                ],
+            },
+        ));
+        graph[subquery_join].add_owner(query_name.clone());
+        graph.add_edge(left_key_proj, subquery_join, 0);
+        graph.add_edge(subquery_proj, subquery_join, 1);
+
+        let leaf = graph.add_node(MirNode::new(
+            "q".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name);
+        graph.add_edge(subquery_join, leaf, 0);
+
+        (graph, leaf, subquery_join)
+    }
+
+    #[test]
+    fn correlated_scalar_subquery() {
+        readyset_tracing::init_test_logging();
+        let (mut graph, leaf, subquery_join) = scalar_subquery_graph(Expr::BinaryOp {
+            lhs: Box::new(Expr::Column("t2.a".into())),
+            op: BinaryOperator::Equal,
+            rhs: Box::new(Expr::Column("t1.a".into())),
+        });
+        let mut query = MirQuery::new("q".into(), leaf, &mut graph);
+
+        eliminate_dependent_joins(&mut query).unwrap();
+
+        match &query.graph[subquery_join].inner {
+            MirNodeInner::LeftJoin { on, residual, .. } => {
+                assert_eq!(on.len(), 2);
+                assert!(on.contains(&(Column::new(Some("t1"), "a"), Column::new(Some("t2"), "a"))));
+                assert!(residual.is_none());
+            }
+            _ => panic!("should have rewritten dependent left join to a left join"),
+        };
+
+        let pull_result = pull_all_required_columns(&mut query);
+        assert!(pull_result.is_ok(), "{}", pull_result.err().unwrap());
+    }
+
+    #[test]
+    fn correlated_scalar_subquery_non_equality() {
+        readyset_tracing::init_test_logging();
+        let (mut graph, leaf, _) = scalar_subquery_graph(Expr::BinaryOp {
+            lhs: Box::new(Expr::Column("t2.a".into())),
+            op: BinaryOperator::Greater,
+            rhs: Box::new(Expr::Column("t1.a".into())),
+        });
+        let mut query = MirQuery::new("q".into(), leaf, &mut graph);
+
+        eliminate_dependent_joins(&mut query).unwrap_err();
+    }
 }
@@ -162,6 +162,13 @@ impl GraphViz for MirNodeInner {
                     AggregationKind::GroupConcat { separator: s } => {
                         format!("||({}, \"{}\")", on, s)
                     }
+                    AggregationKind::CountDistinct => format!("\\|DISTINCT\\|({})", on),
+                    AggregationKind::Stddev { sample: true } => format!("STDDEV_SAMP({})", on),
+                    AggregationKind::Stddev { sample: false } => format!("STDDEV_POP({})", on),
+                    AggregationKind::Variance { sample: true } => format!("VAR_SAMP({})", on),
+                    AggregationKind::Variance { sample: false } => format!("VAR_POP({})", on),
+                    AggregationKind::ArrayAgg => format!("ARRAY_AGG({})", on),
+                    AggregationKind::JsonAgg => format!("JSON_AGG({})", on),
                 };
                 let group_cols = group_by.iter().join(", ");
                 write!(f, "{} | γ: {}", op_string, group_cols)
@@ -197,6 +204,8 @@ impl GraphViz for MirNodeInner {
                 let op_string = match *kind {
                     ExtremumKind::Min => format!("min({})", on),
                     ExtremumKind::Max => format!("max({})", on),
+                    ExtremumKind::BoolAnd => format!("bool_and({})", on),
+                    ExtremumKind::BoolOr => format!("bool_or({})", on),
                 };
                 let group_cols = group_by.iter().join(", ");
                 write!(f, "{} | γ: {}", op_string, group_cols)
@@ -255,6 +264,8 @@ impl GraphViz for MirNodeInner {
                                     PostLookupAggregateFunction::GroupConcat { .. } => "GC",
                                     PostLookupAggregateFunction::Max => "Max",
                                     PostLookupAggregateFunction::Min => "Min",
+                                    PostLookupAggregateFunction::ArrayConcat => "AC",
+                                    PostLookupAggregateFunction::JsonArrayConcat => "JAC",
                                 },
                                 &aggregate.column
                             ))
@@ -265,9 +276,21 @@ impl GraphViz for MirNodeInner {
 
                 Ok(())
             }
-            MirNodeInner::LeftJoin { ref on, .. } => {
+            MirNodeInner::LeftJoin {
+                ref on,
+                ref residual,
+                ..
+            } => {
+                let jc = on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ");
+                write!(f, "⋉  | on: {}", jc)?;
+                if let Some(residual) = residual {
+                    write!(f, " | σ: {}", residual)?;
+                }
+                Ok(())
+            }
+            MirNodeInner::FullJoin { ref on, .. } => {
                 let jc = on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ");
-                write!(f, "⋉  | on: {}", jc)
+                write!(f, "⟗  | on: {}", jc)
             }
             MirNodeInner::DependentJoin { ref on, .. } => {
                 write!(
@@ -276,6 +299,13 @@ impl GraphViz for MirNodeInner {
                     on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ")
                 )
             }
+            MirNodeInner::DependentLeftJoin { ref on, .. } => {
+                write!(
+                    f,
+                    "⟕⧑ | on: {}",
+                    on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ")
+                )
+            }
             MirNodeInner::Latest { ref group_by } => {
                 let key_cols = group_by.iter().join(", ");
                 write!(f, "⧖ | γ: {}", key_cols)
@@ -323,6 +353,43 @@ impl GraphViz for MirNodeInner {
                     .unwrap_or_else(|| "".into());
                 write!(f, "Paginate [limit: {}; {}]", limit, order)
             }
+            MirNodeInner::Window {
+                ref function,
+                ref over,
+                ref partition_by,
+                ref order_by,
+                ..
+            } => {
+                let key_cols = partition_by.iter().join(", ");
+                let order = order_by
+                    .iter()
+                    .map(|(c, o)| format!("{}: {}", c.name.as_str(), o))
+                    .join(", ");
+                write!(
+                    f,
+                    "{}({}) OVER | γ: {} | {}",
+                    function,
+                    over.iter().join(", "),
+                    key_cols,
+                    order
+                )
+            }
+            MirNodeInner::Recursive {
+                ref on,
+                ref condition,
+                ref distinct,
+                ..
+            } => {
+                let jc = on.iter().map(|(s, c)| format!("{}:{}", s, c)).join(", ");
+                write!(f, "⟲ | on: {}", jc)?;
+                if let Some(condition) = condition {
+                    write!(f, " | σ: {}", condition)?;
+                }
+                if *distinct {
+                    write!(f, " | distinct")?;
+                }
+                Ok(())
+            }
             MirNodeInner::TopK {
                 ref order,
                 ref limit,
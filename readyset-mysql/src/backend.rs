@@ -116,6 +116,9 @@ async fn write_column<W: AsyncWrite + Unpin>(
         DfValue::Array(_) => {
             internal!("Cannot write MySQL column: MySQL does not support arrays")
         }
+        DfValue::Range(_) => {
+            internal!("Cannot write MySQL column: MySQL does not support range types")
+        }
         DfValue::BitVector(_) => {
             internal!("Cannot write MySQL column: MySQL does not support bit vectors")
         }
@@ -554,6 +557,7 @@ where
             }
             Err(e) => info.error(e.error_kind(), e.to_string().as_bytes()).await,
         };
+        self.release_upstream().await;
 
         Ok(res?)
     }
@@ -648,12 +652,18 @@ where
                 }
                 rw.finish().await
             }
-            execute_result => handle_query_result(execute_result, results).await,
+            execute_result => {
+                let res = handle_query_result(execute_result, results).await;
+                self.release_upstream().await;
+                res
+            }
         }
     }
 
     async fn on_init(&mut self, database: &str, w: InitWriter<'_, W>) -> io::Result<()> {
-        match self.set_database(database).await {
+        let res = self.set_database(database).await;
+        self.release_upstream().await;
+        match res {
             Ok(()) => w.ok().await,
             Err(e) => {
                 w.error(
@@ -669,7 +679,11 @@ where
 
     async fn on_query(&mut self, query: &str, results: QueryResultWriter<'_, W>) -> io::Result<()> {
         let query_result = self.query(query).await;
-        handle_query_result(query_result, results).await
+        let res = handle_query_result(query_result, results).await;
+        // The results have been written to the client at this point, so the upstream connection can
+        // be returned to the pool
+        self.release_upstream().await;
+        res
     }
 
     fn password_for_username(&self, username: &str) -> Option<Vec<u8>> {
@@ -680,6 +694,10 @@ where
         self.does_require_authentication()
     }
 
+    fn on_authenticated(&mut self, username: &str) {
+        self.noria.set_user(username)
+    }
+
     fn version(&self) -> String {
         self.noria.version()
     }
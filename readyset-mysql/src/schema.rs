@@ -96,6 +96,7 @@ pub(crate) fn convert_column(col: &ColumnSchema) -> ReadySetResult<mysql_srv::Co
         DfType::Inet => unsupported!("MySQL does not support the INET type"),
         DfType::Uuid => unsupported!("MySQL does not support the UUID type"),
         DfType::Jsonb => unsupported!("MySQL does not support the JSONB type"),
+        DfType::Range(_) => unsupported!("MySQL does not support range types"),
         DfType::Bit(size) => {
             if size < 64 {
                 MYSQL_TYPE_BIT
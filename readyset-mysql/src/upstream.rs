@@ -26,7 +26,7 @@ use readyset_client::ColumnSchema;
 use readyset_client_metrics::QueryDestination;
 use readyset_data::DfValue;
 use readyset_errors::{internal_err, ReadySetError};
-use tracing::{error, info, info_span, Instrument};
+use tracing::{error, info, info_span, warn, Instrument};
 
 use crate::schema::{convert_column, is_subtype};
 use crate::Error;
@@ -263,6 +263,20 @@ macro_rules! handle_query_result {
 }
 
 impl MySqlUpstream {
+    /// Returns true if connections made with the given config report the GTIDs of the
+    /// transactions they commit, for read-your-writes consistency
+    fn tracks_session_gtids(upstream_config: &UpstreamConfig) -> bool {
+        cfg!(feature = "ryw") || upstream_config.track_session_gtids
+    }
+
+    /// Makes the server report the GTIDs of the transactions committed by the given connection in
+    /// their OK packets. This is session state, so it has to be set on every new connection.
+    async fn track_session_gtids(conn: &mut Conn) -> Result<(), Error> {
+        conn.query_drop("SET SESSION session_track_gtids = OWN_GTID")
+            .await?;
+        Ok(())
+    }
+
     async fn connect_inner(
         upstream_config: UpstreamConfig,
     ) -> Result<
@@ -276,7 +290,7 @@ impl MySqlUpstream {
         // CLIENT_SESSION_TRACK is required for GTID information to be sent in OK packets on commits
         // GTID information is used for RYW
         // Currently this causes rows affected to return an incorrect result, so this is feature
-        // gated.
+        // gated, or only enabled when read-your-writes consistency is enabled for the adapter.
         let url = upstream_config
             .upstream_db_url
             .as_deref()
@@ -297,12 +311,14 @@ impl MySqlUpstream {
             user = %opts.user().unwrap_or("<NO USER>"),
         );
         span.in_scope(|| info!("Establishing connection"));
-        let conn = if cfg!(feature = "ryw") {
-            Conn::new(
+        let conn = if Self::tracks_session_gtids(&upstream_config) {
+            let mut conn = Conn::new(
                 OptsBuilder::from_opts(opts).add_capability(CapabilityFlags::CLIENT_SESSION_TRACK),
             )
             .instrument(span.clone())
-            .await?
+            .await?;
+            Self::track_session_gtids(&mut conn).await?;
+            conn
         } else {
             Conn::new(OptsBuilder::from_opts(opts))
                 .instrument(span.clone())
@@ -371,7 +387,10 @@ impl UpstreamDatabase for MySqlUpstream {
     #[cfg(feature = "fallback_cache")]
     async fn reset(&mut self) -> Result<(), Error> {
         let opts = self.conn.opts().clone();
-        let conn = Conn::new(opts).await?;
+        let mut conn = Conn::new(opts).await?;
+        if Self::tracks_session_gtids(&self.upstream_config) {
+            Self::track_session_gtids(&mut conn).await?;
+        }
         let prepared_statements = HashMap::new();
         let upstream_config = self.upstream_config.clone();
         let fallback_cache = if let Some(ref cache) = self.fallback_cache {
@@ -396,7 +415,10 @@ impl UpstreamDatabase for MySqlUpstream {
     #[cfg(not(feature = "fallback_cache"))]
     async fn reset(&mut self) -> Result<(), Error> {
         let opts = self.conn.opts().clone();
-        let conn = Conn::new(opts).await?;
+        let mut conn = Conn::new(opts).await?;
+        if Self::tracks_session_gtids(&self.upstream_config) {
+            Self::track_session_gtids(&mut conn).await?;
+        }
         let prepared_statements = HashMap::new();
         let upstream_config = self.upstream_config.clone();
         let old_self = std::mem::replace(
@@ -534,7 +556,7 @@ impl UpstreamDatabase for MySqlUpstream {
     async fn handle_ryw_write<'a, S>(
         &'a mut self,
         query: S,
-    ) -> Result<(Self::QueryResult<'a>, String), Error>
+    ) -> Result<(Self::QueryResult<'a>, Option<String>), Error>
     where
         S: AsRef<str> + Send + Sync + 'a,
     {
@@ -547,12 +569,20 @@ impl UpstreamDatabase for MySqlUpstream {
         let affected_rows = transaction.affected_rows();
         let last_insert_id = transaction.last_insert_id();
         let status_flags = transaction.status();
-        let txid = transaction.commit_returning_gtid().await.map_err(|e| {
-            internal_err!(
-                "Error obtaining GTID from MySQL for RYW-enabled commit: {}",
-                e
-            )
-        })?;
+        let txid = match transaction.commit_returning_gtid().await {
+            Ok(txid) => Some(txid),
+            // The commit failed, or we don't know whether it succeeded
+            Err(e @ (mysql_async::Error::Server(_) | mysql_async::Error::Io(_))) => {
+                return Err(e.into())
+            }
+            // Otherwise the write was committed but its OK packet had no GTID, eg because the
+            // upstream database has GTIDs disabled. Failing here would make the client retry a
+            // write that was already committed.
+            Err(error) => {
+                warn!(%error, "Could not obtain GTID from MySQL for RYW-enabled commit");
+                None
+            }
+        };
         Ok((
             QueryResult::WriteResult {
                 num_rows_affected: affected_rows,
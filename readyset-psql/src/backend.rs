@@ -62,12 +62,24 @@ impl ps::Backend for Backend {
     }
 
     async fn on_query(&mut self, query: &str) -> Result<ps::QueryResponse<Resultset>, ps::Error> {
-        self.query(query).await?.try_into()
+        let response = match self.query(query).await {
+            Ok(response) => response.try_into(),
+            Err(error) => Err(error.into()),
+        };
+        // Results have been fully converted at this point, so the upstream connection can be
+        // returned to the pool
+        self.0.release_upstream().await;
+        response
     }
 
     async fn on_prepare(&mut self, query: &str) -> Result<ps::PrepareResponse, ps::Error> {
         let statement_id = self.next_prepared_id(); // If prepare succeeds it will get this id
-        self.prepare(query).await?.try_into_ps(statement_id)
+        let response = match self.prepare(query).await {
+            Ok(response) => response.try_into_ps(statement_id),
+            Err(error) => Err(error.into()),
+        };
+        self.0.release_upstream().await;
+        response
     }
 
     async fn on_execute(
@@ -79,7 +91,12 @@ impl ps::Backend for Backend {
             .iter()
             .map(|p| ParamRef(p).try_into())
             .collect::<Result<Vec<DfValue>, ps::Error>>()?;
-        self.execute(statement_id, &params).await?.try_into()
+        let response = match self.execute(statement_id, &params).await {
+            Ok(response) => response.try_into(),
+            Err(error) => Err(error.into()),
+        };
+        self.0.release_upstream().await;
+        response
     }
 
     async fn on_close(&mut self, _statement_id: u32) -> Result<(), ps::Error> {
@@ -96,6 +113,10 @@ impl ps::Backend for Backend {
             }
         }
     }
+
+    fn on_authenticated(&mut self, user: &str) {
+        self.0.set_user(user)
+    }
 }
 
 /// A simple wrapper around a request parameter `psql_srv::Value` reference, facilitiating
@@ -134,6 +155,7 @@ impl TryFrom<ParamRef<'_>> for DfValue {
             ps::Value::Json(v) | ps::Value::Jsonb(v) => Ok(DfValue::from(v.to_string())),
             ps::Value::Bit(bits) | ps::Value::VarBit(bits) => Ok(DfValue::from(bits.clone())),
             ps::Value::Array(arr, _) => Ok(DfValue::from(arr.clone())),
+            ps::Value::Range(range, _) => Ok(DfValue::from(range.clone())),
             ps::Value::PassThrough(p) => Ok(DfValue::PassThrough(Arc::new(p.clone()))),
         }
     }
@@ -115,6 +115,13 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         DfType::Uuid => Ok(Type::UUID),
         DfType::Bit(_) => Ok(Type::BIT),
         DfType::VarBit(_) => Ok(Type::VARBIT),
+        DfType::Range(box DfType::Int) => Ok(Type::INT4_RANGE),
+        DfType::Range(box DfType::BigInt) => Ok(Type::INT8_RANGE),
+        DfType::Range(box DfType::Numeric { .. }) => Ok(Type::NUM_RANGE),
+        DfType::Range(box DfType::Timestamp { .. }) => Ok(Type::TS_RANGE),
+        DfType::Range(box DfType::TimestampTz { .. }) => Ok(Type::TSTZ_RANGE),
+        DfType::Range(box DfType::Date) => Ok(Type::DATE_RANGE),
+        DfType::Range(_) => unsupported_type!(),
         DfType::Array(box DfType::Unknown) => {
             // The default type for "unknown" in pgsql is TEXT
             Ok(Type::TEXT)
@@ -179,6 +186,15 @@ pub fn type_to_pgsql(col_type: &DfType) -> Result<pgsql::types::Type, Error> {
         DfType::Array(box DfType::Uuid) => Ok(Type::UUID_ARRAY),
         DfType::Array(box DfType::Bit(_)) => Ok(Type::BIT_ARRAY),
         DfType::Array(box DfType::VarBit(_)) => Ok(Type::VARBIT_ARRAY),
+        DfType::Array(box DfType::Range(box DfType::Int)) => Ok(Type::INT4_RANGE_ARRAY),
+        DfType::Array(box DfType::Range(box DfType::BigInt)) => Ok(Type::INT8_RANGE_ARRAY),
+        DfType::Array(box DfType::Range(box DfType::Numeric { .. })) => Ok(Type::NUM_RANGE_ARRAY),
+        DfType::Array(box DfType::Range(box DfType::Timestamp { .. })) => Ok(Type::TS_RANGE_ARRAY),
+        DfType::Array(box DfType::Range(box DfType::TimestampTz { .. })) => {
+            Ok(Type::TSTZ_RANGE_ARRAY)
+        }
+        DfType::Array(box DfType::Range(box DfType::Date)) => Ok(Type::DATE_RANGE_ARRAY),
+        DfType::Array(box DfType::Range(_)) => unsupported_type!(),
         DfType::Array(box DfType::Array(_)) => unsupported_type!(),
     }
 }
@@ -218,7 +218,7 @@ impl UpstreamDatabase for PostgreSqlUpstream {
     async fn handle_ryw_write<'a, S>(
         &'a mut self,
         _query: S,
-    ) -> Result<(Self::QueryResult<'a>, String), Error>
+    ) -> Result<(Self::QueryResult<'a>, Option<String>), Error>
     where
         S: AsRef<str> + Send + Sync + 'a,
     {
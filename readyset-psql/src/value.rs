@@ -100,21 +100,15 @@ impl TryFrom<Value> for ps::Value {
                 Uuid::parse_str(u.as_str()).map_err(|e| ps::Error::ParseError(e.to_string()))?,
             )),
             (Type::JSON, ref d @ (DfValue::Text(_) | DfValue::TinyText(_))) => Ok(ps::Value::Json(
-                <&str>::try_from(d)
-                    .map_err(|e| ps::Error::InternalError(e.to_string()))
-                    .and_then(|s| {
-                        serde_json::from_str::<serde_json::Value>(s)
-                            .map_err(|e| ps::Error::ParseError(e.to_string()))
-                    })?,
+                d.as_json()
+                    .map_err(|e| ps::Error::ParseError(e.to_string()))?
+                    .into_owned(),
             )),
             (Type::JSONB, ref d @ (DfValue::Text(_) | DfValue::TinyText(_))) => {
                 Ok(ps::Value::Jsonb(
-                    <&str>::try_from(d)
-                        .map_err(|e| ps::Error::InternalError(e.to_string()))
-                        .and_then(|s| {
-                            serde_json::from_str::<serde_json::Value>(s)
-                                .map_err(|e| ps::Error::ParseError(e.to_string()))
-                        })?,
+                    d.as_json()
+                        .map_err(|e| ps::Error::ParseError(e.to_string()))?
+                        .into_owned(),
                 ))
             }
             (Type::BIT, DfValue::BitVector(ref b)) => Ok(ps::Value::Bit(b.as_ref().clone())),
@@ -134,6 +128,15 @@ impl TryFrom<Value> for ps::Value {
                     )))
                 }
             }
+            (t, DfValue::Range(ref range)) => {
+                if let Kind::Range(_) = t.kind() {
+                    Ok(ps::Value::Range((**range).clone(), t))
+                } else {
+                    Err(ps::Error::InternalError(format!(
+                        "Mismatched type for value: expected range type, but got {t}"
+                    )))
+                }
+            }
             (_, DfValue::PassThrough(ref p)) => Ok(ps::Value::PassThrough((**p).clone())),
             (t, val) => {
                 if let Kind::Enum(vs) = t.kind() {
@@ -23,6 +23,7 @@ mod types {
         arbitrary_json_without_f64, arbitrary_mac_address, arbitrary_naive_date,
         arbitrary_naive_time, arbitrary_systemtime, arbitrary_uuid,
     };
+    use launchpad::eventually;
     use proptest::prelude::ProptestConfig;
     use proptest::string::string_regex;
     use readyset_adapter::backend::QueryDestination;
@@ -620,4 +621,102 @@ mod types {
             QueryDestination::Readyset
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial_test::serial]
+    async fn enum_add_value_in_middle() {
+        readyset_tracing::init_test_logging();
+        let (config, _handle) = setup().await;
+        let client = connect(config).await;
+
+        client
+            .simple_query("CREATE TYPE ac AS ENUM ('a', 'c');")
+            .await
+            .unwrap();
+        client.simple_query("CREATE TABLE t (x ac);").await.unwrap();
+        client
+            .simple_query("INSERT INTO t (x) VALUES ('c'), ('a')")
+            .await
+            .unwrap();
+
+        sleep().await;
+
+        client
+            .simple_query("CREATE CACHE FROM SELECT x::text FROM t ORDER BY x ASC")
+            .await
+            .unwrap();
+
+        let sort_res = client
+            .query("SELECT x::text FROM t ORDER BY x ASC", &[])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.get(0))
+            .collect::<Vec<String>>();
+        assert_eq!(sort_res, vec!["a", "c"]);
+
+        // Adding a variant in the middle of the enum changes the index of all the variants after
+        // it, so this requires resnapshotting the table
+        client
+            .simple_query("ALTER TYPE ac ADD VALUE 'b' BEFORE 'c'")
+            .await
+            .unwrap();
+        client
+            .simple_query("INSERT INTO t (x) VALUES ('b')")
+            .await
+            .unwrap();
+
+        // The cache should be re-created automatically once the table has been resnapshotted
+        eventually! {
+            let sort_res = client
+                .query("SELECT x::text FROM t ORDER BY x ASC", &[])
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|r| r.get(0))
+                .collect::<Vec<String>>();
+            sort_res == vec!["a", "b", "c"]
+                && last_query_info(&client).await.destination == QueryDestination::Readyset
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[serial_test::serial]
+    async fn domains() {
+        readyset_tracing::init_test_logging();
+        let (config, _handle) = setup().await;
+        let client = connect(config).await;
+
+        client
+            .simple_query(
+                "CREATE DOMAIN positive_int AS integer CHECK (VALUE > 0);
+                 CREATE DOMAIN small_positive_int AS positive_int CHECK (VALUE < 100);
+                 CREATE TABLE t (x small_positive_int, y positive_int);",
+            )
+            .await
+            .unwrap();
+        client
+            .simple_query("INSERT INTO t (x, y) VALUES (1, 10), (2, 20)")
+            .await
+            .unwrap();
+
+        sleep().await;
+
+        client
+            .simple_query("CREATE CACHE FROM SELECT y FROM t WHERE x = $1")
+            .await
+            .unwrap();
+
+        eventually! {
+            let res = client
+                .query("SELECT y FROM t WHERE x = $1", &[&2i32])
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|r| r.get(0))
+                .collect::<Vec<i32>>();
+            res == vec![20]
+                && last_query_info(&client).await.destination == QueryDestination::Readyset
+        }
+    }
 }
@@ -486,6 +486,16 @@ impl Materializations {
                 able = false;
             }
 
+            // full-text indexes can't be partial, since filling a hole for a single token would
+            // require finding every upstream record containing that token
+            if indexes
+                .iter()
+                .any(|index| index.index_type == IndexType::FullText)
+            {
+                debug!(node = %ni.index(), "full because of full-text index");
+                able = false;
+            }
+
             // we are already fully materialized, so can't be made partial
             if !new.contains(&ni)
                 && self.added.get(&ni).map(|i| i.len()).unwrap_or(0)
@@ -11,10 +11,11 @@ use std::convert::TryInto;
 
 use common::DfValue;
 use dataflow::node::Column as DfColumn;
-use dataflow::ops::grouped::concat::GroupConcat;
+use dataflow::ops::grouped::concat::{ConcatFormat, GroupConcat};
 use dataflow::ops::join::{Join, JoinType};
 use dataflow::ops::latest::Latest;
 use dataflow::ops::project::Project;
+use dataflow::ops::window::WindowFunction;
 use dataflow::{node, ops, Expr as DfExpr, PostLookupAggregates, ReaderProcessing};
 use itertools::Itertools;
 use mir::graph::MirGraph;
@@ -183,6 +184,7 @@ pub(super) fn mir_node_to_flow_parts(
                         &graph.referenced_columns(mir_node),
                         on,
                         project,
+                        None,
                         JoinType::Inner,
                         custom_types,
                         mig,
@@ -201,7 +203,7 @@ pub(super) fn mir_node_to_flow_parts(
                         mig,
                     )?
                 }
-                MirNodeInner::DependentJoin { .. } => {
+                MirNodeInner::DependentJoin { .. } | MirNodeInner::DependentLeftJoin { .. } => {
                     // See the docstring for MirNodeInner::DependentJoin
                     internal!("Encountered dependent join when lowering to dataflow")
                 }
@@ -260,7 +262,7 @@ pub(super) fn mir_node_to_flow_parts(
                 MirNodeInner::LeftJoin {
                     ref on,
                     ref project,
-                    ..
+                    ref residual,
                 } => {
                     invariant_eq!(ancestors.len(), 2);
                     let left = ancestors[0];
@@ -273,11 +275,33 @@ pub(super) fn mir_node_to_flow_parts(
                         &graph.columns(mir_node),
                         on,
                         project,
+                        residual.as_ref(),
                         JoinType::Left,
                         custom_types,
                         mig,
                     )?
                 }
+                MirNodeInner::FullJoin {
+                    ref on,
+                    ref project,
+                } => {
+                    invariant_eq!(ancestors.len(), 2);
+                    let left = ancestors[0];
+                    let right = ancestors[1];
+                    make_join_node(
+                        graph,
+                        name,
+                        left,
+                        right,
+                        &graph.columns(mir_node),
+                        on,
+                        project,
+                        None,
+                        JoinType::Full,
+                        custom_types,
+                        mig,
+                    )?
+                }
                 MirNodeInner::Project {
                     ref emit,
                     ref literals,
@@ -352,6 +376,50 @@ pub(super) fn mir_node_to_flow_parts(
                         mig,
                     )?
                 }
+                MirNodeInner::Window {
+                    function,
+                    ref over,
+                    ref partition_by,
+                    ref order_by,
+                    ..
+                } => {
+                    invariant_eq!(ancestors.len(), 1);
+                    let parent = ancestors[0];
+                    make_window_node(
+                        graph,
+                        name,
+                        parent,
+                        &graph.columns(mir_node),
+                        function,
+                        over.as_ref(),
+                        partition_by,
+                        order_by,
+                        mig,
+                    )?
+                }
+                MirNodeInner::Recursive {
+                    ref on,
+                    ref condition,
+                    ref emit,
+                    distinct,
+                } => {
+                    invariant_eq!(ancestors.len(), 2);
+                    let anchor = ancestors[0];
+                    let step = ancestors[1];
+                    make_recursive_node(
+                        graph,
+                        name,
+                        anchor,
+                        step,
+                        &graph.columns(mir_node),
+                        on,
+                        condition.as_ref(),
+                        emit,
+                        distinct,
+                        custom_types,
+                        mig,
+                    )?
+                }
                 MirNodeInner::AliasTable { .. } => {
                     invariant_eq!(ancestors.len(), 1);
                     // Ancestors should already have a flow node set.
@@ -583,6 +651,31 @@ fn make_grouped_node(
             set_names(&column_names(columns), &mut cols)?;
             mig.add_ingredient(name, cols, gc)
         }
+        // ARRAY_AGG and JSON_AGG are implemented by the same dataflow operator as GROUP_CONCAT
+        GroupedNodeType::Aggregation(Aggregation::ArrayAgg) => {
+            let gc = GroupConcat::with_format(
+                parent_na,
+                over_col_indx,
+                group_col_indx,
+                ConcatFormat::Array,
+            )?;
+            let agg_col = make_agg_col(DfType::Array(Box::new(over_col_ty.clone())));
+            cols.push(agg_col);
+            set_names(&column_names(columns), &mut cols)?;
+            mig.add_ingredient(name, cols, gc)
+        }
+        GroupedNodeType::Aggregation(Aggregation::JsonAgg) => {
+            let gc = GroupConcat::with_format(
+                parent_na,
+                over_col_indx,
+                group_col_indx,
+                ConcatFormat::Json,
+            )?;
+            let agg_col = make_agg_col(DfType::Json);
+            cols.push(agg_col);
+            set_names(&column_names(columns), &mut cols)?;
+            mig.add_ingredient(name, cols, gc)
+        }
         GroupedNodeType::Aggregation(agg) => {
             let grouped = agg.over(
                 parent_na,
@@ -625,7 +718,7 @@ fn make_identity_node(
 /// Lower a join MIR node to dataflow
 ///
 /// See [`MirNodeInner::Join`] for documentation on what `on_left`, `on_right`, and `project` mean
-/// here
+/// here, and [`MirNodeInner::LeftJoin`] for `residual`
 fn make_join_node(
     graph: &MirGraph,
     name: Relation,
@@ -634,6 +727,7 @@ fn make_join_node(
     columns: &[Column],
     on: &[(Column, Column)],
     proj_cols: &[Column],
+    residual: Option<&Expr>,
     kind: JoinType,
     custom_types: &HashMap<Relation, DfType>,
     mig: &mut Migration<'_>,
@@ -726,7 +820,29 @@ fn make_join_node(
         ));
     }
 
-    let j = Join::new(left_na, right_na, kind, emit);
+    let mut j = Join::new(left_na, right_na, kind, emit);
+    if let Some(residual) = residual {
+        let left_cols = mig.dataflow_state.ingredients[left_na].columns();
+        let right_cols = mig.dataflow_state.ingredients[right_na].columns();
+        j = j.with_residual(DfExpr::lower(
+            residual.clone(),
+            mig.dialect,
+            JoinLowerContext {
+                left: LowerContext {
+                    graph,
+                    parent_node_idx: left,
+                    parent_cols: left_cols,
+                    custom_types,
+                },
+                right: LowerContext {
+                    graph,
+                    parent_node_idx: right,
+                    parent_cols: right_cols,
+                    custom_types,
+                },
+            },
+        )?);
+    }
     let n = mig.add_ingredient(name, cols, j);
 
     Ok(FlowNode::New(n))
@@ -866,6 +982,30 @@ impl<'a> dataflow::LowerContext for LowerContext<'a> {
     }
 }
 
+/// Context for lowering the residual predicate of a join, which is evaluated against the
+/// concatenation of a row from the left parent and a row from the right parent
+#[derive(Clone)]
+struct JoinLowerContext<'a> {
+    left: LowerContext<'a>,
+    right: LowerContext<'a>,
+}
+
+impl<'a> dataflow::LowerContext for JoinLowerContext<'a> {
+    fn resolve_column(&self, col: nom_sql::Column) -> ReadySetResult<(usize, DfType)> {
+        match self.left.resolve_column(col.clone()) {
+            Ok(res) => Ok(res),
+            Err(_) => {
+                let (index, ty) = self.right.resolve_column(col)?;
+                Ok((self.left.parent_cols.len() + index, ty))
+            }
+        }
+    }
+
+    fn resolve_type(&self, ty: Relation) -> Option<DfType> {
+        self.left.resolve_type(ty)
+    }
+}
+
 /// Lower the given nom_sql AST expression to a `DfExpr`, resolving columns by looking their
 /// index up in the given parent node.
 fn lower_expression(
@@ -1127,6 +1267,138 @@ fn make_paginate_or_topk_node(
     Ok(FlowNode::New(na))
 }
 
+fn make_window_node(
+    graph: &MirGraph,
+    name: Relation,
+    parent: NodeIndex,
+    columns: &[Column],
+    function: WindowFunction,
+    over: Option<&Column>,
+    partition_by: &[Column],
+    order_by: &[(Column, OrderType)],
+    mig: &mut Migration<'_>,
+) -> ReadySetResult<FlowNode> {
+    invariant!(
+        !partition_by.is_empty(),
+        "need bogokey for Window without partition columns"
+    );
+    let parent_na = graph[parent].flow_node_addr()?;
+    let mut parent_cols = mig.dataflow_state.ingredients[parent_na].columns().to_vec();
+
+    let over_col_indx = over
+        .map(|c| graph.column_id_for_column(parent, c))
+        .transpose()?;
+    let over_col_ty = over_col_indx
+        .map(|i| {
+            parent_cols
+                .get(i)
+                .map(|c| c.ty())
+                .ok_or_else(|| internal_err!("Invalid index"))
+        })
+        .transpose()?;
+    let partition_by_indx = partition_by
+        .iter()
+        .map(|c| graph.column_id_for_column(parent, c))
+        .collect::<ReadySetResult<Vec<_>>>()?;
+    let order_by_indx = order_by
+        .iter()
+        .map(|(c, order_type)| {
+            graph
+                .column_id_for_column(parent, c)
+                .map(|id| (id, *order_type))
+        })
+        .collect::<ReadySetResult<Vec<_>>>()?;
+
+    let window = ops::window::Window::new(
+        parent_na,
+        function,
+        over_col_indx,
+        partition_by_indx,
+        order_by_indx,
+        over_col_ty,
+    )?;
+
+    // set names using MIR columns to ensure aliases are used
+    let column_names = column_names(columns);
+    #[allow(clippy::unwrap_used)] // column_names must be populated
+    parent_cols.push(DfColumn::new(
+        column_names.last().unwrap().into(),
+        window.output_col_type().clone(),
+        Some(name.clone()),
+    ));
+    set_names(&column_names, &mut parent_cols)?;
+
+    Ok(FlowNode::New(mig.add_ingredient(name, parent_cols, window)))
+}
+
+/// Lower a recursive CTE MIR node to dataflow
+///
+/// See [`MirNodeInner::Recursive`] for documentation on what `on`, `condition`, and `emit` mean
+/// here
+fn make_recursive_node(
+    graph: &MirGraph,
+    name: Relation,
+    anchor: NodeIndex,
+    step: NodeIndex,
+    columns: &[Column],
+    on: &[(Column, Column)],
+    condition: Option<&Expr>,
+    emit: &[Expr],
+    distinct: bool,
+    custom_types: &HashMap<Relation, DfType>,
+    mig: &mut Migration<'_>,
+) -> ReadySetResult<FlowNode> {
+    let anchor_na = graph[anchor].flow_node_addr()?;
+    let step_na = graph[step].flow_node_addr()?;
+    let anchor_cols = mig.dataflow_state.ingredients[anchor_na].columns();
+    let step_cols = mig.dataflow_state.ingredients[step_na].columns();
+    invariant_eq!(emit.len(), anchor_cols.len());
+
+    let (step_key, cte_key): (Vec<_>, Vec<_>) = on
+        .iter()
+        .map(|(s, c)| {
+            Ok((
+                graph.column_id_for_column(step, s)?,
+                graph.column_id_for_column(anchor, c)?,
+            ))
+        })
+        .collect::<ReadySetResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    // The recursive term is evaluated against the concatenation of a row from the step relation
+    // and a row of the CTE
+    let context = JoinLowerContext {
+        left: LowerContext {
+            graph,
+            parent_node_idx: step,
+            parent_cols: step_cols,
+            custom_types,
+        },
+        right: LowerContext {
+            graph,
+            parent_node_idx: anchor,
+            parent_cols: anchor_cols,
+            custom_types,
+        },
+    };
+    let condition = condition
+        .map(|expr| DfExpr::lower(expr.clone(), mig.dialect, context.clone()))
+        .transpose()?;
+    let emit = emit
+        .iter()
+        .map(|expr| DfExpr::lower(expr.clone(), mig.dialect, context.clone()))
+        .collect::<ReadySetResult<Vec<_>>>()?;
+
+    let mut cols = anchor_cols.to_vec();
+    set_names(&column_names(columns), &mut cols)?;
+
+    let recursive = ops::recursive::Recursive::new(
+        anchor_na, step_na, step_key, cte_key, condition, emit, distinct,
+    )?;
+    Ok(FlowNode::New(mig.add_ingredient(name, cols, recursive)))
+}
+
 fn make_reader_processing(
     graph: &MirGraph,
     parent: &NodeIndex,
@@ -9,7 +9,7 @@ use petgraph::graph::NodeIndex;
 use petgraph::visit::Bfs;
 use readyset_client::recipe::changelist::{Change, ChangeList};
 use readyset_client::ViewCreateRequest;
-use readyset_data::Dialect;
+use readyset_data::{DfType, Dialect};
 use readyset_errors::{
     internal, internal_err, invariant, invariant_eq, ReadySetError, ReadySetResult,
 };
@@ -331,8 +331,29 @@ impl Recipe {
                             name.schema = Some(first_schema.clone())
                         }
                     }
-                    self.registry.add_custom_type(name.clone());
-                    self.inc.add_custom_type(name, ty)?;
+                    match self.inc.get_custom_type(&name) {
+                        Some(current_ty) if *current_ty == ty => {
+                            trace!(%name, "type exists, but hasn't changed. Ignoring...");
+                        }
+                        Some(_) => {
+                            // This happens when we resnapshot after the type was changed in a way
+                            // we couldn't replicate (see `Change::AlterType` below)
+                            trace!(%name, "type exists and has changed. Redefining...");
+                            let old_ty = self.inc.redefine_custom_type(&name, ty.clone())?;
+                            let reindexed = match (&old_ty, &ty) {
+                                (
+                                    DfType::Enum { variants: old, .. },
+                                    DfType::Enum { variants: new, .. },
+                                ) => sql::variants_reindexed(old, new),
+                                _ => true,
+                            };
+                            self.update_custom_type_dependents(&name, &ty, reindexed, mig)?;
+                        }
+                        None => {
+                            self.registry.add_custom_type(name.clone());
+                            self.inc.add_custom_type(name, ty)?;
+                        }
+                    }
                 }
                 Change::Drop {
                     mut name,
@@ -383,51 +404,107 @@ impl Recipe {
                         self.registry.rename_custom_type(&old_name, &name);
                     }
                     let ty = ty.clone();
+                    self.update_custom_type_dependents(&name, &ty, false, mig)?;
+                }
+            }
+        }
 
-                    let mut table_nodes = vec![];
-                    let mut queries_to_remove = vec![];
-                    for expr in self.registry.expressions_referencing_custom_type(&name) {
-                        match expr {
-                            RecipeExpr::Table(table) => {
-                                for field in table.fields.iter() {
-                                    if matches!(&field.sql_type, SqlType::Other(t) if t == &name) {
-                                        self.inc.set_base_column_type(
-                                            &table.table,
-                                            &field.column,
-                                            ty.clone(),
-                                            mig,
-                                        )?;
-                                    }
-                                }
+        // We upgrade schema version *after* applying changes, so that the initial
+        // queries get correctly tagged with version 0.
+        self.inc.upgrade_version();
 
-                                let ni = self
-                                    .inc
-                                    .get_query_address(&table.table)
-                                    .expect("Already validated above");
-                                table_nodes.push(ni);
-                            }
+        Ok(())
+    }
 
-                            RecipeExpr::View(CreateViewStatement { name, .. })
-                            | RecipeExpr::Cache { name, .. } => {
-                                queries_to_remove.push(name.clone());
-                            }
-                        }
-                    }
+    /// Update all the tables, views, and caches that depend on the custom type with the given
+    /// `name` after its definition has been changed to `ty`.
+    ///
+    /// Columns of tables with the type have their type changed, or if `reindexed` is true (meaning
+    /// the values already stored in those tables are no longer valid for the new definition of the
+    /// type) the tables are dropped and recreated, so that they get snapshotted again. Any views or
+    /// caches which reference either the type or one of those tables are removed and re-created, to
+    /// re-materialize them with the new definition of the type.
+    fn update_custom_type_dependents(
+        &mut self,
+        name: &Relation,
+        ty: &DfType,
+        reindexed: bool,
+        mig: &mut Migration<'_>,
+    ) -> ReadySetResult<()> {
+        let mut tables = vec![];
+        let mut queries: Vec<RecipeExpr> = vec![];
+        let mut push_query = |expr: &RecipeExpr| {
+            if !queries.iter().any(|q| q.name() == expr.name()) {
+                queries.push(expr.clone());
+            }
+        };
+        for expr in self.registry.expressions_referencing_custom_type(name) {
+            match expr {
+                RecipeExpr::Table(table) => {
+                    self.registry
+                        .dependents_of_table(&table.table)
+                        .for_each(&mut push_query);
+                    tables.push(table.clone());
+                }
+                RecipeExpr::View(_) | RecipeExpr::Cache { .. } => push_query(expr),
+            }
+        }
+        // Views have to be re-created before any caches that might read from them
+        queries.sort_by_key(|expr| !matches!(expr, RecipeExpr::View(_)));
 
-                    for ni in table_nodes {
-                        self.remove_downstream_of(ni, mig);
-                    }
+        for query in &queries {
+            self.remove_expression(query.name(), mig)?;
+        }
 
-                    for name in queries_to_remove {
-                        self.remove_expression(&name, mig)?;
+        for table in tables {
+            if reindexed {
+                trace!(
+                    table = %table.table,
+                    custom_type = %name,
+                    "custom type variants were reindexed. Dropping and recreating table..."
+                );
+                self.drop_and_recreate_table(&table.table.clone(), table, mig)?;
+            } else {
+                for field in table.fields.iter() {
+                    if matches!(&field.sql_type, SqlType::Other(t) if t == name) {
+                        self.inc.set_base_column_type(
+                            &table.table,
+                            &field.column,
+                            ty.clone(),
+                            mig,
+                        )?;
                     }
                 }
             }
         }
 
-        // We upgrade schema version *after* applying changes, so that the initial
-        // queries get correctly tagged with version 0.
-        self.inc.upgrade_version();
+        for query in queries {
+            let query_name = query.name().clone();
+            let res = match &query {
+                RecipeExpr::View(stmt) => self.inc.add_view(stmt.clone(), mig),
+                RecipeExpr::Cache {
+                    name, statement, ..
+                } => self
+                    .inc
+                    .add_query(Some(name.clone()), statement.clone(), mig)
+                    .map(|_| ()),
+                RecipeExpr::Table(_) => continue,
+            };
+            match res {
+                Ok(()) => {
+                    debug!(query = %query_name, custom_type = %name, "Re-created query");
+                    self.registry.add_query(query)?;
+                }
+                Err(error) => {
+                    warn!(
+                        %error,
+                        query = %query_name,
+                        custom_type = %name,
+                        "Could not re-create query after change to custom type; dropping query"
+                    );
+                }
+            }
+        }
 
         Ok(())
     }
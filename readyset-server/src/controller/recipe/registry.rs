@@ -348,6 +348,20 @@ impl ExprRegistry {
             .map(|dep| self.expressions.get(dep).expect("Documented invariant"))
     }
 
+    /// Returns an iterator over all the views and caches that read from the table with the given
+    /// name
+    pub(super) fn dependents_of_table(
+        &self,
+        table: &Relation,
+    ) -> impl Iterator<Item = &RecipeExpr> {
+        self.aliases
+            .get(table)
+            .and_then(|table_id| self.dependencies.get(table_id))
+            .into_iter()
+            .flatten()
+            .filter_map(|dep| self.expressions.get(dep))
+    }
+
     fn assign_alias(&mut self, alias: Relation, query_id: QueryID) -> ReadySetResult<()> {
         match self.aliases.entry(alias.clone()) {
             Entry::Occupied(e) => {
@@ -52,7 +52,8 @@ pub(super) fn make_predicates_above_grouped<'a>(
 
 /// Normally, projection happens after grouped nodes - however, if aggregates used in grouped
 /// expressions reference expressions rather than columns directly, we need to project them out
-/// before the grouped nodes.
+/// before the grouped nodes. The same goes for expressions in the GROUP BY clause, which the
+/// grouped nodes then group by the projected columns of.
 ///
 /// This does that projection, and returns a mapping from the expressions themselves to the names of
 /// the columns they have been projected to
@@ -63,7 +64,7 @@ pub(super) fn make_expressions_above_grouped(
     qg: &QueryGraph,
     prev_node: &mut NodeIndex,
 ) -> HashMap<Expr, SqlIdentifier> {
-    let exprs: Vec<_> = qg
+    let mut exprs: Vec<_> = qg
         .aggregates
         .iter()
         .map(|(f, _)| f)
@@ -73,6 +74,11 @@ pub(super) fn make_expressions_above_grouped(
         .filter(|arg| !matches!(arg, Expr::Column(_)))
         .map(|expr| (SqlIdentifier::from(expr.to_string()), expr.clone()))
         .collect();
+    for (name, expr) in &qg.group_by_exprs {
+        if !exprs.iter().any(|(n, _)| n == name) {
+            exprs.push((name.clone(), expr.clone()));
+        }
+    }
 
     if !exprs.is_empty() {
         let cols = mir_converter.columns(*prev_node).to_vec();
@@ -297,15 +303,29 @@ pub(super) fn post_lookup_aggregates(
                 Avg { .. } => {
                     unsupported!("Average is not supported as a post-lookup aggregate")
                 }
+                // Distinct values may appear in more than one of the looked-up groups, so their
+                // counts can't be summed together
+                Count { distinct: true, .. } => {
+                    unsupported!("COUNT(DISTINCT) is not supported as a post-lookup aggregate")
+                }
+                Stddev { .. } | Variance { .. } => {
+                    unsupported!(
+                        "Standard deviation and variance are not supported as post-lookup \
+                         aggregates"
+                    )
+                }
                 // Count and sum are handled the same way, as re-aggregating counts is
                 // done by just summing the numbers together
                 Count { .. } | CountStar | Sum { .. } => PostLookupAggregateFunction::Sum,
-                Max(_) => PostLookupAggregateFunction::Max,
-                Min(_) => PostLookupAggregateFunction::Min,
+                // BOOL_AND and BOOL_OR are the minimum and maximum truth values respectively
+                Max(_) | BoolOr(_) => PostLookupAggregateFunction::Max,
+                Min(_) | BoolAnd(_) => PostLookupAggregateFunction::Min,
+                ArrayAgg(_) => PostLookupAggregateFunction::ArrayConcat,
+                JsonAgg(_) => PostLookupAggregateFunction::JsonArrayConcat,
                 GroupConcat { separator, .. } => PostLookupAggregateFunction::GroupConcat {
                     separator: separator.clone(),
                 },
-                Call { .. } | Substring { .. } => continue,
+                Call { .. } | Substring { .. } | Extract { .. } => continue,
             },
         });
     }
@@ -46,9 +46,11 @@ pub(super) fn make_joins(
     let mut join_chains = Vec::new();
 
     for jref in qg.join_order.iter() {
-        let (mut join_kind, jps) = match &qg.edges[&(jref.src.clone(), jref.dst.clone())] {
-            QueryGraphEdge::Join { on } => (JoinKind::Inner, on),
-            QueryGraphEdge::LeftJoin { on } => (JoinKind::Left, on),
+        let (mut join_kind, jps, residual) = match &qg.edges[&(jref.src.clone(), jref.dst.clone())]
+        {
+            QueryGraphEdge::Join { on } => (JoinKind::Inner, on, None),
+            QueryGraphEdge::LeftJoin { on, residual } => (JoinKind::Left, on, residual.clone()),
+            QueryGraphEdge::FullJoin { on } => (JoinKind::Full, on, None),
         };
 
         let (left_chain, right_chain) =
@@ -57,14 +59,17 @@ pub(super) fn make_joins(
         // TODO(fran): Use NodeIndex instead of name.
         if correlated_nodes.contains(&right_chain.last_node) {
             match join_kind {
-                JoinKind::Left => internal!(
-                    "Dependent left join not yet supported (when joining to {})",
+                JoinKind::Left => {
+                    join_kind = JoinKind::DependentLeft;
+                }
+                JoinKind::Full => internal!(
+                    "Dependent full join not yet supported (when joining to {})",
                     jref.dst
                 ),
                 JoinKind::Inner => {
                     join_kind = JoinKind::Dependent;
                 }
-                JoinKind::Dependent => {}
+                JoinKind::Dependent | JoinKind::DependentLeft => {}
             }
         }
 
@@ -72,6 +77,7 @@ pub(super) fn make_joins(
             query_name,
             mir_converter.generate_label(&name),
             jps,
+            residual,
             left_chain.last_node,
             right_chain.last_node,
             join_kind,
@@ -117,6 +123,7 @@ pub(super) fn make_cross_joins(
             query_name,
             mir_converter.generate_label(&name),
             &[],
+            None,
             n1,
             n2,
             join_kind,
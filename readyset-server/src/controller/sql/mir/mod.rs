@@ -5,6 +5,7 @@ use std::vec::Vec;
 
 use ::serde::{Deserialize, Serialize};
 use common::{DfValue, IndexType};
+use dataflow::ops;
 use dataflow::ops::grouped::aggregate::Aggregation;
 use dataflow::ops::union;
 use launchpad::redacted::Sensitive;
@@ -14,27 +15,31 @@ use mir::node::node_inner::MirNodeInner;
 use mir::node::{GroupedNodeType, MirNode};
 use mir::query::{MirBase, MirQuery};
 pub use mir::Column;
-use nom_sql::analysis::ReferredColumns;
+use nom_sql::analysis::{contains_aggregate, ReferredColumns};
 use nom_sql::{
     BinaryOperator, ColumnSpecification, CompoundSelectOperator, CreateTableStatement, Expr,
-    FieldDefinitionExpr, FieldReference, FunctionExpr, Literal, OrderClause, OrderType, Relation,
-    SelectStatement, SqlIdentifier, TableKey, UnaryOperator,
+    FieldDefinitionExpr, FieldReference, FunctionExpr, JoinConstraint, JoinOperator, Literal,
+    OrderClause, OrderType, RecursiveTerm, Relation, SelectStatement, SqlIdentifier, TableKey,
+    UnaryOperator,
 };
 use petgraph::graph::NodeIndex;
 use petgraph::Direction;
 use readyset_errors::{
-    internal, internal_err, invalid_err, invariant, invariant_eq, unsupported, ReadySetError,
+    internal, internal_err, invalid_err, invariant, invariant_eq, unsupported, unsupported_err,
+    ReadySetError,
 };
 use readyset_sql_passes::is_correlated;
 use tracing::{debug, trace};
 
-use super::query_graph::{extract_limit_offset, JoinPredicate};
+use super::query_graph::{extract_limit_offset, split_conjunctions, JoinPredicate};
 use crate::controller::sql::mir::grouped::{
     make_expressions_above_grouped, make_grouped, make_predicates_above_grouped,
     post_lookup_aggregates,
 };
 use crate::controller::sql::mir::join::{make_cross_joins, make_joins};
-use crate::controller::sql::query_graph::{to_query_graph, OutputColumn, Pagination, QueryGraph};
+use crate::controller::sql::query_graph::{
+    to_query_graph, OutputColumn, Pagination, QueryGraph, ScalarSubquery, WindowFunction,
+};
 use crate::controller::sql::query_signature::Signature;
 use crate::ReadySetResult;
 
@@ -43,6 +48,9 @@ mod join;
 
 lazy_static! {
     pub static ref PAGE_NUMBER_COL: SqlIdentifier = "__page_number".into();
+    /// The constant column used as the partition key for window functions without any partition
+    /// columns
+    static ref WINDOW_BOGOKEY_COL: SqlIdentifier = "__window_bogokey".into();
 }
 
 fn value_columns_needed_for_predicates(
@@ -102,7 +110,12 @@ fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
                     FunctionExpr::Max(..) => DfValue::None,
                     FunctionExpr::Min(..) => DfValue::None,
                     FunctionExpr::GroupConcat { .. } => DfValue::None,
-                    FunctionExpr::Call { .. } | FunctionExpr::Substring { .. } => DfValue::None,
+                    FunctionExpr::Stddev { .. } | FunctionExpr::Variance { .. } => DfValue::None,
+                    FunctionExpr::BoolAnd(..) | FunctionExpr::BoolOr(..) => DfValue::None,
+                    FunctionExpr::ArrayAgg(..) | FunctionExpr::JsonAgg(..) => DfValue::None,
+                    FunctionExpr::Call { .. }
+                    | FunctionExpr::Substring { .. }
+                    | FunctionExpr::Extract { .. } => DfValue::None,
                 },
                 _ => DfValue::None,
             })
@@ -110,6 +123,37 @@ fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
     )
 }
 
+/// Collect the names of all the columns of the table named `table` which are compared for equality
+/// with an expression that doesn't reference that table in the top-level conjunction of `cond`
+fn collect_key_equalities(cond: &Expr, table: &SqlIdentifier, out: &mut HashSet<SqlIdentifier>) {
+    let is_table_column =
+        |col: &nom_sql::Column| col.table.as_ref().map_or(false, |t| t.name == *table);
+    match cond {
+        Expr::BinaryOp {
+            lhs,
+            op: BinaryOperator::And,
+            rhs,
+        } => {
+            collect_key_equalities(lhs, table, out);
+            collect_key_equalities(rhs, table, out);
+        }
+        Expr::BinaryOp {
+            lhs,
+            op: BinaryOperator::Equal,
+            rhs,
+        } => {
+            for (key, value) in [(lhs, rhs), (rhs, lhs)] {
+                if let Expr::Column(col) = &**key {
+                    if is_table_column(col) && !value.referred_columns().any(is_table_column) {
+                        out.insert(col.name.clone());
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Kinds of joins in MIR
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JoinKind {
@@ -117,8 +161,12 @@ pub enum JoinKind {
     Inner,
     /// Left joins - see [`MirNodeInner::LeftJoin`]
     Left,
+    /// Full outer joins - see [`MirNodeInner::FullJoin`]
+    Full,
     /// Dependent joins - see [`MirNodeInner::DependentJoin`]
     Dependent,
+    /// Dependent left joins - see [`MirNodeInner::DependentLeftJoin`]
+    DependentLeft,
 }
 
 /// Configuration for how SQL is converted to MIR
@@ -298,6 +346,207 @@ impl SqlToMirConverter {
         Ok(leaf_node)
     }
 
+    /// Adds the MIR nodes for the recursive term of a `WITH RECURSIVE` common table expression
+    /// named `cte_name`, whose anchor has already been converted to MIR with the given leaf, and
+    /// returns the index of the node emitting all the rows of the CTE.
+    ///
+    /// Only recursive terms which join a single base table against the CTE on at least one
+    /// equality, and which don't group, aggregate, or paginate, are supported.
+    pub(super) fn recursive_cte_to_mir(
+        &mut self,
+        query_name: &Relation,
+        cte_name: &SqlIdentifier,
+        cte: &Relation,
+        anchor: NodeIndex,
+        term: RecursiveTerm,
+    ) -> ReadySetResult<NodeIndex> {
+        let RecursiveTerm { all, mut statement } = term;
+        if !statement.ctes.is_empty()
+            || statement.distinct
+            || statement.group_by.is_some()
+            || statement.having.is_some()
+            || statement.order.is_some()
+            || statement.limit.is_some()
+            || statement.offset.is_some()
+            || statement.fields.iter().any(
+                |f| matches!(f, FieldDefinitionExpr::Expr { expr, .. } if contains_aggregate(expr)),
+            )
+        {
+            unsupported!(
+                "Recursive term of CTE {} must be a plain join without grouping, ordering, or \
+                 pagination",
+                cte_name
+            );
+        }
+
+        let mut conditions = statement
+            .where_clause
+            .take()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut relations = statement
+            .tables
+            .iter()
+            .map(|t| t.inner.as_table().cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                unsupported_err!("Subqueries in recursive CTE terms are not supported")
+            })?;
+        for join in statement.join.drain(..) {
+            if !(join.operator.is_inner_join() || join.operator == JoinOperator::CrossJoin) {
+                unsupported!(
+                    "Recursive term of CTE {} may only use inner joins (found {})",
+                    cte_name,
+                    join.operator
+                );
+            }
+            for table in join.right.table_exprs() {
+                relations.push(table.inner.as_table().cloned().ok_or_else(|| {
+                    unsupported_err!("Subqueries in recursive CTE terms are not supported")
+                })?);
+            }
+            match join.constraint {
+                JoinConstraint::On(expr) => conditions.push(expr),
+                JoinConstraint::Empty => {}
+                JoinConstraint::Using(_) => {
+                    unsupported!("JOIN ... USING in recursive CTE terms is not supported")
+                }
+            }
+        }
+
+        let is_cte = |rel: &Relation| rel.schema.is_none() && rel.name == *cte_name;
+        let step_table = match &relations[..] {
+            [a, b] if is_cte(a) && !is_cte(b) => b.clone(),
+            [a, b] if is_cte(b) && !is_cte(a) => a.clone(),
+            _ => unsupported!(
+                "Recursive term of CTE {} must join exactly one table against the CTE",
+                cte_name
+            ),
+        };
+        let step_base = self
+            .get_relation(&step_table)
+            .filter(|&n| self.mir_graph[n].is_base())
+            .ok_or_else(|| {
+                unsupported_err!(
+                    "Recursive term of CTE {} may only join against a base table (found {})",
+                    cte_name,
+                    step_table
+                )
+            })?;
+
+        // Columns of the CTE are emitted by the anchor, which is aliased to `cte` below
+        let mut emit = statement
+            .fields
+            .into_iter()
+            .map(|f| match f {
+                FieldDefinitionExpr::Expr { expr, .. } => Ok(expr),
+                _ => internal!("Stars should have been expanded by now"),
+            })
+            .collect::<ReadySetResult<Vec<_>>>()?;
+        for expr in emit.iter_mut().chain(conditions.iter_mut()) {
+            for col in expr.referred_columns_mut() {
+                if col.table.is_none() {
+                    unsupported!(
+                        "Columns in the recursive term of CTE {} must be qualified (found {})",
+                        cte_name,
+                        col.name
+                    );
+                }
+                if col.table.as_ref().map_or(false, |t| is_cte(t)) {
+                    col.table = Some(cte.clone());
+                }
+            }
+        }
+
+        let anchor_columns = self.mir_graph.columns(anchor);
+        if emit.len() != anchor_columns.len() {
+            return Err(invalid_err!(
+                "Recursive term of CTE {} projects {} columns, but its anchor projects {}",
+                cte_name,
+                emit.len(),
+                anchor_columns.len()
+            ));
+        }
+
+        // Equalities between a column of the step table and a column of the CTE become the key
+        // the two are joined on, and everything else is evaluated against the joined rows
+        let mut on = vec![];
+        let mut condition = None;
+        for cond in split_conjunctions(conditions.iter()) {
+            match cond {
+                Expr::BinaryOp {
+                    lhs: box Expr::Column(l),
+                    op: BinaryOperator::Equal,
+                    rhs: box Expr::Column(r),
+                } if l.table.as_ref() == Some(cte) && r.table.as_ref() == Some(&step_table) => {
+                    on.push((Column::from(r), Column::from(l)));
+                }
+                Expr::BinaryOp {
+                    lhs: box Expr::Column(l),
+                    op: BinaryOperator::Equal,
+                    rhs: box Expr::Column(r),
+                } if l.table.as_ref() == Some(&step_table) && r.table.as_ref() == Some(cte) => {
+                    on.push((Column::from(l), Column::from(r)));
+                }
+                cond => {
+                    condition = Some(match condition {
+                        None => cond,
+                        Some(c) => Expr::BinaryOp {
+                            lhs: Box::new(c),
+                            op: BinaryOperator::And,
+                            rhs: Box::new(cond),
+                        },
+                    });
+                }
+            }
+        }
+        if on.is_empty() {
+            unsupported!(
+                "Recursive term of CTE {} must join the CTE on at least one equality",
+                cte_name
+            );
+        }
+
+        self.mir_graph[step_base].add_owner(query_name.clone());
+        let anchor_alias = self.add_query_node(
+            query_name.clone(),
+            MirNode::new(
+                format!("{}_{}_alias_table", query_name, cte.name).into(),
+                MirNodeInner::AliasTable { table: cte.clone() },
+            ),
+            &[anchor],
+        );
+        let step_alias = self.add_query_node(
+            query_name.clone(),
+            MirNode::new(
+                format!(
+                    "{}_{}_alias_table_{}",
+                    query_name, cte.name, step_table.name
+                )
+                .into(),
+                MirNodeInner::AliasTable {
+                    table: step_table.clone(),
+                },
+            ),
+            &[step_base],
+        );
+
+        trace!(%cte_name, ?on, "Added recursive node");
+        Ok(self.add_query_node(
+            query_name.clone(),
+            MirNode::new(
+                format!("{}_{}_recursive", query_name, cte.name).into(),
+                MirNodeInner::Recursive {
+                    on,
+                    condition,
+                    emit,
+                    distinct: !all,
+                },
+            ),
+            &[anchor_alias, step_alias],
+        ))
+    }
+
     // pub(super) viz for tests
     pub(super) fn get_flow_node_address(&self, name: &Relation) -> Option<NodeIndex> {
         match self.relations.get(name) {
@@ -683,6 +932,19 @@ impl SqlToMirConverter {
             out_nodes
         };
 
+        let over_column = |expr: &Expr| -> ReadySetResult<Column> {
+            match expr {
+                Expr::Column(col) => Ok(Column::from(col.clone())),
+                // TODO(celine): replace with ParentRef
+                _ => Ok(Column::named(
+                    projected_exprs
+                        .get(expr)
+                        .cloned()
+                        .ok_or_else(|| mk_error!(expr))?,
+                )),
+            }
+        };
+
         Ok(match function {
             Sum {
                 expr: box Expr::Column(col),
@@ -706,6 +968,16 @@ impl SqlToMirConverter {
             CountStar => {
                 internal!("COUNT(*) should have been rewritten earlier!")
             }
+            // COUNT(DISTINCT) is maintained incrementally by its own aggregation, rather than by
+            // counting the output of a distinct node
+            Count {
+                ref expr,
+                distinct: true,
+            } => mknode(
+                over_column(expr)?,
+                GroupedNodeType::Aggregation(Aggregation::CountDistinct),
+                false,
+            ),
             Count {
                 expr: box Expr::Column(col),
                 distinct,
@@ -786,6 +1058,36 @@ impl SqlToMirConverter {
                 GroupedNodeType::Aggregation(Aggregation::GroupConcat { separator }),
                 false,
             ),
+            Stddev { ref expr, sample } => mknode(
+                over_column(expr)?,
+                GroupedNodeType::Aggregation(Aggregation::Stddev { sample }),
+                false,
+            ),
+            Variance { ref expr, sample } => mknode(
+                over_column(expr)?,
+                GroupedNodeType::Aggregation(Aggregation::Variance { sample }),
+                false,
+            ),
+            BoolAnd(ref expr) => mknode(
+                over_column(expr)?,
+                GroupedNodeType::Extremum(Extremum::BoolAnd),
+                false,
+            ),
+            BoolOr(ref expr) => mknode(
+                over_column(expr)?,
+                GroupedNodeType::Extremum(Extremum::BoolOr),
+                false,
+            ),
+            ArrayAgg(ref expr) => mknode(
+                over_column(expr)?,
+                GroupedNodeType::Aggregation(Aggregation::ArrayAgg),
+                false,
+            ),
+            JsonAgg(ref expr) => mknode(
+                over_column(expr)?,
+                GroupedNodeType::Aggregation(Aggregation::JsonAgg),
+                false,
+            ),
             _ => {
                 internal!("not an aggregate: {:?}", Sensitive(&function));
             }
@@ -832,6 +1134,7 @@ impl SqlToMirConverter {
         query_name: &Relation,
         name: Relation,
         join_predicates: &[JoinPredicate],
+        residual: Option<Expr>,
         left_node: NodeIndex,
         right_node: NodeIndex,
         kind: JoinKind,
@@ -891,10 +1194,20 @@ impl SqlToMirConverter {
             on.push((l_col, r_col));
         }
 
+        if residual.is_some() && kind != JoinKind::Left {
+            internal!("Residual join predicates are only supported for left joins");
+        }
+
         let inner = match kind {
             JoinKind::Inner => MirNodeInner::Join { on, project },
-            JoinKind::Left => MirNodeInner::LeftJoin { on, project },
+            JoinKind::Left => MirNodeInner::LeftJoin {
+                on,
+                project,
+                residual,
+            },
+            JoinKind::Full => MirNodeInner::FullJoin { on, project },
             JoinKind::Dependent => MirNodeInner::DependentJoin { on, project },
+            JoinKind::DependentLeft => MirNodeInner::DependentLeftJoin { on, project },
         };
         trace!(?inner, "Added join node");
         Ok(self.add_query_node(
@@ -1069,6 +1382,66 @@ impl SqlToMirConverter {
         Ok(nodes)
     }
 
+    /// Make a window node computing the given window function over partitions of the rows of
+    /// `parent` given by `partition_by`
+    fn make_window_node(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        parent: NodeIndex,
+        window_function: &WindowFunction,
+        partition_by: Vec<Column>,
+    ) -> ReadySetResult<NodeIndex> {
+        let over_column = |expr: &Expr| -> ReadySetResult<Option<Column>> {
+            match expr {
+                Expr::Column(col) => Ok(Some(Column::from(col))),
+                _ => unsupported!("Window functions over expressions are not yet supported"),
+            }
+        };
+
+        let (function, over) = match &window_function.function {
+            FunctionExpr::Call { name, arguments } if arguments.is_empty() => {
+                match name.to_ascii_lowercase().as_str() {
+                    "row_number" => (ops::window::WindowFunction::RowNumber, None),
+                    "rank" => (ops::window::WindowFunction::Rank, None),
+                    "dense_rank" => (ops::window::WindowFunction::DenseRank, None),
+                    _ => unsupported!("Unsupported window function: {}", window_function.function),
+                }
+            }
+            FunctionExpr::CountStar => (ops::window::WindowFunction::Count, None),
+            FunctionExpr::Count {
+                expr,
+                distinct: false,
+            } => (ops::window::WindowFunction::Count, over_column(expr)?),
+            FunctionExpr::Sum {
+                expr,
+                distinct: false,
+            } => (ops::window::WindowFunction::Sum, over_column(expr)?),
+            FunctionExpr::Min(expr) => (ops::window::WindowFunction::Min, over_column(expr)?),
+            FunctionExpr::Max(expr) => (ops::window::WindowFunction::Max, over_column(expr)?),
+            function => unsupported!("Unsupported window function: {}", function),
+        };
+
+        Ok(self.add_query_node(
+            query_name.clone(),
+            MirNode::new(
+                name,
+                MirNodeInner::Window {
+                    function,
+                    over,
+                    partition_by,
+                    order_by: window_function
+                        .order_by
+                        .iter()
+                        .map(|(col, ot)| (Column::from(col), *ot))
+                        .collect(),
+                    output_column: Column::named(window_function.name.clone()),
+                },
+            ),
+            &[parent],
+        ))
+    }
+
     fn make_predicate_nodes(
         &mut self,
         query_name: &Relation,
@@ -1192,6 +1565,7 @@ impl SqlToMirConverter {
                         left: Expr::Column("__exists_join_key".into()),
                         right: Expr::Column("__count_grp".into()),
                     }],
+                    None,
                     left_literal_join_key_proj,
                     gt_0_filter,
                     if is_correlated(subquery) {
@@ -1301,6 +1675,190 @@ impl SqlToMirConverter {
         })
     }
 
+    /// Join the value of the given scalar subquery to `parent`, projected as a column named after
+    /// the subquery.
+    ///
+    /// This is implemented as a left join on a literal key, which is made into a dependent left
+    /// join if the subquery is correlated with the outer query. Decorrelation then turns that into
+    /// a regular left join on the columns the subquery was correlated on.
+    fn make_scalar_subquery_join(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        parent: NodeIndex,
+        subquery: &ScalarSubquery,
+    ) -> ReadySetResult<NodeIndex> {
+        // The join below duplicates rows of the outer query for each row returned by the
+        // subquery, where SQL would instead return an error if it returns more than one row
+        if !self.scalar_subquery_returns_at_most_one_row(&subquery.statement) {
+            unsupported!(
+                "Scalar subqueries must be aggregated or select a single row by a unique key"
+            );
+        }
+
+        let qg = to_query_graph(&subquery.statement)?;
+        let subquery_leaf = self.named_query_to_mir(
+            query_name,
+            subquery.statement.clone(),
+            &qg,
+            HashMap::new(),
+            false,
+        )?;
+
+        let value_col = match subquery.statement.fields.first() {
+            Some(FieldDefinitionExpr::Expr {
+                alias: Some(alias), ..
+            }) => Column::named(alias.clone()),
+            Some(FieldDefinitionExpr::Expr {
+                expr: Expr::Column(c),
+                ..
+            }) => Column::from(c),
+            Some(FieldDefinitionExpr::Expr { expr, .. }) => Column::named(expr.to_string()),
+            _ => internal!("Scalar subqueries should project exactly one expression"),
+        };
+        let value_col = self
+            .mir_graph
+            .columns(subquery_leaf)
+            .into_iter()
+            .find(|c| *c == value_col)
+            .ok_or_else(|| internal_err!("Column {} not projected by subquery", value_col))?;
+
+        // The value of a COUNT over no rows is 0, not NULL, so if the subquery is a COUNT we have
+        // to coalesce the NULLs produced by the left join for rows without a match
+        let value_name: SqlIdentifier = if subquery.is_count() {
+            format!("{}_count", subquery.name).into()
+        } else {
+            subquery.name.clone()
+        };
+        let key_col: SqlIdentifier = format!("{}_key", subquery.name).into();
+        let grp_col: SqlIdentifier = format!("{}_grp", subquery.name).into();
+
+        // -> π[<value>, lit: 0]
+        let value_proj = self.make_project_node(
+            query_name,
+            format!("{}_prj", name).into(),
+            subquery_leaf,
+            vec![value_col.aliased_as(value_name.clone())],
+            vec![],
+            vec![(grp_col.clone(), DfValue::from(0u32))],
+        );
+
+        // left -> π[...left, lit: 0]
+        let parent_columns = self.mir_graph.columns(parent);
+        let left_literal_join_key_proj = self.make_project_node(
+            query_name,
+            format!("{}_join_key", name).into(),
+            parent,
+            parent_columns,
+            vec![],
+            vec![(key_col.clone(), DfValue::from(0u32))],
+        );
+
+        // -> ⟕ on: l.<key> ≡ r.<grp>
+        let join = self.make_join_node(
+            query_name,
+            format!("{}_join", name).into(),
+            &[JoinPredicate {
+                left: Expr::Column(nom_sql::Column {
+                    name: key_col,
+                    table: None,
+                }),
+                right: Expr::Column(nom_sql::Column {
+                    name: grp_col,
+                    table: None,
+                }),
+            }],
+            None,
+            left_literal_join_key_proj,
+            value_proj,
+            if is_correlated(&subquery.statement) {
+                JoinKind::DependentLeft
+            } else {
+                JoinKind::Left
+            },
+        )?;
+
+        if !subquery.is_count() {
+            return Ok(join);
+        }
+
+        // -> π[...join, coalesce(<count>, 0)]
+        let count_col = Column::named(value_name.clone());
+        let join_columns = self
+            .mir_graph
+            .columns(join)
+            .into_iter()
+            .filter(|c| *c != count_col)
+            .collect();
+        Ok(self.make_project_node(
+            query_name,
+            format!("{}_coalesce", name).into(),
+            join,
+            join_columns,
+            vec![(
+                subquery.name.clone(),
+                Expr::Call(FunctionExpr::Call {
+                    name: "coalesce".into(),
+                    arguments: vec![
+                        Expr::Column(nom_sql::Column {
+                            name: value_name,
+                            table: None,
+                        }),
+                        Expr::Literal(Literal::Integer(0)),
+                    ],
+                }),
+            )],
+            vec![],
+        ))
+    }
+
+    /// Returns true if the given scalar subquery is guaranteed to return at most one row for each
+    /// row of the outer query: either because it's aggregated without a GROUP BY, because it has
+    /// a `LIMIT 1`, or because it selects from a single table with equality conditions on all the
+    /// columns of one of that table's unique keys.
+    fn scalar_subquery_returns_at_most_one_row(&self, subquery: &SelectStatement) -> bool {
+        if subquery.group_by.is_none()
+            && subquery.fields.iter().any(|field| {
+                matches!(field, FieldDefinitionExpr::Expr { expr, .. } if contains_aggregate(expr))
+            })
+        {
+            return true;
+        }
+
+        if matches!(
+            subquery.limit,
+            Some(Literal::Integer(1) | Literal::UnsignedInteger(1))
+        ) {
+            return true;
+        }
+
+        let ([table], []) = (subquery.tables.as_slice(), subquery.join.as_slice()) else {
+            return false;
+        };
+        let Some(relation) = table.inner.as_table() else {
+            return false;
+        };
+        let Some(MirNodeInner::Base {
+            primary_key,
+            unique_keys,
+            ..
+        }) = self.get_relation(relation).map(|ni| &self.mir_graph[ni].inner)
+        else {
+            return false;
+        };
+
+        let table_name = table.alias.as_ref().unwrap_or(&relation.name);
+        let mut equal_columns = HashSet::new();
+        if let Some(where_clause) = &subquery.where_clause {
+            collect_key_equalities(where_clause, table_name, &mut equal_columns);
+        }
+
+        primary_key
+            .iter()
+            .chain(unique_keys.iter())
+            .any(|key| key.iter().all(|col| equal_columns.contains(&col.name)))
+    }
+
     /// Adds all the MIR nodes corresponding to the given query,
     /// and returns the index of its leaf node.
     #[allow(clippy::cognitive_complexity)]
@@ -1378,8 +1936,32 @@ impl SqlToMirConverter {
                     &[base_for_rel],
                 );
 
-                base_nodes.push(alias_table_node);
-                node_for_rel.insert(*rel, alias_table_node);
+                // Parameters compared against expressions are keyed on columns computed from
+                // those expressions, so project them out as soon as the relation is available
+                let mut param_exprs: Vec<(SqlIdentifier, Expr)> = vec![];
+                for param in &qg.relations[*rel].parameters {
+                    if let Some(expr) = &param.expr {
+                        if !param_exprs.iter().any(|(name, _)| *name == param.col.name) {
+                            param_exprs.push((param.col.name.clone(), expr.clone()));
+                        }
+                    }
+                }
+                let rel_node = if param_exprs.is_empty() {
+                    alias_table_node
+                } else {
+                    let cols = self.mir_graph.columns(alias_table_node);
+                    self.make_project_node(
+                        query_name,
+                        format!("q_{:x}_{}_param_exprs", qg.signature().hash, rel.name).into(),
+                        alias_table_node,
+                        cols,
+                        param_exprs,
+                        vec![],
+                    )
+                };
+
+                base_nodes.push(rel_node);
+                node_for_rel.insert(*rel, rel_node);
             }
 
             let join_nodes = make_joins(
@@ -1418,6 +2000,17 @@ impl SqlToMirConverter {
                 }
             };
 
+            // 1a. Join in the values of any scalar subqueries, which are referenced as columns by
+            // the rest of the query
+            for subquery in &qg.scalar_subqueries {
+                prev_node = self.make_scalar_subquery_join(
+                    query_name,
+                    format!("q_{:x}_{}", qg.signature().hash, subquery.name).into(),
+                    prev_node,
+                    subquery,
+                )?;
+            }
+
             // 2. If we're aggregating on expressions rather than directly on columns, project out
             // those expressions before the aggregate itself
             let expressions_above_grouped = make_expressions_above_grouped(
@@ -1551,6 +2144,74 @@ impl SqlToMirConverter {
                 prev_node = subquery_leaf;
             }
 
+            // 9a. Add window function nodes. Window functions are computed after all filters,
+            // including the lookup key of the view, so we partition by the query parameters in
+            // addition to the partition columns of each window.
+            if !qg.window_functions.is_empty() {
+                let params = qg.parameters();
+                if params.iter().any(|p| p.op != BinaryOperator::Equal) {
+                    unsupported!(
+                        "Window functions are not supported in queries with range parameters"
+                    );
+                }
+
+                let partitions = qg
+                    .window_functions
+                    .iter()
+                    .map(|wf| {
+                        let mut partition_by: Vec<Column> =
+                            wf.partition_by.iter().map(Column::from).collect();
+                        for param in &params {
+                            let col = Column::from(&param.col);
+                            if !partition_by.contains(&col) {
+                                partition_by.push(col);
+                            }
+                        }
+                        partition_by
+                    })
+                    .collect::<Vec<_>>();
+
+                if partitions.iter().any(|p| p.is_empty()) {
+                    // need another projection to introduce a bogokey to partition by for any
+                    // windows without partition columns
+                    let cols = self.mir_graph.columns(prev_node);
+                    prev_node = self.make_project_node(
+                        query_name,
+                        format!(
+                            "q_{:x}_n{}",
+                            qg.signature().hash,
+                            self.mir_graph.node_count()
+                        )
+                        .into(),
+                        prev_node,
+                        cols,
+                        vec![],
+                        vec![(WINDOW_BOGOKEY_COL.clone(), DfValue::from(0i32))],
+                    );
+                }
+
+                for (window_function, mut partition_by) in
+                    qg.window_functions.iter().zip(partitions)
+                {
+                    if partition_by.is_empty() {
+                        partition_by.push(Column::named(WINDOW_BOGOKEY_COL.clone()));
+                    }
+
+                    prev_node = self.make_window_node(
+                        query_name,
+                        format!(
+                            "q_{:x}_n{}",
+                            qg.signature().hash,
+                            self.mir_graph.node_count()
+                        )
+                        .into(),
+                        prev_node,
+                        window_function,
+                        partition_by,
+                    )?;
+                }
+            }
+
             // 10. Get the final node
             let mut final_node = prev_node;
 
@@ -1563,8 +2224,11 @@ impl SqlToMirConverter {
                 order,
                 limit,
                 offset,
-            }) = qg.pagination.as_ref()
-            {
+            }) = qg.pagination.as_ref().filter(|p| {
+                // Range queries with an OFFSET can't be keyed on a page number, so the reader
+                // applies both the offset and the limit to the results of the lookup instead
+                !(p.offset.is_some() && view_key.index_type == IndexType::BTreeMap)
+            }) {
                 let make_topk = offset.is_none();
                 let group_by = if qg.parameters().is_empty() {
                     // need to add another projection to introduce a bogokey to group by if there
@@ -11,7 +11,7 @@ use nom_sql::{
 use petgraph::graph::NodeIndex;
 use readyset_client::recipe::changelist::AlterTypeChange;
 use readyset_data::{DfType, Dialect, PgEnumMetadata};
-use readyset_errors::{invalid_err, ReadySetError, ReadySetResult};
+use readyset_errors::{invalid_err, unsupported_err, ReadySetError, ReadySetResult};
 use readyset_sql_passes::alias_removal::TableAliasRewrite;
 use readyset_sql_passes::{AliasRemoval, Rewrite, RewriteContext};
 use tracing::{debug, trace};
@@ -251,13 +251,10 @@ impl SqlIncorporator {
             AlterTypeChange::SetVariants(new_variants) => {
                 let metadata = match ty {
                     DfType::Enum { variants, metadata } => {
-                        if new_variants.len() > variants.len()
-                            && new_variants[..variants.len()] != **variants
-                        {
-                            return Err(invalid_err!(
-                                "Cannot drop variants or add new variants unless they're at the \
-                                 end"
-                            ));
+                        if variants_reindexed(variants, &new_variants) {
+                            // Enum values are stored as indices into the list of variants, so
+                            // existing rows can only be fixed up by snapshotting them again
+                            return Err(ReadySetError::ResnapshotNeeded);
                         }
                         if old_name.is_some() {
                             if let Some(metadata) = metadata {
@@ -279,6 +276,39 @@ impl SqlIncorporator {
         Ok((ty, old_name))
     }
 
+    /// Replace the definition of the existing custom type with the given `name` with `ty`,
+    /// returning the previous definition of the type.
+    pub(crate) fn redefine_custom_type(
+        &mut self,
+        name: &Relation,
+        ty: DfType,
+    ) -> ReadySetResult<DfType> {
+        let Some(existing) = self.custom_types.get_mut(name) else {
+            return Err(invalid_err!("Could not find custom type {name}"));
+        };
+        if let DfType::Enum {
+            metadata: Some(PgEnumMetadata { oid, .. }),
+            ..
+        } = existing
+        {
+            self.custom_types_by_oid.remove(oid);
+        }
+        if let DfType::Enum {
+            metadata: Some(PgEnumMetadata { oid, .. }),
+            ..
+        } = ty
+        {
+            self.custom_types_by_oid.insert(oid, name.clone());
+        }
+
+        Ok(std::mem::replace(existing, ty))
+    }
+
+    /// Returns the definition of the custom type with the given `name`, if any
+    pub(crate) fn get_custom_type(&self, name: &Relation) -> Option<&DfType> {
+        self.custom_types.get(name)
+    }
+
     pub(crate) fn drop_custom_type(&mut self, name: &Relation) -> Option<DfType> {
         self.custom_types.remove(name)
     }
@@ -440,13 +470,39 @@ impl SqlIncorporator {
                     anon_queries.insert(to_view, subquery_leaf);
                 }
                 TableAliasRewrite::Cte {
+                    from,
                     to_view,
                     for_statement,
-                    ..
+                    recursive_term,
                 } => {
-                    let subquery_leaf = self
+                    let mut subquery_leaf = self
                         .add_select_query(query_name.clone(), *for_statement, false, mig)
                         .map_err(on_err)?;
+                    if let Some(mut term) = recursive_term {
+                        // The recursive term is only ever joined against the CTE itself, so it can
+                        // only have aliases for base tables
+                        if term
+                            .statement
+                            .rewrite_table_aliases(&query_name.name)
+                            .iter()
+                            .any(|r| !matches!(r, TableAliasRewrite::Table { .. }))
+                        {
+                            return Err(on_err(unsupported_err!(
+                                "Recursive term of CTE {} may not contain self-joins or CTEs",
+                                from
+                            )));
+                        }
+                        subquery_leaf = self
+                            .mir_converter
+                            .recursive_cte_to_mir(
+                                &query_name,
+                                &from,
+                                &to_view,
+                                subquery_leaf,
+                                *term,
+                            )
+                            .map_err(on_err)?;
+                    }
                     anon_queries.insert(to_view, subquery_leaf);
                 }
                 TableAliasRewrite::Table { .. } => {}
@@ -513,6 +569,16 @@ impl SqlIncorporator {
     }
 }
 
+/// Returns `true` if changing the variants of an enum type from `old` to `new` changes the index of
+/// any of the existing variants.
+///
+/// Since enum values are stored as indices into their type's list of variants, this means any
+/// existing values of the type would refer to the wrong variant after the change. Renaming variants
+/// or adding new variants to the end of the list leaves existing indices untouched.
+pub(crate) fn variants_reindexed(old: &[String], new: &[String]) -> bool {
+    new.len() < old.len() || (new.len() > old.len() && new[..old.len()] != *old)
+}
+
 #[cfg(test)]
 mod tests {
     use dataflow::prelude::*;
@@ -3753,4 +3819,135 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn it_incorporates_scalar_subqueries_returning_one_row() {
+        let mut g = integration_utils::start_simple_unsharded(
+            "it_incorporates_scalar_subqueries_returning_one_row",
+        )
+        .await;
+        g.migrate(|mig| {
+            let mut inc = SqlIncorporator::default();
+            for table in [
+                "CREATE TABLE p (id int, cid int);",
+                "CREATE TABLE c (id int, name text, category int, PRIMARY KEY (id));",
+            ] {
+                inc.add_table(
+                    inc.rewrite(
+                        parse_create_table(Dialect::MySQL, table).unwrap(),
+                        &[],
+                        DataDialect::DEFAULT_MYSQL,
+                        None,
+                    )
+                    .unwrap(),
+                    mig,
+                )
+                .unwrap();
+            }
+
+            let mut add_query = |q: &str| {
+                inc.add_query(
+                    None,
+                    inc.rewrite(
+                        parse_select_statement(Dialect::MySQL, q).unwrap(),
+                        &[],
+                        DataDialect::DEFAULT_MYSQL,
+                        None,
+                    )
+                    .unwrap(),
+                    mig,
+                )
+            };
+
+            // Aggregated without a GROUP BY
+            let res = add_query("SELECT p.id, (SELECT count(*) FROM c WHERE c.id = p.cid) FROM p");
+            assert!(res.is_ok(), "{}", res.as_ref().unwrap_err());
+
+            // Equality on the primary key
+            let res = add_query("SELECT p.id, (SELECT c.name FROM c WHERE c.id = p.cid) FROM p");
+            assert!(res.is_ok(), "{}", res.as_ref().unwrap_err());
+
+            // Might return more than one row for each row of `p`
+            add_query("SELECT p.id, (SELECT c.name FROM c WHERE c.category = p.cid) FROM p")
+                .unwrap_err();
+        })
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn it_incorporates_recursive_ctes() {
+        let mut g =
+            integration_utils::start_simple_unsharded("it_incorporates_recursive_ctes").await;
+        g.migrate(|mig| {
+            let mut inc = SqlIncorporator::default();
+            inc.add_table(
+                inc.rewrite(
+                    parse_create_table(
+                        Dialect::MySQL,
+                        "CREATE TABLE employees (id int, manager_id int);",
+                    )
+                    .unwrap(),
+                    &[],
+                    DataDialect::DEFAULT_MYSQL,
+                    None,
+                )
+                .unwrap(),
+                mig,
+            )
+            .unwrap();
+
+            let mut add_query = |q: &str| {
+                inc.add_query(
+                    None,
+                    inc.rewrite(
+                        parse_select_statement(Dialect::MySQL, q).unwrap(),
+                        &[],
+                        DataDialect::DEFAULT_MYSQL,
+                        None,
+                    )
+                    .unwrap(),
+                    mig,
+                )
+            };
+
+            for (all, distinct) in [("ALL ", false), ("", true)] {
+                let res = add_query(&format!(
+                    "WITH RECURSIVE reports AS (
+                         SELECT employees.id FROM employees WHERE employees.manager_id = 1
+                         UNION {all}
+                         SELECT e.id FROM employees e JOIN reports r ON e.manager_id = r.id
+                     ) SELECT reports.id FROM reports"
+                ));
+                assert!(res.is_ok(), "{}", res.as_ref().unwrap_err());
+                let recursive = mig
+                    .graph()
+                    .node_weights()
+                    .filter(|n| n.description(true).contains('⟲'))
+                    .last()
+                    .unwrap();
+                assert_eq!(recursive.description(true).ends_with("DISTINCT"), distinct);
+            }
+
+            // Can't join against the CTE with an outer join
+            add_query(
+                "WITH RECURSIVE reports AS (
+                     SELECT employees.id FROM employees WHERE employees.manager_id = 1
+                     UNION ALL
+                     SELECT e.id FROM employees e LEFT JOIN reports r ON e.manager_id = r.id
+                 ) SELECT reports.id FROM reports",
+            )
+            .unwrap_err();
+
+            // Needs an equality between the table and the CTE to key the join on
+            add_query(
+                "WITH RECURSIVE reports AS (
+                     SELECT employees.id FROM employees WHERE employees.manager_id = 1
+                     UNION ALL
+                     SELECT e.id FROM employees e JOIN reports r ON e.manager_id > r.id
+                 ) SELECT reports.id FROM reports",
+            )
+            .unwrap_err();
+        })
+        .await;
+    }
 }
@@ -7,6 +7,7 @@ use std::vec::Vec;
 use std::{iter, mem};
 
 use common::IndexType;
+use dataflow::full_text::full_text_predicate;
 use nom_sql::analysis::visit_mut::{walk_expr, VisitorMut};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::{
@@ -17,7 +18,7 @@ use nom_sql::{
 use readyset_client::{PlaceholderIdx, ViewPlaceholder};
 use readyset_errors::{
     internal, invalid, invalid_err, invariant, invariant_eq, no_table_for_col, unsupported,
-    unsupported_err, ReadySetResult,
+    unsupported_err, ReadySetError, ReadySetResult,
 };
 use readyset_sql_passes::{is_aggregate, is_predicate, map_aggregates, LogicalOp};
 use serde::{Deserialize, Serialize};
@@ -179,6 +180,10 @@ pub struct Parameter {
     pub col: Column,
     pub op: nom_sql::BinaryOperator,
     pub placeholder_idx: Option<PlaceholderIdx>,
+    /// If the placeholder is compared against an expression rather than a column, the
+    /// expression, which is projected as the (table-less) column `col` so that the reader can be
+    /// keyed on it
+    pub expr: Option<Expr>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Serialize, Deserialize)]
@@ -195,8 +200,18 @@ pub struct QueryGraphNode {
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum QueryGraphEdge {
-    Join { on: Vec<JoinPredicate> },
-    LeftJoin { on: Vec<JoinPredicate> },
+    Join {
+        on: Vec<JoinPredicate>,
+    },
+    LeftJoin {
+        on: Vec<JoinPredicate>,
+        /// Any part of the join condition which isn't an equality between columns in both sides
+        /// of the join, which must hold for rows to be joined
+        residual: Option<Expr>,
+    },
+    FullJoin {
+        on: Vec<JoinPredicate>,
+    },
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -206,6 +221,43 @@ pub struct Pagination {
     pub offset: Option<ViewPlaceholder>,
 }
 
+/// A window function projected by a query
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowFunction {
+    /// The function to compute for each row
+    pub function: FunctionExpr,
+    /// Columns to partition the rows of the query by
+    pub partition_by: Vec<Column>,
+    /// Columns to order the rows within each partition by
+    pub order_by: Vec<(Column, OrderType)>,
+    /// The name of the column for the result of the window function
+    pub name: SqlIdentifier,
+}
+
+/// A scalar subquery (a subquery returning a single value) appearing in the SELECT list or the
+/// WHERE clause of a query
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScalarSubquery {
+    /// The name of the column that the value of the subquery is projected as in the outer query
+    pub name: SqlIdentifier,
+    /// The subquery itself
+    pub statement: SelectStatement,
+}
+
+impl ScalarSubquery {
+    /// Returns true if the single column projected by this subquery is a `COUNT` aggregate, in
+    /// which case the value of the subquery for an empty set of rows is `0` rather than `NULL`
+    pub fn is_count(&self) -> bool {
+        matches!(
+            self.statement.fields.first(),
+            Some(FieldDefinitionExpr::Expr {
+                expr: Expr::Call(FunctionExpr::Count { .. } | FunctionExpr::CountStar),
+                ..
+            })
+        )
+    }
+}
+
 /// Description of the lookup key for a view
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ViewKey {
@@ -235,6 +287,10 @@ pub struct QueryGraph {
     pub aggregates: HashMap<FunctionExpr, SqlIdentifier>,
     /// Set of columns that appear in the GROUP BY clause
     pub group_by: HashSet<Column>,
+    /// Expressions other than bare columns that appear in the GROUP BY clause, along with the
+    /// name of the column each is projected as before grouping. These columns also appear (with
+    /// no table) in `group_by`.
+    pub group_by_exprs: Vec<(SqlIdentifier, Expr)>,
     /// Final set of projected columns in this query; may include literals in addition to the
     /// columns reflected in individual relations' `QueryGraphNode` structures.
     pub columns: Vec<OutputColumn>,
@@ -247,6 +303,11 @@ pub struct QueryGraph {
     pub having_predicates: Vec<Expr>,
     /// The pagination (order, limit, offset) for the query, if any
     pub pagination: Option<Pagination>,
+    /// Window functions in the query, in the order they were projected
+    pub window_functions: Vec<WindowFunction>,
+    /// Scalar subqueries in the SELECT list or WHERE clause of the query, each of which has been
+    /// replaced with a reference to a column named after the subquery
+    pub scalar_subqueries: Vec<ScalarSubquery>,
 }
 
 impl QueryGraph {
@@ -283,6 +344,14 @@ impl QueryGraph {
         } else {
             let mut parameters = self.parameters();
 
+            if parameters.len() > 1
+                && parameters
+                    .iter()
+                    .any(|param| param.op == BinaryOperator::AtAt)
+            {
+                unsupported!("Full-text search can't be combined with other query parameters");
+            }
+
             // Sort the parameters to put equal comparisons first, to take advantage of
             // lexicographic key ordering for queries that mix equality and range comparisons
             parameters.sort_by(|param1, param2| {
@@ -357,9 +426,10 @@ impl QueryGraph {
             }
 
             if let Some(offset) = offset {
-                if index_type == Some(IndexType::BTreeMap) {
-                    unsupported!("ReadySet does not support Pagination and range queries")
-                } else {
+                // Pages can't be precomputed for range queries, since they're relative to the
+                // start of the range being looked up - instead, the offset is applied to the
+                // results of the lookup, so we don't key on the page number
+                if index_type != Some(IndexType::BTreeMap) {
                     columns.push((mir::Column::named(PAGE_NUMBER_COL.clone()), offset));
                 }
             }
@@ -390,6 +460,7 @@ impl Hash for QueryGraph {
         let mut group_by = self.group_by.iter().collect::<Vec<_>>();
         group_by.sort();
         group_by.hash(state);
+        self.group_by_exprs.hash(state);
 
         let mut aggregates = self.aggregates.iter().collect::<Vec<_>>();
         aggregates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
@@ -401,11 +472,13 @@ impl Hash for QueryGraph {
         self.global_predicates.hash(state);
         self.having_predicates.hash(state);
         self.pagination.hash(state);
+        self.window_functions.hash(state);
+        self.scalar_subqueries.hash(state);
     }
 }
 
 /// Splits top level conjunctions into multiple predicates
-fn split_conjunctions<'a, T>(ces: T) -> Vec<Expr>
+pub(super) fn split_conjunctions<'a, T>(ces: T) -> Vec<Expr>
 where
     T: Iterator<Item = &'a Expr>,
 {
@@ -446,12 +519,30 @@ fn classify_conditionals(
     // Handling OR and AND expressions requires some care as there are some corner cases.
     //    a) we don't support OR expressions with predicates with placeholder parameters,
     //       because these expressions are meaningless in the Soup context.
-    //    b) we don't support OR expressions with join predicates because they are weird and
-    //       too hard.
+    //    b) we don't support OR expressions with join predicates, since they can't be used as
+    //       join keys and would have to be evaluated on the cross product of both tables.
     //    c) we don't support OR expressions between different tables (e.g table1.x = 1 OR
     //       table2.y= 42). this is a global predicate according to finkelstein algorithm
     //       and we don't support these yet.
 
+    if let Some(predicate) = full_text_predicate(ce)? {
+        // full-text search predicates are looked up in a full-text index on the searched column,
+        // so they're only supported as query parameters
+        let Expr::Literal(Literal::Placeholder(placeholder)) = predicate.query else {
+            unsupported!("Full-text search predicates are only supported as query parameters")
+        };
+        params.push(Parameter {
+            col: predicate.column.clone(),
+            op: BinaryOperator::AtAt,
+            placeholder_idx: match placeholder {
+                ItemPlaceholder::DollarNumber(idx) => Some(*idx as usize),
+                _ => None,
+            },
+            expr: None,
+        });
+        return Ok(());
+    }
+
     match ce {
         Expr::BinaryOp { op, lhs, rhs } => {
             if let Ok(op) = LogicalOp::try_from(*op) {
@@ -514,14 +605,14 @@ fn classify_conditionals(
                         global.extend(new_global);
                     }
                     LogicalOp::Or => {
-                        if !new_join.is_empty() {
-                            unsupported!("can't handle OR expressions between JOIN predicates")
-                        }
                         if !new_params.is_empty() {
                             unsupported!(
                                 "can't handle OR expressions between query parameter predicates"
                             );
                         }
+                        if !new_join.is_empty() {
+                            unsupported!("can't handle OR expressions between JOIN predicates")
+                        }
                         if new_local.keys().len() == 1 && new_global.is_empty() {
                             // OR over a single table => local predicate
                             // just checked that new_local has one entry
@@ -595,16 +686,35 @@ fn classify_conditionals(
                     // We carry placeholder numbers all the way to reader nodes so that they can be
                     // mapped to a reader key column
                     Expr::Literal(Literal::Placeholder(ref placeholder)) => {
-                        if let Expr::Column(ref lf) = **lhs {
-                            let idx = match placeholder {
-                                ItemPlaceholder::DollarNumber(idx) => Some(*idx as usize),
-                                _ => None,
-                            };
-                            params.push(Parameter {
+                        let idx = match placeholder {
+                            ItemPlaceholder::DollarNumber(idx) => Some(*idx as usize),
+                            _ => None,
+                        };
+                        match **lhs {
+                            Expr::Column(ref lf) => params.push(Parameter {
                                 col: lf.clone(),
                                 op: *op,
                                 placeholder_idx: idx,
-                            });
+                                expr: None,
+                            }),
+                            Expr::Literal(_) => {
+                                unsupported!(
+                                    "Placeholders compared against literals are not supported"
+                                )
+                            }
+                            ref expr => {
+                                // the placeholder is compared against a computed value, so we key
+                                // the reader on a column computed from the expression instead
+                                params.push(Parameter {
+                                    col: Column {
+                                        name: expr.to_string().into(),
+                                        table: None,
+                                    },
+                                    op: *op,
+                                    placeholder_idx: idx,
+                                    expr: Some(expr.clone()),
+                                })
+                            }
                         }
                     }
                     // right-hand side is a non-placeholder expr, so this is a predicate
@@ -634,7 +744,10 @@ fn classify_conditionals(
                     | Expr::Between { .. }
                     | Expr::Cast { .. }
                     | Expr::In { .. }
-                    | Expr::Variable(_) => {
+                    | Expr::Variable(_)
+                    | Expr::WindowFunction { .. }
+                    | Expr::Interval { .. }
+                    | Expr::Match { .. } => {
                         unsupported!(
                             "Unsupported right-hand side of condition expression: {}",
                             rhs
@@ -697,33 +810,30 @@ fn classify_conditionals(
     Ok(())
 }
 
-/// Convert the given `Expr`, which should be a set of AND-ed together direct
-/// comparison predicates, into a list of predicate expressions
-fn collect_join_predicates(cond: Expr, out: &mut Vec<JoinPredicate>) -> ReadySetResult<()> {
+/// Split the given join condition into the equality comparisons between columns in its top-level
+/// conjunction, which are candidates for the join key, and the rest of the conjunction, which
+/// becomes the residual predicate of the join
+fn collect_join_predicates(cond: Expr, out: &mut Vec<JoinPredicate>, residual: &mut Vec<Expr>) {
     match cond {
         Expr::BinaryOp {
             op: BinaryOperator::Equal,
             lhs,
             rhs,
-        } => {
+        } if matches!(*lhs, Expr::Column(_)) && matches!(*rhs, Expr::Column(_)) => {
             out.push(JoinPredicate {
                 left: *lhs,
                 right: *rhs,
             });
-            Ok(())
         }
         Expr::BinaryOp {
             lhs,
             op: BinaryOperator::And,
             rhs,
         } => {
-            collect_join_predicates(*lhs, out)?;
-            collect_join_predicates(*rhs, out)?;
-            Ok(())
-        }
-        _ => {
-            unsupported!("Only direct comparisons combined with AND supported for join conditions")
+            collect_join_predicates(*lhs, out, residual);
+            collect_join_predicates(*rhs, out, residual);
         }
+        cond => residual.push(cond),
     }
 }
 
@@ -781,6 +891,129 @@ fn extract_having_aggregates(
     having_predicates
 }
 
+/// Replaces all occurrences of the expressions in `qg.group_by_exprs` within the projected columns
+/// and HAVING predicates of the given query graph with references to the columns those
+/// expressions are projected as before grouping.
+fn replace_group_by_exprs(qg: &mut QueryGraph) {
+    struct GroupByExprReplacer<'a> {
+        group_by_exprs: &'a [(SqlIdentifier, Expr)],
+    }
+
+    impl<'ast, 'a> VisitorMut<'ast> for GroupByExprReplacer<'a> {
+        type Error = !;
+
+        fn visit_expr(&mut self, expr: &'ast mut Expr) -> Result<(), Self::Error> {
+            if let Some((name, _)) = self.group_by_exprs.iter().find(|(_, e)| e == expr) {
+                *expr = Expr::Column(Column {
+                    name: name.clone(),
+                    table: None,
+                });
+                Ok(())
+            } else {
+                walk_expr(self, expr)
+            }
+        }
+
+        fn visit_select_statement(
+            &mut self,
+            _: &'ast mut SelectStatement,
+        ) -> Result<(), Self::Error> {
+            // Don't walk into subqueries
+            Ok(())
+        }
+    }
+
+    let mut replacer = GroupByExprReplacer {
+        group_by_exprs: &qg.group_by_exprs,
+    };
+    for pred in qg.having_predicates.iter_mut() {
+        let _ = replacer.visit_expr(pred);
+    }
+    for col in qg.columns.iter_mut() {
+        if let OutputColumn::Expr(ExprColumn {
+            name, expression, ..
+        }) = col
+        {
+            let _ = replacer.visit_expr(expression);
+            // Expressions which were *entirely* a GROUP BY expression just project the grouped
+            // column
+            if let Expr::Column(column) = expression {
+                *col = OutputColumn::Data {
+                    alias: name.clone(),
+                    column: column.clone(),
+                };
+            }
+        }
+    }
+}
+
+/// Replaces all scalar subqueries in the SELECT list and the WHERE clause of the given statement
+/// with references to columns named `__subquery_<n>`, returning the extracted subqueries.
+///
+/// Projected fields containing a subquery without an alias are aliased to their original
+/// expression, so that their names don't change as a result of the rewrite.
+fn extract_scalar_subqueries(st: &mut SelectStatement) -> ReadySetResult<Vec<ScalarSubquery>> {
+    #[derive(Default)]
+    struct ScalarSubqueryExtractor {
+        result: Vec<ScalarSubquery>,
+    }
+
+    impl<'ast> VisitorMut<'ast> for ScalarSubqueryExtractor {
+        type Error = ReadySetError;
+
+        fn visit_expr(&mut self, expr: &'ast mut Expr) -> Result<(), Self::Error> {
+            if let Expr::NestedSelect(statement) = expr {
+                if statement.fields.len() != 1 {
+                    unsupported!("Scalar subqueries must project exactly one column");
+                }
+                let name: SqlIdentifier = format!("__subquery_{}", self.result.len()).into();
+                let col_expr = Expr::Column(Column {
+                    name: name.clone(),
+                    table: None,
+                });
+                let Expr::NestedSelect(statement) = mem::replace(expr, col_expr) else {
+                    unreachable!("Checked matches above")
+                };
+                self.result.push(ScalarSubquery {
+                    name,
+                    statement: *statement,
+                });
+                Ok(())
+            } else {
+                walk_expr(self, expr)
+            }
+        }
+
+        fn visit_select_statement(
+            &mut self,
+            _: &'ast mut SelectStatement,
+        ) -> Result<(), Self::Error> {
+            // Don't walk into subqueries
+            Ok(())
+        }
+    }
+
+    let mut extractor = ScalarSubqueryExtractor::default();
+    for field in st.fields.iter_mut() {
+        if let FieldDefinitionExpr::Expr { expr, alias } = field {
+            if iter::once(&*expr)
+                .chain(expr.recursive_subexpressions())
+                .any(|e| matches!(e, Expr::NestedSelect(_)))
+            {
+                if alias.is_none() {
+                    *alias = Some(expr.to_string().into());
+                }
+                extractor.visit_expr(expr)?;
+            }
+        }
+    }
+    if let Some(where_clause) = &mut st.where_clause {
+        extractor.visit_expr(where_clause)?;
+    }
+
+    Ok(extractor.result)
+}
+
 /// Convert limit and offset fields to an optional constant numeric limit and optional placeholder
 /// for the offset
 pub(crate) fn extract_limit_offset(
@@ -827,6 +1060,40 @@ pub(crate) fn extract_limit_offset(
     Ok(Some((limit as _, offset)))
 }
 
+/// Build a [`WindowFunction`] for the given call to `function` projected as `name`, with the
+/// given PARTITION BY and ORDER BY clauses
+fn make_window_function(
+    function: &FunctionExpr,
+    partition_by: &[Expr],
+    order_by: &[(Expr, Option<OrderType>)],
+    name: SqlIdentifier,
+) -> ReadySetResult<WindowFunction> {
+    let window_column = |expr: &Expr| -> ReadySetResult<Column> {
+        match expr {
+            Expr::Column(c) => Ok(c.clone()),
+            _ => unsupported!("Only column references are currently supported in window clauses"),
+        }
+    };
+
+    Ok(WindowFunction {
+        function: function.clone(),
+        partition_by: partition_by
+            .iter()
+            .map(window_column)
+            .collect::<ReadySetResult<_>>()?,
+        order_by: order_by
+            .iter()
+            .map(|(expr, ot)| {
+                Ok((
+                    window_column(expr)?,
+                    ot.unwrap_or(OrderType::OrderAscending),
+                ))
+            })
+            .collect::<ReadySetResult<_>>()?,
+        name,
+    })
+}
+
 fn table_expr_name(table_expr: &TableExpr) -> ReadySetResult<Relation> {
     match &table_expr.inner {
         TableExprInner::Table(t) => Ok(t.clone()),
@@ -843,6 +1110,17 @@ fn table_expr_name(table_expr: &TableExpr) -> ReadySetResult<Relation> {
 pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
     let mut qg = QueryGraph::new();
 
+    // 0. Pull out any scalar subqueries, replacing them with references to the columns they'll be
+    // projected as once they've been joined to the rest of the query.
+    let mut st = st.clone();
+    qg.scalar_subqueries = extract_scalar_subqueries(&mut st)?;
+    let st = &st;
+    let scalar_subquery_names = qg
+        .scalar_subqueries
+        .iter()
+        .map(|sq| sq.name.clone())
+        .collect::<HashSet<_>>();
+
     // a handy closure for making new relation nodes
     let new_node = |rel: Relation,
                     preds: Vec<Expr>,
@@ -867,6 +1145,8 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                             expr: Expr::Column(c),
                             ..
                         } => match c.table.as_ref() {
+                            // References to scalar subqueries aren't associated with a relation
+                            None if scalar_subquery_names.contains(&c.name) => None,
                             None => internal!("No table name set for column {} on {}", c.name, rel),
                             Some(t) => {
                                 if rel == *t {
@@ -977,6 +1257,7 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
         // will be defined by join constraint
         let left_table;
         let right_table;
+        let join_residual;
 
         let join_preds = match &jc.constraint {
             JoinConstraint::On(cond) => {
@@ -988,7 +1269,8 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                     cond.referred_tables().into_iter().collect();
 
                 let mut join_preds = vec![];
-                collect_join_predicates(cond.clone(), &mut join_preds)?;
+                let mut residual = vec![];
+                collect_join_predicates(cond.clone(), &mut join_preds, &mut residual);
 
                 if tables_mentioned.len() == 2 {
                     // tables can appear in any order in the join predicate, but
@@ -1010,7 +1292,8 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                     unsupported!("more than 2 tables mentioned in join condition!");
                 };
 
-                for pred in join_preds.iter_mut() {
+                let mut key_preds = Vec::with_capacity(join_preds.len());
+                for mut pred in join_preds {
                     // the condition tree might specify tables in opposite order to
                     // their join order in the query; if so, flip them
                     // TODO(malte): this only deals with simple, flat join
@@ -1023,14 +1306,48 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                         Expr::Column(f) => f,
                         ref x => unsupported!("join condition not supported: {:?}", x),
                     };
-                    if *l.table.as_ref().ok_or_else(|| no_table_for_col())? == right_table
-                        && *r.table.as_ref().ok_or_else(|| no_table_for_col())? == left_table
-                    {
+                    let l_table = l.table.as_ref().ok_or_else(no_table_for_col)?;
+                    let r_table = r.table.as_ref().ok_or_else(no_table_for_col)?;
+                    if left_table != right_table && l_table == r_table {
+                        // comparison between two columns of the same table can't be part of the
+                        // join key
+                        residual.push(Expr::BinaryOp {
+                            lhs: Box::new(pred.left),
+                            op: BinaryOperator::Equal,
+                            rhs: Box::new(pred.right),
+                        });
+                        continue;
+                    }
+                    if *l_table == right_table && *r_table == left_table {
                         mem::swap(&mut pred.left, &mut pred.right);
                     }
+                    key_preds.push(pred);
+                }
+
+                if key_preds.is_empty() {
+                    // Without an equality between columns of both tables there's nothing to key
+                    // the join on, so it would have to be computed as a cross join
+                    unsupported!(
+                        "Join condition must include an equality between columns of both joined \
+                         tables: {}",
+                        cond
+                    );
+                }
+
+                if jc.operator.is_inner_join() {
+                    // for inner joins, filtering the result of the join is equivalent to filtering
+                    // within the join
+                    qg.global_predicates.extend(residual);
+                    join_residual = None;
+                } else {
+                    join_residual = residual.into_iter().reduce(|lhs, rhs| Expr::BinaryOp {
+                        lhs: Box::new(lhs),
+                        op: BinaryOperator::And,
+                        rhs: Box::new(rhs),
+                    });
                 }
 
-                join_preds
+                key_preds
             }
             JoinConstraint::Using(cols) => {
                 invariant_eq!(cols.len(), 1);
@@ -1039,6 +1356,7 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
 
                 left_table = prev_table.clone();
                 right_table = rhs_relation.clone();
+                join_residual = None;
 
                 vec![JoinPredicate {
                     left: col_expr(&left_table, &col.name),
@@ -1048,19 +1366,47 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
             JoinConstraint::Empty => {
                 left_table = prev_table.clone();
                 right_table = rhs_relation.clone();
+                join_residual = None;
                 // An empty predicate indicates a cartesian product is expected
                 vec![]
             }
         };
 
+        // `a RIGHT JOIN b` is equivalent to `b LEFT JOIN a`, so flip the sides of right joins
+        let (left_table, right_table, join_preds) = if jc.operator.is_right_join() {
+            let join_preds = join_preds
+                .into_iter()
+                .map(|jp| JoinPredicate {
+                    left: jp.right,
+                    right: jp.left,
+                })
+                .collect();
+            (right_table, left_table, join_preds)
+        } else {
+            (left_table, right_table, join_preds)
+        };
+
         // add edge for join
         // FIXME(eta): inefficient cloning!
         if let std::collections::hash_map::Entry::Vacant(e) =
             qg.edges.entry((left_table.clone(), right_table.clone()))
         {
             e.insert(match jc.operator {
-                JoinOperator::LeftJoin | JoinOperator::LeftOuterJoin => {
-                    QueryGraphEdge::LeftJoin { on: join_preds }
+                JoinOperator::LeftJoin
+                | JoinOperator::LeftOuterJoin
+                | JoinOperator::RightJoin
+                | JoinOperator::RightOuterJoin => QueryGraphEdge::LeftJoin {
+                    on: join_preds,
+                    residual: join_residual,
+                },
+                JoinOperator::FullJoin | JoinOperator::FullOuterJoin => {
+                    if join_residual.is_some() {
+                        unsupported!(
+                            "Only equality comparisons between columns are supported for full \
+                             outer join conditions"
+                        );
+                    }
+                    QueryGraphEdge::FullJoin { on: join_preds }
                 }
                 JoinOperator::Join | JoinOperator::InnerJoin => {
                     QueryGraphEdge::Join { on: join_preds }
@@ -1147,11 +1493,30 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
         //    implementing the query (unlike in a traditional query plan, where the predicates on
         //    parameters might be evaluated sooner).
         for param in query_parameters.into_iter() {
-            match param.col.table {
+            // parameters compared against an expression belong to the (single) relation that the
+            // expression refers to
+            let expr_table = match &param.expr {
+                Some(expr) => {
+                    let tables = expr
+                        .referred_columns()
+                        .map(|c| c.table.as_ref())
+                        .collect::<HashSet<_>>();
+                    if tables.len() != 1 {
+                        unsupported!(
+                            "Placeholders can only be compared against expressions referring to \
+                             exactly one table (got {})",
+                            expr
+                        );
+                    }
+                    tables.into_iter().next().flatten().cloned()
+                }
+                None => None,
+            };
+            match param.col.table.as_ref().or(expr_table.as_ref()) {
                 None => {
                     unsupported!("each parameter's column must have an associated table! (no such column \"{}\")", param.col);
                 }
-                Some(ref table) => {
+                Some(table) => {
                     let rel = qg.relations.get_mut(table).ok_or_else(|| {
                         invalid_err!(
                             "Column {} references non-existent table {}",
@@ -1159,7 +1524,9 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                             table
                         )
                     })?;
-                    if !rel.columns.contains(&param.col) {
+                    // computed parameter columns are projected separately, since they don't exist
+                    // in the relation itself
+                    if param.expr.is_none() && !rel.columns.contains(&param.col) {
                         rel.columns.push(param.col.clone());
                     }
                     // the parameter column is included in the projected columns of the output, but
@@ -1171,7 +1538,7 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
         }
 
         // 4. Add global predicates
-        qg.global_predicates = global_predicates;
+        qg.global_predicates.extend(global_predicates);
     }
 
     // Add HAVING predicates and aggregates. Note that unlike below for selected columns, we don't
@@ -1221,6 +1588,35 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
                             },
                         })
                     }
+                    Expr::WindowFunction {
+                        function,
+                        partition_by,
+                        order_by,
+                    } => {
+                        // Like aggregates, the values of window functions will have already been
+                        // projected by the time we project the result set columns
+                        qg.columns.push(OutputColumn::Data {
+                            alias: alias.clone().unwrap_or_else(|| name.clone()),
+                            column: Column {
+                                name: name.clone(),
+                                table: None,
+                            },
+                        });
+                        qg.window_functions.push(make_window_function(
+                            function,
+                            partition_by,
+                            order_by,
+                            name,
+                        )?);
+                    }
+                    _ if expr
+                        .recursive_subexpressions()
+                        .any(|e| matches!(e, Expr::WindowFunction { .. })) =>
+                    {
+                        unsupported!(
+                            "Window functions nested within other expressions are not supported"
+                        )
+                    }
                     _ => {
                         let mut expr = expr.clone();
                         let aggs = map_aggregates(&mut expr);
@@ -1238,21 +1634,41 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
     }
 
     if let Some(group_by_clause) = &st.group_by {
-        qg.group_by.extend(
-            group_by_clause
-                .fields
-                .iter()
-                .map(|f| match f {
-                    FieldReference::Numeric(_) => {
-                        internal!("Numeric field references should have been removed")
-                    }
-                    FieldReference::Expr(Expr::Column(c)) => Ok(c.clone()),
-                    FieldReference::Expr(_) => {
-                        unsupported!("Only column references are currently supported in GROUP BY")
+        for field in &group_by_clause.fields {
+            match field {
+                FieldReference::Numeric(_) => {
+                    internal!("Numeric field references should have been removed")
+                }
+                FieldReference::Expr(Expr::Column(c)) => {
+                    qg.group_by.insert(c.clone());
+                }
+                FieldReference::Expr(expr)
+                    if expr
+                        .recursive_subexpressions()
+                        .chain(iter::once(expr))
+                        .any(|e| matches!(e, Expr::Call(f) if is_aggregate(f))) =>
+                {
+                    unsupported!("Aggregates are not allowed in GROUP BY")
+                }
+                FieldReference::Expr(expr) => {
+                    // Expressions are projected as a column named after the expression before
+                    // grouping, and then grouped on that column
+                    let name: SqlIdentifier = expr.to_string().into();
+                    if !qg.group_by_exprs.iter().any(|(n, _)| *n == name) {
+                        qg.group_by_exprs.push((name.clone(), expr.clone()));
                     }
-                })
-                .collect::<ReadySetResult<Vec<_>>>()?,
-        );
+                    qg.group_by.insert(Column { name, table: None });
+                }
+            }
+        }
+    }
+
+    if !qg.window_functions.is_empty() && (!qg.aggregates.is_empty() || !qg.group_by.is_empty()) {
+        unsupported!("Window functions are not yet supported in aggregated queries");
+    }
+
+    if !qg.scalar_subqueries.is_empty() && (!qg.aggregates.is_empty() || !qg.group_by.is_empty()) {
+        unsupported!("Scalar subqueries are not yet supported in aggregated queries");
     }
 
     if let Some(ref order) = st.order {
@@ -1304,6 +1720,12 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
             })
     }
 
+    // Now that all the projected columns and HAVING predicates are known, make any references they
+    // contain to expressions in the GROUP BY clause refer to the grouped columns instead
+    if !qg.group_by_exprs.is_empty() {
+        replace_group_by_exprs(&mut qg);
+    }
+
     // Extract pagination parameters
     if let Some((limit, offset)) = extract_limit_offset(&st.limit, &st.offset)? {
         qg.pagination = Some(Pagination {
@@ -1357,7 +1779,9 @@ pub fn to_query_graph(st: &SelectStatement) -> ReadySetResult<QueryGraph> {
 #[cfg(test)]
 mod tests {
     use assert_unordered::assert_eq_unordered;
-    use nom_sql::{parse_query, parse_select_statement, Dialect, FunctionExpr, SqlQuery};
+    use nom_sql::{
+        parse_expr, parse_query, parse_select_statement, Dialect, FunctionExpr, SqlQuery,
+    };
 
     use super::*;
 
@@ -1559,6 +1983,247 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inner_join_with_non_equality_condition() {
+        let qg = make_query_graph(
+            "SELECT t1.x FROM t1 JOIN t2 ON t1.id = t2.t1_id AND t2.valid_from <= t1.ts",
+        );
+        assert_eq!(
+            qg.edges[&("t1".into(), "t2".into())],
+            QueryGraphEdge::Join {
+                on: vec![JoinPredicate {
+                    left: Expr::Column("t1.id".into()),
+                    right: Expr::Column("t2.t1_id".into()),
+                }]
+            }
+        );
+        assert_eq!(
+            qg.global_predicates,
+            vec![Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("t2.valid_from".into())),
+                op: BinaryOperator::LessOrEqual,
+                rhs: Box::new(Expr::Column("t1.ts".into())),
+            }]
+        );
+    }
+
+    #[test]
+    fn join_with_or_condition() {
+        for sql in [
+            "SELECT t1.x FROM t1 JOIN t2 ON t1.x = t2.x OR t1.y = t2.y",
+            "SELECT t1.x FROM t1 LEFT JOIN t2 ON t1.x = t2.x OR t1.y = t2.y",
+            "SELECT t1.x FROM t1, t2 WHERE t1.x = t2.x OR t1.y = t2.y",
+        ] {
+            let query = parse_select_statement(Dialect::MySQL, sql).unwrap();
+            to_query_graph(&query).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn join_without_equi_join_key() {
+        let query = parse_select_statement(
+            Dialect::MySQL,
+            "SELECT t1.x FROM t1 LEFT JOIN t2 ON t2.valid_from <= t1.ts",
+        )
+        .unwrap();
+        to_query_graph(&query).unwrap_err();
+    }
+
+    #[test]
+    fn left_join_with_residual() {
+        let qg = make_query_graph(
+            "SELECT t1.x FROM t1 LEFT JOIN t2 ON t2.t1_id = t1.id AND t2.valid_from <= t1.ts",
+        );
+        assert_eq!(
+            qg.edges[&("t1".into(), "t2".into())],
+            QueryGraphEdge::LeftJoin {
+                on: vec![JoinPredicate {
+                    left: Expr::Column("t1.id".into()),
+                    right: Expr::Column("t2.t1_id".into()),
+                }],
+                residual: Some(Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("t2.valid_from".into())),
+                    op: BinaryOperator::LessOrEqual,
+                    rhs: Box::new(Expr::Column("t1.ts".into())),
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn right_join_becomes_left_join() {
+        let qg = make_query_graph("SELECT t1.x, t2.y FROM t1 RIGHT JOIN t2 ON t1.id = t2.t1_id");
+        assert!(!qg.edges.contains_key(&("t1".into(), "t2".into())));
+        assert_eq!(
+            qg.edges[&("t2".into(), "t1".into())],
+            QueryGraphEdge::LeftJoin {
+                on: vec![JoinPredicate {
+                    left: Expr::Column("t2.t1_id".into()),
+                    right: Expr::Column("t1.id".into()),
+                }],
+                residual: None,
+            }
+        );
+    }
+
+    #[test]
+    fn full_join() {
+        let qg =
+            make_query_graph("SELECT t1.x, t2.y FROM t1 FULL OUTER JOIN t2 ON t1.id = t2.t1_id");
+        assert_eq!(
+            qg.edges[&("t1".into(), "t2".into())],
+            QueryGraphEdge::FullJoin {
+                on: vec![JoinPredicate {
+                    left: Expr::Column("t1.id".into()),
+                    right: Expr::Column("t2.t1_id".into()),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn scalar_subquery_in_select() {
+        let query = parse_select_statement(
+            Dialect::MySQL,
+            "SELECT p.id, (SELECT count(*) FROM c WHERE c.post_id = p.id) FROM p",
+        )
+        .unwrap();
+        let qg = to_query_graph(&query).unwrap();
+
+        assert_eq!(
+            qg.relations.keys().cloned().collect::<HashSet<_>>(),
+            HashSet::from(["p".into()])
+        );
+        assert_eq!(qg.scalar_subqueries.len(), 1);
+        let subquery = &qg.scalar_subqueries[0];
+        assert_eq!(subquery.name, "__subquery_0");
+        assert!(subquery.is_count());
+
+        let FieldDefinitionExpr::Expr { expr, .. } = &query.fields[1] else {
+            panic!("Expected an expression")
+        };
+        assert_eq!(
+            qg.columns[1],
+            OutputColumn::Data {
+                alias: expr.to_string().into(),
+                column: Column {
+                    name: "__subquery_0".into(),
+                    table: None,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn scalar_subquery_in_where() {
+        let qg = make_query_graph(
+            "SELECT p.id FROM p WHERE p.score > (SELECT max(q.score) FROM q WHERE q.x = p.x)",
+        );
+
+        assert_eq!(qg.scalar_subqueries.len(), 1);
+        assert!(!qg.scalar_subqueries[0].is_count());
+        assert_eq!(
+            qg.global_predicates,
+            vec![Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("p.score".into())),
+                op: BinaryOperator::Greater,
+                rhs: Box::new(Expr::Column("__subquery_0".into())),
+            }]
+        );
+    }
+
+    #[test]
+    fn scalar_subquery_in_aggregated_query() {
+        let query = parse_select_statement(
+            Dialect::MySQL,
+            "SELECT count(*), (SELECT max(q.score) FROM q) FROM p",
+        )
+        .unwrap();
+        to_query_graph(&query).unwrap_err();
+    }
+
+    #[test]
+    fn parameter_compared_against_expression() {
+        let qg = make_query_graph("SELECT t.id FROM t WHERE lower(t.email) = ?");
+        let params = qg.parameters();
+        assert_eq!(params.len(), 1);
+        assert_eq!(
+            params[0].col,
+            Column {
+                name: "lower(`t`.`email`)".into(),
+                table: None,
+            }
+        );
+        assert_eq!(
+            params[0].expr,
+            Some(parse_expr(Dialect::MySQL, "lower(t.email)").unwrap())
+        );
+        assert!(qg.relations[&Relation::from("t")]
+            .columns
+            .iter()
+            .all(|c| c.table.is_some()));
+    }
+
+    #[test]
+    fn parameter_compared_against_multi_table_expression() {
+        let query = parse_select_statement(
+            Dialect::MySQL,
+            "SELECT t1.id FROM t1 JOIN t2 ON t1.id = t2.id WHERE t1.x + t2.y = ?",
+        )
+        .unwrap();
+        to_query_graph(&query).unwrap_err();
+    }
+
+    #[test]
+    fn group_by_expression() {
+        let qg =
+            make_query_graph("SELECT lower(t.email), count(t.id) FROM t GROUP BY lower(t.email)");
+        let grouped_col = Column {
+            name: "lower(`t`.`email`)".into(),
+            table: None,
+        };
+        assert_eq!(qg.group_by, HashSet::from([grouped_col.clone()]));
+        assert_eq!(qg.group_by_exprs.len(), 1);
+        assert_eq!(qg.group_by_exprs[0].0, grouped_col.name);
+        assert_eq!(
+            qg.columns[0],
+            OutputColumn::Data {
+                alias: "lower(`t`.`email`)".into(),
+                column: grouped_col,
+            }
+        );
+    }
+
+    #[test]
+    fn group_by_expression_within_projected_expression() {
+        let qg = make_query_graph(
+            "SELECT upper(lower(t.email)) AS e, count(t.id) FROM t GROUP BY lower(t.email)",
+        );
+        match &qg.columns[0] {
+            OutputColumn::Expr(ExprColumn { expression, .. }) => assert_eq!(
+                *expression,
+                Expr::Call(FunctionExpr::Call {
+                    name: "upper".into(),
+                    arguments: vec![Expr::Column(Column {
+                        name: "lower(`t`.`email`)".into(),
+                        table: None,
+                    })],
+                })
+            ),
+            col => panic!("Expected an expression column, got {:?}", col),
+        }
+    }
+
+    #[test]
+    fn aggregate_in_group_by() {
+        let query = parse_select_statement(
+            Dialect::MySQL,
+            "SELECT count(t.id) FROM t GROUP BY count(t.id)",
+        )
+        .unwrap();
+        to_query_graph(&query).unwrap_err();
+    }
+
     mod view_key {
         use super::*;
 
@@ -1846,5 +2511,27 @@ mod tests {
                 ]
             );
         }
+
+        #[test]
+        fn paginated_range() {
+            let qg = make_query_graph(
+                "SELECT t.x FROM t WHERE t.x > $1 ORDER BY t.x ASC LIMIT 3 OFFSET $2",
+            );
+            let key = qg
+                .view_key(&mir::Config {
+                    allow_paginate: true,
+                    ..Default::default()
+                })
+                .unwrap();
+
+            assert_eq!(key.index_type, IndexType::BTreeMap);
+            assert_eq!(
+                key.columns,
+                vec![(
+                    mir::Column::new(Some("t"), "x"),
+                    ViewPlaceholder::OneToOne(1)
+                )]
+            );
+        }
     }
 }
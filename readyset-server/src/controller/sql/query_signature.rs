@@ -75,12 +75,19 @@ impl Signature for QueryGraph {
 
         for e in self.edges.values() {
             match e {
-                QueryGraphEdge::Join { on } | QueryGraphEdge::LeftJoin { on } => {
+                QueryGraphEdge::Join { on } | QueryGraphEdge::FullJoin { on } => {
                     on.iter()
                         .flat_map(|p| vec![&p.left, &p.right])
                         .flat_map(|p| p.referred_columns())
                         .for_each(&mut record_column);
                 }
+                QueryGraphEdge::LeftJoin { on, residual } => {
+                    on.iter()
+                        .flat_map(|p| vec![&p.left, &p.right])
+                        .chain(residual)
+                        .flat_map(|p| p.referred_columns())
+                        .for_each(&mut record_column);
+                }
             }
         }
 
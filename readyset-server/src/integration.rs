@@ -4011,6 +4011,67 @@ async fn union_all_basic() {
     assert_eq!(result_ids, expected_ids);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn recursive_cte() {
+    use itertools::sorted;
+
+    let mut g = start_simple_unsharded("recursive_cte").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE employees (id INTEGER PRIMARY KEY, manager_id INTEGER);
+         CREATE CACHE reports FROM
+         WITH RECURSIVE r AS (
+             SELECT employees.id FROM employees WHERE employees.manager_id = 1
+             UNION ALL
+             SELECT e.id FROM employees e JOIN r ON e.manager_id = r.id
+         ) SELECT r.id FROM r;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut employees = g.table("employees").await.unwrap();
+    employees
+        .insert_many(
+            [(2, 1), (3, 1), (4, 2), (5, 9)]
+                .into_iter()
+                .map(|(id, manager_id): (i32, i32)| vec![id.into(), manager_id.into()]),
+        )
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let mut reports = g.view("reports").await.unwrap();
+    let rows = reports.lookup(&[0.into()], true).await.unwrap().into_vec();
+    assert_eq!(
+        sorted(rows).collect::<Vec<_>>(),
+        vec![vec![DfValue::from(2)], vec![3.into()], vec![4.into()]]
+    );
+
+    // Reports of reports are picked up transitively
+    employees.insert(vec![6.into(), 4.into()]).await.unwrap();
+    sleep().await;
+    let rows = reports.lookup(&[0.into()], true).await.unwrap().into_vec();
+    assert_eq!(
+        sorted(rows).collect::<Vec<_>>(),
+        vec![
+            vec![DfValue::from(2)],
+            vec![3.into()],
+            vec![4.into()],
+            vec![6.into()]
+        ]
+    );
+
+    // Removing a manager removes everyone only reachable through them
+    employees.delete(vec![2.into()]).await.unwrap();
+    sleep().await;
+    let rows = reports.lookup(&[0.into()], true).await.unwrap().into_vec();
+    assert_eq!(rows, vec![vec![DfValue::from(3)]]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn between() {
     let mut g = start_simple_unsharded("between_query").await;
@@ -5512,6 +5573,63 @@ async fn left_join_null() {
     assert_eq!(num_res, 2);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn scalar_subquery_count() {
+    let mut g = start_simple_unsharded("scalar_subquery_count").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE posts (id int, title text);
+             CREATE TABLE comments (id int, post_id int);
+             CREATE CACHE post_comments FROM
+             SELECT p.*, (SELECT COUNT(*) FROM comments c WHERE c.post_id = p.id) AS comment_count
+             FROM posts p;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut posts = g.table("posts").await.unwrap();
+    let mut comments = g.table("comments").await.unwrap();
+    let mut q = g.view("post_comments").await.unwrap();
+
+    posts
+        .insert_many(vec![
+            vec![DfValue::from(1), DfValue::from("first")],
+            vec![DfValue::from(2), DfValue::from("second")],
+        ])
+        .await
+        .unwrap();
+    comments
+        .insert_many(vec![
+            vec![DfValue::from(1), DfValue::from(1)],
+            vec![DfValue::from(2), DfValue::from(1)],
+        ])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let res = q
+        .lookup(&[0.into()], true)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| {
+            (
+                get_col!(q, r, "id", i32),
+                get_col!(q, r, "comment_count").clone(),
+            )
+        })
+        .sorted()
+        .collect::<Vec<_>>();
+
+    // Posts without any comments have a count of 0, not NULL
+    assert_eq!(res, vec![(1, DfValue::from(2)), (2, DfValue::from(0))]);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn overlapping_indices() {
     let mut g = start_simple_unsharded("overlapping_indices").await;
@@ -319,7 +319,8 @@ async fn check_query(
                         // These types are PostgreSQL specific
                         | DfValue::BitVector(_)
                         | DfValue::PassThrough(_)
-                        | DfValue::Array(_) => {
+                        | DfValue::Array(_)
+                        | DfValue::Range(_) => {
                             unimplemented!()
                         }
                     })
@@ -4,8 +4,8 @@ use std::mem;
 use itertools::Itertools;
 use nom_sql::analysis::visit_mut::{self, walk_select_statement, VisitorMut};
 use nom_sql::{
-    Column, CommonTableExpr, JoinRightSide, Relation, SelectStatement, SqlIdentifier, SqlQuery,
-    TableExpr, TableExprInner,
+    Column, CommonTableExpr, JoinRightSide, RecursiveTerm, Relation, SelectStatement,
+    SqlIdentifier, SqlQuery, TableExpr, TableExprInner,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -28,6 +28,9 @@ pub enum TableAliasRewrite {
         from: SqlIdentifier,
         to_view: Relation,
         for_statement: Box<SelectStatement>, // box for perf
+        /// If the CTE was declared with `WITH RECURSIVE`, its recursive term, which still refers
+        /// to the CTE as `from`
+        recursive_term: Option<Box<RecursiveTerm>>,
     },
 }
 
@@ -68,48 +71,48 @@ impl<'ast, 'a> VisitorMut<'ast> for RemoveAliasesVisitor<'a> {
             .into_group_map();
 
         // Use the map of unique table references to identify any necessary alias rewrites.
-        let table_alias_rewrites: Vec<TableAliasRewrite> =
-            table_refs
-                .into_iter()
-                .flat_map(|(table, aliases)| match aliases[..] {
-                    [None] => {
-                        // The table is never referred to by an alias. No rewrite is needed.
-                        vec![]
-                    }
-
-                    [Some(ref alias)] => {
-                        // The table is only ever referred to using one specific alias. Rewrite
-                        // to remove the alias and refer to the table itself.
-                        vec![TableAliasRewrite::Table {
+        let table_alias_rewrites: Vec<TableAliasRewrite> = table_refs
+            .into_iter()
+            .flat_map(|(table, aliases)| match aliases[..] {
+                [None] => {
+                    // The table is never referred to by an alias. No rewrite is needed.
+                    vec![]
+                }
+
+                [Some(ref alias)] => {
+                    // The table is only ever referred to using one specific alias. Rewrite
+                    // to remove the alias and refer to the table itself.
+                    vec![TableAliasRewrite::Table {
+                        from: alias.clone(),
+                        to_table: table,
+                    }]
+                }
+
+                _ => aliases
+                    .into_iter()
+                    .flatten()
+                    .map(|alias| {
+                        // The alias is one among multiple distinct references to the
+                        // table. Create a globally unique view name, derived from the
+                        // query name, and rewrite to remove the alias and refer to this
+                        // view.
+                        TableAliasRewrite::View {
                             from: alias.clone(),
-                            to_table: table,
-                        }]
-                    }
-
-                    _ => aliases
-                        .into_iter()
-                        .flatten()
-                        .map(|alias| {
-                            // The alias is one among multiple distinct references to the
-                            // table. Create a globally unique view name, derived from the
-                            // query name, and rewrite to remove the alias and refer to this
-                            // view.
-                            TableAliasRewrite::View {
-                                from: alias.clone(),
-                                to_view: format!("__{}__{}", self.query_name, alias).into(),
-                                for_table: table.clone(),
-                            }
-                        })
-                        .collect(),
-                })
-                .chain(select_statement.ctes.drain(..).map(
-                    |CommonTableExpr { name, statement }| TableAliasRewrite::Cte {
-                        to_view: format!("__{}__{}", self.query_name, name).into(),
-                        from: name,
-                        for_statement: Box::new(statement),
-                    },
-                ))
-                .collect();
+                            to_view: format!("__{}__{}", self.query_name, alias).into(),
+                            for_table: table.clone(),
+                        }
+                    })
+                    .collect(),
+            })
+            .chain(select_statement.ctes.drain(..).map(|cte: CommonTableExpr| {
+                TableAliasRewrite::Cte {
+                    to_view: format!("__{}__{}", self.query_name, cte.name).into(),
+                    from: cte.name,
+                    for_statement: Box::new(cte.statement),
+                    recursive_term: cte.recursive_term.map(Box::new),
+                }
+            }))
+            .collect();
 
         // Extract remappings for FROM and JOIN table references from the alias rewrites.
         let new_table_remap = self
@@ -522,12 +525,56 @@ mod tests {
                 {
                     SqlQuery::Select(stmt) => Box::new(stmt),
                     _ => panic!(),
-                }
+                },
+                recursive_term: None,
             }]
         );
         assert_eq!(res, expected, "\n\n   {}\n!= {}", res, expected);
     }
 
+    #[test]
+    fn recursive_cte() {
+        let mut res = parse_query(
+            Dialect::MySQL,
+            "WITH RECURSIVE reports AS (
+                 SELECT e.id FROM employees e WHERE e.id = 1
+                 UNION ALL
+                 SELECT e.id FROM employees e JOIN reports r ON e.manager_id = r.id
+             )
+             SELECT reports.id FROM reports;",
+        )
+        .unwrap();
+        let expected = parse_query(
+            Dialect::MySQL,
+            "SELECT __query__reports.id FROM __query__reports;",
+        )
+        .unwrap();
+        let rewritten = res.rewrite_table_aliases("query");
+        assert_eq!(rewritten.len(), 1);
+        match &rewritten[0] {
+            TableAliasRewrite::Cte {
+                from,
+                to_view,
+                recursive_term: Some(term),
+                ..
+            } => {
+                assert_eq!(from, "reports");
+                assert_eq!(to_view, &Relation::from("__query__reports"));
+                assert!(term.all);
+                assert_eq!(
+                    SqlQuery::Select(term.statement.clone()),
+                    parse_query(
+                        Dialect::MySQL,
+                        "SELECT e.id FROM employees e JOIN reports r ON e.manager_id = r.id"
+                    )
+                    .unwrap()
+                );
+            }
+            r => panic!("Unexpected rewrite: {:?}", r),
+        }
+        assert_eq!(res, expected, "\n\n   {}\n!= {}", res, expected);
+    }
+
     #[test]
     fn schemas() {
         rewrites_to!(
@@ -196,7 +196,29 @@ impl<'ast> VisitorMut<'ast> for AnonymizeVisitor<'_> {
             | nom_sql::ShowStatement::CachedQueries(..)
             | nom_sql::ShowStatement::ProxiedQueries(..)
             | nom_sql::ShowStatement::ReadySetStatus
-            | nom_sql::ShowStatement::ReadySetVersion => {}
+            | nom_sql::ShowStatement::ReadySetVersion
+            | nom_sql::ShowStatement::RoutingRules => {}
+        }
+        Ok(())
+    }
+
+    fn visit_create_routing_rule_statement(
+        &mut self,
+        create_routing_rule_statement: &'ast mut nom_sql::CreateRoutingRuleStatement,
+    ) -> Result<(), Self::Error> {
+        let nom_sql::CreateRoutingRuleStatement {
+            user,
+            database,
+            tables,
+            pattern,
+            hint,
+            ..
+        } = create_routing_rule_statement;
+        for string in [user, database, pattern, hint].into_iter().flatten() {
+            self.anonymize_string(string)
+        }
+        for table in tables {
+            self.visit_table(table)?;
         }
         Ok(())
     }
@@ -1,5 +1,5 @@
 use nom_sql::analysis::visit_mut::{self, VisitorMut};
-use nom_sql::{Column, CreateTableStatement, Relation};
+use nom_sql::{Column, ColumnConstraint, CreateTableOption, CreateTableStatement, Relation};
 
 #[derive(Debug, Default)]
 struct CreateTableColumnsVisitor {
@@ -14,6 +14,36 @@ impl<'ast> VisitorMut<'ast> for CreateTableColumnsVisitor {
         create_table_statement: &'ast mut CreateTableStatement,
     ) -> Result<(), Self::Error> {
         self.table = Some(create_table_statement.table.clone());
+
+        // Propagate the table's default collation to any text columns which don't specify their
+        // own collation
+        if let Some(collation) =
+            create_table_statement
+                .options
+                .iter()
+                .rev()
+                .find_map(|opt| match opt {
+                    CreateTableOption::Collate(collation) => Some(collation.to_string()),
+                    _ => None,
+                })
+        {
+            for field in create_table_statement
+                .fields
+                .iter_mut()
+                .filter(|f| f.sql_type.is_any_text())
+            {
+                if !field
+                    .constraints
+                    .iter()
+                    .any(|c| matches!(c, ColumnConstraint::Collation(_)))
+                {
+                    field
+                        .constraints
+                        .push(ColumnConstraint::Collation(collation.clone()));
+                }
+            }
+        }
+
         visit_mut::walk_create_table_statement(self, create_table_statement)
     }
 
@@ -59,4 +89,21 @@ mod tests {
         .unwrap();
         assert_eq!(orig.normalize_create_table_columns(), expected);
     }
+
+    #[test]
+    fn table_collation() {
+        let orig = parse_create_table(
+            Dialect::MySQL,
+            "CREATE TABLE t (a int, b text, c varchar(10) COLLATE utf8mb4_bin) \
+             COLLATE=utf8mb4_general_ci",
+        )
+        .unwrap();
+        let expected = parse_create_table(
+            Dialect::MySQL,
+            "CREATE TABLE t (t.a int, t.b text COLLATE utf8mb4_general_ci, \
+             t.c varchar(10) COLLATE utf8mb4_bin) COLLATE=utf8mb4_general_ci",
+        )
+        .unwrap();
+        assert_eq!(orig.normalize_create_table_columns(), expected);
+    }
 }
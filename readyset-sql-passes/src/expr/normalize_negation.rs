@@ -39,6 +39,12 @@ fn negate_expr(expr: &mut Expr) -> bool {
                 | BinaryOperator::HashSubtract
                 | BinaryOperator::Multiply
                 | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::BitwiseAnd
+                | BinaryOperator::BitwiseOr
+                | BinaryOperator::ShiftLeft
+                | BinaryOperator::ShiftRight
+                | BinaryOperator::Caret
                 | BinaryOperator::DoublePipe
                 | BinaryOperator::Arrow1
                 | BinaryOperator::Arrow2
@@ -48,7 +54,9 @@ fn negate_expr(expr: &mut Expr) -> bool {
                 | BinaryOperator::QuestionMarkPipe
                 | BinaryOperator::QuestionMarkAnd
                 | BinaryOperator::AtArrowRight
-                | BinaryOperator::AtArrowLeft => {
+                | BinaryOperator::AtArrowLeft
+                | BinaryOperator::DoubleAmpersand
+                | BinaryOperator::AtAt => {
                     // Note we return true in this case to bypass the *op = ... above
                     *expr = Expr::UnaryOp {
                         op: UnaryOperator::Not,
@@ -91,10 +91,15 @@ impl<'ast, 'schema> VisitorMut<'ast> for ResolveSchemaVisitor<'schema> {
         &mut self,
         cte: &'ast mut nom_sql::CommonTableExpr,
     ) -> Result<(), Self::Error> {
-        // Walk first, since the alias for the CTE is not visible inside the CTE itself (TODO:
-        // except in the case of `WITH RECURSIVE`, which we don't even parse yet).
-        visit_mut::walk_common_table_expr(self, cte)?;
+        // Walk the statement first, since the alias for the CTE is not visible inside the CTE
+        // itself - except in the recursive term of a `WITH RECURSIVE` CTE, which refers back to
+        // the CTE by name.
+        self.visit_sql_identifier(&mut cte.name)?;
+        self.visit_select_statement(&mut cte.statement)?;
         self.insert_alias(cte.name.clone());
+        if let Some(term) = &mut cte.recursive_term {
+            self.visit_select_statement(&mut term.statement)?;
+        }
         Ok(())
     }
 
@@ -278,6 +283,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ignores_cte_alias_reference_in_recursive_term() {
+        select_rewrites_to(
+            "with recursive t2 as (select * from t1 union all select t1.* from t1 join t2 on t1.x = t2.y) select * from t2",
+            "with recursive t2 as (select * from s1.t1 union all select s1.t1.* from s1.t1 join t2 on s1.t1.x = t2.y) select * from t2",
+        );
+    }
+
     #[test]
     fn ignores_table_expr_alias_reference() {
         select_rewrites_to("select t2.* from t1 as t2", "select t2.* from s1.t1 as t2");
@@ -566,12 +566,7 @@ impl NoriaAdapter {
         if self.supports_resnapshot && changelist.changes().any(Change::requires_resnapshot) {
             // In case we detect a DDL change that requires a full schema resnapshot exit the loop
             // with the proper status
-            if let Some(pos) = self.replication_offsets.max_offset()?.cloned() {
-                // Forward all positions to the maximum position (the one prior to this statement)
-                // to avoid needless replay later
-                self.handle_log_position(pos).await?;
-            }
-            return Err(ReadySetError::ResnapshotNeeded);
+            return self.resnapshot_needed().await;
         }
 
         match self
@@ -585,6 +580,12 @@ impl NoriaAdapter {
         {
             // ReadySet likely entered an invalid state, fail the replicator.
             Err(e @ ReadySetError::RecipeInvariantViolated(_)) => return Err(e),
+            // ReadySet couldn't apply the change to the data it already has (eg because an enum
+            // variant was added in the middle of an enum type), so we have to snapshot it again
+            Err(e) if self.supports_resnapshot && e.caused_by_resnapshot_needed() => {
+                info!(error = %e, "DDL change requires resnapshot");
+                return self.resnapshot_needed().await;
+            }
             Err(e) => {
                 warn!(error = %e, "Error extending recipe, DDL statement will not be used");
                 counter!(recorded::REPLICATOR_FAILURE, 1u64,);
@@ -597,6 +598,16 @@ impl NoriaAdapter {
         Ok(())
     }
 
+    /// Forward all table positions to the maximum position (the one prior to the current
+    /// statement) to avoid needless replay later, then return [`ReadySetError::ResnapshotNeeded`]
+    /// to exit the main loop and resnapshot
+    async fn resnapshot_needed(&mut self) -> ReadySetResult<()> {
+        if let Some(pos) = self.replication_offsets.max_offset()?.cloned() {
+            self.handle_log_position(pos).await?;
+        }
+        Err(ReadySetError::ResnapshotNeeded)
+    }
+
     /// Update the log position of the schema and the tables
     async fn handle_log_position(&mut self, pos: ReplicationOffset) -> ReadySetResult<()> {
         // Update the log position for the schema
@@ -35,6 +35,13 @@
 //!   to construct a full `ALTER TABLE` statement, `ALTER TABLE` events are replicated as a `CREATE
 //!   TABLE` statement - ReadySet will then know that a `CREATE TABLE` statement for a table that
 //!   already exists should be treated as an alter table.
+//! * Columns whose type is a [domain][domains] are replicated as if they had the domain's base
+//!   type, since ReadySet doesn't enforce the domain's constraints anyway.
+//! * `CREATE TYPE` and `ALTER TYPE` for enum types are replicated with the full list of the enum's
+//!   variants, in sort order. If a variant is added anywhere other than at the end of the list,
+//!   values of the type that ReadySet has already stored have to be snapshotted again.
+//!
+//! [domains]: https://www.postgresql.org/docs/current/domains.html
 //!
 //! [dialect]: nom_sql::Dialect
 
@@ -716,4 +723,83 @@ mod tests {
 
         client.teardown().await;
     }
+
+    #[parallel_group(GROUP)]
+    #[tokio::test]
+    async fn add_enum_value_before() {
+        let client = setup("add_enum_value_before").await;
+
+        client
+            .simple_query("create type abc as enum ('a', 'b', 'c')")
+            .await
+            .unwrap();
+        get_last_ddl(&client, "add_enum_value_before")
+            .await
+            .unwrap();
+
+        client
+            .simple_query("alter type abc add value 'b2' before 'c'")
+            .await
+            .unwrap();
+        let ddl = get_last_ddl(&client, "add_enum_value_before")
+            .await
+            .unwrap();
+
+        match ddl.data {
+            DdlEventData::AlterType { name, variants, .. } => {
+                assert_eq!(name, "abc");
+                assert_eq!(
+                    variants.into_iter().map(|v| v.label).collect::<Vec<_>>(),
+                    vec!["a", "b", "b2", "c"]
+                );
+            }
+            _ => panic!(),
+        }
+
+        client.teardown().await;
+    }
+
+    #[parallel_group(GROUP)]
+    #[tokio::test]
+    async fn create_table_with_domain_columns() {
+        let client = setup("create_table_with_domain_columns").await;
+
+        client
+            .simple_query(
+                "create domain positive_int as integer check (value > 0);
+                 create domain small_positive_int as positive_int check (value < 100);
+                 create domain short_text as varchar(10);
+                 create table t (x small_positive_int, y short_text);",
+            )
+            .await
+            .unwrap();
+
+        let ddl = get_last_ddl(&client, "create_table_with_domain_columns")
+            .await
+            .unwrap();
+
+        match ddl.data {
+            DdlEventData::CreateTable { name, columns, .. } => {
+                assert_eq!(name, "t");
+                assert_eq!(
+                    columns,
+                    vec![
+                        DdlCreateTableColumn {
+                            name: "x".into(),
+                            column_type: SqlType::Int(None),
+                            not_null: false
+                        },
+                        DdlCreateTableColumn {
+                            name: "y".into(),
+                            column_type: SqlType::VarChar(Some(10)),
+                            not_null: false
+                        },
+                    ]
+                );
+            }
+            data => panic!("Unexpected DDL event data: {data:?}"),
+        }
+
+        client.teardown().await;
+    }
 }
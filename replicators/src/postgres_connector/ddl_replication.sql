@@ -9,6 +9,26 @@ BEGIN
     SELECT current_setting('server_version_num') INTO ver;
     RETURN ver < 140000;
 END $$;
+
+-- Format the given type like `pg_catalog.format_type`, except that domain types are replaced with
+-- their (innermost) base type
+CREATE OR REPLACE FUNCTION readyset.format_base_type(typ oid, typmod integer)
+RETURNS text
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    base record;
+BEGIN
+    LOOP
+        SELECT typtype, typbasetype, typtypmod INTO base
+        FROM pg_catalog.pg_type
+        WHERE oid = typ;
+        EXIT WHEN base.typtype IS DISTINCT FROM 'd';
+        typ := base.typbasetype;
+        typmod := base.typtypmod;
+    END LOOP;
+    RETURN pg_catalog.format_type(typ, typmod);
+END $$;
 ----
 
 DO $$
@@ -35,7 +55,7 @@ BEGIN
             'columns', (
                 SELECT json_agg(json_build_object(
                     'name', attr.attname,
-                    'column_type', pg_catalog.format_type(
+                    'column_type', readyset.format_base_type(
                         attr.atttypid,
                         attr.atttypmod
                     ),
@@ -125,7 +125,9 @@ impl TryFrom<pgsql::Row> for ColumnEntry {
             match typtype as u8 as char {
                 'b' => Ok(Kind::Simple),
                 'c' => unsupported!("Composite types are not supported"),
-                'd' => unsupported!("Domain types are not supported"),
+                // Columns of domain types are resolved to their base type when querying the
+                // schema, so we only see domains here as the member type of arrays
+                'd' => unsupported!("Arrays of domain types are not supported"),
                 'e' => Ok(Kind::Enum(row.try_get(12 /* array_agg(e.enumlabel)... */)?)),
                 'p' => Ok(Kind::Pseudo),
                 'r' => unsupported!("Range types are not supported"),
@@ -270,7 +272,24 @@ impl TableEntry {
         oid: u32,
         transaction: &'a pgsql::Transaction<'a>,
     ) -> Result<Vec<ColumnEntry>, ReadySetError> {
+        // Columns with domain types are treated as if they had the domain's base type, which we
+        // find by following the (possibly nested) chain of domains
         let query = r#"
+            WITH RECURSIVE domain_base AS (
+                SELECT oid AS domain_oid, typbasetype AS base_oid, typtypmod AS base_typmod
+                FROM pg_catalog.pg_type
+                WHERE typtype = 'd'
+                UNION ALL
+                SELECT db.domain_oid, t.typbasetype, t.typtypmod
+                FROM domain_base db
+                JOIN pg_catalog.pg_type t ON t.oid = db.base_oid
+                WHERE t.typtype = 'd'
+            ), resolved_domains AS (
+                SELECT db.domain_oid, db.base_oid, db.base_typmod
+                FROM domain_base db
+                JOIN pg_catalog.pg_type t ON t.oid = db.base_oid
+                WHERE t.typtype <> 'd'
+            )
             SELECT
                 a.attname,
                 a.attnotnull,
@@ -280,7 +299,7 @@ impl TableEntry {
                 THEN format('"%s"."%s"', tn.nspname, t.typname)
                 WHEN member_t.oid IS NOT NULL AND member_t.typtype = 'e'
                 THEN format('"%s"."%s"[]', member_tn.nspname, member_t.typname)
-                ELSE pg_catalog.format_type(a.atttypid, a.atttypmod)
+                ELSE pg_catalog.format_type(t.oid, coalesce(d.base_typmod, a.atttypmod))
                 END AS sql_type,
                 t.oid,
                 t.typtype,
@@ -294,7 +313,8 @@ impl TableEntry {
                  FROM pg_enum e
                  WHERE (member_t.oid IS NULL AND (e.enumtypid = t.oid)) OR e.enumtypid = member_t.oid)
             FROM pg_catalog.pg_attribute a
-            JOIN pg_catalog.pg_type t ON a.atttypid = t.oid
+            LEFT JOIN resolved_domains d ON d.domain_oid = a.atttypid
+            JOIN pg_catalog.pg_type t ON t.oid = coalesce(d.base_oid, a.atttypid)
             JOIN pg_catalog.pg_namespace tn ON t.typnamespace = tn.oid
             LEFT JOIN pg_catalog.pg_type member_t ON t.typelem = member_t.oid
             LEFT JOIN pg_catalog.pg_namespace member_tn ON member_t.typnamespace = member_tn.oid
@@ -6,7 +6,7 @@ use bit_vec::BitVec;
 use mysql_time::MySqlTime;
 use postgres_types::Kind;
 use readyset_client::ReadySetError;
-use readyset_data::{Array, Collation, DfType, DfValue, Dialect};
+use readyset_data::{Array, Collation, DfType, DfValue, Dialect, Range};
 use readyset_errors::unsupported;
 use rust_decimal::prelude::FromStr;
 use rust_decimal::Decimal;
@@ -446,6 +446,22 @@ impl wal::TupleData {
                                 DfValue::from(str.parse::<Array>()?)
                                     .coerce_to(&target_type, &DfType::Unknown)?
                             }
+                            Kind::Range(subtype) => {
+                                let subsecond_digits =
+                                    Dialect::DEFAULT_POSTGRESQL.default_subsecond_digits();
+
+                                let subtype = match *subtype {
+                                    PGType::INT4 => DfType::Int,
+                                    PGType::INT8 => DfType::BigInt,
+                                    PGType::NUMERIC => DfType::DEFAULT_NUMERIC,
+                                    PGType::TIMESTAMP => DfType::Timestamp { subsecond_digits },
+                                    PGType::TIMESTAMPTZ => DfType::TimestampTz { subsecond_digits },
+                                    PGType::DATE => DfType::Date,
+                                    ref ty => unsupported!("Unsupported range subtype: {ty}"),
+                                };
+
+                                DfValue::from(Range::parse(str.as_ref(), &subtype)?)
+                            }
                             Kind::Enum(variants) => DfValue::from(
                                 variants
                                     .iter()
@@ -486,11 +502,11 @@ impl wal::TupleData {
                                 // So, in order to get
                                 // the same values, we parse the json into a
                                 // serde_json::Value and then convert it
-                                // back to String. ♪ ┏(・o･)┛ ♪
+                                // back to its canonical form. ♪ ┏(・o･)┛ ♪
                                 PGType::JSONB => {
                                     serde_json::from_str::<serde_json::Value>(str.as_ref())
                                         .map_err(|e| WalError::JsonParseError(e.to_string()))
-                                        .map(|v| DfValue::from(v.to_string()))?
+                                        .map(DfValue::from_jsonb)?
                                 }
                                 PGType::TIMESTAMP => DfValue::TimestampTz(
                                     str.parse().map_err(|_| WalError::TimestampParseError)?,